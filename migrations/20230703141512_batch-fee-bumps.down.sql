@@ -0,0 +1,2 @@
+DROP TABLE bria_batch_fee_bump_wallet_summaries;
+DROP TABLE bria_batch_fee_bumps;
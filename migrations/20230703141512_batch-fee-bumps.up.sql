@@ -0,0 +1,27 @@
+CREATE TABLE bria_batch_fee_bumps (
+  id UUID PRIMARY KEY NOT NULL,
+  batch_id UUID REFERENCES bria_batches(id) NOT NULL,
+  bitcoin_tx_id BYTEA NOT NULL,
+  total_fee_sats BIGINT NOT NULL,
+  replaced_bitcoin_tx_id BYTEA NOT NULL,
+  replaced_total_fee_sats BIGINT NOT NULL,
+  replaced_unsigned_psbt BYTEA NOT NULL,
+  replaced_signed_tx BYTEA NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_bria_batch_fee_bumps_batch_id ON bria_batch_fee_bumps (batch_id);
+
+CREATE TABLE bria_batch_fee_bump_wallet_summaries (
+  fee_bump_id UUID REFERENCES bria_batch_fee_bumps(id) NOT NULL,
+  wallet_id UUID REFERENCES bria_wallets(id) NOT NULL,
+  fee_sats BIGINT NOT NULL,
+  change_sats BIGINT NOT NULL,
+  replaced_fee_sats BIGINT NOT NULL,
+  replaced_change_sats BIGINT NOT NULL,
+  replaced_ledger_tx_id UUID NOT NULL,
+  batch_fee_bumped_ledger_tx_id UUID,
+  reverted_ledger_tx_id UUID,
+  modified_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  UNIQUE(fee_bump_id, wallet_id)
+);
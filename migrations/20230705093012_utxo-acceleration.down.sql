@@ -0,0 +1 @@
+ALTER TABLE bria_batches ALTER COLUMN payout_queue_id SET NOT NULL;
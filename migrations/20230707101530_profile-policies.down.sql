@@ -0,0 +1 @@
+ALTER TABLE bria_profiles DROP COLUMN policy;
@@ -0,0 +1 @@
+ALTER TABLE bria_profiles ADD COLUMN policy JSONB;
@@ -0,0 +1,2 @@
+ALTER TABLE bria_profile_api_keys DROP COLUMN expires_at;
+ALTER TABLE bria_profile_api_keys DROP COLUMN scopes;
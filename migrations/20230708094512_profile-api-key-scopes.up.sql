@@ -0,0 +1,2 @@
+ALTER TABLE bria_profile_api_keys ADD COLUMN scopes JSONB NOT NULL DEFAULT '["full"]';
+ALTER TABLE bria_profile_api_keys ADD COLUMN expires_at TIMESTAMPTZ;
@@ -0,0 +1 @@
+ALTER TABLE bria_admin_api_keys DROP COLUMN last_used_at;
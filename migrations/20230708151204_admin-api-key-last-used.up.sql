@@ -0,0 +1 @@
+ALTER TABLE bria_admin_api_keys ADD COLUMN last_used_at TIMESTAMPTZ;
@@ -0,0 +1 @@
+DROP TABLE bria_audit_log;
@@ -0,0 +1,13 @@
+CREATE TABLE bria_audit_log (
+  sequence BIGSERIAL PRIMARY KEY,
+  id UUID NOT NULL UNIQUE,
+  account_id UUID REFERENCES bria_accounts(id),
+  profile_id UUID REFERENCES bria_profiles(id),
+  admin_api_key_id UUID REFERENCES bria_admin_api_keys(id),
+  rpc VARCHAR NOT NULL,
+  request JSONB NOT NULL,
+  prev_hash VARCHAR NOT NULL,
+  hash VARCHAR NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX idx_bria_audit_log_account_id ON bria_audit_log(account_id, sequence);
@@ -0,0 +1,2 @@
+DROP TABLE bria_account_events;
+ALTER TABLE bria_accounts DROP COLUMN active;
@@ -0,0 +1,18 @@
+ALTER TABLE bria_accounts ADD COLUMN active BOOL NOT NULL DEFAULT true;
+
+CREATE TABLE bria_account_events (
+  id UUID REFERENCES bria_accounts(id) NOT NULL,
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  UNIQUE(id, sequence)
+);
+
+INSERT INTO bria_account_events (id, sequence, event_type, event)
+SELECT id, 1, 'initialized', jsonb_build_object('type', 'initialized', 'id', id)
+FROM bria_accounts;
+
+INSERT INTO bria_account_events (id, sequence, event_type, event)
+SELECT id, 2, 'name_updated', jsonb_build_object('type', 'name_updated', 'name', name)
+FROM bria_accounts;
@@ -0,0 +1,2 @@
+DROP TABLE bria_blocked_payout_attempts;
+DROP TABLE bria_blocked_addresses;
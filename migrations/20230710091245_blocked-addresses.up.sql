@@ -0,0 +1,18 @@
+CREATE TABLE bria_blocked_addresses (
+  address VARCHAR PRIMARY KEY,
+  reason VARCHAR,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE bria_blocked_payout_attempts (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  account_id UUID REFERENCES bria_accounts(id) NOT NULL,
+  profile_id UUID REFERENCES bria_profiles(id),
+  payout_id UUID REFERENCES bria_payouts(id),
+  address VARCHAR NOT NULL,
+  satoshis BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_bria_blocked_payout_attempts_account_id ON bria_blocked_payout_attempts(account_id, created_at);
+-- A payout stuck in its queue is only recorded once, not on every processing run
+CREATE UNIQUE INDEX idx_bria_blocked_payout_attempts_payout_id ON bria_blocked_payout_attempts(payout_id) WHERE payout_id IS NOT NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE bria_utxos DROP COLUMN quarantine_released_at;
+ALTER TABLE bria_utxos DROP COLUMN quarantined_source_address;
+ALTER TABLE bria_utxos DROP COLUMN quarantined;
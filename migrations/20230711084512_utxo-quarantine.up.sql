@@ -0,0 +1,4 @@
+ALTER TABLE bria_utxos ADD COLUMN quarantined BOOLEAN NOT NULL DEFAULT FALSE;
+-- The blocked address the funding transaction spent from
+ALTER TABLE bria_utxos ADD COLUMN quarantined_source_address VARCHAR;
+ALTER TABLE bria_utxos ADD COLUMN quarantine_released_at TIMESTAMPTZ;
@@ -0,0 +1,4 @@
+ALTER TABLE bria_utxos DROP COLUMN frozen_at;
+ALTER TABLE bria_utxos DROP COLUMN frozen_by_profile_id;
+ALTER TABLE bria_utxos DROP COLUMN frozen_reason;
+ALTER TABLE bria_utxos DROP COLUMN frozen;
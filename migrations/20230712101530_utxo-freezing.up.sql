@@ -0,0 +1,4 @@
+ALTER TABLE bria_utxos ADD COLUMN frozen BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE bria_utxos ADD COLUMN frozen_reason VARCHAR;
+ALTER TABLE bria_utxos ADD COLUMN frozen_by_profile_id UUID REFERENCES bria_profiles(id);
+ALTER TABLE bria_utxos ADD COLUMN frozen_at TIMESTAMPTZ;
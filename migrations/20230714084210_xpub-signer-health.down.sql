@@ -0,0 +1,3 @@
+ALTER TABLE bria_xpub_signer_configs DROP COLUMN health_checked_at;
+ALTER TABLE bria_xpub_signer_configs DROP COLUMN health_error;
+ALTER TABLE bria_xpub_signer_configs DROP COLUMN healthy;
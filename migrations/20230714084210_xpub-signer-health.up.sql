@@ -0,0 +1,3 @@
+ALTER TABLE bria_xpub_signer_configs ADD COLUMN healthy BOOLEAN;
+ALTER TABLE bria_xpub_signer_configs ADD COLUMN health_error VARCHAR;
+ALTER TABLE bria_xpub_signer_configs ADD COLUMN health_checked_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+ALTER TABLE bria_profiles DROP COLUMN created_by_profile_id;
+ALTER TABLE bria_profiles DROP COLUMN payout_approver;
@@ -0,0 +1,2 @@
+ALTER TABLE bria_profiles ADD COLUMN payout_approver BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE bria_profiles ADD COLUMN created_by_profile_id UUID REFERENCES bria_profiles(id);
@@ -0,0 +1 @@
+ALTER TABLE bria_audit_log DROP COLUMN outcome;
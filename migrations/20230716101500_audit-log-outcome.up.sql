@@ -0,0 +1 @@
+ALTER TABLE bria_audit_log ADD COLUMN outcome JSONB;
@@ -0,0 +1 @@
+DELETE FROM sqlx_ledger_accounts WHERE code LIKE 'WALLET\_%\_FROZEN';
@@ -0,0 +1,9 @@
+-- Wallets created before the frozen ledger account existed get one derived from their dust account
+INSERT INTO sqlx_ledger_accounts (id, code, name, description, status, normal_balance_type)
+SELECT overlay(overlay(id::text placing '2' from 10 for 1) placing '4000' from 20 for 4)::uuid,
+       regexp_replace(code, '_DUST$', '_FROZEN'),
+       regexp_replace(name, '-dust$', '-frozen'),
+       description, status, normal_balance_type
+FROM sqlx_ledger_accounts
+WHERE code LIKE 'WALLET\_%\_DUST' AND version = 1
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,2 @@
+ALTER TABLE bria_batches DROP COLUMN cancelled_by_profile_id;
+ALTER TABLE bria_batches DROP COLUMN cancelled_at;
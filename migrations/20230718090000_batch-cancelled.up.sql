@@ -0,0 +1,2 @@
+ALTER TABLE bria_batches ADD COLUMN cancelled_at TIMESTAMPTZ;
+ALTER TABLE bria_batches ADD COLUMN cancelled_by_profile_id UUID REFERENCES bria_profiles(id);
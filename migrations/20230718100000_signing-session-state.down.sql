@@ -0,0 +1,3 @@
+DROP INDEX idx_bria_signing_sessions_account_state;
+ALTER TABLE bria_signing_sessions DROP COLUMN created_at;
+ALTER TABLE bria_signing_sessions DROP COLUMN state;
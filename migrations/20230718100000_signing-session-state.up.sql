@@ -0,0 +1,32 @@
+ALTER TABLE bria_signing_sessions ADD COLUMN state VARCHAR NOT NULL DEFAULT 'initialized';
+ALTER TABLE bria_signing_sessions ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+UPDATE bria_signing_sessions s SET created_at = e.recorded_at
+FROM bria_signing_session_events e
+WHERE e.id = s.id AND e.sequence = 1;
+
+UPDATE bria_signing_sessions s SET state = CASE
+  WHEN EXISTS (
+    SELECT 1 FROM bria_signing_session_events e
+    WHERE e.id = s.id AND e.event_type = 'signing_aborted'
+  ) THEN 'aborted'
+  ELSE COALESCE((
+    SELECT CASE e.event_type
+      WHEN 'signing_attempt_failed' THEN 'failed'
+      WHEN 'remote_signing_completed' THEN 'complete'
+      WHEN 'externally_signed_psbt_submitted' THEN 'complete'
+      ELSE 'initialized'
+    END
+    FROM bria_signing_session_events e
+    WHERE e.id = s.id AND e.event_type IN (
+      'signing_attempt_failed',
+      'remote_signing_completed',
+      'externally_signed_psbt_submitted',
+      'unsigned_psbt_replaced'
+    )
+    ORDER BY e.sequence DESC
+    LIMIT 1
+  ), 'initialized')
+END;
+
+CREATE INDEX idx_bria_signing_sessions_account_state ON bria_signing_sessions (account_id, state, created_at, id);
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashSet;
+
+use crate::{
+    fees::FeesConfig,
+    primitives::{bitcoin, PayoutDestination},
+};
+
+/// Stands in for secrets when a config is handed out over the api
+pub const REDACTED_SECRET: &str = "[REDACTED]";
+
+/// Per account overrides of the daemon wide `AppConfig`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountConfig {
+    /// Blocked in addition to the addresses of the global `SecurityConfig`
+    #[serde(default)]
+    pub blocked_addresses: HashSet<bitcoin::Address>,
+    /// Replaces the global fee config for this account when set
+    #[serde(default)]
+    pub fees: Option<FeesConfig>,
+}
+
+impl AccountConfig {
+    pub fn is_blocked(&self, destination: &PayoutDestination) -> bool {
+        if let Some(addr) = destination.onchain_address() {
+            self.blocked_addresses.contains(&addr)
+        } else {
+            false
+        }
+    }
+
+    pub fn redacted(mut self) -> Self {
+        if let Some(password) = self
+            .fees
+            .as_mut()
+            .and_then(|fees| fees.bitcoind.rpc_password.as_mut())
+        {
+            *password = REDACTED_SECRET.to_string();
+        }
+        self
+    }
+
+    /// Keeps the stored secrets where an update sends back the redacted placeholder
+    pub(super) fn restore_redacted(&mut self, current: &AccountConfig) {
+        if let Some(fees) = self.fees.as_mut() {
+            if fees.bitcoind.rpc_password.as_deref() == Some(REDACTED_SECRET) {
+                fees.bitcoind.rpc_password = current
+                    .fees
+                    .as_ref()
+                    .and_then(|fees| fees.bitcoind.rpc_password.clone());
+            }
+        }
+    }
+}
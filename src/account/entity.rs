@@ -1,15 +1,83 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
 use sqlx_ledger::JournalId;
 
-use crate::primitives::*;
+use super::config::*;
+use crate::{entity::*, primitives::*};
 
-#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AccountEvent {
+    Initialized { id: AccountId },
+    NameUpdated { name: String },
+    ConfigUpdated { config: AccountConfig },
+    Deactivated,
+}
+
+#[derive(Debug, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EntityError"))]
 pub struct Account {
     pub id: AccountId,
     pub name: String,
+    #[builder(default = "true")]
+    pub active: bool,
+    #[builder(default)]
+    pub config: AccountConfig,
+
+    pub(super) events: EntityEvents<AccountEvent>,
 }
 
 impl Account {
     pub fn journal_id(&self) -> JournalId {
         JournalId::from(self.id)
     }
+
+    pub fn rename(&mut self, name: String) {
+        if self.name != name {
+            self.name = name.clone();
+            self.events.push(AccountEvent::NameUpdated { name });
+        }
+    }
+
+    pub fn update_config(&mut self, mut config: AccountConfig) {
+        config.restore_redacted(&self.config);
+        if self.config != config {
+            self.config = config.clone();
+            self.events.push(AccountEvent::ConfigUpdated { config });
+        }
+    }
+
+    pub fn deactivate(&mut self) {
+        if self.active {
+            self.active = false;
+            self.events.push(AccountEvent::Deactivated);
+        }
+    }
+}
+
+impl TryFrom<EntityEvents<AccountEvent>> for Account {
+    type Error = EntityError;
+
+    fn try_from(events: EntityEvents<AccountEvent>) -> Result<Self, Self::Error> {
+        let mut builder = AccountBuilder::default();
+        use AccountEvent::*;
+        for event in events.iter() {
+            match event {
+                Initialized { id } => {
+                    builder = builder.id(*id);
+                }
+                NameUpdated { name } => {
+                    builder = builder.name(name.clone());
+                }
+                ConfigUpdated { config } => {
+                    builder = builder.config(config.clone());
+                }
+                Deactivated => {
+                    builder = builder.active(false);
+                }
+            }
+        }
+        builder.events(events).build()
+    }
 }
@@ -1,9 +1,30 @@
 use thiserror::Error;
 
+use crate::primitives::AccountId;
+
 #[derive(Error, Debug)]
 pub enum AccountError {
     #[error("AccountError - Sqlx: {0}")]
-    Sqlx(#[from] sqlx::Error),
+    Sqlx(sqlx::Error),
     #[error("AccountError - EntityError: {0}")]
     EntityError(#[from] crate::entity::EntityError),
+    #[error("AccountError - Could not find account with id: {0}")]
+    AccountIdNotFound(AccountId),
+    #[error("AccountError - Account {0} has been deactivated")]
+    AccountDeactivated(AccountId),
+    #[error("AccountError - An account with this name already exists")]
+    AccountNameAlreadyExists,
+}
+
+impl From<sqlx::Error> for AccountError {
+    fn from(error: sqlx::Error) -> Self {
+        if let Some(err) = error.as_database_error() {
+            if let Some(constraint) = err.constraint() {
+                if constraint.contains("name") {
+                    return Self::AccountNameAlreadyExists;
+                }
+            }
+        }
+        Self::Sqlx(error)
+    }
 }
@@ -1,7 +1,9 @@
 pub mod balance;
+mod config;
 mod entity;
 pub mod error;
 mod repo;
 
+pub use config::*;
 pub use entity::*;
 pub use repo::*;
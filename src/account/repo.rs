@@ -1,9 +1,12 @@
 use sqlx::{Pool, Postgres, Transaction};
-use uuid::Uuid;
+use tracing::instrument;
+
+use std::collections::{HashMap, HashSet};
 
 use super::{entity::*, error::AccountError};
-use crate::{admin::error::*, primitives::*};
+use crate::{admin::error::*, entity::*, primitives::*};
 
+#[derive(Debug, Clone)]
 pub struct Accounts {
     pool: Pool<Postgres>,
 }
@@ -18,35 +21,103 @@ impl Accounts {
         tx: &mut Transaction<'_, Postgres>,
         account_name: String,
     ) -> Result<Account, AdminApiError> {
-        let id = Uuid::new_v4();
-        let record = sqlx::query!(
+        let id = AccountId::new();
+        sqlx::query!(
             r#"INSERT INTO bria_accounts (id, name, journal_id)
-            VALUES ($1, $2, $1)
-            RETURNING (id)"#,
-            id,
+            VALUES ($1, $2, $1)"#,
+            id as AccountId,
             account_name,
         )
-        .fetch_one(&mut *tx)
+        .execute(&mut *tx)
+        .await
+        .map_err(AccountError::from)?;
+        let events = EntityEvents::init([
+            AccountEvent::Initialized { id },
+            AccountEvent::NameUpdated { name: account_name },
+        ]);
+        EntityEvents::<AccountEvent>::persist(
+            "bria_account_events",
+            tx,
+            events.new_serialized_events(id),
+        )
         .await?;
-        Ok(Account {
-            name: account_name,
-            id: AccountId::from(record.id),
-        })
+        Ok(Account::try_from(events).map_err(AccountError::from)?)
+    }
+
+    pub async fn find_by_id(&self, id: AccountId) -> Result<Account, AccountError> {
+        let rows = sqlx::query!(
+            r#"SELECT sequence, event
+              FROM bria_account_events
+              WHERE id = $1
+              ORDER BY sequence"#,
+            id as AccountId,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        if rows.is_empty() {
+            return Err(AccountError::AccountIdNotFound(id));
+        }
+        let mut events = EntityEvents::new();
+        for row in rows {
+            events.load_event(row.sequence as usize, row.event)?;
+        }
+        Ok(Account::try_from(events)?)
     }
 
     pub async fn list(&self) -> Result<Vec<Account>, AccountError> {
-        let records = sqlx::query!(r#"SELECT id, name FROM bria_accounts"#)
+        let rows = sqlx::query!(
+            r#"SELECT id, sequence, event
+              FROM bria_account_events
+              ORDER BY id, sequence"#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let mut entity_events = HashMap::new();
+        for row in rows {
+            let id = AccountId::from(row.id);
+            let events = entity_events.entry(id).or_insert_with(EntityEvents::new);
+            events.load_event(row.sequence as usize, row.event)?;
+        }
+        let mut accounts = entity_events
+            .into_values()
+            .map(Account::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        accounts.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(accounts)
+    }
+
+    pub async fn inactive_ids(&self) -> Result<HashSet<AccountId>, AccountError> {
+        let rows = sqlx::query!(r#"SELECT id FROM bria_accounts WHERE active = false"#)
             .fetch_all(&self.pool)
             .await?;
-
-        let accounts = records
+        Ok(rows
             .into_iter()
-            .map(|record| Account {
-                id: AccountId::from(record.id),
-                name: record.name,
-            })
-            .collect();
+            .map(|row| AccountId::from(row.id))
+            .collect())
+    }
 
-        Ok(accounts)
+    #[instrument(name = "accounts.update", skip(self))]
+    pub async fn update(&self, account: Account) -> Result<(), AccountError> {
+        if !account.events.is_dirty() {
+            return Ok(());
+        }
+        let mut tx = self.pool.begin().await?;
+        sqlx::query!(
+            r#"UPDATE bria_accounts SET name = $2, active = $3, modified_at = NOW()
+            WHERE id = $1"#,
+            account.id as AccountId,
+            account.name,
+            account.active,
+        )
+        .execute(&mut tx)
+        .await?;
+        EntityEvents::<AccountEvent>::persist(
+            "bria_account_events",
+            &mut tx,
+            account.events.new_serialized_events(account.id),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
     }
 }
@@ -1,14 +1,26 @@
 use tracing::instrument;
 
 use super::{error::*, keys::*};
-use crate::{account::*, dev_constants, ledger::Ledger, primitives::bitcoin, profile::*};
+use crate::{
+    account::*,
+    audit::*,
+    dev_constants,
+    ledger::{Ledger, UtxoUnfrozenMeta, UtxoUnfrozenParams, WalletLedgerAccountIds},
+    primitives::{bitcoin, AccountId, AdminApiKeyId, LedgerTransactionId},
+    profile::*,
+    security::*,
+    utxo::{QuarantinedUtxo, Utxos},
+};
 
 const BOOTSTRAP_KEY_NAME: &str = "admin_bootstrap_key";
 
 pub struct AdminApp {
     keys: AdminApiKeys,
+    audit: AuditLog,
     accounts: Accounts,
     profiles: Profiles,
+    blocked_addresses: BlockedAddresses,
+    utxos: Utxos,
     ledger: Ledger,
     pool: sqlx::PgPool,
     network: bitcoin::Network,
@@ -18,8 +30,11 @@ impl AdminApp {
     pub fn new(pool: sqlx::PgPool, network: bitcoin::Network) -> Self {
         Self {
             keys: AdminApiKeys::new(&pool),
+            audit: AuditLog::new(&pool),
             accounts: Accounts::new(&pool),
             profiles: Profiles::new(&pool),
+            blocked_addresses: BlockedAddresses::new(&pool),
+            utxos: Utxos::new(&pool),
             ledger: Ledger::new(&pool),
             pool,
             network,
@@ -45,11 +60,11 @@ impl AdminApp {
             .await?;
         let profile = self
             .profiles
-            .create_in_tx(&mut tx, account.id, account.name)
+            .create_in_tx(&mut tx, account.id, account.name, None, false, None)
             .await?;
         let profile_key = self
             .profiles
-            .create_key_for_profile_in_tx(&mut tx, profile, true)
+            .create_key_for_profile_in_tx(&mut tx, profile, true, vec![ApiKeyScope::Full], None)
             .await?;
         tx.commit().await?;
         Ok((admin_key, profile_key))
@@ -60,12 +75,53 @@ impl AdminApp {
         self.keys.create(BOOTSTRAP_KEY_NAME.to_string()).await
     }
 
+    #[instrument(name = "admin_app.rotate_bootstrap_key", skip(self), err)]
+    pub async fn rotate_bootstrap_key(&self) -> Result<AdminApiKey, AdminApiError> {
+        self.keys.rotate(BOOTSTRAP_KEY_NAME).await
+    }
+
+    #[instrument(name = "admin_app.create_admin_api_key", skip(self), err)]
+    pub async fn create_admin_api_key(&self, name: String) -> Result<AdminApiKey, AdminApiError> {
+        self.keys.create(name).await
+    }
+
+    #[instrument(name = "admin_app.list_admin_api_keys", skip(self), err)]
+    pub async fn list_admin_api_keys(&self) -> Result<Vec<AdminApiKeySummary>, AdminApiError> {
+        self.keys.list().await
+    }
+
+    #[instrument(name = "admin_app.revoke_admin_api_key", skip(self), err)]
+    pub async fn revoke_admin_api_key(&self, id: AdminApiKeyId) -> Result<(), AdminApiError> {
+        self.keys.revoke(id).await
+    }
+
     #[instrument(name = "admin_app.authenticate", skip(self), err)]
-    pub async fn authenticate(&self, key: &str) -> Result<(), AdminApiError> {
-        self.keys.find_by_key(key).await?;
+    pub async fn authenticate(&self, key: &str) -> Result<AdminApiKeyId, AdminApiError> {
+        let key = self.keys.find_by_key(key).await?;
+        Ok(key.id)
+    }
+
+    #[instrument(name = "admin_app.record_audit_event", skip(self, request), err)]
+    pub async fn record_audit_event<T: serde::Serialize>(
+        &self,
+        actor: AuditActor,
+        rpc: &str,
+        request: &T,
+        outcome: AuditOutcome,
+    ) -> Result<(), AdminApiError> {
+        self.audit.append(actor, rpc, request, outcome).await?;
         Ok(())
     }
 
+    #[instrument(name = "admin_app.list_audit_events", skip(self), err)]
+    pub async fn list_audit_events(
+        &self,
+        account_id: Option<AccountId>,
+        filter: AuditFilter,
+    ) -> Result<Vec<AuditEntry>, AdminApiError> {
+        Ok(self.audit.list(account_id, filter).await?)
+    }
+
     #[instrument(name = "admin_app.create_account", skip(self), err)]
     pub async fn create_account(
         &self,
@@ -81,11 +137,11 @@ impl AdminApp {
             .await?;
         let profile = self
             .profiles
-            .create_in_tx(&mut tx, account.id, account.name)
+            .create_in_tx(&mut tx, account.id, account.name, None, false, None)
             .await?;
         let key = self
             .profiles
-            .create_key_for_profile_in_tx(&mut tx, profile, false)
+            .create_key_for_profile_in_tx(&mut tx, profile, false, vec![ApiKeyScope::Full], None)
             .await?;
         tx.commit().await?;
         Ok(key)
@@ -95,4 +151,106 @@ impl AdminApp {
     pub async fn list_accounts(&self) -> Result<Vec<Account>, AdminApiError> {
         Ok(self.accounts.list().await?)
     }
+
+    #[instrument(name = "admin_app.rename_account", skip(self), err)]
+    pub async fn rename_account(&self, id: AccountId, name: String) -> Result<(), AdminApiError> {
+        let mut account = self.accounts.find_by_id(id).await?;
+        account.rename(name);
+        self.accounts.update(account).await?;
+        Ok(())
+    }
+
+    #[instrument(name = "admin_app.deactivate_account", skip(self), err)]
+    pub async fn deactivate_account(&self, id: AccountId) -> Result<(), AdminApiError> {
+        let mut account = self.accounts.find_by_id(id).await?;
+        account.deactivate();
+        self.accounts.update(account).await?;
+        Ok(())
+    }
+
+    #[instrument(name = "admin_app.update_account_config", skip(self), err)]
+    pub async fn update_account_config(
+        &self,
+        id: AccountId,
+        config: AccountConfig,
+    ) -> Result<(), AdminApiError> {
+        let mut account = self.accounts.find_by_id(id).await?;
+        account.update_config(config);
+        self.accounts.update(account).await?;
+        Ok(())
+    }
+
+    #[instrument(name = "admin_app.add_blocked_addresses", skip(self, addresses), err)]
+    pub async fn add_blocked_addresses(
+        &self,
+        addresses: Vec<bitcoin::Address>,
+        reason: Option<String>,
+    ) -> Result<u64, AdminApiError> {
+        Ok(self.blocked_addresses.add(addresses, reason).await?)
+    }
+
+    #[instrument(name = "admin_app.remove_blocked_address", skip(self), err)]
+    pub async fn remove_blocked_address(
+        &self,
+        address: bitcoin::Address,
+    ) -> Result<(), AdminApiError> {
+        Ok(self.blocked_addresses.remove(address).await?)
+    }
+
+    #[instrument(name = "admin_app.list_blocked_addresses", skip(self), err)]
+    pub async fn list_blocked_addresses(&self) -> Result<Vec<BlockedAddress>, AdminApiError> {
+        Ok(self.blocked_addresses.list().await?)
+    }
+
+    #[instrument(name = "admin_app.list_blocked_payout_attempts", skip(self), err)]
+    pub async fn list_blocked_payout_attempts(
+        &self,
+        account_id: Option<AccountId>,
+    ) -> Result<Vec<BlockedPayoutAttempt>, AdminApiError> {
+        Ok(self
+            .blocked_addresses
+            .list_payout_attempts(account_id)
+            .await?)
+    }
+
+    #[instrument(name = "admin_app.list_quarantined_utxos", skip(self), err)]
+    pub async fn list_quarantined_utxos(
+        &self,
+        account_id: Option<AccountId>,
+    ) -> Result<Vec<QuarantinedUtxo>, AdminApiError> {
+        Ok(self.utxos.list_quarantined(account_id).await?)
+    }
+
+    #[instrument(name = "admin_app.release_quarantined_utxo", skip(self), err)]
+    pub async fn release_quarantined_utxo(
+        &self,
+        account_id: AccountId,
+        outpoint: bitcoin::OutPoint,
+    ) -> Result<(), AdminApiError> {
+        let (utxo, tx) = self.utxos.release_quarantined(account_id, outpoint).await?;
+        if !utxo.ledger_hold_changed {
+            tx.commit().await?;
+            return Ok(());
+        }
+        self.ledger
+            .utxo_unfrozen(
+                tx,
+                LedgerTransactionId::new(),
+                UtxoUnfrozenParams {
+                    journal_id: account_id.into(),
+                    ledger_account_ids: WalletLedgerAccountIds::from(utxo.wallet_id),
+                    meta: UtxoUnfrozenMeta {
+                        account_id,
+                        wallet_id: utxo.wallet_id,
+                        keychain_id: utxo.keychain_id,
+                        outpoint: utxo.outpoint,
+                        satoshis: utxo.value,
+                        address: utxo.address,
+                        settled: utxo.settled,
+                    },
+                },
+            )
+            .await?;
+        Ok(())
+    }
 }
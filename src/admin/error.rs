@@ -1,8 +1,9 @@
 use thiserror::Error;
 
 use crate::{
-    account::error::AccountError, app::error::ApplicationError, ledger::error::LedgerError,
-    profile::error::ProfileError,
+    account::error::AccountError, app::error::ApplicationError, audit::error::AuditError,
+    ledger::error::LedgerError, primitives::AdminApiKeyId, profile::error::ProfileError,
+    security::error::SecurityError, utxo::error::UtxoError,
 };
 
 #[allow(clippy::large_enum_variant)]
@@ -16,12 +17,28 @@ pub enum AdminApiError {
     BriaError(ApplicationError),
     #[error("AdminApiError - BadNetworkForDev")]
     BadNetworkForDev,
+    #[error("AdminApiError - Admin api key does not exist")]
+    AdminApiKeyNotFound,
+    #[error("AdminApiError - Could not find admin api key with id: {0}")]
+    AdminApiKeyIdNotFound(AdminApiKeyId),
+    #[error("AdminApiError - Could not find active admin api key with name: {0}")]
+    AdminApiKeyNameNotFound(String),
+    #[error("AdminApiError - An admin api key named {0} already exists")]
+    AdminApiKeyNameAlreadyExists(String),
+    #[error("AdminApiError - Cannot revoke the last active admin api key")]
+    CannotRevokeLastAdminApiKey,
     #[error("{0}")]
     AccountError(#[from] AccountError),
     #[error("{0}")]
     ProfileError(#[from] ProfileError),
     #[error("{0}")]
     LedgerError(#[from] LedgerError),
+    #[error("{0}")]
+    AuditError(#[from] AuditError),
+    #[error("{0}")]
+    SecurityError(#[from] SecurityError),
+    #[error("{0}")]
+    UtxoError(#[from] UtxoError),
 }
 
 impl From<ApplicationError> for AdminApiError {
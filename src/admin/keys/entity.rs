@@ -5,3 +5,12 @@ pub struct AdminApiKey {
     pub key: String,
     pub id: AdminApiKeyId,
 }
+
+/// An admin api key as listed, without the secret
+pub struct AdminApiKeySummary {
+    pub id: AdminApiKeyId,
+    pub name: String,
+    pub revoked: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+}
@@ -14,8 +14,7 @@ impl AdminApiKeys {
     }
 
     pub async fn create(&self, name: String) -> Result<AdminApiKey, AdminApiError> {
-        let code = Alphanumeric.sample_string(&mut rand::thread_rng(), 64);
-        let key = format!("bria_admin_{code}");
+        let key = generate_key();
         let record = sqlx::query!(
             r#"INSERT INTO bria_admin_api_keys (name, encrypted_key)
             VALUES ($1, crypt($2, gen_salt('bf'))) RETURNING (id)"#,
@@ -23,7 +22,15 @@ impl AdminApiKeys {
             key
         )
         .fetch_one(&self.pool)
-        .await?;
+        .await
+        .map_err(
+            |e| match e.as_database_error().and_then(|e| e.constraint()) {
+                Some(constraint) if constraint.contains("name") => {
+                    AdminApiError::AdminApiKeyNameAlreadyExists(name.clone())
+                }
+                _ => AdminApiError::from(e),
+            },
+        )?;
         Ok(AdminApiKey {
             name,
             key,
@@ -33,15 +40,95 @@ impl AdminApiKeys {
 
     pub async fn find_by_key(&self, key: &str) -> Result<AdminApiKey, AdminApiError> {
         let record = sqlx::query!(
-            r#"SELECT id, name FROM bria_admin_api_keys WHERE encrypted_key = crypt($1, encrypted_key)"#,
+            r#"UPDATE bria_admin_api_keys SET last_used_at = NOW()
+            WHERE active = true AND encrypted_key = crypt($1, encrypted_key)
+            RETURNING id, name"#,
             key
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await?;
+        let record = record.ok_or(AdminApiError::AdminApiKeyNotFound)?;
         Ok(AdminApiKey {
             name: record.name,
             key: key.to_string(),
             id: AdminApiKeyId::from(record.id),
         })
     }
+
+    pub async fn list(&self) -> Result<Vec<AdminApiKeySummary>, AdminApiError> {
+        let records = sqlx::query!(
+            r#"SELECT id, name, active, created_at, last_used_at
+            FROM bria_admin_api_keys ORDER BY created_at"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(records
+            .into_iter()
+            .map(|record| AdminApiKeySummary {
+                id: AdminApiKeyId::from(record.id),
+                name: record.name,
+                revoked: !record.active,
+                created_at: record.created_at,
+                last_used_at: record.last_used_at,
+            })
+            .collect())
+    }
+
+    pub async fn revoke(&self, id: AdminApiKeyId) -> Result<(), AdminApiError> {
+        let mut tx = self.pool.begin().await?;
+        // Locking every active key serializes concurrent revokes so that the
+        // last remaining key can never be revoked from under us
+        let active_ids = sqlx::query!(
+            r#"SELECT id FROM bria_admin_api_keys
+            WHERE active = true FOR UPDATE"#
+        )
+        .fetch_all(&mut tx)
+        .await?;
+        if !active_ids
+            .iter()
+            .any(|record| AdminApiKeyId::from(record.id) == id)
+        {
+            return Err(AdminApiError::AdminApiKeyIdNotFound(id));
+        }
+        if active_ids.len() == 1 {
+            return Err(AdminApiError::CannotRevokeLastAdminApiKey);
+        }
+        sqlx::query!(
+            r#"UPDATE bria_admin_api_keys SET active = false, modified_at = NOW()
+            WHERE id = $1"#,
+            id as AdminApiKeyId
+        )
+        .execute(&mut tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Replaces the secret of the active key with the given name,
+    /// invalidating the previous one immediately
+    pub async fn rotate(&self, name: &str) -> Result<AdminApiKey, AdminApiError> {
+        let key = generate_key();
+        let record = sqlx::query!(
+            r#"UPDATE bria_admin_api_keys
+            SET encrypted_key = crypt($2, gen_salt('bf')), last_used_at = NULL, modified_at = NOW()
+            WHERE active = true AND name = $1
+            RETURNING id"#,
+            name,
+            key
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        let record =
+            record.ok_or_else(|| AdminApiError::AdminApiKeyNameNotFound(name.to_string()))?;
+        Ok(AdminApiKey {
+            name: name.to_string(),
+            key,
+            id: AdminApiKeyId::from(record.id),
+        })
+    }
+}
+
+fn generate_key() -> String {
+    let code = Alphanumeric.sample_string(&mut rand::thread_rng(), 64);
+    format!("bria_admin_{code}")
 }
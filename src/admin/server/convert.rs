@@ -1,7 +1,106 @@
-use crate::admin::AdminApiError;
+use super::proto;
+use crate::{
+    account::{error::AccountError, AccountConfig},
+    admin::AdminApiError,
+    audit::{AuditEntry, AuditOutcome},
+    primitives::bitcoin,
+    security::error::SecurityError,
+    utxo::error::UtxoError,
+};
+
+impl From<AccountConfig> for proto::AccountConfig {
+    fn from(config: AccountConfig) -> Self {
+        Self {
+            blocked_addresses: config
+                .blocked_addresses
+                .into_iter()
+                .map(|addr| addr.to_string())
+                .collect(),
+            fees: config.fees.map(|fees| {
+                serde_json::from_value(serde_json::to_value(fees).expect("Couldn't serialize fees"))
+                    .expect("Could not transfer json -> struct")
+            }),
+        }
+    }
+}
+
+impl TryFrom<proto::AccountConfig> for AccountConfig {
+    type Error = tonic::Status;
+
+    fn try_from(config: proto::AccountConfig) -> Result<Self, Self::Error> {
+        let blocked_addresses = config
+            .blocked_addresses
+            .into_iter()
+            .map(|addr| addr.parse::<bitcoin::Address>())
+            .collect::<Result<_, _>>()
+            .map_err(|e| tonic::Status::invalid_argument(format!("Invalid address: {e}")))?;
+        let fees = config
+            .fees
+            .map(|fees| serde_json::to_value(fees).and_then(serde_json::from_value))
+            .transpose()
+            .map_err(|e| tonic::Status::invalid_argument(format!("Invalid fees config: {e}")))?;
+        Ok(Self {
+            blocked_addresses,
+            fees,
+        })
+    }
+}
+
+impl TryFrom<AuditEntry> for proto::AuditEvent {
+    type Error = tonic::Status;
+
+    fn try_from(entry: AuditEntry) -> Result<Self, Self::Error> {
+        let request = serde_json::from_value(entry.request).map_err(|e| {
+            tonic::Status::internal(format!("could not convert audit request: {e}"))
+        })?;
+        let (succeeded, error) = match entry.outcome {
+            Some(AuditOutcome::Succeeded) => (Some(true), None),
+            Some(AuditOutcome::Failed { error }) => (Some(false), Some(error)),
+            None => (None, None),
+        };
+        Ok(Self {
+            sequence: entry.sequence,
+            id: entry.id.to_string(),
+            account_id: entry.actor.account_id().map(|id| id.to_string()),
+            profile_id: entry.actor.profile_id().map(|id| id.to_string()),
+            admin_api_key_id: entry.actor.admin_api_key_id().map(|id| id.to_string()),
+            rpc: entry.rpc,
+            request: Some(request),
+            prev_hash: entry.prev_hash,
+            hash: entry.hash,
+            recorded_at: entry.recorded_at.timestamp() as u32,
+            succeeded,
+            error,
+        })
+    }
+}
 
 impl From<AdminApiError> for tonic::Status {
     fn from(err: AdminApiError) -> Self {
-        tonic::Status::new(tonic::Code::Unknown, format!("{err}"))
+        match err {
+            AdminApiError::AdminApiKeyNotFound => tonic::Status::unauthenticated(err.to_string()),
+            AdminApiError::AdminApiKeyIdNotFound(_) | AdminApiError::AdminApiKeyNameNotFound(_) => {
+                tonic::Status::not_found(err.to_string())
+            }
+            AdminApiError::AccountError(AccountError::AccountIdNotFound(_)) => {
+                tonic::Status::not_found(err.to_string())
+            }
+            AdminApiError::AccountError(AccountError::AccountNameAlreadyExists) => {
+                tonic::Status::already_exists(err.to_string())
+            }
+            AdminApiError::SecurityError(SecurityError::BlockedAddressNotFound(_)) => {
+                tonic::Status::not_found(err.to_string())
+            }
+            AdminApiError::UtxoError(UtxoError::QuarantinedUtxoNotFound(_)) => {
+                tonic::Status::not_found(err.to_string())
+            }
+            AdminApiError::AdminApiKeyNameAlreadyExists(_) => {
+                tonic::Status::already_exists(err.to_string())
+            }
+            AdminApiError::CannotRevokeLastAdminApiKey => {
+                tonic::Status::failed_precondition(err.to_string())
+            }
+            _ => tonic::Status::new(tonic::Code::Unknown, format!("{err}")),
+        }
     }
 }
@@ -11,6 +11,7 @@ use tracing::instrument;
 use proto::{admin_service_server::AdminService, *};
 
 use super::{app::*, config::*, error::*};
+use crate::audit::{AuditActor, AuditFilter, AuditOutcome};
 
 pub const ADMIN_API_KEY_HEADER: &str = "x-bria-admin-api-key";
 
@@ -18,6 +19,41 @@ pub struct Admin {
     app: AdminApp,
 }
 
+impl Admin {
+    /// Runs `handler` and records the request in the audit log together with its outcome
+    #[allow(clippy::result_large_err)]
+    async fn audited<T, R, F, Fut>(
+        &self,
+        actor: AuditActor,
+        rpc: &str,
+        request: Request<T>,
+        handler: F,
+    ) -> Result<Response<R>, Status>
+    where
+        T: serde::Serialize + Clone,
+        F: FnOnce(Request<T>) -> Fut,
+        Fut: std::future::Future<Output = Result<Response<R>, Status>>,
+    {
+        let audited_request = request.get_ref().clone();
+        let result = handler(request).await;
+        let outcome = match &result {
+            Ok(_) => AuditOutcome::Succeeded,
+            Err(status) => AuditOutcome::Failed {
+                error: status.message().to_string(),
+            },
+        };
+        // Failing to audit must not turn an already committed change into an error
+        if let Err(err) = self
+            .app
+            .record_audit_event(actor, rpc, &audited_request, outcome)
+            .await
+        {
+            tracing::error!(%err, rpc, "could not record audit event");
+        }
+        result
+    }
+}
+
 #[tonic::async_trait]
 impl AdminService for Admin {
     #[instrument(skip_all, err)]
@@ -63,17 +99,25 @@ impl AdminService for Admin {
         request: Request<CreateAccountRequest>,
     ) -> Result<Response<CreateAccountResponse>, Status> {
         let admin_api_key = extract_api_token(&request)?;
-        self.app.authenticate(admin_api_key).await?;
-        let name = request.into_inner().name;
-        let key = self.app.create_account(name.clone()).await?;
-        Ok(Response::new(CreateAccountResponse {
-            key: Some(ProfileApiKey {
-                profile_id: key.profile_id.to_string(),
-                name,
-                key: key.key,
-                account_id: key.account_id.to_string(),
-            }),
-        }))
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "CreateAccount",
+            request,
+            |request| async move {
+                let name = request.into_inner().name;
+                let key = self.app.create_account(name.clone()).await?;
+                Ok(Response::new(CreateAccountResponse {
+                    key: Some(ProfileApiKey {
+                        profile_id: key.profile_id.to_string(),
+                        name,
+                        key: key.key,
+                        account_id: key.account_id.to_string(),
+                    }),
+                }))
+            },
+        )
+        .await
     }
 
     #[instrument(skip_all, err)]
@@ -89,12 +133,381 @@ impl AdminService for Admin {
             .map(|account| Account {
                 id: account.id.to_string(),
                 name: account.name,
+                active: account.active,
+                config: Some(AccountConfig::from(account.config.redacted())),
             })
             .collect();
         Ok(Response::new(ListAccountsResponse {
             accounts: response_accounts,
         }))
     }
+
+    #[instrument(skip_all, err)]
+    async fn rename_account(
+        &self,
+        request: Request<RenameAccountRequest>,
+    ) -> Result<Response<RenameAccountResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "RenameAccount",
+            request,
+            |request| async move {
+                let RenameAccountRequest { id, name } = request.into_inner();
+                let id = id
+                    .parse()
+                    .map_err(|_| Status::invalid_argument("Could not parse account id"))?;
+                self.app.rename_account(id, name).await?;
+                Ok(Response::new(RenameAccountResponse {}))
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn deactivate_account(
+        &self,
+        request: Request<DeactivateAccountRequest>,
+    ) -> Result<Response<DeactivateAccountResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "DeactivateAccount",
+            request,
+            |request| async move {
+                let id = request
+                    .into_inner()
+                    .id
+                    .parse()
+                    .map_err(|_| Status::invalid_argument("Could not parse account id"))?;
+                self.app.deactivate_account(id).await?;
+                Ok(Response::new(DeactivateAccountResponse {}))
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn update_account_config(
+        &self,
+        request: Request<UpdateAccountConfigRequest>,
+    ) -> Result<Response<UpdateAccountConfigResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "UpdateAccountConfig",
+            request,
+            |request| async move {
+                let UpdateAccountConfigRequest { id, config } = request.into_inner();
+                let id = id
+                    .parse()
+                    .map_err(|_| Status::invalid_argument("Could not parse account id"))?;
+                let config = config
+                    .map(crate::account::AccountConfig::try_from)
+                    .transpose()?
+                    .unwrap_or_default();
+                self.app.update_account_config(id, config).await?;
+                Ok(Response::new(UpdateAccountConfigResponse {}))
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn add_blocked_addresses(
+        &self,
+        request: Request<AddBlockedAddressesRequest>,
+    ) -> Result<Response<AddBlockedAddressesResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "AddBlockedAddresses",
+            request,
+            |request| async move {
+                let AddBlockedAddressesRequest { addresses, reason } = request.into_inner();
+                let addresses = addresses
+                    .into_iter()
+                    .map(|addr| addr.parse())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| Status::invalid_argument(format!("Invalid address: {e}")))?;
+                let n_added = self.app.add_blocked_addresses(addresses, reason).await?;
+                Ok(Response::new(AddBlockedAddressesResponse { n_added }))
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn remove_blocked_address(
+        &self,
+        request: Request<RemoveBlockedAddressRequest>,
+    ) -> Result<Response<RemoveBlockedAddressResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "RemoveBlockedAddress",
+            request,
+            |request| async move {
+                let address = request
+                    .into_inner()
+                    .address
+                    .parse()
+                    .map_err(|e| Status::invalid_argument(format!("Invalid address: {e}")))?;
+                self.app.remove_blocked_address(address).await?;
+                Ok(Response::new(RemoveBlockedAddressResponse {}))
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn list_blocked_addresses(
+        &self,
+        request: Request<ListBlockedAddressesRequest>,
+    ) -> Result<Response<ListBlockedAddressesResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        self.app.authenticate(admin_api_key).await?;
+        let addresses = self.app.list_blocked_addresses().await?;
+        Ok(Response::new(ListBlockedAddressesResponse {
+            addresses: addresses
+                .into_iter()
+                .map(|addr| BlockedAddress {
+                    address: addr.address.to_string(),
+                    reason: addr.reason,
+                    created_at: addr.created_at.timestamp() as u32,
+                })
+                .collect(),
+        }))
+    }
+
+    #[instrument(skip_all, err)]
+    async fn list_blocked_payout_attempts(
+        &self,
+        request: Request<ListBlockedPayoutAttemptsRequest>,
+    ) -> Result<Response<ListBlockedPayoutAttemptsResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        self.app.authenticate(admin_api_key).await?;
+        let account_id = request
+            .into_inner()
+            .account_id
+            .map(|id| id.parse())
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Could not parse account id"))?;
+        let attempts = self.app.list_blocked_payout_attempts(account_id).await?;
+        Ok(Response::new(ListBlockedPayoutAttemptsResponse {
+            attempts: attempts
+                .into_iter()
+                .map(|attempt| BlockedPayoutAttempt {
+                    id: attempt.id.to_string(),
+                    account_id: attempt.account_id.to_string(),
+                    profile_id: attempt.profile_id.map(|id| id.to_string()),
+                    payout_id: attempt.payout_id.map(|id| id.to_string()),
+                    address: attempt.address.to_string(),
+                    satoshis: u64::from(attempt.satoshis),
+                    attempted_at: attempt.attempted_at.timestamp() as u32,
+                })
+                .collect(),
+        }))
+    }
+
+    #[instrument(skip_all, err)]
+    async fn list_quarantined_utxos(
+        &self,
+        request: Request<ListQuarantinedUtxosRequest>,
+    ) -> Result<Response<ListQuarantinedUtxosResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        self.app.authenticate(admin_api_key).await?;
+        let account_id = request
+            .into_inner()
+            .account_id
+            .map(|id| id.parse())
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Could not parse account id"))?;
+        let utxos = self.app.list_quarantined_utxos(account_id).await?;
+        Ok(Response::new(ListQuarantinedUtxosResponse {
+            utxos: utxos
+                .into_iter()
+                .map(|utxo| QuarantinedUtxo {
+                    account_id: utxo.account_id.to_string(),
+                    wallet_id: utxo.wallet_id.to_string(),
+                    keychain_id: utxo.keychain_id.to_string(),
+                    outpoint: utxo.outpoint.to_string(),
+                    value: u64::from(utxo.value),
+                    address: utxo.address.to_string(),
+                    source_address: utxo.source_address.map(|addr| addr.to_string()),
+                    created_at: utxo.created_at.timestamp() as u32,
+                })
+                .collect(),
+        }))
+    }
+
+    #[instrument(skip_all, err)]
+    async fn release_quarantined_utxo(
+        &self,
+        request: Request<ReleaseQuarantinedUtxoRequest>,
+    ) -> Result<Response<ReleaseQuarantinedUtxoResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "ReleaseQuarantinedUtxo",
+            request,
+            |request| async move {
+                let ReleaseQuarantinedUtxoRequest {
+                    account_id,
+                    outpoint,
+                } = request.into_inner();
+                let account_id = account_id
+                    .parse()
+                    .map_err(|_| Status::invalid_argument("Could not parse account id"))?;
+                let outpoint = outpoint
+                    .parse()
+                    .map_err(|_| Status::invalid_argument("Could not parse outpoint"))?;
+                self.app
+                    .release_quarantined_utxo(account_id, outpoint)
+                    .await?;
+                Ok(Response::new(ReleaseQuarantinedUtxoResponse {}))
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn create_admin_api_key(
+        &self,
+        request: Request<CreateAdminApiKeyRequest>,
+    ) -> Result<Response<CreateAdminApiKeyResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "CreateAdminApiKey",
+            request,
+            |request| async move {
+                let name = request.into_inner().name;
+                let super::AdminApiKey { id, name, key } =
+                    self.app.create_admin_api_key(name).await?;
+                Ok(Response::new(CreateAdminApiKeyResponse {
+                    key: Some(AdminApiKey {
+                        id: id.to_string(),
+                        name,
+                        key,
+                    }),
+                }))
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn list_admin_api_keys(
+        &self,
+        request: Request<ListAdminApiKeysRequest>,
+    ) -> Result<Response<ListAdminApiKeysResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        self.app.authenticate(admin_api_key).await?;
+        let keys = self.app.list_admin_api_keys().await?;
+        let response_keys = keys
+            .into_iter()
+            .map(|key| AdminApiKeySummary {
+                id: key.id.to_string(),
+                name: key.name,
+                revoked: key.revoked,
+                created_at: key.created_at.timestamp() as u32,
+                last_used_at: key
+                    .last_used_at
+                    .map(|last_used_at| last_used_at.timestamp() as u32),
+            })
+            .collect();
+        Ok(Response::new(ListAdminApiKeysResponse {
+            keys: response_keys,
+        }))
+    }
+
+    #[instrument(skip_all, err)]
+    async fn revoke_admin_api_key(
+        &self,
+        request: Request<RevokeAdminApiKeyRequest>,
+    ) -> Result<Response<RevokeAdminApiKeyResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "RevokeAdminApiKey",
+            request,
+            |request| async move {
+                let id =
+                    request.into_inner().id.parse().map_err(|_| {
+                        Status::invalid_argument("Could not parse admin api key id")
+                    })?;
+                self.app.revoke_admin_api_key(id).await?;
+                Ok(Response::new(RevokeAdminApiKeyResponse {}))
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn rotate_bootstrap_key(
+        &self,
+        request: Request<RotateBootstrapKeyRequest>,
+    ) -> Result<Response<RotateBootstrapKeyResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        let id = self.app.authenticate(admin_api_key).await?;
+        self.audited(
+            AuditActor::AdminApiKey { id },
+            "RotateBootstrapKey",
+            request,
+            |_| async move {
+                let super::AdminApiKey { id, name, key } = self.app.rotate_bootstrap_key().await?;
+                Ok(Response::new(RotateBootstrapKeyResponse {
+                    key: Some(AdminApiKey {
+                        id: id.to_string(),
+                        name,
+                        key,
+                    }),
+                }))
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip_all, err)]
+    async fn list_audit_events(
+        &self,
+        request: Request<ListAuditEventsRequest>,
+    ) -> Result<Response<ListAuditEventsResponse>, Status> {
+        let admin_api_key = extract_api_token(&request)?;
+        self.app.authenticate(admin_api_key).await?;
+        let ListAuditEventsRequest {
+            account_id,
+            rpc,
+            after_sequence,
+            limit,
+        } = request.into_inner();
+        let account_id = account_id
+            .map(|id| id.parse())
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Could not parse account id"))?;
+        let filter = AuditFilter {
+            rpc,
+            after_sequence,
+            limit,
+            ..Default::default()
+        };
+        let events = self.app.list_audit_events(account_id, filter).await?;
+        Ok(Response::new(ListAuditEventsResponse {
+            events: events
+                .into_iter()
+                .map(AuditEvent::try_from)
+                .collect::<Result<_, _>>()?,
+        }))
+    }
 }
 
 pub(crate) async fn start(
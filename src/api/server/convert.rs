@@ -1,17 +1,19 @@
-use std::time::Duration;
+use rust_decimal::prelude::ToPrimitive;
+use std::{collections::HashSet, time::Duration};
 
 use super::proto;
 use crate::{
     account::balance::AccountBalanceSummary,
     address::*,
     app::error::*,
+    audit::*,
     batch::*,
     outbox::*,
     payout::*,
     payout_queue::*,
     primitives::{bitcoin::*, *},
     profile::*,
-    signing_session::*,
+    signing_session::{error::SigningSessionError, *},
     tracing::ToTraceLevel,
     utxo::*,
     wallet::balance::WalletBalanceSummary,
@@ -24,10 +26,141 @@ impl From<Profile> for proto::Profile {
         Self {
             id: p.id.to_string(),
             name: p.name,
+            policy: p.policy.map(proto::ProfilePolicy::from),
+            payout_approver: p.payout_approver,
         }
     }
 }
 
+impl From<ProfilePolicy> for proto::ProfilePolicy {
+    fn from(policy: ProfilePolicy) -> Self {
+        Self {
+            allowed_wallet_ids: policy
+                .allowed_wallet_ids
+                .into_iter()
+                .flatten()
+                .map(|id| id.to_string())
+                .collect(),
+            allowed_payout_queue_ids: policy
+                .allowed_payout_queue_ids
+                .into_iter()
+                .flatten()
+                .map(|id| id.to_string())
+                .collect(),
+            max_payout_sats: policy.max_payout_sats.map(u64::from),
+            max_daily_payout_sats: policy.max_daily_payout_sats.map(u64::from),
+            allowed_destinations: policy
+                .allowed_destinations
+                .into_iter()
+                .flatten()
+                .map(|address| address.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::ProfilePolicy> for ProfilePolicy {
+    type Error = tonic::Status;
+
+    fn try_from(policy: proto::ProfilePolicy) -> Result<Self, Self::Error> {
+        let allowed_wallet_ids = policy
+            .allowed_wallet_ids
+            .iter()
+            .map(|id| id.parse::<WalletId>())
+            .collect::<Result<HashSet<_>, _>>()
+            .map_err(|_| tonic::Status::invalid_argument("wallet id couldn't be parsed"))?;
+        let allowed_payout_queue_ids = policy
+            .allowed_payout_queue_ids
+            .iter()
+            .map(|id| id.parse::<PayoutQueueId>())
+            .collect::<Result<HashSet<_>, _>>()
+            .map_err(|_| tonic::Status::invalid_argument("payout queue id couldn't be parsed"))?;
+        let allowed_destinations = policy
+            .allowed_destinations
+            .iter()
+            .map(|address| address.parse::<bitcoin::Address>())
+            .collect::<Result<HashSet<_>, _>>()
+            .map_err(|_| tonic::Status::invalid_argument("on chain address couldn't be parsed"))?;
+        Ok(Self {
+            allowed_wallet_ids: Some(allowed_wallet_ids).filter(|ids| !ids.is_empty()),
+            allowed_payout_queue_ids: Some(allowed_payout_queue_ids).filter(|ids| !ids.is_empty()),
+            max_payout_sats: policy.max_payout_sats.map(Satoshis::from),
+            max_daily_payout_sats: policy.max_daily_payout_sats.map(Satoshis::from),
+            allowed_destinations: Some(allowed_destinations).filter(|addrs| !addrs.is_empty()),
+        })
+    }
+}
+
+impl From<ApiKeyScope> for proto::ApiKeyScope {
+    fn from(scope: ApiKeyScope) -> Self {
+        match scope {
+            ApiKeyScope::Full => proto::ApiKeyScope::Full,
+            ApiKeyScope::ReadOnly => proto::ApiKeyScope::ReadOnly,
+            ApiKeyScope::Addresses => proto::ApiKeyScope::Addresses,
+            ApiKeyScope::SubmitPayouts => proto::ApiKeyScope::SubmitPayouts,
+            ApiKeyScope::SubscribeEvents => proto::ApiKeyScope::SubscribeEvents,
+        }
+    }
+}
+
+impl From<proto::ApiKeyScope> for ApiKeyScope {
+    fn from(scope: proto::ApiKeyScope) -> Self {
+        match scope {
+            proto::ApiKeyScope::Full => ApiKeyScope::Full,
+            proto::ApiKeyScope::ReadOnly => ApiKeyScope::ReadOnly,
+            proto::ApiKeyScope::Addresses => ApiKeyScope::Addresses,
+            proto::ApiKeyScope::SubmitPayouts => ApiKeyScope::SubmitPayouts,
+            proto::ApiKeyScope::SubscribeEvents => ApiKeyScope::SubscribeEvents,
+        }
+    }
+}
+
+impl From<ProfileApiKeySummary> for proto::ProfileApiKeySummary {
+    fn from(key: ProfileApiKeySummary) -> Self {
+        Self {
+            id: key.id.to_string(),
+            profile_id: key.profile_id.to_string(),
+            scopes: key
+                .scopes
+                .into_iter()
+                .map(|scope| proto::ApiKeyScope::from(scope) as i32)
+                .collect(),
+            expires_at: key
+                .expires_at
+                .map(|expires_at| expires_at.timestamp() as u32),
+            revoked: key.revoked,
+            created_at: key.created_at.timestamp() as u32,
+        }
+    }
+}
+
+impl TryFrom<AuditEntry> for proto::AuditEvent {
+    type Error = tonic::Status;
+
+    fn try_from(entry: AuditEntry) -> Result<Self, Self::Error> {
+        let request = serde_json::from_value(entry.request).map_err(|e| {
+            tonic::Status::internal(format!("could not convert audit request: {e}"))
+        })?;
+        let (succeeded, error) = match entry.outcome {
+            Some(AuditOutcome::Succeeded) => (Some(true), None),
+            Some(AuditOutcome::Failed { error }) => (Some(false), Some(error)),
+            None => (None, None),
+        };
+        Ok(Self {
+            sequence: entry.sequence,
+            id: entry.id.to_string(),
+            profile_id: entry.actor.profile_id().map(|id| id.to_string()),
+            rpc: entry.rpc,
+            request: Some(request),
+            prev_hash: entry.prev_hash,
+            hash: entry.hash,
+            recorded_at: entry.recorded_at.timestamp() as u32,
+            succeeded,
+            error,
+        })
+    }
+}
+
 impl TryFrom<Option<proto::set_signer_config_request::Config>> for SignerConfig {
     type Error = tonic::Status;
 
@@ -49,6 +182,25 @@ impl TryFrom<Option<proto::set_signer_config_request::Config>> for SignerConfig
                     rpc_password: config.rpc_password,
                 }))
             }
+            Some(proto::set_signer_config_request::Config::LocalXpriv(config)) => {
+                Ok(SignerConfig::LocalXpriv(LocalXprivSignerConfig {
+                    xpriv: config.xpriv,
+                }))
+            }
+            Some(proto::set_signer_config_request::Config::Webhook(config)) => {
+                let endpoint = url::Url::parse(&config.endpoint).map_err(|e| {
+                    tonic::Status::invalid_argument(format!("invalid webhook endpoint: {e}"))
+                })?;
+                if !matches!(endpoint.scheme(), "http" | "https") {
+                    return Err(tonic::Status::invalid_argument(
+                        "webhook endpoint must be an http(s) url",
+                    ));
+                }
+                Ok(SignerConfig::Webhook(WebhookSignerConfig {
+                    endpoint: config.endpoint,
+                    hmac_secret: config.hmac_secret,
+                }))
+            }
             None => Err(tonic::Status::new(
                 tonic::Code::InvalidArgument,
                 "missing signer config",
@@ -154,6 +306,17 @@ impl From<AccountXPub> for proto::Xpub {
                 .derivation_path()
                 .map(|derivation_path| derivation_path.to_string()),
             has_signer_config: xpub.has_signer_config(),
+            signer_health: xpub.signer_health().cloned().map(proto::SignerHealth::from),
+        }
+    }
+}
+
+impl From<SignerHealth> for proto::SignerHealth {
+    fn from(health: SignerHealth) -> Self {
+        Self {
+            healthy: health.healthy,
+            error: health.error,
+            checked_at: health.checked_at.timestamp() as u32,
         }
     }
 }
@@ -167,6 +330,9 @@ impl From<WalletUtxo> for proto::Utxo {
             address: utxo.address.map(|a| a.to_string()),
             change_output: utxo.kind == KeychainKind::Internal,
             block_height: utxo.block_height,
+            quarantined: utxo.quarantined,
+            frozen: utxo.frozen,
+            frozen_reason: utxo.frozen_reason,
         }
     }
 }
@@ -183,6 +349,8 @@ impl From<KeychainUtxos> for proto::KeychainUtxos {
 impl From<Payout> for proto::Payout {
     fn from(payout: Payout) -> Self {
         let cancelled = payout.is_cancelled();
+        let scheduled = payout.is_scheduled();
+        let awaiting_approval = payout.is_awaiting_approval();
         let destination = match payout.destination {
             PayoutDestination::OnchainAddress { value } => {
                 proto::payout::Destination::OnchainAddress(value.to_string())
@@ -198,6 +366,19 @@ impl From<Payout> for proto::Payout {
             destination: Some(destination),
             cancelled,
             external_id: payout.external_id,
+            priority: payout.priority,
+            execute_after: payout
+                .execute_after
+                .map(|execute_after| execute_after.timestamp() as u32),
+            scheduled,
+            required_approvals: payout.required_approvals,
+            approved_by: payout
+                .approved_by
+                .into_iter()
+                .map(|id| id.to_string())
+                .collect(),
+            awaiting_approval,
+            rejected_by: payout.rejected_by.map(|id| id.to_string()),
             metadata: payout.metadata.map(|json| {
                 serde_json::from_value(json).expect("Could not transfer json -> struct")
             }),
@@ -223,6 +404,7 @@ impl From<WalletConfig> for proto::WalletConfig {
         Self {
             settle_income_after_n_confs: config.settle_income_after_n_confs,
             settle_change_after_n_confs: config.settle_change_after_n_confs,
+            dust_threshold_sats: config.dust_threshold_sats.map(u64::from),
         }
     }
 }
@@ -238,12 +420,67 @@ impl From<PayoutQueue> for proto::PayoutQueue {
                 proto::payout_queue_config::Trigger::IntervalSecs(seconds.as_secs() as u32)
             }
             PayoutQueueTrigger::Manual => proto::payout_queue_config::Trigger::Manual(true),
+            PayoutQueueTrigger::PayoutTotal {
+                min_total_sats,
+                max_interval,
+            } => proto::payout_queue_config::Trigger::PayoutTotal(proto::PayoutTotalTrigger {
+                min_total_sats: u64::from(min_total_sats),
+                max_interval_secs: max_interval.map(|interval| interval.as_secs() as u32),
+            }),
+            PayoutQueueTrigger::PayoutCount {
+                min_payouts,
+                max_interval,
+            } => proto::payout_queue_config::Trigger::PayoutCount(proto::PayoutCountTrigger {
+                min_payouts: min_payouts as u32,
+                max_interval_secs: max_interval.map(|interval| interval.as_secs() as u32),
+            }),
+            PayoutQueueTrigger::PayoutAge {
+                max_age,
+                max_interval,
+            } => proto::payout_queue_config::Trigger::PayoutAge(proto::PayoutAgeTrigger {
+                max_age_secs: max_age.as_secs() as u32,
+                max_interval_secs: max_interval.map(|interval| interval.as_secs() as u32),
+            }),
+            PayoutQueueTrigger::FeeRateWindow {
+                max_sats_per_vbyte,
+                deadline,
+            } => proto::payout_queue_config::Trigger::FeeRateWindow(proto::FeeRateWindowTrigger {
+                max_sats_per_vbyte,
+                deadline_secs: deadline.as_secs() as u32,
+            }),
+        };
+        let fee_strategy = match payout_queue.config.fee_strategy {
+            FeeStrategy::PriorityMultiplier { multiplier } => {
+                proto::payout_queue_config::FeeStrategy::PriorityMultiplier(
+                    multiplier.to_f64().expect("multiplier overflow"),
+                )
+            }
+            FeeStrategy::FixedRate { sats_per_vbyte } => {
+                proto::payout_queue_config::FeeStrategy::FixedSatsPerVbyte(sats_per_vbyte)
+            }
+            FeeStrategy::TargetBlocks { n_blocks } => {
+                proto::payout_queue_config::FeeStrategy::TargetBlocks(n_blocks)
+            }
         };
         let tx_priority: proto::TxPriority = payout_queue.config.tx_priority.into();
         let config = Some(proto::PayoutQueueConfig {
+            fee_strategy: Some(fee_strategy),
             trigger: Some(trigger),
             tx_priority: tx_priority as i32,
             consolidate_deprecated_keychains,
+            max_fee_rate_sats_per_vbyte: payout_queue.config.max_fee_rate_sats_per_vbyte,
+            max_payouts_per_batch: payout_queue
+                .config
+                .max_payouts_per_batch
+                .map(|max_payouts| max_payouts as u32),
+            max_tx_vbytes: payout_queue.config.max_tx_vbytes,
+            approval: payout_queue
+                .config
+                .approval
+                .map(|approval| proto::PayoutApprovalConfig {
+                    min_sats: u64::from(approval.min_sats),
+                    required_approvals: approval.required_approvals,
+                }),
         });
         proto::PayoutQueue {
             id,
@@ -276,8 +513,22 @@ impl From<SigningSession> for proto::SigningSession {
     }
 }
 
-impl From<proto::PayoutQueueConfig> for PayoutQueueConfig {
-    fn from(proto_config: proto::PayoutQueueConfig) -> Self {
+impl From<InputSigningStatus> for proto::InputSigningStatus {
+    fn from(status: InputSigningStatus) -> Self {
+        proto::InputSigningStatus {
+            outpoint: status.outpoint.to_string(),
+            required_signatures: status.required_signatures as u32,
+            signed_by: status.signed_by.iter().map(|id| id.to_string()).collect(),
+            missing: status.missing.iter().map(|id| id.to_string()).collect(),
+            finalized: status.finalized,
+        }
+    }
+}
+
+impl TryFrom<proto::PayoutQueueConfig> for PayoutQueueConfig {
+    type Error = tonic::Status;
+
+    fn try_from(proto_config: proto::PayoutQueueConfig) -> Result<Self, Self::Error> {
         let tx_priority =
             proto::TxPriority::from_i32(proto_config.tx_priority).map(TxPriority::from);
         let consolidate_deprecated_keychains = proto_config.consolidate_deprecated_keychains;
@@ -291,11 +542,81 @@ impl From<proto::PayoutQueueConfig> for PayoutQueueConfig {
             Some(proto::payout_queue_config::Trigger::Manual(true)) => {
                 Some(PayoutQueueTrigger::Manual)
             }
+            Some(proto::payout_queue_config::Trigger::PayoutTotal(trigger)) => {
+                Some(PayoutQueueTrigger::PayoutTotal {
+                    min_total_sats: Satoshis::from(trigger.min_total_sats),
+                    max_interval: trigger
+                        .max_interval_secs
+                        .map(|secs| Duration::from_secs(secs as u64)),
+                })
+            }
+            Some(proto::payout_queue_config::Trigger::PayoutCount(trigger)) => {
+                Some(PayoutQueueTrigger::PayoutCount {
+                    min_payouts: trigger.min_payouts as usize,
+                    max_interval: trigger
+                        .max_interval_secs
+                        .map(|secs| Duration::from_secs(secs as u64)),
+                })
+            }
+            Some(proto::payout_queue_config::Trigger::PayoutAge(trigger)) => {
+                Some(PayoutQueueTrigger::PayoutAge {
+                    max_age: Duration::from_secs(trigger.max_age_secs as u64),
+                    max_interval: trigger
+                        .max_interval_secs
+                        .map(|secs| Duration::from_secs(secs as u64)),
+                })
+            }
+            Some(proto::payout_queue_config::Trigger::FeeRateWindow(trigger)) => {
+                Some(PayoutQueueTrigger::FeeRateWindow {
+                    max_sats_per_vbyte: trigger.max_sats_per_vbyte,
+                    deadline: Duration::from_secs(trigger.deadline_secs as u64),
+                })
+            }
             _ => None,
         };
 
+        let fee_strategy = match proto_config.fee_strategy {
+            Some(proto::payout_queue_config::FeeStrategy::PriorityMultiplier(multiplier)) => {
+                let multiplier = rust_decimal::Decimal::try_from(multiplier)
+                    .ok()
+                    .filter(|multiplier| *multiplier > rust_decimal::Decimal::ZERO)
+                    .ok_or_else(|| {
+                        tonic::Status::invalid_argument(
+                            "priority multiplier must be a positive number",
+                        )
+                    })?;
+                Some(FeeStrategy::PriorityMultiplier { multiplier })
+            }
+            Some(proto::payout_queue_config::FeeStrategy::FixedSatsPerVbyte(sats_per_vbyte)) => {
+                if sats_per_vbyte == 0 {
+                    return Err(tonic::Status::invalid_argument(
+                        "fixed sats per vbyte must be a positive number",
+                    ));
+                }
+                Some(FeeStrategy::FixedRate { sats_per_vbyte })
+            }
+            Some(proto::payout_queue_config::FeeStrategy::TargetBlocks(n_blocks)) => {
+                if !(1..=MAX_TARGET_BLOCKS).contains(&n_blocks) {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "target blocks must be between 1 and {MAX_TARGET_BLOCKS}"
+                    )));
+                }
+                Some(FeeStrategy::TargetBlocks { n_blocks })
+            }
+            None => None,
+        };
+
         let mut ret = Self {
             consolidate_deprecated_keychains,
+            max_fee_rate_sats_per_vbyte: proto_config.max_fee_rate_sats_per_vbyte,
+            max_payouts_per_batch: proto_config
+                .max_payouts_per_batch
+                .map(|max_payouts| max_payouts as usize),
+            max_tx_vbytes: proto_config.max_tx_vbytes,
+            approval: proto_config.approval.map(|approval| PayoutApprovalConfig {
+                min_sats: Satoshis::from(approval.min_sats),
+                required_approvals: approval.required_approvals,
+            }),
             ..Self::default()
         };
 
@@ -305,7 +626,20 @@ impl From<proto::PayoutQueueConfig> for PayoutQueueConfig {
         if let Some(tx_priority) = tx_priority {
             ret.tx_priority = tx_priority;
         }
-        ret
+        if let Some(fee_strategy) = fee_strategy {
+            ret.fee_strategy = fee_strategy;
+        }
+        Ok(ret)
+    }
+}
+
+impl From<PayoutSkipReason> for proto::PayoutSkipReason {
+    fn from(reason: PayoutSkipReason) -> Self {
+        match reason {
+            PayoutSkipReason::InsufficientFunds => proto::PayoutSkipReason::InsufficientFunds,
+            PayoutSkipReason::Dust => proto::PayoutSkipReason::Dust,
+            PayoutSkipReason::Blocked => proto::PayoutSkipReason::Blocked,
+        }
     }
 }
 
@@ -366,6 +700,9 @@ impl From<WalletBalanceSummary> for proto::GetWalletBalanceSummaryResponse {
                 .expect("Satoshis -> u64 failed"),
             effective_encumbered_outgoing: u64::try_from(balance.effective_encumbered_outgoing)
                 .expect("Satoshis -> u64 failed"),
+            utxo_frozen: u64::from(balance.utxo_frozen),
+            dust_pending_income: u64::from(balance.dust_pending_income),
+            dust_settled: u64::from(balance.dust_settled),
         }
     }
 }
@@ -443,6 +780,70 @@ impl From<OutboxEvent<Augmentation>> for proto::BriaEvent {
                 satoshis: u64::from(satoshis),
                 address: address.to_string(),
             }),
+            OutboxEventPayload::UtxoQuarantined {
+                tx_id,
+                vout,
+                satoshis,
+                address,
+                wallet_id,
+                source_address,
+                ..
+            } => proto::bria_event::Payload::UtxoQuarantined(proto::UtxoQuarantined {
+                wallet_id: wallet_id.to_string(),
+                tx_id: tx_id.to_string(),
+                vout,
+                satoshis: u64::from(satoshis),
+                address: address.to_string(),
+                source_address: source_address.to_string(),
+            }),
+            OutboxEventPayload::UtxoDustDetected {
+                tx_id,
+                vout,
+                satoshis,
+                address,
+                wallet_id,
+                ..
+            } => proto::bria_event::Payload::UtxoDustDetected(proto::UtxoDustDetected {
+                wallet_id: wallet_id.to_string(),
+                tx_id: tx_id.to_string(),
+                vout,
+                satoshis: u64::from(satoshis),
+                address: address.to_string(),
+            }),
+            OutboxEventPayload::UtxoFrozen {
+                tx_id,
+                vout,
+                satoshis,
+                address,
+                wallet_id,
+                profile_id,
+                reason,
+                ..
+            } => proto::bria_event::Payload::UtxoFrozen(proto::UtxoFrozen {
+                wallet_id: wallet_id.to_string(),
+                tx_id: tx_id.to_string(),
+                vout,
+                satoshis: u64::from(satoshis),
+                address: address.to_string(),
+                profile_id: profile_id.to_string(),
+                reason,
+            }),
+            OutboxEventPayload::UtxoUnfrozen {
+                tx_id,
+                vout,
+                satoshis,
+                address,
+                wallet_id,
+                profile_id,
+                ..
+            } => proto::bria_event::Payload::UtxoUnfrozen(proto::UtxoUnfrozen {
+                wallet_id: wallet_id.to_string(),
+                tx_id: tx_id.to_string(),
+                vout,
+                satoshis: u64::from(satoshis),
+                address: address.to_string(),
+                profile_id: profile_id.to_string(),
+            }),
             OutboxEventPayload::PayoutSubmitted {
                 id,
                 wallet_id,
@@ -519,6 +920,30 @@ impl From<OutboxEvent<Augmentation>> for proto::BriaEvent {
                 )),
                 proportional_fee_sats: u64::from(proportional_fee),
             }),
+            OutboxEventPayload::PayoutFeeBumped {
+                id,
+                tx_id,
+                replaced_tx_id,
+                vout,
+                wallet_id,
+                payout_queue_id,
+                satoshis,
+                destination: PayoutDestination::OnchainAddress { value: destination },
+                proportional_fee,
+                ..
+            } => proto::bria_event::Payload::PayoutFeeBumped(proto::PayoutFeeBumped {
+                id: id.to_string(),
+                tx_id: tx_id.to_string(),
+                replaced_tx_id: replaced_tx_id.to_string(),
+                vout,
+                wallet_id: wallet_id.to_string(),
+                payout_queue_id: payout_queue_id.to_string(),
+                satoshis: u64::from(satoshis),
+                destination: Some(proto::payout_fee_bumped::Destination::OnchainAddress(
+                    destination.to_string(),
+                )),
+                proportional_fee_sats: u64::from(proportional_fee),
+            }),
             OutboxEventPayload::PayoutSettled {
                 id,
                 tx_id,
@@ -541,6 +966,104 @@ impl From<OutboxEvent<Augmentation>> for proto::BriaEvent {
                 )),
                 proportional_fee_sats: u64::from(proportional_fee),
             }),
+            OutboxEventPayload::PayoutQueueFeeRateExceeded {
+                payout_queue_id,
+                fee_rate_sats_per_vbyte,
+                max_fee_rate_sats_per_vbyte,
+            } => proto::bria_event::Payload::PayoutQueueFeeRateExceeded(
+                proto::PayoutQueueFeeRateExceeded {
+                    payout_queue_id: payout_queue_id.to_string(),
+                    fee_rate_sats_per_vbyte,
+                    max_fee_rate_sats_per_vbyte,
+                },
+            ),
+            OutboxEventPayload::PayoutSkipped {
+                id,
+                wallet_id,
+                payout_queue_id,
+                satoshis,
+                destination: PayoutDestination::OnchainAddress { value: destination },
+                reason,
+            } => proto::bria_event::Payload::PayoutSkipped(proto::PayoutSkipped {
+                id: id.to_string(),
+                wallet_id: wallet_id.to_string(),
+                payout_queue_id: payout_queue_id.to_string(),
+                satoshis: u64::from(satoshis),
+                destination: Some(proto::payout_skipped::Destination::OnchainAddress(
+                    destination.to_string(),
+                )),
+                reason: proto::PayoutSkipReason::from(reason) as i32,
+            }),
+            OutboxEventPayload::PayoutApprovalRequested {
+                id,
+                wallet_id,
+                payout_queue_id,
+                satoshis,
+                destination: PayoutDestination::OnchainAddress { value: destination },
+                required_approvals,
+            } => proto::bria_event::Payload::PayoutApprovalRequested(
+                proto::PayoutApprovalRequested {
+                    id: id.to_string(),
+                    wallet_id: wallet_id.to_string(),
+                    payout_queue_id: payout_queue_id.to_string(),
+                    satoshis: u64::from(satoshis),
+                    destination: Some(
+                        proto::payout_approval_requested::Destination::OnchainAddress(
+                            destination.to_string(),
+                        ),
+                    ),
+                    required_approvals,
+                },
+            ),
+            OutboxEventPayload::PayoutApproved {
+                id,
+                wallet_id,
+                payout_queue_id,
+                satoshis,
+                destination: PayoutDestination::OnchainAddress { value: destination },
+                approved_by,
+                approvals,
+                required_approvals,
+            } => proto::bria_event::Payload::PayoutApproved(proto::PayoutApproved {
+                id: id.to_string(),
+                wallet_id: wallet_id.to_string(),
+                payout_queue_id: payout_queue_id.to_string(),
+                satoshis: u64::from(satoshis),
+                destination: Some(proto::payout_approved::Destination::OnchainAddress(
+                    destination.to_string(),
+                )),
+                approved_by: approved_by.to_string(),
+                approvals,
+                required_approvals,
+            }),
+            OutboxEventPayload::PayoutRejected {
+                id,
+                wallet_id,
+                payout_queue_id,
+                satoshis,
+                destination: PayoutDestination::OnchainAddress { value: destination },
+                rejected_by,
+            } => proto::bria_event::Payload::PayoutRejected(proto::PayoutRejected {
+                id: id.to_string(),
+                wallet_id: wallet_id.to_string(),
+                payout_queue_id: payout_queue_id.to_string(),
+                satoshis: u64::from(satoshis),
+                destination: Some(proto::payout_rejected::Destination::OnchainAddress(
+                    destination.to_string(),
+                )),
+                rejected_by: rejected_by.to_string(),
+            }),
+            OutboxEventPayload::SigningSessionFailed {
+                id,
+                batch_id,
+                xpub_id,
+                reason,
+            } => proto::bria_event::Payload::SigningSessionFailed(proto::SigningSessionFailed {
+                id: id.to_string(),
+                batch_id: batch_id.to_string(),
+                xpub_id: xpub_id.to_string(),
+                reason,
+            }),
         };
 
         let augmentation = event.augmentation.map(|a| proto::EventAugmentation {
@@ -571,8 +1094,8 @@ impl From<AddressAugmentation> for proto::WalletAddress {
 impl From<ApplicationError> for tonic::Status {
     fn from(err: ApplicationError) -> Self {
         use crate::{
-            address::error::*, payout::error::*, payout_queue::error::*, profile::error::*,
-            wallet::error::*,
+            address::error::*, batch::error::*, payout::error::*, payout_queue::error::*,
+            profile::error::*, utxo::error::*, wallet::error::*, xpub::error::*,
         };
 
         match err {
@@ -603,9 +1126,51 @@ impl From<ApplicationError> for tonic::Status {
             ApplicationError::ProfileError(ProfileError::ProfileNameNotFound(_)) => {
                 tonic::Status::not_found(err.to_string())
             }
+            ApplicationError::ProfileError(ProfileError::ProfileIdNotFound(_)) => {
+                tonic::Status::not_found(err.to_string())
+            }
+            ApplicationError::ProfileError(ProfileError::ProfileApiKeyIdNotFound(_)) => {
+                tonic::Status::not_found(err.to_string())
+            }
+            ApplicationError::ProfileError(ProfileError::ProfileKeyExpired) => {
+                tonic::Status::unauthenticated(err.to_string())
+            }
+            ApplicationError::ProfileError(ProfileError::AccountDeactivated(_)) => {
+                tonic::Status::permission_denied(err.to_string())
+            }
+            ApplicationError::ProfileError(ProfileError::ApiKeyScopeNotPermitted(_)) => {
+                tonic::Status::permission_denied(err.to_string())
+            }
+            ApplicationError::ProfileError(
+                ProfileError::ProfileManagementNotAllowed
+                | ProfileError::AccountLevelAccessNotAllowed
+                | ProfileError::NotAPayoutApprover(_)
+                | ProfileError::ApproverKeyNotAllowed(_)
+                | ProfileError::WalletNotAllowed(_)
+                | ProfileError::PayoutQueueNotAllowed(_)
+                | ProfileError::DestinationNotAllowed(_)
+                | ProfileError::PayoutLimitExceeded(_)
+                | ProfileError::DailyPayoutLimitExceeded(_),
+            ) => tonic::Status::permission_denied(err.to_string()),
             ApplicationError::PayoutError(PayoutError::PayoutIdNotFound(_)) => {
                 tonic::Status::not_found(err.to_string())
             }
+            ApplicationError::PayoutError(
+                PayoutError::PayoutNotAwaitingApproval(_)
+                | PayoutError::AlreadyApprovedByProfile(_),
+            ) => tonic::Status::failed_precondition(err.to_string()),
+            ApplicationError::PayoutError(
+                PayoutError::SubmitterCannotApprove | PayoutError::ApproverCreatedBySubmitter,
+            ) => tonic::Status::permission_denied(err.to_string()),
+            ApplicationError::XPubError(XPubError::SigningClient(
+                SigningClientError::InvalidXpriv(_),
+            )) => tonic::Status::invalid_argument(err.to_string()),
+            ApplicationError::ProfileError(ProfileError::ApiKeyExpiryInPast(_)) => {
+                tonic::Status::invalid_argument(err.to_string())
+            }
+            ApplicationError::XPubError(XPubError::SignerConfigMissing) => {
+                tonic::Status::failed_precondition(err.to_string())
+            }
             ApplicationError::CouldNotParseIncomingMetadata(_) => {
                 tonic::Status::invalid_argument(err.to_string())
             }
@@ -624,6 +1189,17 @@ impl From<ApplicationError> for tonic::Status {
             ApplicationError::SigningSessionNotFoundForXPubId(_) => {
                 tonic::Status::not_found(err.to_string())
             }
+            ApplicationError::SigningSessionError(SigningSessionError::NotFound(_)) => {
+                tonic::Status::not_found(err.to_string())
+            }
+            ApplicationError::SigningSessionError(
+                SigningSessionError::UnknownState(_) | SigningSessionError::CouldNotCombinePsbt(_),
+            ) => tonic::Status::invalid_argument(err.to_string()),
+            ApplicationError::SigningSessionError(
+                SigningSessionError::AlreadyComplete(_)
+                | SigningSessionError::Aborted(_)
+                | SigningSessionError::NothingToAbort(_),
+            ) => tonic::Status::failed_precondition(err.to_string()),
             ApplicationError::WalletError(WalletError::PsbtDoesNotHaveValidSignatures) => {
                 tonic::Status::invalid_argument(err.to_string())
             }
@@ -636,6 +1212,39 @@ impl From<ApplicationError> for tonic::Status {
             ApplicationError::PayoutAlreadyCommitted => {
                 tonic::Status::failed_precondition(err.to_string())
             }
+            ApplicationError::BatchError(BatchError::BatchIdNotFound(_)) => {
+                tonic::Status::not_found(err.to_string())
+            }
+            ApplicationError::BatchError(BatchError::BatchNotBroadcast(_)) => {
+                tonic::Status::failed_precondition(err.to_string())
+            }
+            ApplicationError::BatchError(BatchError::BatchNotCancellable(_)) => {
+                tonic::Status::failed_precondition(err.to_string())
+            }
+            ApplicationError::BatchError(BatchError::BatchNotReplaceable(_)) => {
+                tonic::Status::failed_precondition(err.to_string())
+            }
+            ApplicationError::BatchError(BatchError::FeeRateTooLowForReplacement(_)) => {
+                tonic::Status::invalid_argument(err.to_string())
+            }
+            ApplicationError::BatchError(BatchError::InsufficientChangeForFeeBump(_)) => {
+                tonic::Status::failed_precondition(err.to_string())
+            }
+            ApplicationError::BatchError(BatchError::BatchModifiedConcurrently(_)) => {
+                tonic::Status::aborted(err.to_string())
+            }
+            ApplicationError::BatchChangeNotReplaceable(_) => {
+                tonic::Status::failed_precondition(err.to_string())
+            }
+            ApplicationError::CouldNotParseIncomingOutpoint(_) => {
+                tonic::Status::invalid_argument(err.to_string())
+            }
+            ApplicationError::UtxoError(
+                UtxoError::UnspentUtxoNotFound(_) | UtxoError::FrozenUtxoNotFound(_),
+            ) => tonic::Status::not_found(err.to_string()),
+            ApplicationError::UtxoCannotBeAccelerated(_) => {
+                tonic::Status::failed_precondition(err.to_string())
+            }
             _ => tonic::Status::internal(err.to_string()),
         }
     }
@@ -5,6 +5,7 @@ pub mod proto {
     tonic::include_proto!("services.bria.v1");
 }
 
+use chrono::TimeZone;
 use futures::StreamExt;
 use opentelemetry::{
     propagation::{Extractor, TextMapPropagator},
@@ -19,8 +20,11 @@ use proto::{bria_service_server::BriaService, *};
 use super::config::*;
 use crate::{
     app::{error::ApplicationError, *},
+    audit::{AuditFilter, AuditOutcome},
     payout_queue,
     primitives::*,
+    profile::{check_scopes, ApiAccess, ApiKeyScope, Profile, ProfilePolicy},
+    wallet,
 };
 
 pub const PROFILE_API_KEY_HEADER: &str = "x-bria-api-key";
@@ -29,6 +33,52 @@ pub struct Bria {
     app: App,
 }
 
+impl Bria {
+    async fn authenticate(
+        &self,
+        key: &str,
+        access: ApiAccess,
+    ) -> Result<Profile, ApplicationError> {
+        let (profile, scopes) = self.app.authenticate(key).await?;
+        check_scopes(&scopes, access)?;
+        Ok(profile)
+    }
+
+    /// Runs `handler` and records the request in the audit log together with its outcome
+    #[allow(clippy::result_large_err)]
+    async fn audited<T, R, F, Fut>(
+        &self,
+        profile: Profile,
+        rpc: &str,
+        request: Request<T>,
+        handler: F,
+    ) -> Result<Response<R>, Status>
+    where
+        T: serde::Serialize + Clone,
+        F: FnOnce(Profile, Request<T>) -> Fut,
+        Fut: std::future::Future<Output = Result<Response<R>, Status>>,
+    {
+        let audited_request = request.get_ref().clone();
+        let result = handler(profile.clone(), request).await;
+        let outcome = match &result {
+            Ok(_) => AuditOutcome::Succeeded,
+            Err(status) => AuditOutcome::Failed {
+                error: status.message().to_string(),
+            },
+        };
+        // The handler has already committed its changes, so a failure to record the
+        // audit event must not be reported as a failure of the call itself
+        if let Err(err) = self
+            .app
+            .record_audit_event(&profile, rpc, &audited_request, outcome)
+            .await
+        {
+            tracing::error!(%err, rpc, "could not record audit event");
+        }
+        result
+    }
+}
+
 #[tonic::async_trait]
 impl BriaService for Bria {
     #[instrument(name = "bria.create_profile", skip_all, fields(error, error.level, error.message), err)]
@@ -40,12 +90,62 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let request = request.into_inner();
-            let profile = self.app.create_profile(profile, request.name).await?;
-            Ok(Response::new(CreateProfileResponse {
-                id: profile.id.to_string(),
-            }))
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "CreateProfile",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let profile = self
+                        .app
+                        .create_profile(
+                            profile,
+                            request.name,
+                            request.policy.map(ProfilePolicy::try_from).transpose()?,
+                            request.payout_approver,
+                        )
+                        .await?;
+                    Ok(Response::new(CreateProfileResponse {
+                        id: profile.id.to_string(),
+                    }))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.update_profile", skip_all, fields(error, error.level, error.message), err)]
+    async fn update_profile(
+        &self,
+        request: Request<UpdateProfileRequest>,
+    ) -> Result<Response<UpdateProfileResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "UpdateProfile",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    self.app
+                        .update_profile(
+                            profile,
+                            request
+                                .id
+                                .parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                            request.policy.map(ProfilePolicy::try_from).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::new(UpdateProfileResponse {}))
+                },
+            )
+            .await
         })
         .await
     }
@@ -59,7 +159,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let profiles = self.app.list_profiles(profile).await?;
             let profile_messages: Vec<proto::Profile> =
                 profiles.into_iter().map(proto::Profile::from).collect();
@@ -80,20 +180,101 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "CreateProfileApiKey",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let scopes = request
+                        .scopes
+                        .iter()
+                        .map(|scope| proto::ApiKeyScope::from_i32(*scope).map(ApiKeyScope::from))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| Status::invalid_argument("unknown api key scope"))?;
+                    let expires_at = match request.expires_at {
+                        Some(expires_at) => Some(
+                            chrono::Utc
+                                .timestamp_opt(expires_at as i64, 0)
+                                .single()
+                                .ok_or_else(|| Status::invalid_argument("invalid expires_at"))?,
+                        ),
+                        None => None,
+                    };
+                    let key = self
+                        .app
+                        .create_profile_api_key(profile, request.profile_name, scopes, expires_at)
+                        .await?;
+                    Ok(Response::new(CreateProfileApiKeyResponse {
+                        id: key.id.to_string(),
+                        key: key.key,
+                    }))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.list_profile_api_keys", skip_all, fields(error, error.level, error.message), err)]
+    async fn list_profile_api_keys(
+        &self,
+        request: Request<ListProfileApiKeysRequest>,
+    ) -> Result<Response<ListProfileApiKeysResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let request = request.into_inner();
-            let key = self
+            let keys = self
                 .app
-                .create_profile_api_key(profile, request.profile_name)
+                .list_profile_api_keys(profile, request.profile_name)
                 .await?;
-            Ok(Response::new(CreateProfileApiKeyResponse {
-                id: key.id.to_string(),
-                key: key.key,
+            Ok(Response::new(ListProfileApiKeysResponse {
+                keys: keys
+                    .into_iter()
+                    .map(proto::ProfileApiKeySummary::from)
+                    .collect(),
             }))
         })
         .await
     }
 
+    #[instrument(name = "bria.revoke_profile_api_key", skip_all, fields(error, error.level, error.message), err)]
+    async fn revoke_profile_api_key(
+        &self,
+        request: Request<RevokeProfileApiKeyRequest>,
+    ) -> Result<Response<RevokeProfileApiKeyResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "RevokeProfileApiKey",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    self.app
+                        .revoke_profile_api_key(
+                            profile,
+                            request
+                                .id
+                                .parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                        )
+                        .await?;
+                    Ok(Response::new(RevokeProfileApiKeyResponse {}))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
     #[instrument(name = "bria.import_xpub", skip_all, fields(error, error.level, error.message), err)]
     async fn import_xpub(
         &self,
@@ -103,22 +284,30 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let ImportXpubRequest {
-                name,
-                xpub,
-                derivation,
-            } = request.into_inner();
-            let derivation = if derivation.is_empty() {
-                None
-            } else {
-                Some(derivation)
-            };
-            let id = self
-                .app
-                .import_xpub(profile, name, xpub, derivation)
-                .await?;
-            Ok(Response::new(ImportXpubResponse { id: id.to_string() }))
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "ImportXpub",
+                request,
+                |profile, request| async move {
+                    let ImportXpubRequest {
+                        name,
+                        xpub,
+                        derivation,
+                    } = request.into_inner();
+                    let derivation = if derivation.is_empty() {
+                        None
+                    } else {
+                        Some(derivation)
+                    };
+                    let id = self
+                        .app
+                        .import_xpub(profile, name, xpub, derivation)
+                        .await?;
+                    Ok(Response::new(ImportXpubResponse { id: id.to_string() }))
+                },
+            )
+            .await
         })
         .await
     }
@@ -132,7 +321,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let xpubs = self.app.list_xpubs(profile).await?;
             let xpub_messages: Vec<proto::Xpub> =
                 xpubs.into_iter().map(proto::Xpub::from).collect();
@@ -153,12 +342,47 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let SetSignerConfigRequest { xpub_ref, config } = request.into_inner();
-            self.app
-                .set_signer_config(profile, xpub_ref, config.try_into()?)
-                .await?;
-            Ok(Response::new(SetSignerConfigResponse {}))
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "SetSignerConfig",
+                request,
+                |profile, request| async move {
+                    let SetSignerConfigRequest { xpub_ref, config } = request.into_inner();
+                    self.app
+                        .set_signer_config(profile, xpub_ref, config.try_into()?)
+                        .await?;
+                    Ok(Response::new(SetSignerConfigResponse {}))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.test_signer_config", skip_all, fields(error, error.level, error.message), err)]
+    async fn test_signer_config(
+        &self,
+        request: Request<TestSignerConfigRequest>,
+    ) -> Result<Response<TestSignerConfigResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "TestSignerConfig",
+                request,
+                |profile, request| async move {
+                    let TestSignerConfigRequest { xpub_ref } = request.into_inner();
+                    let signer_health = self.app.test_signer_config(profile, xpub_ref).await?;
+                    Ok(Response::new(TestSignerConfigResponse {
+                        signer_health: Some(proto::SignerHealth::from(signer_health)),
+                    }))
+                },
+            )
+            .await
         })
         .await
     }
@@ -171,26 +395,141 @@ impl BriaService for Bria {
         crate::tracing::record_error(|| async move {
             extract_tracing(&request);
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let request = request.into_inner();
-            let SubmitSignedPsbtRequest {
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "SubmitSignedPsbt",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let SubmitSignedPsbtRequest {
+                        batch_id,
+                        xpub_ref,
+                        signed_psbt,
+                    } = request;
+                    self.app
+                        .submit_signed_psbt(
+                            profile,
+                            batch_id
+                                .parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                            xpub_ref,
+                            signed_psbt
+                                .parse::<bitcoin::psbt::PartiallySignedTransaction>()
+                                .map_err(ApplicationError::CouldNotParseIncomingPsbt)?,
+                        )
+                        .await?;
+                    Ok(Response::new(SubmitSignedPsbtResponse {}))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.list_signing_sessions", skip_all, fields(error, error.level, error.message), err)]
+    async fn list_signing_sessions(
+        &self,
+        request: Request<ListSigningSessionsRequest>,
+    ) -> Result<Response<ListSigningSessionsResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
+            let ListSigningSessionsRequest {
                 batch_id,
                 xpub_ref,
-                signed_psbt,
-            } = request;
-            self.app
-                .submit_signed_psbt(
+                state,
+                after_id,
+                limit,
+            } = request.into_inner();
+            let sessions = self
+                .app
+                .list_signing_sessions(
                     profile,
                     batch_id
-                        .parse()
+                        .map(|id| id.parse())
+                        .transpose()
                         .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
                     xpub_ref,
-                    signed_psbt
-                        .parse::<bitcoin::psbt::PartiallySignedTransaction>()
-                        .map_err(ApplicationError::CouldNotParseIncomingPsbt)?,
+                    state
+                        .map(|state| state.parse())
+                        .transpose()
+                        .map_err(ApplicationError::from)?,
+                    after_id
+                        .map(|id| id.parse())
+                        .transpose()
+                        .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                    limit,
                 )
                 .await?;
-            Ok(Response::new(SubmitSignedPsbtResponse {}))
+            Ok(Response::new(ListSigningSessionsResponse {
+                signing_sessions: sessions
+                    .into_iter()
+                    .map(proto::SigningSession::from)
+                    .collect(),
+            }))
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.retry_signing_session", skip_all, fields(error, error.level, error.message), err)]
+    async fn retry_signing_session(
+        &self,
+        request: Request<RetrySigningSessionRequest>,
+    ) -> Result<Response<RetrySigningSessionResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "RetrySigningSession",
+                request,
+                |profile, request| async move {
+                    let RetrySigningSessionRequest { id } = request.into_inner();
+                    self.app
+                        .retry_signing_session(
+                            profile,
+                            id.parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                        )
+                        .await?;
+                    Ok(Response::new(RetrySigningSessionResponse {}))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.abort_batch_signing", skip_all, fields(error, error.level, error.message), err)]
+    async fn abort_batch_signing(
+        &self,
+        request: Request<AbortBatchSigningRequest>,
+    ) -> Result<Response<AbortBatchSigningResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "AbortBatchSigning",
+                request,
+                |profile, request| async move {
+                    let AbortBatchSigningRequest { batch_id } = request.into_inner();
+                    self.app
+                        .abort_batch_signing(
+                            profile,
+                            batch_id
+                                .parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                        )
+                        .await?;
+                    Ok(Response::new(AbortBatchSigningResponse {}))
+                },
+            )
+            .await
         })
         .await
     }
@@ -204,52 +543,59 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let CreateWalletRequest {
-                name,
-                keychain_config,
-            } = request.into_inner();
-            let (id, xpub_ids) = match keychain_config {
-                Some(KeychainConfig {
-                    config:
-                        Some(keychain_config::Config::Wpkh(keychain_config::Wpkh {
-                            xpub,
-                            derivation_path,
-                        })),
-                }) => {
-                    self.app
-                        .create_wpkh_wallet(profile, name, xpub, derivation_path)
-                        .await?
-                }
-                Some(KeychainConfig {
-                    config:
-                        Some(keychain_config::Config::Descriptors(keychain_config::Descriptors {
-                            external,
-                            internal,
-                        })),
-                }) => {
-                    self.app
-                        .create_descriptors_wallet(profile, name, external, internal)
-                        .await?
-                }
-                Some(KeychainConfig {
-                    config:
-                        Some(keychain_config::Config::SortedMultisig(
-                            keychain_config::SortedMultisig {
-                                xpubs,
-                                threshold,
-                            })),
-                }) => {
-                    self.app.create_sorted_multisig_wallet(profile, name, xpubs, threshold).await?
-                }
-                _ => {
-                    return Err(Status::invalid_argument("invalid keychain config"));
-                }
-            };
-            Ok(Response::new(CreateWalletResponse {
-                id: id.to_string(),
-                xpub_ids: xpub_ids.into_iter().map(|id| id.to_string()).collect(),
-            }))
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "CreateWallet",
+                request,
+                |profile, request| async move {
+                    let CreateWalletRequest {
+                        name,
+                        keychain_config,
+                    } = request.into_inner();
+                    let (id, xpub_ids) = match keychain_config {
+                        Some(KeychainConfig {
+                            config:
+                                Some(keychain_config::Config::Wpkh(keychain_config::Wpkh {
+                                    xpub,
+                                    derivation_path,
+                                })),
+                        }) => {
+                            self.app
+                                .create_wpkh_wallet(profile, name, xpub, derivation_path)
+                                .await?
+                        }
+                        Some(KeychainConfig {
+                            config:
+                                Some(keychain_config::Config::Descriptors(
+                                    keychain_config::Descriptors { external, internal },
+                                )),
+                        }) => {
+                            self.app
+                                .create_descriptors_wallet(profile, name, external, internal)
+                                .await?
+                        }
+                        Some(KeychainConfig {
+                            config:
+                                Some(keychain_config::Config::SortedMultisig(
+                                    keychain_config::SortedMultisig { xpubs, threshold },
+                                )),
+                        }) => {
+                            self.app
+                                .create_sorted_multisig_wallet(profile, name, xpubs, threshold)
+                                .await?
+                        }
+                        _ => {
+                            return Err(Status::invalid_argument("invalid keychain config"));
+                        }
+                    };
+                    Ok(Response::new(CreateWalletResponse {
+                        id: id.to_string(),
+                        xpub_ids: xpub_ids.into_iter().map(|id| id.to_string()).collect(),
+                    }))
+                },
+            )
+            .await
         })
         .await
     }
@@ -263,7 +609,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let request = request.into_inner();
             let balance = self
                 .app
@@ -286,7 +632,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let balance = self.app.get_account_balance_summary(profile).await?;
             Ok(Response::new(GetAccountBalanceSummaryResponse::from(
                 balance,
@@ -304,27 +650,35 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let request = request.into_inner();
-            let NewAddressRequest {
-                wallet_name,
-                external_id,
-                metadata,
-            } = request;
-
-            let address = self
-                .app
-                .new_address(
-                    profile,
-                    wallet_name,
-                    external_id,
-                    metadata
-                        .map(serde_json::to_value)
-                        .transpose()
-                        .map_err(ApplicationError::CouldNotParseIncomingMetadata)?,
-                )
-                .await?;
-            Ok(Response::new(NewAddressResponse { address }))
+            let profile = self.authenticate(key, ApiAccess::WriteAddresses).await?;
+            self.audited(
+                profile,
+                "NewAddress",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let NewAddressRequest {
+                        wallet_name,
+                        external_id,
+                        metadata,
+                    } = request;
+
+                    let address = self
+                        .app
+                        .new_address(
+                            profile,
+                            wallet_name,
+                            external_id,
+                            metadata
+                                .map(serde_json::to_value)
+                                .transpose()
+                                .map_err(ApplicationError::CouldNotParseIncomingMetadata)?,
+                        )
+                        .await?;
+                    Ok(Response::new(NewAddressResponse { address }))
+                },
+            )
+            .await
         })
         .await
     }
@@ -338,26 +692,34 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let request = request.into_inner();
-            let UpdateAddressRequest {
-                address,
-                new_external_id,
-                new_metadata,
-            } = request;
+            let profile = self.authenticate(key, ApiAccess::WriteAddresses).await?;
+            self.audited(
+                profile,
+                "UpdateAddress",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let UpdateAddressRequest {
+                        address,
+                        new_external_id,
+                        new_metadata,
+                    } = request;
 
-            self.app
-                .update_address(
-                    profile,
-                    address,
-                    new_external_id,
-                    new_metadata
-                        .map(serde_json::to_value)
-                        .transpose()
-                        .map_err(ApplicationError::CouldNotParseIncomingMetadata)?,
-                )
-                .await?;
-            Ok(Response::new(UpdateAddressResponse {}))
+                    self.app
+                        .update_address(
+                            profile,
+                            address,
+                            new_external_id,
+                            new_metadata
+                                .map(serde_json::to_value)
+                                .transpose()
+                                .map_err(ApplicationError::CouldNotParseIncomingMetadata)?,
+                        )
+                        .await?;
+                    Ok(Response::new(UpdateAddressResponse {}))
+                },
+            )
+            .await
         })
         .await
     }
@@ -371,7 +733,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::ReadAddresses).await?;
             let wallet_name = request.into_inner().wallet_name;
 
             let (wallet_id, addresses) = self
@@ -398,7 +760,7 @@ impl BriaService for Bria {
         crate::tracing::record_error(|| async move {
             extract_tracing(&request);
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::ReadAddresses).await?;
             let request = request.into_inner();
             let FindAddressByExternalIdRequest { external_id } = request;
 
@@ -424,7 +786,7 @@ impl BriaService for Bria {
         crate::tracing::record_error(|| async move {
             extract_tracing(&request);
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::ReadAddresses).await?;
             let request = request.into_inner();
             let addr = match request.identifier {
                 Some(get_address_request::Identifier::Address(address)) => {
@@ -456,7 +818,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let request = request.into_inner();
             let (wallet_id, keychain_utxos) =
                 self.app.list_utxos(profile, request.wallet_name).await?;
@@ -474,6 +836,80 @@ impl BriaService for Bria {
         .await
     }
 
+    #[instrument(name = "bria.freeze_utxo", skip_all, fields(error, error.level, error.message), err)]
+    async fn freeze_utxo(
+        &self,
+        request: Request<FreezeUtxoRequest>,
+    ) -> Result<Response<FreezeUtxoResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "FreezeUtxo",
+                request,
+                |profile, request| async move {
+                    let FreezeUtxoRequest {
+                        wallet_name,
+                        outpoint,
+                        reason,
+                    } = request.into_inner();
+                    self.app
+                        .freeze_utxo(
+                            profile,
+                            wallet_name,
+                            outpoint
+                                .parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingOutpoint)?,
+                            reason,
+                        )
+                        .await?;
+                    Ok(Response::new(FreezeUtxoResponse {}))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.unfreeze_utxo", skip_all, fields(error, error.level, error.message), err)]
+    async fn unfreeze_utxo(
+        &self,
+        request: Request<UnfreezeUtxoRequest>,
+    ) -> Result<Response<UnfreezeUtxoResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "UnfreezeUtxo",
+                request,
+                |profile, request| async move {
+                    let UnfreezeUtxoRequest {
+                        wallet_name,
+                        outpoint,
+                    } = request.into_inner();
+                    self.app
+                        .unfreeze_utxo(
+                            profile,
+                            wallet_name,
+                            outpoint
+                                .parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingOutpoint)?,
+                        )
+                        .await?;
+                    Ok(Response::new(UnfreezeUtxoResponse {}))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
     #[instrument(name = "bria.create_payout_queue", skip_all, fields(error, error.level, error.message), err)]
     async fn create_payout_queue(
         &self,
@@ -483,20 +919,31 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let request = request.into_inner();
-            let id = self
-                .app
-                .create_payout_queue(
-                    profile,
-                    request.name,
-                    request.description,
-                    request.config.map(payout_queue::PayoutQueueConfig::from),
-                )
-                .await?;
-            Ok(Response::new(CreatePayoutQueueResponse {
-                id: id.to_string(),
-            }))
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "CreatePayoutQueue",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let id = self
+                        .app
+                        .create_payout_queue(
+                            profile,
+                            request.name,
+                            request.description,
+                            request
+                                .config
+                                .map(payout_queue::PayoutQueueConfig::try_from)
+                                .transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::new(CreatePayoutQueueResponse {
+                        id: id.to_string(),
+                    }))
+                },
+            )
+            .await
         })
         .await
     }
@@ -509,11 +956,19 @@ impl BriaService for Bria {
         crate::tracing::record_error(|| async move {
             extract_tracing(&request);
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let request = request.into_inner();
-            let TriggerPayoutQueueRequest { name } = request;
-            self.app.trigger_payout_queue(profile, name).await?;
-            Ok(Response::new(TriggerPayoutQueueResponse {}))
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "TriggerPayoutQueue",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let TriggerPayoutQueueRequest { name } = request;
+                    self.app.trigger_payout_queue(profile, name).await?;
+                    Ok(Response::new(TriggerPayoutQueueResponse {}))
+                },
+            )
+            .await
         })
         .await
     }
@@ -527,7 +982,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let request = request.into_inner();
             let EstimatePayoutFeeRequest {
                 wallet_name,
@@ -562,33 +1017,50 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let request = request.into_inner();
-            let SubmitPayoutRequest {
-                wallet_name,
-                payout_queue_name,
-                destination,
-                satoshis,
-                external_id,
-                metadata,
-            } = request;
-
-            let id = self
-                .app
-                .submit_payout(
-                    profile,
-                    wallet_name,
-                    payout_queue_name,
-                    destination.try_into()?,
-                    Satoshis::from(satoshis),
-                    external_id,
-                    metadata
-                        .map(serde_json::to_value)
-                        .transpose()
-                        .map_err(ApplicationError::CouldNotParseIncomingMetadata)?,
-                )
-                .await?;
-            Ok(Response::new(SubmitPayoutResponse { id: id.to_string() }))
+            let profile = self.authenticate(key, ApiAccess::SubmitPayout).await?;
+            self.audited(
+                profile,
+                "SubmitPayout",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let SubmitPayoutRequest {
+                        wallet_name,
+                        payout_queue_name,
+                        destination,
+                        satoshis,
+                        external_id,
+                        metadata,
+                        priority,
+                        execute_after,
+                    } = request;
+
+                    let id = self
+                        .app
+                        .submit_payout(
+                            profile,
+                            wallet_name,
+                            payout_queue_name,
+                            destination.try_into()?,
+                            Satoshis::from(satoshis),
+                            external_id,
+                            metadata
+                                .map(serde_json::to_value)
+                                .transpose()
+                                .map_err(ApplicationError::CouldNotParseIncomingMetadata)?,
+                            priority,
+                            execute_after.map(|secs| {
+                                chrono::Utc
+                                    .timestamp_opt(secs as i64, 0)
+                                    .single()
+                                    .expect("u32 timestamps are always valid")
+                            }),
+                        )
+                        .await?;
+                    Ok(Response::new(SubmitPayoutResponse { id: id.to_string() }))
+                },
+            )
+            .await
         })
         .await
     }
@@ -602,7 +1074,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let payouts = self
                 .app
                 .list_payouts(profile, request.into_inner().wallet_name)
@@ -626,7 +1098,7 @@ impl BriaService for Bria {
         crate::tracing::record_error(|| async move {
             extract_tracing(&request);
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let request = request.into_inner();
             let FindPayoutByExternalIdRequest { external_id } = request;
 
@@ -651,7 +1123,7 @@ impl BriaService for Bria {
         crate::tracing::record_error(|| async move {
             extract_tracing(&request);
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let request = request.into_inner();
             let payout = match request.identifier {
                 Some(get_payout_request::Identifier::Id(id)) => {
@@ -688,17 +1160,88 @@ impl BriaService for Bria {
         crate::tracing::record_error(|| async move {
             extract_tracing(&request);
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let request = request.into_inner();
-            let CancelPayoutRequest { id } = request;
-            self.app
-                .cancel_payout(
-                    profile,
-                    id.parse()
-                        .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
-                )
-                .await?;
-            Ok(Response::new(CancelPayoutResponse {}))
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "CancelPayout",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let CancelPayoutRequest { id } = request;
+                    self.app
+                        .cancel_payout(
+                            profile,
+                            id.parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                        )
+                        .await?;
+                    Ok(Response::new(CancelPayoutResponse {}))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.approve_payout", skip_all, fields(error, error.level, error.message), err)]
+    async fn approve_payout(
+        &self,
+        request: Request<ApprovePayoutRequest>,
+    ) -> Result<Response<ApprovePayoutResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "ApprovePayout",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let ApprovePayoutRequest { id } = request;
+                    let fully_approved = self
+                        .app
+                        .approve_payout(
+                            profile,
+                            id.parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                        )
+                        .await?;
+                    Ok(Response::new(ApprovePayoutResponse { fully_approved }))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.reject_payout", skip_all, fields(error, error.level, error.message), err)]
+    async fn reject_payout(
+        &self,
+        request: Request<RejectPayoutRequest>,
+    ) -> Result<Response<RejectPayoutResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "RejectPayout",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let RejectPayoutRequest { id } = request;
+                    self.app
+                        .reject_payout(
+                            profile,
+                            id.parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                        )
+                        .await?;
+                    Ok(Response::new(RejectPayoutResponse {}))
+                },
+            )
+            .await
         })
         .await
     }
@@ -712,7 +1255,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let wallets = self.app.list_wallets(profile).await?;
             let wallet_messages: Vec<proto::Wallet> =
                 wallets.into_iter().map(proto::Wallet::from).collect();
@@ -724,6 +1267,43 @@ impl BriaService for Bria {
         .await
     }
 
+    #[instrument(name = "bria.update_wallet", skip_all, fields(error, error.level, error.message), err)]
+    async fn update_wallet(
+        &self,
+        request: Request<UpdateWalletRequest>,
+    ) -> Result<Response<UpdateWalletResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "UpdateWallet",
+                request,
+                |profile, request| async move {
+                    let UpdateWalletRequest {
+                        wallet_name,
+                        settle_income_after_n_confs,
+                        settle_change_after_n_confs,
+                        dust_threshold_sats,
+                    } = request.into_inner();
+                    let config_update = wallet::WalletConfigUpdate {
+                        settle_income_after_n_confs,
+                        settle_change_after_n_confs,
+                        dust_threshold_sats: dust_threshold_sats.map(Satoshis::from),
+                    };
+                    self.app
+                        .update_wallet(profile, wallet_name, config_update)
+                        .await?;
+                    Ok(Response::new(UpdateWalletResponse {}))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
     #[instrument(name = "bria.list_payout_queues", skip_all, fields(error, error.level, error.message), err)]
     async fn list_payout_queues(
         &self,
@@ -733,7 +1313,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let payout_queues = self.app.list_payout_queues(profile).await?;
             let payout_queue_messages: Vec<proto::PayoutQueue> = payout_queues
                 .into_iter()
@@ -756,24 +1336,34 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
-            let request = request.into_inner();
-            let UpdatePayoutQueueRequest {
-                id,
-                new_description,
-                new_config,
-            } = request;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "UpdatePayoutQueue",
+                request,
+                |profile, request| async move {
+                    let request = request.into_inner();
+                    let UpdatePayoutQueueRequest {
+                        id,
+                        new_description,
+                        new_config,
+                    } = request;
 
-            self.app
-                .update_payout_queue(
-                    profile,
-                    id.parse()
-                        .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
-                    new_description,
-                    new_config.map(payout_queue::PayoutQueueConfig::from),
-                )
-                .await?;
-            Ok(Response::new(UpdatePayoutQueueResponse {}))
+                    self.app
+                        .update_payout_queue(
+                            profile,
+                            id.parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                            new_description,
+                            new_config
+                                .map(payout_queue::PayoutQueueConfig::try_from)
+                                .transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::new(UpdatePayoutQueueResponse {}))
+                },
+            )
+            .await
         })
         .await
     }
@@ -787,7 +1377,7 @@ impl BriaService for Bria {
             extract_tracing(&request);
 
             let key = extract_api_token(&request)?;
-            let profile = self.app.authenticate(key).await?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
             let batch_id = request.into_inner().id;
 
             let (batch, mut payouts, sessions) = self
@@ -811,10 +1401,14 @@ impl BriaService for Bria {
                 .collect();
             Ok(Response::new(GetBatchResponse {
                 id: batch.id.to_string(),
-                payout_queue_id: batch.payout_queue_id.to_string(),
+                payout_queue_id: batch
+                    .payout_queue_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
                 tx_id: batch.bitcoin_tx_id.to_string(),
                 unsigned_psbt: batch.unsigned_psbt.to_string(),
                 wallet_summaries,
+                cancelled: batch.cancelled,
                 signing_sessions: sessions
                     .map(|sessions| {
                         sessions
@@ -829,6 +1423,152 @@ impl BriaService for Bria {
         .await
     }
 
+    #[instrument(name = "bria.get_batch_signing_status", skip_all, fields(error, error.level, error.message), err)]
+    async fn get_batch_signing_status(
+        &self,
+        request: Request<GetBatchSigningStatusRequest>,
+    ) -> Result<Response<GetBatchSigningStatusResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
+            let GetBatchSigningStatusRequest { batch_id } = request.into_inner();
+            let (batch, status) = self
+                .app
+                .get_batch_signing_status(
+                    profile,
+                    batch_id
+                        .parse()
+                        .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                )
+                .await?;
+            Ok(Response::new(GetBatchSigningStatusResponse {
+                batch_id: batch.id.to_string(),
+                threshold_met: status.threshold_met(),
+                signed: batch.signed_tx.is_some(),
+                inputs: status
+                    .inputs
+                    .into_iter()
+                    .map(proto::InputSigningStatus::from)
+                    .collect(),
+            }))
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.bump_batch_fee", skip_all, fields(error, error.level, error.message), err)]
+    async fn bump_batch_fee(
+        &self,
+        request: Request<BumpBatchFeeRequest>,
+    ) -> Result<Response<BumpBatchFeeResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "BumpBatchFee",
+                request,
+                |profile, request| async move {
+                    let BumpBatchFeeRequest { id, sats_per_vbyte } = request.into_inner();
+                    let tx_id = self
+                        .app
+                        .bump_batch_fee(
+                            profile,
+                            id.parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                            sats_per_vbyte,
+                        )
+                        .await?;
+                    Ok(Response::new(BumpBatchFeeResponse {
+                        tx_id: tx_id.to_string(),
+                    }))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.accelerate_utxo", skip_all, fields(error, error.level, error.message), err)]
+    async fn accelerate_utxo(
+        &self,
+        request: Request<AccelerateUtxoRequest>,
+    ) -> Result<Response<AccelerateUtxoResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Write).await?;
+            self.audited(
+                profile,
+                "AccelerateUtxo",
+                request,
+                |profile, request| async move {
+                    let AccelerateUtxoRequest {
+                        wallet_name,
+                        outpoint,
+                        sats_per_vbyte,
+                    } = request.into_inner();
+                    let (batch_id, tx_id) = self
+                        .app
+                        .accelerate_utxo(
+                            profile,
+                            wallet_name,
+                            outpoint
+                                .parse()
+                                .map_err(ApplicationError::CouldNotParseIncomingOutpoint)?,
+                            sats_per_vbyte,
+                        )
+                        .await?;
+                    Ok(Response::new(AccelerateUtxoResponse {
+                        batch_id: batch_id.to_string(),
+                        tx_id: tx_id.to_string(),
+                    }))
+                },
+            )
+            .await
+        })
+        .await
+    }
+
+    #[instrument(name = "bria.list_audit_events", skip_all, fields(error, error.level, error.message), err)]
+    async fn list_audit_events(
+        &self,
+        request: Request<ListAuditEventsRequest>,
+    ) -> Result<Response<ListAuditEventsResponse>, Status> {
+        crate::tracing::record_error(|| async move {
+            extract_tracing(&request);
+
+            let key = extract_api_token(&request)?;
+            let profile = self.authenticate(key, ApiAccess::Read).await?;
+            let ListAuditEventsRequest {
+                profile_id,
+                rpc,
+                after_sequence,
+                limit,
+            } = request.into_inner();
+            let filter = AuditFilter {
+                profile_id: profile_id
+                    .map(|id| id.parse())
+                    .transpose()
+                    .map_err(ApplicationError::CouldNotParseIncomingUuid)?,
+                rpc,
+                after_sequence,
+                limit,
+            };
+            let events = self.app.list_audit_events(profile, filter).await?;
+            Ok(Response::new(ListAuditEventsResponse {
+                events: events
+                    .into_iter()
+                    .map(proto::AuditEvent::try_from)
+                    .collect::<Result<_, _>>()?,
+            }))
+        })
+        .await
+    }
+
     type SubscribeAllStream = std::pin::Pin<
         Box<dyn futures::Stream<Item = Result<BriaEvent, Status>> + Send + Sync + 'static>,
     >;
@@ -841,7 +1581,7 @@ impl BriaService for Bria {
         extract_tracing(&request);
 
         let key = extract_api_token(&request)?;
-        let profile = self.app.authenticate(key).await?;
+        let profile = self.authenticate(key, ApiAccess::SubscribeEvents).await?;
         let SubscribeAllRequest {
             after_sequence,
             augment,
@@ -914,3 +1654,113 @@ impl<'a, T> Extractor for RequestContextExtractor<'a, T> {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audited_signer_config_omits_secrets() {
+        let xpriv = "tprv8ZgxMBicQKsPd3krDUsBAmtnRsK3rb8u5yi1zhQgMhF1tR8MW7xfE4rnrbbsrbPR52e7rKapu6ztw1jXveJSCGHEriUGZV7mCe88duLp5pj";
+        let request = SetSignerConfigRequest {
+            xpub_ref: "ref".to_string(),
+            config: Some(set_signer_config_request::Config::LocalXpriv(
+                LocalXprivSignerConfig {
+                    xpriv: xpriv.to_string(),
+                },
+            )),
+        };
+        let audited =
+            crate::audit::sanitize("SetSignerConfig", serde_json::to_value(&request).unwrap());
+        assert_eq!(audited["xpubRef"], "ref");
+        assert!(!audited.to_string().contains(xpriv));
+
+        let hmac_secret = "webhook-shared-secret";
+        let request = SetSignerConfigRequest {
+            xpub_ref: "ref".to_string(),
+            config: Some(set_signer_config_request::Config::Webhook(
+                WebhookSignerConfig {
+                    endpoint: "https://signer.example.com".to_string(),
+                    hmac_secret: hmac_secret.to_string(),
+                },
+            )),
+        };
+        let audited =
+            crate::audit::sanitize("SetSignerConfig", serde_json::to_value(&request).unwrap());
+        assert!(!audited.to_string().contains(hmac_secret));
+    }
+
+    #[test]
+    fn webhook_endpoint_must_be_http() {
+        let webhook = |endpoint: &str| {
+            crate::xpub::SignerConfig::try_from(Some(set_signer_config_request::Config::Webhook(
+                WebhookSignerConfig {
+                    endpoint: endpoint.to_string(),
+                    hmac_secret: "secret".to_string(),
+                },
+            )))
+            .err()
+            .map(|status| status.code())
+        };
+        assert_eq!(webhook("https://signer.example.com/sign"), None);
+        for endpoint in [
+            "not a url",
+            "file:///etc/passwd",
+            "ftp://signer.example.com",
+        ] {
+            assert_eq!(webhook(endpoint), Some(tonic::Code::InvalidArgument));
+        }
+    }
+
+    #[test]
+    fn priority_multiplier_must_be_positive() {
+        let config = |multiplier: f64| {
+            crate::payout_queue::PayoutQueueConfig::try_from(PayoutQueueConfig {
+                fee_strategy: Some(payout_queue_config::FeeStrategy::PriorityMultiplier(
+                    multiplier,
+                )),
+                ..Default::default()
+            })
+            .err()
+            .map(|status| status.code())
+        };
+        assert_eq!(config(1.5), None);
+        for multiplier in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(config(multiplier), Some(tonic::Code::InvalidArgument));
+        }
+    }
+
+    #[test]
+    fn fixed_rates_and_target_blocks_must_be_in_range() {
+        let config = |fee_strategy| {
+            crate::payout_queue::PayoutQueueConfig::try_from(PayoutQueueConfig {
+                fee_strategy: Some(fee_strategy),
+                ..Default::default()
+            })
+            .err()
+            .map(|status| status.code())
+        };
+        assert_eq!(
+            config(payout_queue_config::FeeStrategy::FixedSatsPerVbyte(5)),
+            None
+        );
+        assert_eq!(
+            config(payout_queue_config::FeeStrategy::FixedSatsPerVbyte(0)),
+            Some(tonic::Code::InvalidArgument)
+        );
+        assert_eq!(
+            config(payout_queue_config::FeeStrategy::TargetBlocks(1)),
+            None
+        );
+        assert_eq!(
+            config(payout_queue_config::FeeStrategy::TargetBlocks(1008)),
+            None
+        );
+        for n_blocks in [0, 1009, u32::MAX] {
+            assert_eq!(
+                config(payout_queue_config::FeeStrategy::TargetBlocks(n_blocks)),
+                Some(tonic::Code::InvalidArgument)
+            );
+        }
+    }
+}
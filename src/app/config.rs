@@ -2,7 +2,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashSet;
 
 use crate::{
-    fees::MempoolSpaceConfig,
+    fees::FeesConfig,
     job::JobsConfig,
     primitives::{
         bitcoin::{self, Network},
@@ -57,12 +57,6 @@ fn default_electrum_url() -> String {
     "127.0.0.1:50001".to_string()
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct FeesConfig {
-    #[serde(default)]
-    pub mempool_space: MempoolSpaceConfig,
-}
-
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SecurityConfig {
     blocked_addresses: HashSet<bitcoin::Address>,
@@ -71,11 +65,19 @@ pub struct SecurityConfig {
 impl SecurityConfig {
     pub fn is_blocked(&self, destination: &PayoutDestination) -> bool {
         if let Some(addr) = destination.onchain_address() {
-            self.blocked_addresses.contains(&addr)
+            self.is_address_blocked(&addr)
         } else {
             false
         }
     }
+
+    pub fn is_address_blocked(&self, address: &bitcoin::Address) -> bool {
+        self.blocked_addresses.contains(address)
+    }
+
+    pub fn has_blocked_addresses(&self) -> bool {
+        !self.blocked_addresses.is_empty()
+    }
 }
 
 fn deserialize_network<'de, D>(deserializer: D) -> Result<Network, D::Error>
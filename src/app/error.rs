@@ -2,7 +2,9 @@ use chacha20poly1305;
 use thiserror::Error;
 
 use crate::{
+    account::error::AccountError,
     address::error::AddressError,
+    audit::error::AuditError,
     batch::error::BatchError,
     bdk::error::BdkError,
     descriptor::error::DescriptorError,
@@ -14,6 +16,7 @@ use crate::{
     payout_queue::error::PayoutQueueError,
     primitives::{bitcoin, PayoutDestination},
     profile::error::ProfileError,
+    security::error::SecurityError,
     signing_session::error::SigningSessionError,
     utxo::error::UtxoError,
     wallet::error::WalletError,
@@ -27,6 +30,12 @@ pub enum ApplicationError {
     #[error("{0}")]
     WalletError(#[from] WalletError),
     #[error("{0}")]
+    AuditError(#[from] AuditError),
+    #[error("{0}")]
+    AccountError(#[from] AccountError),
+    #[error("{0}")]
+    SecurityError(#[from] SecurityError),
+    #[error("{0}")]
     PayoutError(#[from] PayoutError),
     #[error("{0}")]
     AddressError(#[from] AddressError),
@@ -70,6 +79,12 @@ pub enum ApplicationError {
     SigningSessionNotFoundForXPubId(crate::primitives::XPubId),
     #[error("Could not parse incoming psbt: {0}")]
     CouldNotParseIncomingPsbt(bitcoin::psbt::PsbtParseError),
+    #[error("Could not parse incoming outpoint: {0}")]
+    CouldNotParseIncomingOutpoint(bitcoin::ParseOutPointError),
+    #[error("Change of batch {0} has already been spent or settled")]
+    BatchChangeNotReplaceable(crate::primitives::BatchId),
+    #[error("Utxo {0} is not an unconfirmed, unspent utxo of this wallet")]
+    UtxoCannotBeAccelerated(bitcoin::OutPoint),
     #[error("Payout already committed to a batch")]
     PayoutAlreadyCommitted,
     #[error("Hex decode error: {0}")]
@@ -1,6 +1,7 @@
 mod config;
 pub mod error;
 
+use sqlx::Acquire;
 use sqlxmq::OwnedHandle;
 use tracing::instrument;
 
@@ -10,9 +11,10 @@ pub use config::*;
 use error::*;
 
 use crate::{
-    account::balance::AccountBalanceSummary,
+    account::{balance::AccountBalanceSummary, *},
     address::*,
-    batch::*,
+    audit::*,
+    batch::{error::BatchError, *},
     descriptor::*,
     fees::{self, *},
     job,
@@ -21,9 +23,10 @@ use crate::{
     payout::*,
     payout_queue::*,
     primitives::*,
-    profile::*,
-    signing_session::*,
-    utxo::*,
+    profile::{error::ProfileError, *},
+    security::*,
+    signing_session::{error::SigningSessionError, *},
+    utxo::{error::UtxoError, *},
     wallet::{balance::*, *},
     xpub::*,
 };
@@ -32,6 +35,9 @@ use crate::{
 pub struct App {
     _runner: OwnedHandle,
     outbox: Outbox,
+    audit: AuditLog,
+    accounts: Accounts,
+    blocked_addresses: BlockedAddresses,
     profiles: Profiles,
     xpubs: XPubs,
     descriptors: Descriptors,
@@ -43,7 +49,7 @@ pub struct App {
     ledger: Ledger,
     utxos: Utxos,
     addresses: Addresses,
-    mempool_space_client: MempoolSpaceClient,
+    fee_estimators: AccountFeeEstimators,
     pool: sqlx::PgPool,
     config: AppConfig,
 }
@@ -60,10 +66,13 @@ impl App {
         let signing_sessions = SigningSessions::new(&pool);
         let addresses = Addresses::new(&pool);
         let outbox = Outbox::init(&pool, Augmenter::new(&addresses, &payouts)).await?;
-        let mempool_space_client = MempoolSpaceClient::new(config.fees.mempool_space.clone());
+        let fee_estimators = AccountFeeEstimators::new(FeeEstimatorChain::new(&config.fees));
+        let accounts = Accounts::new(&pool);
+        let blocked_addresses = BlockedAddresses::new(&pool);
         let runner = job::start_job_runner(
             &pool,
             outbox.clone(),
+            accounts.clone(),
             wallets.clone(),
             xpubs.clone(),
             payout_queues.clone(),
@@ -76,7 +85,9 @@ impl App {
             config.jobs.clone(),
             config.blockchain.clone(),
             config.signer_encryption.clone(),
-            mempool_space_client.clone(),
+            fee_estimators.clone(),
+            config.security.clone(),
+            blocked_addresses.clone(),
         )
         .await?;
         Self::spawn_sync_all_wallets(pool.clone(), config.jobs.sync_all_wallets_delay).await?;
@@ -90,8 +101,12 @@ impl App {
             config.jobs.respawn_all_outbox_handlers_delay,
         )
         .await?;
+        Self::spawn_check_all_signers(pool.clone(), config.jobs.check_all_signers_delay).await?;
         let app = Self {
             outbox,
+            audit: AuditLog::new(&pool),
+            accounts,
+            blocked_addresses,
             profiles: Profiles::new(&pool),
             xpubs,
             descriptors: Descriptors::new(&pool),
@@ -104,7 +119,7 @@ impl App {
             ledger,
             utxos,
             addresses,
-            mempool_space_client,
+            fee_estimators,
             config,
             _runner: runner,
         };
@@ -116,9 +131,41 @@ impl App {
     }
 
     #[instrument(name = "app.authenticate", skip_all, err)]
-    pub async fn authenticate(&self, key: &str) -> Result<Profile, ApplicationError> {
-        let profile = self.profiles.find_by_key(key).await?;
-        Ok(profile)
+    pub async fn authenticate(
+        &self,
+        key: &str,
+    ) -> Result<(Profile, Vec<ApiKeyScope>), ApplicationError> {
+        let (profile, scopes) = self.profiles.find_by_key(key).await?;
+        Ok((profile, scopes))
+    }
+
+    #[instrument(name = "app.record_audit_event", skip(self, request), err)]
+    pub async fn record_audit_event<T: serde::Serialize>(
+        &self,
+        profile: &Profile,
+        rpc: &str,
+        request: &T,
+        outcome: AuditOutcome,
+    ) -> Result<(), ApplicationError> {
+        let actor = AuditActor::Profile {
+            account_id: profile.account_id,
+            profile_id: profile.id,
+        };
+        self.audit.append(actor, rpc, request, outcome).await?;
+        Ok(())
+    }
+
+    #[instrument(name = "app.list_audit_events", skip(self), err)]
+    pub async fn list_audit_events(
+        &self,
+        profile: Profile,
+        filter: AuditFilter,
+    ) -> Result<Vec<AuditEntry>, ApplicationError> {
+        profile.check_can_manage_profiles()?;
+        Ok(self
+            .audit
+            .list_for_account(profile.account_id, filter)
+            .await?)
     }
 
     #[instrument(name = "app.create_profile", skip(self), err)]
@@ -126,16 +173,40 @@ impl App {
         &self,
         profile: Profile,
         name: String,
+        policy: Option<ProfilePolicy>,
+        payout_approver: bool,
     ) -> Result<Profile, ApplicationError> {
+        profile.check_can_manage_profiles()?;
         let mut tx = self.pool.begin().await?;
         let new_profile = self
             .profiles
-            .create_in_tx(&mut tx, profile.account_id, name)
+            .create_in_tx(
+                &mut tx,
+                profile.account_id,
+                name,
+                policy,
+                payout_approver,
+                Some(profile.id),
+            )
             .await?;
         tx.commit().await?;
         Ok(new_profile)
     }
 
+    #[instrument(name = "app.update_profile", skip(self), err)]
+    pub async fn update_profile(
+        &self,
+        profile: Profile,
+        id: ProfileId,
+        policy: Option<ProfilePolicy>,
+    ) -> Result<(), ApplicationError> {
+        profile.check_can_manage_profiles()?;
+        self.profiles
+            .update_policy(profile.account_id, id, policy)
+            .await?;
+        Ok(())
+    }
+
     #[instrument(name = "app.list_profiles", skip(self), err)]
     pub async fn list_profiles(&self, profile: Profile) -> Result<Vec<Profile>, ApplicationError> {
         let profiles = self.profiles.list_for_account(profile.account_id).await?;
@@ -147,20 +218,68 @@ impl App {
         &self,
         profile: Profile,
         profile_name: String,
+        scopes: Vec<ApiKeyScope>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<ProfileApiKey, ApplicationError> {
+        profile.check_can_manage_profiles()?;
+        if let Some(expires_at) = expires_at.filter(|e| *e <= chrono::Utc::now()) {
+            return Err(ProfileError::ApiKeyExpiryInPast(expires_at).into());
+        }
         let found_profile = self
             .profiles
             .find_by_name(profile.account_id, profile_name)
             .await?;
+        if found_profile.payout_approver
+            && !self
+                .profiles
+                .creator_chain(found_profile.id)
+                .await?
+                .contains(&profile.id)
+        {
+            return Err(ProfileError::ApproverKeyNotAllowed(found_profile.id).into());
+        }
+        let scopes = if scopes.is_empty() {
+            vec![ApiKeyScope::Full]
+        } else {
+            scopes
+        };
         let mut tx = self.pool.begin().await?;
         let key = self
             .profiles
-            .create_key_for_profile_in_tx(&mut tx, found_profile, false)
+            .create_key_for_profile_in_tx(&mut tx, found_profile, false, scopes, expires_at)
             .await?;
         tx.commit().await?;
         Ok(key)
     }
 
+    #[instrument(name = "app.list_profile_api_keys", skip(self), err)]
+    pub async fn list_profile_api_keys(
+        &self,
+        profile: Profile,
+        profile_name: String,
+    ) -> Result<Vec<ProfileApiKeySummary>, ApplicationError> {
+        profile.check_can_manage_profiles()?;
+        let found_profile = self
+            .profiles
+            .find_by_name(profile.account_id, profile_name)
+            .await?;
+        Ok(self
+            .profiles
+            .list_keys_for_profile(profile.account_id, found_profile.id)
+            .await?)
+    }
+
+    #[instrument(name = "app.revoke_profile_api_key", skip(self), err)]
+    pub async fn revoke_profile_api_key(
+        &self,
+        profile: Profile,
+        id: ProfileApiKeyId,
+    ) -> Result<(), ApplicationError> {
+        profile.check_can_manage_profiles()?;
+        self.profiles.revoke_key(profile.account_id, id).await?;
+        Ok(())
+    }
+
     #[instrument(name = "app.import_xpub", skip(self), err)]
     pub async fn import_xpub(
         &self,
@@ -169,6 +288,7 @@ impl App {
         xpub: String,
         derivation: Option<String>,
     ) -> Result<XPubId, ApplicationError> {
+        profile.check_account_level_access()?;
         let value = XPub::try_from((&xpub, derivation))?;
         let xpub = NewAccountXPub::builder()
             .account_id(profile.account_id)
@@ -188,6 +308,7 @@ impl App {
         xpub_ref: String,
         config: SignerConfig,
     ) -> Result<(), ApplicationError> {
+        profile.check_account_level_access()?;
         let mut xpub = self
             .xpubs
             .find_from_ref(
@@ -210,6 +331,30 @@ impl App {
         Ok(())
     }
 
+    #[instrument(name = "app.test_signer_config", skip(self), err)]
+    pub async fn test_signer_config(
+        &self,
+        profile: Profile,
+        xpub_ref: String,
+    ) -> Result<SignerHealth, ApplicationError> {
+        profile.check_account_level_access()?;
+        let mut xpub = self
+            .xpubs
+            .find_from_ref(
+                profile.account_id,
+                xpub_ref
+                    .parse::<XPubRef>()
+                    .expect("ref should always parse"),
+            )
+            .await?;
+        let health = xpub
+            .check_signer_health(self.config.signer_encryption.key)
+            .await?
+            .clone();
+        self.xpubs.record_signer_health(&xpub).await?;
+        Ok(health)
+    }
+
     #[instrument(name = "app.rotate_encryption_key", skip_all, err)]
     pub async fn rotate_encryption_key(
         &self,
@@ -246,6 +391,11 @@ impl App {
         xpub_ref: String,
         signed_psbt: bitcoin::psbt::PartiallySignedTransaction,
     ) -> Result<(), ApplicationError> {
+        let batch = self
+            .batches
+            .find_by_id(profile.account_id, batch_id)
+            .await?;
+        Self::check_batch(&profile, &batch)?;
         let xpub = self
             .xpubs
             .find_from_ref(
@@ -257,12 +407,7 @@ impl App {
             .await?;
         let xpub_id = xpub.id();
         let xpub = xpub.value;
-        let unsigned_psbt = self
-            .batches
-            .find_by_id(profile.account_id, batch_id)
-            .await?
-            .unsigned_psbt;
-        psbt_validator::validate_psbt(&signed_psbt, xpub, &unsigned_psbt)?;
+        psbt_validator::validate_psbt(&signed_psbt, xpub.clone(), &batch.unsigned_psbt)?;
         let mut sessions = self
             .signing_sessions
             .list_for_batch(profile.account_id, batch_id)
@@ -273,8 +418,8 @@ impl App {
             .get_mut(&xpub_id)
             .ok_or_else(|| ApplicationError::SigningSessionNotFoundForXPubId(xpub_id))?;
 
+        session.submit_externally_signed_psbt(signed_psbt, &xpub)?;
         let mut tx = self.pool.begin().await?;
-        session.submit_externally_signed_psbt(signed_psbt);
         self.signing_sessions
             .update_sessions(&mut tx, &sessions)
             .await?;
@@ -282,6 +427,142 @@ impl App {
         Ok(())
     }
 
+    #[instrument(name = "app.list_signing_sessions", skip(self), err)]
+    pub async fn list_signing_sessions(
+        &self,
+        profile: Profile,
+        batch_id: Option<BatchId>,
+        xpub_ref: Option<String>,
+        state: Option<SigningSessionState>,
+        after_id: Option<SigningSessionId>,
+        limit: Option<u32>,
+    ) -> Result<Vec<SigningSession>, ApplicationError> {
+        match batch_id {
+            Some(batch_id) => Self::check_batch(
+                &profile,
+                &self
+                    .batches
+                    .find_by_id(profile.account_id, batch_id)
+                    .await?,
+            )?,
+            None => profile.check_account_level_access()?,
+        }
+        let xpub_id = match xpub_ref {
+            Some(xpub_ref) => Some(
+                self.xpubs
+                    .find_from_ref(
+                        profile.account_id,
+                        xpub_ref
+                            .parse::<XPubRef>()
+                            .expect("ref should always parse"),
+                    )
+                    .await?
+                    .id(),
+            ),
+            None => None,
+        };
+        Ok(self
+            .signing_sessions
+            .list(
+                profile.account_id,
+                SigningSessionFilter {
+                    batch_id,
+                    xpub_id,
+                    state,
+                    after_id,
+                    limit,
+                },
+            )
+            .await?)
+    }
+
+    #[instrument(name = "app.retry_signing_session", skip(self), err)]
+    pub async fn retry_signing_session(
+        &self,
+        profile: Profile,
+        id: SigningSessionId,
+    ) -> Result<(), ApplicationError> {
+        let session = self
+            .signing_sessions
+            .find_by_id(profile.account_id, id)
+            .await?;
+        Self::check_batch(
+            &profile,
+            &self
+                .batches
+                .find_by_id(profile.account_id, session.batch_id)
+                .await?,
+        )?;
+        match session.state() {
+            SigningSessionState::Complete => {
+                return Err(SigningSessionError::AlreadyComplete(id).into())
+            }
+            SigningSessionState::Aborted => return Err(SigningSessionError::Aborted(id).into()),
+            _ => (),
+        }
+        let tx = self.pool.begin().await?;
+        job::retry_batch_signing(tx, profile.account_id, session.batch_id).await?;
+        Ok(())
+    }
+
+    #[instrument(name = "app.abort_batch_signing", skip(self), err)]
+    pub async fn abort_batch_signing(
+        &self,
+        profile: Profile,
+        batch_id: BatchId,
+    ) -> Result<(), ApplicationError> {
+        let batch = self
+            .batches
+            .find_by_id(profile.account_id, batch_id)
+            .await?;
+        Self::check_batch(&profile, &batch)?;
+        let mut sessions = self
+            .signing_sessions
+            .list_for_batch(profile.account_id, batch_id)
+            .await?
+            .ok_or(ApplicationError::SigningSessionNotFoundForBatchId(batch_id))?
+            .xpub_sessions;
+        if sessions
+            .values()
+            .all(|session| session.is_completed() || session.is_aborted())
+        {
+            return Err(SigningSessionError::NothingToAbort(batch_id).into());
+        }
+        if batch.signed_tx.is_some() || batch.cancelled {
+            return Err(BatchError::BatchNotCancellable(batch_id).into());
+        }
+        for session in sessions.values_mut() {
+            if !session.is_completed() && !session.is_aborted() {
+                session.abort(profile.id);
+            }
+        }
+        let mut tx = self.pool.begin().await?;
+        let batch_created_ledger_tx_ids =
+            self.batches.cancel(&mut tx, batch_id, profile.id).await?;
+        self.signing_sessions
+            .update_sessions(&mut tx, &sessions)
+            .await?;
+        self.utxos
+            .unreserve_utxos_in_batch(&mut tx, batch_id)
+            .await?;
+        self.payouts
+            .remove_from_batch(&mut tx, profile.account_id, batch_id)
+            .await?;
+        for (wallet_id, batch_created_ledger_tx_id) in batch_created_ledger_tx_ids {
+            let wallet = self.wallets.find_by_id(wallet_id).await?;
+            self.ledger
+                .batch_cancelled(
+                    (&mut tx).begin().await?,
+                    LedgerTransactionId::new(),
+                    batch_created_ledger_tx_id,
+                    wallet.ledger_account_ids,
+                )
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
     #[instrument(name = "app.create_wpkh_wallet", skip(self), err)]
     pub async fn create_wpkh_wallet(
         &self,
@@ -351,6 +632,7 @@ impl App {
         wallet_name: String,
         keychain: KeychainConfig,
     ) -> Result<(WalletId, Vec<XPubId>), ApplicationError> {
+        profile.check_account_level_access()?;
         let mut tx = self.pool.begin().await?;
         let xpubs = keychain.xpubs();
         let mut xpub_ids = Vec::new();
@@ -425,13 +707,12 @@ impl App {
             .wallets
             .find_by_name(profile.account_id, wallet_name)
             .await?;
+        profile.check_wallet(wallet.id)?;
         let wallet_ledger_account_balances = self
             .ledger
             .get_wallet_ledger_account_balances(wallet.journal_id, wallet.ledger_account_ids)
             .await?;
-        let summary = WalletBalanceSummary::from(wallet_ledger_account_balances);
-
-        Ok(summary)
+        Ok(WalletBalanceSummary::from(wallet_ledger_account_balances))
     }
 
     #[instrument(name = "app.get_account_balance_summary", skip(self), err)]
@@ -439,6 +720,7 @@ impl App {
         &self,
         profile: Profile,
     ) -> Result<AccountBalanceSummary, ApplicationError> {
+        profile.check_account_level_access()?;
         let account_ledger_account_balances = self
             .ledger
             .get_account_ledger_account_balances(profile.account_id.into())
@@ -459,6 +741,7 @@ impl App {
             .wallets
             .find_by_name(profile.account_id, wallet_name)
             .await?;
+        profile.check_wallet(wallet.id)?;
         let keychain_wallet = wallet.current_keychain_wallet(&self.pool);
         let addr = keychain_wallet.new_external_address().await?;
 
@@ -493,6 +776,7 @@ impl App {
             .addresses
             .find_by_address(profile.account_id, address)
             .await?;
+        profile.check_wallet(address.wallet_id)?;
         if let Some(id) = new_external_id {
             address.update_external_id(id);
         }
@@ -513,6 +797,7 @@ impl App {
             .wallets
             .find_by_name(profile.account_id, wallet_name)
             .await?;
+        profile.check_wallet(wallet.id)?;
         let addresses = self
             .addresses
             .list_external_by_wallet_id(profile.account_id, wallet.id)
@@ -531,6 +816,7 @@ impl App {
             .addresses
             .find_by_external_id(profile.account_id, external_id)
             .await?;
+        profile.check_wallet(address.wallet_id)?;
         Ok(address)
     }
 
@@ -544,11 +830,13 @@ impl App {
             .addresses
             .find_by_address(profile.account_id, address)
             .await?;
+        profile.check_wallet(address.wallet_id)?;
         Ok(address)
     }
 
     #[instrument(name = "app.list_xpubs", skip(self), err)]
     pub async fn list_xpubs(&self, profile: Profile) -> Result<Vec<AccountXPub>, ApplicationError> {
+        profile.check_account_level_access()?;
         let xpubs = self.xpubs.list_xpubs(profile.account_id).await?;
         Ok(xpubs)
     }
@@ -563,6 +851,7 @@ impl App {
             .wallets
             .find_by_name(profile.account_id, wallet_name)
             .await?;
+        profile.check_wallet(wallet.id)?;
         let mut utxos = self
             .utxos
             .find_keychain_utxos(wallet.keychain_ids())
@@ -574,6 +863,119 @@ impl App {
         Ok((wallet.id, ordered_utxos))
     }
 
+    #[instrument(name = "app.freeze_utxo", skip(self), err)]
+    pub async fn freeze_utxo(
+        &self,
+        profile: Profile,
+        wallet_name: String,
+        outpoint: bitcoin::OutPoint,
+        reason: Option<String>,
+    ) -> Result<(), ApplicationError> {
+        let wallet = self
+            .wallets
+            .find_by_name(profile.account_id, wallet_name)
+            .await?;
+        profile.check_wallet(wallet.id)?;
+        let (utxo, tx) = self
+            .utxos
+            .freeze(wallet.id, outpoint, reason.clone(), profile.id)
+            .await?;
+        if utxo.ledger_hold_changed {
+            self.ledger
+                .utxo_frozen(
+                    tx,
+                    LedgerTransactionId::new(),
+                    UtxoFrozenParams {
+                        journal_id: wallet.journal_id,
+                        ledger_account_ids: wallet.ledger_account_ids,
+                        meta: UtxoFrozenMeta {
+                            account_id: profile.account_id,
+                            wallet_id: wallet.id,
+                            keychain_id: utxo.keychain_id,
+                            outpoint,
+                            satoshis: utxo.value,
+                            address: utxo.address.clone(),
+                            settled: utxo.settled,
+                            frozen_by: profile.id,
+                            reason: reason.clone(),
+                        },
+                    },
+                )
+                .await?;
+        } else {
+            tx.commit().await?;
+        }
+        self.outbox
+            .add_event(
+                profile.account_id,
+                OutboxEventPayload::UtxoFrozen {
+                    tx_id: outpoint.txid,
+                    vout: outpoint.vout,
+                    satoshis: utxo.value,
+                    address: utxo.address,
+                    wallet_id: wallet.id,
+                    keychain_id: utxo.keychain_id,
+                    profile_id: profile.id,
+                    reason,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[instrument(name = "app.unfreeze_utxo", skip(self), err)]
+    pub async fn unfreeze_utxo(
+        &self,
+        profile: Profile,
+        wallet_name: String,
+        outpoint: bitcoin::OutPoint,
+    ) -> Result<(), ApplicationError> {
+        let wallet = self
+            .wallets
+            .find_by_name(profile.account_id, wallet_name)
+            .await?;
+        profile.check_wallet(wallet.id)?;
+        let (utxo, tx) = self.utxos.unfreeze(wallet.id, outpoint).await?;
+        if utxo.ledger_hold_changed {
+            self.ledger
+                .utxo_unfrozen(
+                    tx,
+                    LedgerTransactionId::new(),
+                    UtxoUnfrozenParams {
+                        journal_id: wallet.journal_id,
+                        ledger_account_ids: wallet.ledger_account_ids,
+                        meta: UtxoUnfrozenMeta {
+                            account_id: profile.account_id,
+                            wallet_id: wallet.id,
+                            keychain_id: utxo.keychain_id,
+                            outpoint,
+                            satoshis: utxo.value,
+                            address: utxo.address.clone(),
+                            settled: utxo.settled,
+                        },
+                    },
+                )
+                .await?;
+        } else {
+            tx.commit().await?;
+        }
+        self.outbox
+            .add_event(
+                profile.account_id,
+                OutboxEventPayload::UtxoUnfrozen {
+                    tx_id: outpoint.txid,
+                    vout: outpoint.vout,
+                    satoshis: utxo.value,
+                    address: utxo.address,
+                    wallet_id: wallet.id,
+                    keychain_id: utxo.keychain_id,
+                    profile_id: profile.id,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
     #[instrument(name = "app.create_payout_queue", skip(self), err)]
     pub async fn create_payout_queue(
         &self,
@@ -582,6 +984,7 @@ impl App {
         description: Option<String>,
         config: Option<PayoutQueueConfig>,
     ) -> Result<PayoutQueueId, ApplicationError> {
+        profile.check_account_level_access()?;
         let mut builder = NewPayoutQueue::builder();
         builder
             .account_id(profile.account_id)
@@ -605,6 +1008,7 @@ impl App {
             .payout_queues
             .find_by_name(profile.account_id, name)
             .await?;
+        profile.check_payout_queue(payout_queue.id)?;
         job::spawn_process_payout_queue(&self.pool, (payout_queue.account_id, payout_queue.id))
             .await?;
         Ok(())
@@ -623,10 +1027,12 @@ impl App {
             .wallets
             .find_by_name(profile.account_id, wallet_name)
             .await?;
+        profile.check_wallet(wallet.id)?;
         let payout_queue = self
             .payout_queues
             .find_by_name(profile.account_id, queue_name)
             .await?;
+        profile.check_payout_queue(payout_queue.id)?;
         let mut unbatched_payouts = self
             .payouts
             .list_unbatched(profile.account_id, payout_queue.id)
@@ -639,8 +1045,9 @@ impl App {
             .include_simulated_payout(wallet.id, (payout_id, destination.clone(), sats));
 
         let queue_id = payout_queue.id;
-        let tx_priority = payout_queue.config.tx_priority;
-        let fee_rate = self.mempool_space_client.fee_rate(tx_priority).await?;
+        let account = self.accounts.find_by_id(profile.account_id).await?;
+        let fee_estimator = self.fee_estimators.for_account(&account).await;
+        let fee_rate = payout_queue.config.fee_rate(&fee_estimator).await?;
 
         let psbt = {
             let mut tx = self.pool.begin().await?;
@@ -650,7 +1057,7 @@ impl App {
                 &unbatched_payouts,
                 &self.utxos,
                 &self.wallets,
-                payout_queue,
+                &payout_queue,
                 fee_rate,
             )
             .await?
@@ -690,19 +1097,39 @@ impl App {
         sats: Satoshis,
         external_id: Option<String>,
         metadata: Option<serde_json::Value>,
+        priority: Option<u32>,
+        execute_after: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<PayoutId, ApplicationError> {
         let wallet = self
             .wallets
             .find_by_name(profile.account_id, wallet_name)
             .await?;
+        profile.check_wallet(wallet.id)?;
         let payout_queue = self
             .payout_queues
             .find_by_name(profile.account_id, queue_name)
             .await?;
+        profile.check_payout_queue(payout_queue.id)?;
 
-        if self.config.security.is_blocked(&destination) {
+        let account = self.accounts.find_by_id(profile.account_id).await?;
+        if self.config.security.is_blocked(&destination)
+            || account.config.is_blocked(&destination)
+            || self.blocked_addresses.is_blocked(&destination).await?
+        {
+            if let Some(address) = destination.onchain_address() {
+                self.blocked_addresses
+                    .record_payout_attempt(NewBlockedPayoutAttempt {
+                        account_id: profile.account_id,
+                        profile_id: Some(profile.id),
+                        payout_id: None,
+                        address,
+                        satoshis: sats,
+                    })
+                    .await?;
+            }
             return Err(ApplicationError::DestinationBlocked(destination));
         }
+        profile.check_payout(&destination, sats)?;
 
         let mut builder = NewPayout::builder();
         builder
@@ -712,12 +1139,35 @@ impl App {
             .payout_queue_id(payout_queue.id)
             .destination(destination.clone())
             .satoshis(sats)
-            .metadata(metadata.clone());
+            .metadata(metadata.clone())
+            .execute_after(execute_after);
         if let Some(external_id) = external_id.as_ref() {
             builder.external_id(external_id);
         }
+        if let Some(priority) = priority {
+            builder.priority(priority);
+        }
+        let required_approvals = payout_queue.config.required_approvals(sats);
+        builder.required_approvals(required_approvals);
         let new_payout = builder.build().expect("Couldn't build NewPayout");
         let mut tx = self.pool.begin().await?;
+        if profile
+            .policy
+            .as_ref()
+            .and_then(|policy| policy.max_daily_payout_sats)
+            .is_some()
+        {
+            let submitted = self
+                .payouts
+                .submitted_total_since_in_tx(
+                    &mut tx,
+                    profile.account_id,
+                    profile.id,
+                    chrono::Utc::now() - chrono::Duration::days(1),
+                )
+                .await?;
+            profile.check_daily_payout_total(submitted + sats)?;
+        }
         let id = self.payouts.create_in_tx(&mut tx, new_payout).await?;
         self.ledger
             .payout_submitted(
@@ -734,11 +1184,26 @@ impl App {
                         wallet_id: wallet.id,
                         profile_id: profile.id,
                         satoshis: sats,
-                        destination,
+                        destination: destination.clone(),
                     },
                 },
             )
             .await?;
+        if let Some(required_approvals) = required_approvals {
+            self.outbox
+                .add_event(
+                    profile.account_id,
+                    OutboxEventPayload::PayoutApprovalRequested {
+                        id,
+                        wallet_id: wallet.id,
+                        payout_queue_id: payout_queue.id,
+                        satoshis: sats,
+                        destination,
+                        required_approvals,
+                    },
+                )
+                .await?;
+        }
         Ok(id)
     }
 
@@ -750,12 +1215,13 @@ impl App {
         let mut tx = self.pool.begin().await?;
         let mut payout = self
             .payouts
-            .find_by_id_for_cancellation(&mut tx, profile.account_id, id)
+            .find_by_id_for_update(&mut tx, profile.account_id, id)
             .await?;
+        profile.check_wallet(payout.wallet_id)?;
         if payout.batch_id.is_some() {
             return Err(ApplicationError::PayoutAlreadyCommitted);
         }
-        if payout.is_cancelled() {
+        if payout.is_cancelled() || payout.is_rejected() {
             return Ok(());
         }
         payout.cancel_payout(profile.id);
@@ -766,9 +1232,95 @@ impl App {
         Ok(())
     }
 
+    #[instrument(name = "app.approve_payout", skip(self), err)]
+    pub async fn approve_payout(
+        &self,
+        profile: Profile,
+        id: PayoutId,
+    ) -> Result<bool, ApplicationError> {
+        profile.check_payout_approver()?;
+        let creator_chain = self.profiles.creator_chain(profile.id).await?;
+        let mut tx = self.pool.begin().await?;
+        let mut payout = self
+            .payouts
+            .find_by_id_for_update(&mut tx, profile.account_id, id)
+            .await?;
+        profile.check_wallet(payout.wallet_id)?;
+        profile.check_payout_queue(payout.payout_queue_id)?;
+        let fully_approved = payout.approve(profile.id, &creator_chain)?;
+        let event = OutboxEventPayload::PayoutApproved {
+            id: payout.id,
+            wallet_id: payout.wallet_id,
+            payout_queue_id: payout.payout_queue_id,
+            satoshis: payout.satoshis,
+            destination: payout.destination.clone(),
+            approved_by: profile.id,
+            approvals: payout.approved_by.len() as u32,
+            required_approvals: payout.required_approvals,
+        };
+        self.payouts.update_in_tx(&mut tx, payout).await?;
+        tx.commit().await?;
+        self.outbox.add_event(profile.account_id, event).await?;
+        Ok(fully_approved)
+    }
+
+    #[instrument(name = "app.reject_payout", skip(self), err)]
+    pub async fn reject_payout(
+        &self,
+        profile: Profile,
+        id: PayoutId,
+    ) -> Result<(), ApplicationError> {
+        profile.check_payout_approver()?;
+        let mut tx = self.pool.begin().await?;
+        let mut payout = self
+            .payouts
+            .find_by_id_for_update(&mut tx, profile.account_id, id)
+            .await?;
+        profile.check_wallet(payout.wallet_id)?;
+        profile.check_payout_queue(payout.payout_queue_id)?;
+        payout.reject(profile.id)?;
+        let event = OutboxEventPayload::PayoutRejected {
+            id: payout.id,
+            wallet_id: payout.wallet_id,
+            payout_queue_id: payout.payout_queue_id,
+            satoshis: payout.satoshis,
+            destination: payout.destination.clone(),
+            rejected_by: profile.id,
+        };
+        self.payouts.update_in_tx(&mut tx, payout).await?;
+        self.ledger
+            .payout_cancelled(tx, LedgerTransactionId::new(), id)
+            .await?;
+        self.outbox.add_event(profile.account_id, event).await?;
+        Ok(())
+    }
+
     #[instrument(name = "app.list_wallets", skip_all, err)]
     pub async fn list_wallets(&self, profile: Profile) -> Result<Vec<Wallet>, ApplicationError> {
-        Ok(self.wallets.list_by_account_id(profile.account_id).await?)
+        Ok(self
+            .wallets
+            .list_by_account_id(profile.account_id)
+            .await?
+            .into_iter()
+            .filter(|wallet| profile.check_wallet(wallet.id).is_ok())
+            .collect())
+    }
+
+    #[instrument(name = "app.update_wallet", skip(self), err)]
+    pub async fn update_wallet(
+        &self,
+        profile: Profile,
+        wallet_name: String,
+        config_update: WalletConfigUpdate,
+    ) -> Result<(), ApplicationError> {
+        let mut wallet = self
+            .wallets
+            .find_by_name(profile.account_id, wallet_name)
+            .await?;
+        profile.check_wallet(wallet.id)?;
+        wallet.update_config(config_update);
+        self.wallets.update(wallet).await?;
+        Ok(())
     }
 
     #[instrument(name = "app.find_payout_by_external_id", skip_all, err)]
@@ -777,10 +1329,13 @@ impl App {
         profile: Profile,
         external_id: String,
     ) -> Result<Payout, ApplicationError> {
-        Ok(self
+        let payout = self
             .payouts
             .find_by_external_id(profile.account_id, external_id)
-            .await?)
+            .await?;
+        profile.check_wallet(payout.wallet_id)?;
+        profile.check_payout_queue(payout.payout_queue_id)?;
+        Ok(payout)
     }
 
     #[instrument(name = "app.find_payout", skip_all, err)]
@@ -789,7 +1344,10 @@ impl App {
         profile: Profile,
         id: PayoutId,
     ) -> Result<Payout, ApplicationError> {
-        Ok(self.payouts.find_by_id(profile.account_id, id).await?)
+        let payout = self.payouts.find_by_id(profile.account_id, id).await?;
+        profile.check_wallet(payout.wallet_id)?;
+        profile.check_payout_queue(payout.payout_queue_id)?;
+        Ok(payout)
     }
 
     #[instrument(name = "app.list_payouts", skip_all, err)]
@@ -802,6 +1360,7 @@ impl App {
             .wallets
             .find_by_name(profile.account_id, wallet_name)
             .await?;
+        profile.check_wallet(wallet.id)?;
         Ok(self
             .payouts
             .list_for_wallet(profile.account_id, wallet.id)
@@ -816,7 +1375,10 @@ impl App {
         let payout_queues = self
             .payout_queues
             .list_by_account_id(profile.account_id)
-            .await?;
+            .await?
+            .into_iter()
+            .filter(|payout_queue| profile.check_payout_queue(payout_queue.id).is_ok())
+            .collect();
         Ok(payout_queues)
     }
 
@@ -832,6 +1394,7 @@ impl App {
             .payout_queues
             .find_by_id(profile.account_id, id)
             .await?;
+        profile.check_payout_queue(payout_queue.id)?;
         if let Some(desc) = new_description {
             payout_queue.update_description(desc)
         }
@@ -860,6 +1423,7 @@ impl App {
             .batches
             .find_by_id(profile.account_id, batch_id)
             .await?;
+        Self::check_batch(&profile, &batch)?;
         let payouts = self
             .payouts
             .list_for_batch(profile.account_id, batch_id)
@@ -871,6 +1435,203 @@ impl App {
         Ok((batch, payouts, signing_sessions))
     }
 
+    #[instrument(name = "app.get_batch_signing_status", skip(self), err)]
+    pub async fn get_batch_signing_status(
+        &self,
+        profile: Profile,
+        batch_id: BatchId,
+    ) -> Result<(Batch, BatchSigningStatus), ApplicationError> {
+        let batch = self
+            .batches
+            .find_by_id(profile.account_id, batch_id)
+            .await?;
+        Self::check_batch(&profile, &batch)?;
+        let sessions = self
+            .signing_sessions
+            .list_for_batch(profile.account_id, batch_id)
+            .await?
+            .ok_or(ApplicationError::SigningSessionNotFoundForBatchId(batch_id))?;
+        let mut xpubs = Vec::new();
+        for xpub_id in sessions.xpub_sessions.keys() {
+            xpubs.push(
+                self.xpubs
+                    .find_from_ref(profile.account_id, *xpub_id)
+                    .await?
+                    .value,
+            );
+        }
+        let status = sessions.signing_status(&xpubs);
+        Ok((batch, status))
+    }
+
+    #[instrument(name = "app.bump_batch_fee", skip(self), err)]
+    pub async fn bump_batch_fee(
+        &self,
+        profile: Profile,
+        batch_id: BatchId,
+        sats_per_vbyte: f32,
+    ) -> Result<bitcoin::Txid, ApplicationError> {
+        let batch = self
+            .batches
+            .find_by_id(profile.account_id, batch_id)
+            .await?;
+        Self::check_batch(&profile, &batch)?;
+        let fee_bump = batch.bump_fee(bitcoin::FeeRate::from_sat_per_vb(sats_per_vbyte))?;
+
+        let mut change_outpoints: HashMap<KeychainId, Vec<bitcoin::OutPoint>> = HashMap::new();
+        for summary in batch.wallet_summaries.values() {
+            if let Some(outpoint) = summary.change_outpoint {
+                change_outpoints
+                    .entry(summary.current_keychain_id)
+                    .or_default()
+                    .push(outpoint);
+            }
+        }
+        let n_change_outpoints = change_outpoints.values().map(Vec::len).sum::<usize>();
+        let change_utxos = self.utxos.list_utxos_by_outpoint(&change_outpoints).await?;
+        if change_utxos.len() != n_change_outpoints
+            || change_utxos.iter().any(|utxo| {
+                utxo.bdk_spent
+                    || utxo.spending_batch_id.is_some()
+                    || utxo.utxo_settled_ledger_tx_id.is_some()
+            })
+        {
+            return Err(ApplicationError::BatchChangeNotReplaceable(batch_id));
+        }
+
+        let mut sessions = self
+            .signing_sessions
+            .list_for_batch(profile.account_id, batch_id)
+            .await?
+            .ok_or(ApplicationError::SigningSessionNotFoundForBatchId(batch_id))?
+            .xpub_sessions;
+        for session in sessions.values_mut() {
+            session.replace_unsigned_psbt(fee_bump.unsigned_psbt().clone());
+        }
+
+        let bitcoin_tx_id = fee_bump.bitcoin_tx_id;
+        let wallet_ids: Vec<_> = fee_bump.wallet_ids().collect();
+        let mut tx = self.pool.begin().await?;
+        let fee_bump_id = self
+            .batches
+            .create_fee_bump_in_tx(&mut tx, fee_bump)
+            .await?;
+        self.signing_sessions
+            .update_sessions(&mut tx, &sessions)
+            .await?;
+        job::spawn_batch_fee_bump(
+            tx,
+            profile.account_id,
+            batch_id,
+            fee_bump_id,
+            wallet_ids.into_iter(),
+        )
+        .await?;
+        Ok(bitcoin_tx_id)
+    }
+
+    #[instrument(name = "app.accelerate_utxo", skip(self), err)]
+    pub async fn accelerate_utxo(
+        &self,
+        profile: Profile,
+        wallet_name: String,
+        outpoint: bitcoin::OutPoint,
+        sats_per_vbyte: f32,
+    ) -> Result<(BatchId, bitcoin::Txid), ApplicationError> {
+        let wallet = self
+            .wallets
+            .find_by_name(profile.account_id, wallet_name)
+            .await?;
+        profile.check_wallet(wallet.id)?;
+        let utxo = self
+            .utxos
+            .find_keychain_utxos(wallet.keychain_ids())
+            .await?
+            .into_values()
+            .flat_map(|keychain_utxos| keychain_utxos.utxos)
+            .find(|utxo| utxo.outpoint == outpoint)
+            .filter(|utxo| {
+                utxo.block_height.is_none()
+                    && utxo.spending_batch_id.is_none()
+                    && !utxo.frozen
+                    && !utxo.quarantined
+            })
+            .ok_or(ApplicationError::UtxoCannotBeAccelerated(outpoint))?;
+        let keychain_wallet = wallet
+            .keychain_wallets(self.pool.clone())
+            .find(|keychain| keychain.keychain_id == utxo.keychain_id)
+            .expect("keychain of utxo not found");
+
+        let fee_rate = bitcoin::FeeRate::from_sat_per_vb(sats_per_vbyte);
+        let builder = CpfpPsbtBuilder::new(
+            outpoint,
+            fee_rate,
+            keychain_wallet.max_satisfaction_weight(),
+        );
+        let CpfpPsbt {
+            keychain_id,
+            tx_id,
+            psbt,
+            fee_satoshis,
+            change_satoshis,
+            change_address,
+            change_outpoint,
+        } = keychain_wallet
+            .dispatch_bdk_wallet(builder)
+            .await?
+            .finish()
+            .ok_or(ApplicationError::UtxoCannotBeAccelerated(outpoint))?;
+
+        let batch = NewBatch::builder()
+            .account_id(profile.account_id)
+            .id(BatchId::new())
+            .tx_id(tx_id)
+            .unsigned_psbt(psbt)
+            .total_fee_sats(fee_satoshis)
+            .wallet_summaries(
+                std::iter::once((
+                    wallet.id,
+                    WalletSummary {
+                        wallet_id: wallet.id,
+                        current_keychain_id: keychain_id,
+                        signing_keychains: vec![keychain_id],
+                        total_in_sats: utxo.value,
+                        total_spent_sats: Satoshis::ZERO,
+                        fee_sats: fee_satoshis,
+                        change_sats: change_satoshis,
+                        change_address: Some(change_address.address),
+                        change_outpoint: Some(change_outpoint),
+                        batch_created_ledger_tx_id: None,
+                        batch_broadcast_ledger_tx_id: None,
+                    },
+                ))
+                .collect(),
+            )
+            .build()
+            .expect("Couldn't build batch");
+
+        let mut tx = self.pool.begin().await?;
+        let batch_id = self.batches.create_in_tx(&mut tx, batch).await?;
+        self.utxos
+            .reserve_utxos_in_batch(
+                &mut tx,
+                profile.account_id,
+                batch_id,
+                None,
+                fee_rate,
+                std::iter::once((keychain_id, outpoint)),
+            )
+            .await
+            .map_err(|e| match e {
+                UtxoError::UtxosNotReservable => {
+                    ApplicationError::UtxoCannotBeAccelerated(outpoint)
+                }
+                e => e.into(),
+            })?;
+        job::spawn_utxo_acceleration(tx, profile.account_id, batch_id, wallet.id).await?;
+        Ok((batch_id, tx_id))
+    }
+
     #[instrument(name = "app.subscribe_all", skip(self), err)]
     pub async fn subscribe_all(
         &self,
@@ -889,6 +1650,16 @@ impl App {
         Ok(res)
     }
 
+    fn check_batch(profile: &Profile, batch: &Batch) -> Result<(), ProfileError> {
+        for wallet_id in batch.wallet_summaries.keys() {
+            profile.check_wallet(*wallet_id)?;
+        }
+        if let Some(payout_queue_id) = batch.payout_queue_id {
+            profile.check_payout_queue(payout_queue_id)?;
+        }
+        Ok(())
+    }
+
     #[instrument(name = "app.spawn_sync_all_wallets", skip_all, err)]
     async fn spawn_sync_all_wallets(
         pool: sqlx::PgPool,
@@ -919,6 +1690,21 @@ impl App {
         Ok(())
     }
 
+    #[instrument(name = "app.spawn_check_all_signers", skip_all, err)]
+    async fn spawn_check_all_signers(
+        pool: sqlx::PgPool,
+        delay: std::time::Duration,
+    ) -> Result<(), ApplicationError> {
+        tokio::spawn(async move {
+            loop {
+                let _ =
+                    job::spawn_check_all_signers(&pool, std::time::Duration::from_secs(1)).await;
+                tokio::time::sleep(delay).await;
+            }
+        });
+        Ok(())
+    }
+
     #[instrument(name = "app.spawn_respawn_all_outbox_handlers", skip_all, err)]
     async fn spawn_respawn_all_outbox_handlers(
         pool: sqlx::PgPool,
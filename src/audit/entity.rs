@@ -0,0 +1,247 @@
+use bdk::bitcoin::hashes::{sha256, Hash};
+use serde::{Deserialize, Serialize};
+
+use crate::primitives::*;
+
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const REDACTED: &str = "[REDACTED]";
+const SENSITIVE_FIELDS: [&str; 7] = [
+    "certBase64",
+    "macaroonBase64",
+    "rpcPassword",
+    "rpc_password",
+    "hmacSecret",
+    "hmac_secret",
+    "xpriv",
+];
+/// Request fields that are dropped wholesale for a given rpc, regardless of their shape
+const REDACTED_REQUEST_FIELDS: [(&str, &str); 1] = [("SetSignerConfig", "config")];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditActor {
+    Profile {
+        account_id: AccountId,
+        profile_id: ProfileId,
+    },
+    AdminApiKey {
+        id: AdminApiKeyId,
+    },
+    Unauthenticated,
+}
+
+impl AuditActor {
+    pub fn account_id(&self) -> Option<AccountId> {
+        match self {
+            Self::Profile { account_id, .. } => Some(*account_id),
+            _ => None,
+        }
+    }
+
+    pub fn profile_id(&self) -> Option<ProfileId> {
+        match self {
+            Self::Profile { profile_id, .. } => Some(*profile_id),
+            _ => None,
+        }
+    }
+
+    pub fn admin_api_key_id(&self) -> Option<AdminApiKeyId> {
+        match self {
+            Self::AdminApiKey { id } => Some(*id),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the audited rpc succeeded, recorded once the handler has returned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Succeeded,
+    Failed { error: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub sequence: u64,
+    pub id: AuditEntryId,
+    pub actor: AuditActor,
+    pub rpc: String,
+    pub request: serde_json::Value,
+    /// `None` for entries recorded before outcomes were tracked
+    pub outcome: Option<AuditOutcome>,
+    pub prev_hash: String,
+    pub hash: String,
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AuditEntry {
+    pub fn computed_hash(&self) -> String {
+        compute_hash(
+            &self.prev_hash,
+            self.id,
+            &self.actor,
+            &self.rpc,
+            &self.request,
+            self.outcome.as_ref(),
+            self.recorded_at,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub profile_id: Option<ProfileId>,
+    pub rpc: Option<String>,
+    pub after_sequence: Option<u64>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditVerification {
+    pub n_entries: u64,
+    pub last_hash: String,
+    pub first_invalid_sequence: Option<u64>,
+}
+
+impl AuditVerification {
+    pub fn is_valid(&self) -> bool {
+        self.first_invalid_sequence.is_none()
+    }
+}
+
+pub(super) fn compute_hash(
+    prev_hash: &str,
+    id: AuditEntryId,
+    actor: &AuditActor,
+    rpc: &str,
+    request: &serde_json::Value,
+    outcome: Option<&AuditOutcome>,
+    recorded_at: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let mut payload = serde_json::json!({
+        "prev_hash": prev_hash,
+        "id": id,
+        "actor": actor,
+        "rpc": rpc,
+        "request": request,
+        "recorded_at": recorded_at.timestamp_micros(),
+    });
+    if let Some(outcome) = outcome {
+        payload["outcome"] = serde_json::json!(outcome);
+    }
+    sha256::Hash::hash(payload.to_string().as_bytes()).to_string()
+}
+
+pub fn sanitize(rpc: &str, request: serde_json::Value) -> serde_json::Value {
+    let mut request = sanitize_value(request);
+    if let serde_json::Value::Object(ref mut map) = request {
+        for (_, field) in REDACTED_REQUEST_FIELDS.iter().filter(|(r, _)| *r == rpc) {
+            if let Some(value) = map.get_mut(*field) {
+                *value = serde_json::Value::String(REDACTED.to_string());
+            }
+        }
+    }
+    request
+}
+
+fn sanitize_value(request: serde_json::Value) -> serde_json::Value {
+    match request {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .map(|(k, v)| {
+                    if SENSITIVE_FIELDS.contains(&k.as_str()) {
+                        (k, serde_json::Value::String(REDACTED.to_string()))
+                    } else {
+                        (k, sanitize_value(v))
+                    }
+                })
+                .collect(),
+        ),
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(sanitize_value).collect())
+        }
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_redacts_nested_secrets() {
+        let request = serde_json::json!({
+            "xpubRef": "ref",
+            "config": { "lnd": { "endpoint": "localhost", "macaroonBase64": "secret" } }
+        });
+        let sanitized = sanitize("UpdateAccount", request);
+        assert_eq!(sanitized["xpubRef"], "ref");
+        assert_eq!(sanitized["config"]["lnd"]["endpoint"], "localhost");
+        assert_eq!(sanitized["config"]["lnd"]["macaroonBase64"], REDACTED);
+    }
+
+    #[test]
+    fn sanitize_drops_signer_config() {
+        let request = serde_json::json!({
+            "xpubRef": "ref",
+            "config": { "localXpriv": { "xpriv": "secret" } }
+        });
+        let sanitized = sanitize("SetSignerConfig", request);
+        assert_eq!(sanitized["xpubRef"], "ref");
+        assert_eq!(sanitized["config"], REDACTED);
+    }
+
+    #[test]
+    fn hash_depends_on_prev_hash() {
+        let id = AuditEntryId::new();
+        let now = chrono::Utc::now();
+        let request = serde_json::json!({ "name": "test" });
+        let first = compute_hash(
+            GENESIS_HASH,
+            id,
+            &AuditActor::Unauthenticated,
+            "Bootstrap",
+            &request,
+            None,
+            now,
+        );
+        let second = compute_hash(
+            &first,
+            id,
+            &AuditActor::Unauthenticated,
+            "Bootstrap",
+            &request,
+            None,
+            now,
+        );
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn hash_depends_on_outcome() {
+        let id = AuditEntryId::new();
+        let now = chrono::Utc::now();
+        let actor = AuditActor::AdminApiKey {
+            id: AdminApiKeyId::new(),
+        };
+        let request = serde_json::json!({ "name": "test" });
+        let hash = |outcome: Option<AuditOutcome>| {
+            compute_hash(
+                GENESIS_HASH,
+                id,
+                &actor,
+                "Test",
+                &request,
+                outcome.as_ref(),
+                now,
+            )
+        };
+        let succeeded = hash(Some(AuditOutcome::Succeeded));
+        let failed = hash(Some(AuditOutcome::Failed {
+            error: "denied".to_string(),
+        }));
+        assert_ne!(succeeded, failed);
+        assert_ne!(succeeded, hash(None));
+    }
+}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AuditError {
+    #[error("AuditError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("AuditError - SerdeJson: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+}
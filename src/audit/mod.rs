@@ -0,0 +1,6 @@
+mod entity;
+pub mod error;
+mod repo;
+
+pub use entity::*;
+pub use repo::*;
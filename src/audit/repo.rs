@@ -0,0 +1,186 @@
+use sqlx::{Pool, Postgres};
+use tracing::instrument;
+
+use super::{entity::*, error::*};
+use crate::primitives::*;
+
+const AUDIT_LOG_LOCK_ID: i64 = 7_342_918_066;
+const VERIFY_PAGE_SIZE: i64 = 1000;
+const DEFAULT_LIST_LIMIT: u32 = 100;
+
+#[derive(Clone)]
+pub struct AuditLog {
+    pool: Pool<Postgres>,
+}
+
+impl AuditLog {
+    pub fn new(pool: &Pool<Postgres>) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    #[instrument(name = "audit_log.append", skip(self, request))]
+    pub async fn append<T: serde::Serialize>(
+        &self,
+        actor: AuditActor,
+        rpc: &str,
+        request: &T,
+        outcome: AuditOutcome,
+    ) -> Result<AuditEntryId, AuditError> {
+        let request = sanitize(rpc, serde_json::to_value(request)?);
+        let id = AuditEntryId::new();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query!("SELECT pg_advisory_xact_lock($1)", AUDIT_LOG_LOCK_ID)
+            .execute(&mut *tx)
+            .await?;
+        let prev_hash =
+            sqlx::query!(r#"SELECT hash FROM bria_audit_log ORDER BY sequence DESC LIMIT 1"#)
+                .fetch_optional(&mut *tx)
+                .await?
+                .map(|row| row.hash)
+                .unwrap_or_else(|| GENESIS_HASH.to_string());
+        let recorded_at = sqlx::query!(r#"SELECT NOW() as "now!""#)
+            .fetch_one(&mut *tx)
+            .await?
+            .now;
+        let hash = compute_hash(
+            &prev_hash,
+            id,
+            &actor,
+            rpc,
+            &request,
+            Some(&outcome),
+            recorded_at,
+        );
+        sqlx::query!(
+            r#"INSERT INTO bria_audit_log
+            (id, account_id, profile_id, admin_api_key_id, rpc, request, outcome, prev_hash, hash, recorded_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"#,
+            uuid::Uuid::from(id),
+            actor.account_id().map(uuid::Uuid::from),
+            actor.profile_id().map(uuid::Uuid::from),
+            actor.admin_api_key_id().map(uuid::Uuid::from),
+            rpc,
+            request,
+            serde_json::to_value(&outcome)?,
+            prev_hash,
+            hash,
+            recorded_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    pub async fn list_for_account(
+        &self,
+        account_id: AccountId,
+        filter: AuditFilter,
+    ) -> Result<Vec<AuditEntry>, AuditError> {
+        self.list(Some(account_id), filter).await
+    }
+
+    /// Lists entries across all accounts (including admin calls) unless `account_id` is given
+    pub async fn list(
+        &self,
+        account_id: Option<AccountId>,
+        filter: AuditFilter,
+    ) -> Result<Vec<AuditEntry>, AuditError> {
+        let rows = sqlx::query!(
+            r#"SELECT sequence, id, account_id, profile_id, admin_api_key_id, rpc, request, outcome, prev_hash, hash, recorded_at
+            FROM bria_audit_log
+            WHERE ($1::uuid IS NULL OR account_id = $1)
+              AND ($2::uuid IS NULL OR profile_id = $2)
+              AND ($3::varchar IS NULL OR rpc = $3)
+              AND sequence > $4
+            ORDER BY sequence
+            LIMIT $5"#,
+            account_id.map(uuid::Uuid::from),
+            filter.profile_id.map(uuid::Uuid::from),
+            filter.rpc,
+            filter.after_sequence.unwrap_or(0) as i64,
+            filter.limit.unwrap_or(DEFAULT_LIST_LIMIT) as i64,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(AuditEntry {
+                    sequence: row.sequence as u64,
+                    id: AuditEntryId::from(row.id),
+                    actor: actor_from_columns(row.account_id, row.profile_id, row.admin_api_key_id),
+                    rpc: row.rpc,
+                    request: row.request,
+                    outcome: row.outcome.map(serde_json::from_value).transpose()?,
+                    prev_hash: row.prev_hash,
+                    hash: row.hash,
+                    recorded_at: row.recorded_at,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(name = "audit_log.verify", skip(self))]
+    pub async fn verify(&self) -> Result<AuditVerification, AuditError> {
+        let mut verification = AuditVerification {
+            n_entries: 0,
+            last_hash: GENESIS_HASH.to_string(),
+            first_invalid_sequence: None,
+        };
+        let mut after_sequence = 0;
+        loop {
+            let rows = sqlx::query!(
+                r#"SELECT sequence, id, account_id, profile_id, admin_api_key_id, rpc, request, outcome, prev_hash, hash, recorded_at
+                FROM bria_audit_log
+                WHERE sequence > $1
+                ORDER BY sequence
+                LIMIT $2"#,
+                after_sequence,
+                VERIFY_PAGE_SIZE,
+            )
+            .fetch_all(&self.pool)
+            .await?;
+            if rows.is_empty() {
+                return Ok(verification);
+            }
+            for row in rows {
+                after_sequence = row.sequence;
+                let entry = AuditEntry {
+                    sequence: row.sequence as u64,
+                    id: AuditEntryId::from(row.id),
+                    actor: actor_from_columns(row.account_id, row.profile_id, row.admin_api_key_id),
+                    rpc: row.rpc,
+                    request: row.request,
+                    outcome: row.outcome.map(serde_json::from_value).transpose()?,
+                    prev_hash: row.prev_hash,
+                    hash: row.hash,
+                    recorded_at: row.recorded_at,
+                };
+                verification.n_entries += 1;
+                if entry.prev_hash != verification.last_hash || entry.computed_hash() != entry.hash
+                {
+                    verification.first_invalid_sequence = Some(entry.sequence);
+                    return Ok(verification);
+                }
+                verification.last_hash = entry.hash;
+            }
+        }
+    }
+}
+
+fn actor_from_columns(
+    account_id: Option<uuid::Uuid>,
+    profile_id: Option<uuid::Uuid>,
+    admin_api_key_id: Option<uuid::Uuid>,
+) -> AuditActor {
+    match (account_id, profile_id, admin_api_key_id) {
+        (Some(account_id), Some(profile_id), _) => AuditActor::Profile {
+            account_id: AccountId::from(account_id),
+            profile_id: ProfileId::from(profile_id),
+        },
+        (_, _, Some(id)) => AuditActor::AdminApiKey {
+            id: AdminApiKeyId::from(id),
+        },
+        _ => AuditActor::Unauthenticated,
+    }
+}
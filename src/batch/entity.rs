@@ -2,16 +2,19 @@ use derive_builder::Builder;
 
 use std::collections::HashMap;
 
+use super::fee_bump::BatchFeeBump;
 use crate::primitives::*;
 
 pub struct Batch {
     pub id: BatchId,
     pub account_id: AccountId,
-    pub payout_queue_id: PayoutQueueId,
+    pub payout_queue_id: Option<PayoutQueueId>,
     pub bitcoin_tx_id: bitcoin::Txid,
     pub wallet_summaries: HashMap<WalletId, WalletSummary>,
     pub unsigned_psbt: bitcoin::psbt::PartiallySignedTransaction,
     pub signed_tx: Option<bitcoin::Transaction>,
+    pub fee_bumps: Vec<BatchFeeBump>,
+    pub cancelled: bool,
 }
 
 impl Batch {
@@ -19,6 +22,11 @@ impl Batch {
         self.wallet_summaries
             .values()
             .all(|s| s.batch_created_ledger_tx_id.is_some())
+            && self.fee_bumps.iter().all(|bump| {
+                bump.wallet_summaries.values().all(|s| {
+                    s.batch_fee_bumped_ledger_tx_id.is_some() || s.reverted_ledger_tx_id.is_some()
+                })
+            })
     }
 }
 
@@ -26,7 +34,8 @@ impl Batch {
 pub struct NewBatch {
     pub id: BatchId,
     pub(super) account_id: AccountId,
-    pub(super) payout_queue_id: PayoutQueueId,
+    #[builder(setter(strip_option), default)]
+    pub(super) payout_queue_id: Option<PayoutQueueId>,
     pub(super) tx_id: bitcoin::Txid,
     pub(super) total_fee_sats: Satoshis,
     pub(super) unsigned_psbt: bitcoin::psbt::PartiallySignedTransaction,
@@ -4,6 +4,18 @@ use thiserror::Error;
 pub enum BatchError {
     #[error("BatchError - Could not find batch with id: {0}")]
     BatchIdNotFound(String),
+    #[error("BatchError - Batch {0} has not been broadcast")]
+    BatchNotBroadcast(crate::primitives::BatchId),
+    #[error("BatchError - Batch {0} does not signal replaceability")]
+    BatchNotReplaceable(crate::primitives::BatchId),
+    #[error("BatchError - Fee rate too low for replacement, need at least {0} sats in fees")]
+    FeeRateTooLowForReplacement(u64),
+    #[error("BatchError - Insufficient change to bump fee for wallet: {0}")]
+    InsufficientChangeForFeeBump(crate::primitives::WalletId),
+    #[error("BatchError - Batch {0} was modified concurrently")]
+    BatchModifiedConcurrently(crate::primitives::BatchId),
+    #[error("BatchError - Batch {0} has already been signed or cancelled")]
+    BatchNotCancellable(crate::primitives::BatchId),
     #[error("BatchError - Sqlx: {0}")]
     Sqlx(#[from] sqlx::Error),
     #[error("BatchError - EntityError: {0}")]
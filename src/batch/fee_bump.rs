@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use super::{entity::*, error::BatchError};
+use crate::primitives::{bitcoin::psbt, *};
+
+// BIP125 requires the replacement to pay for its own bandwidth at the minimum relay fee
+const MIN_RELAY_SATS_PER_VBYTE: u64 = 1;
+const MAX_BIP125_RBF_SEQUENCE: u32 = 0xfffffffd;
+
+pub struct BatchFeeBump {
+    pub id: BatchFeeBumpId,
+    pub bitcoin_tx_id: bitcoin::Txid,
+    pub total_fee_sats: Satoshis,
+    pub replaced_bitcoin_tx_id: bitcoin::Txid,
+    pub replaced_total_fee_sats: Satoshis,
+    pub wallet_summaries: HashMap<WalletId, WalletFeeBumpSummary>,
+}
+
+impl BatchFeeBump {
+    pub fn is_reverted(&self) -> bool {
+        self.wallet_summaries
+            .values()
+            .all(|s| s.reverted_ledger_tx_id.is_some())
+    }
+}
+
+#[derive(Clone)]
+pub struct WalletFeeBumpSummary {
+    pub wallet_id: WalletId,
+    pub fee_sats: Satoshis,
+    pub change_sats: Satoshis,
+    pub replaced_fee_sats: Satoshis,
+    pub replaced_change_sats: Satoshis,
+    pub replaced_ledger_tx_id: LedgerTransactionId,
+    pub batch_fee_bumped_ledger_tx_id: Option<LedgerTransactionId>,
+    pub reverted_ledger_tx_id: Option<LedgerTransactionId>,
+}
+
+pub struct NewBatchFeeBump {
+    pub id: BatchFeeBumpId,
+    pub(super) batch_id: BatchId,
+    pub bitcoin_tx_id: bitcoin::Txid,
+    pub(super) total_fee_sats: Satoshis,
+    pub(super) unsigned_psbt: psbt::PartiallySignedTransaction,
+    pub(super) replaced_bitcoin_tx_id: bitcoin::Txid,
+    pub(super) replaced_total_fee_sats: Satoshis,
+    pub(super) replaced_unsigned_psbt: psbt::PartiallySignedTransaction,
+    pub(super) replaced_signed_tx: bitcoin::Transaction,
+    pub(super) wallet_summaries: HashMap<WalletId, NewWalletFeeBumpSummary>,
+}
+
+impl NewBatchFeeBump {
+    pub fn unsigned_psbt(&self) -> &psbt::PartiallySignedTransaction {
+        &self.unsigned_psbt
+    }
+
+    pub fn wallet_ids(&self) -> impl Iterator<Item = WalletId> + '_ {
+        self.wallet_summaries.keys().copied()
+    }
+}
+
+pub struct RevertedFeeBumps {
+    pub batch_id: BatchId,
+    pub wallet_id: WalletId,
+    pub current_keychain_id: KeychainId,
+    pub change_vout: Option<u32>,
+    pub bitcoin_tx_id: bitcoin::Txid,
+    pub fee_sats: Satoshis,
+    pub change_sats: Satoshis,
+    pub restored_ledger_tx_id: LedgerTransactionId,
+    pub reverted: Option<(bitcoin::Txid, LedgerTransactionId)>,
+    pub ledger_tx_id: LedgerTransactionId,
+}
+
+pub(super) struct NewWalletFeeBumpSummary {
+    pub(super) fee_sats: Satoshis,
+    pub(super) change_sats: Satoshis,
+    pub(super) replaced_fee_sats: Satoshis,
+    pub(super) replaced_change_sats: Satoshis,
+}
+
+impl Batch {
+    pub fn bump_fee(&self, fee_rate: bitcoin::FeeRate) -> Result<NewBatchFeeBump, BatchError> {
+        let signed_tx = self
+            .signed_tx
+            .as_ref()
+            .ok_or(BatchError::BatchNotBroadcast(self.id))?;
+        if !self.accounting_complete()
+            || self
+                .wallet_summaries
+                .values()
+                .any(|s| s.batch_broadcast_ledger_tx_id.is_none())
+        {
+            return Err(BatchError::BatchNotBroadcast(self.id));
+        }
+        if !signed_tx
+            .input
+            .iter()
+            .any(|input| input.sequence.0 <= MAX_BIP125_RBF_SEQUENCE)
+        {
+            return Err(BatchError::BatchNotReplaceable(self.id));
+        }
+
+        let vsize = signed_tx.vsize();
+        let replaced_total_fee_sats = self
+            .wallet_summaries
+            .values()
+            .fold(Satoshis::ZERO, |s, summary| s + summary.fee_sats);
+        let total_fee_sats = Satoshis::from(fee_rate.fee_vb(vsize));
+        let min_total_fee_sats =
+            replaced_total_fee_sats + Satoshis::from(vsize as u64 * MIN_RELAY_SATS_PER_VBYTE);
+        if total_fee_sats < min_total_fee_sats {
+            return Err(BatchError::FeeRateTooLowForReplacement(u64::from(
+                min_total_fee_sats,
+            )));
+        }
+
+        let additional_fees = allocate_additional_fees(
+            total_fee_sats - replaced_total_fee_sats,
+            self.wallet_summaries
+                .values()
+                .map(|summary| (summary.wallet_id, summary.fee_sats)),
+        );
+        let mut unsigned_psbt = self.unsigned_psbt.clone();
+        let mut wallet_summaries = HashMap::new();
+        for (wallet_id, additional_fee) in additional_fees {
+            let summary = &self.wallet_summaries[&wallet_id];
+            let output = summary
+                .change_outpoint
+                .and_then(|outpoint| {
+                    unsigned_psbt
+                        .unsigned_tx
+                        .output
+                        .get_mut(outpoint.vout as usize)
+                })
+                .ok_or(BatchError::InsufficientChangeForFeeBump(wallet_id))?;
+            let change_sats = Satoshis::from(output.value);
+            if change_sats < additional_fee
+                || u64::from(change_sats - additional_fee)
+                    < output.script_pubkey.dust_value().to_sat()
+            {
+                return Err(BatchError::InsufficientChangeForFeeBump(wallet_id));
+            }
+            output.value = u64::from(change_sats - additional_fee);
+            wallet_summaries.insert(
+                wallet_id,
+                NewWalletFeeBumpSummary {
+                    fee_sats: summary.fee_sats + additional_fee,
+                    change_sats: change_sats - additional_fee,
+                    replaced_fee_sats: summary.fee_sats,
+                    replaced_change_sats: change_sats,
+                },
+            );
+        }
+
+        Ok(NewBatchFeeBump {
+            id: BatchFeeBumpId::new(),
+            batch_id: self.id,
+            bitcoin_tx_id: unsigned_psbt.unsigned_tx.txid(),
+            total_fee_sats,
+            unsigned_psbt,
+            replaced_bitcoin_tx_id: self.bitcoin_tx_id,
+            replaced_total_fee_sats,
+            replaced_unsigned_psbt: self.unsigned_psbt.clone(),
+            replaced_signed_tx: signed_tx.clone(),
+            wallet_summaries,
+        })
+    }
+}
+
+fn allocate_additional_fees(
+    additional_fees: Satoshis,
+    wallet_fees: impl Iterator<Item = (WalletId, Satoshis)>,
+) -> Vec<(WalletId, Satoshis)> {
+    let mut wallet_fees: Vec<_> = wallet_fees.collect();
+    wallet_fees.sort_by_key(|(_, fees)| *fees);
+    let total_fees = wallet_fees
+        .iter()
+        .fold(Satoshis::ZERO, |s, (_, fees)| s + *fees);
+    let mut allocated = Satoshis::ZERO;
+    let n_wallets = wallet_fees.len();
+    wallet_fees
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (wallet_id, fees))| {
+            let share = if idx == n_wallets - 1 {
+                additional_fees - allocated
+            } else if total_fees == Satoshis::ZERO {
+                Satoshis::ZERO
+            } else {
+                Satoshis::from(u64::from(additional_fees) * u64::from(fees) / u64::from(total_fees))
+            };
+            allocated += share;
+            (wallet_id, share)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allocate_additional_fees_proportionally() {
+        let small = WalletId::new();
+        let large = WalletId::new();
+        let allocations: HashMap<_, _> = allocate_additional_fees(
+            Satoshis::from(1_001),
+            vec![
+                (large, Satoshis::from(3_000)),
+                (small, Satoshis::from(1_000)),
+            ]
+            .into_iter(),
+        )
+        .into_iter()
+        .collect();
+        assert_eq!(allocations[&small], Satoshis::from(250));
+        assert_eq!(allocations[&large], Satoshis::from(751));
+    }
+}
@@ -1,6 +1,8 @@
 mod entity;
 pub mod error;
+mod fee_bump;
 mod repo;
 
 pub use entity::*;
+pub use fee_bump::*;
 pub use repo::*;
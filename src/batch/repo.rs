@@ -4,7 +4,7 @@ use tracing::instrument;
 
 use std::{collections::HashMap, str::FromStr};
 
-use super::{entity::*, error::BatchError};
+use super::{entity::*, error::BatchError, fee_bump::*};
 use crate::primitives::{bitcoin::*, *};
 
 #[derive(Debug, Clone)]
@@ -28,7 +28,7 @@ impl Batches {
             VALUES ($1, $2, $3, $4, $5, $6)"#,
             batch.id as BatchId,
             batch.account_id as AccountId,
-            batch.payout_queue_id as PayoutQueueId,
+            batch.payout_queue_id as Option<PayoutQueueId>,
             i64::from(batch.total_fee_sats),
             batch.tx_id.as_ref(),
             bitcoin::consensus::encode::serialize(&batch.unsigned_psbt)
@@ -74,7 +74,7 @@ impl Batches {
         id: BatchId,
     ) -> Result<Batch, BatchError> {
         let rows = sqlx::query!(
-            r#"SELECT payout_queue_id, unsigned_psbt, signed_tx, bitcoin_tx_id, s.batch_id, s.wallet_id, s.current_keychain_id, s.signing_keychains, total_in_sats, total_spent_sats, change_sats, change_address, change_vout, fee_sats, batch_created_ledger_tx_id, batch_broadcast_ledger_tx_id
+            r#"SELECT payout_queue_id, unsigned_psbt, signed_tx, bitcoin_tx_id, cancelled_at, s.batch_id, s.wallet_id, s.current_keychain_id, s.signing_keychains, total_in_sats, total_spent_sats, change_sats, change_address, change_vout, fee_sats, batch_created_ledger_tx_id, batch_broadcast_ledger_tx_id
             FROM bria_batch_wallet_summaries s
             LEFT JOIN bria_batches b ON b.id = s.batch_id
             WHERE s.batch_id = $1 AND b.account_id = $2"#,
@@ -128,27 +128,168 @@ impl Batches {
             );
         }
 
+        let fee_bumps = self.list_fee_bumps(id).await?;
+
         Ok(Batch {
             id,
             account_id,
-            payout_queue_id: PayoutQueueId::from(rows[0].payout_queue_id),
+            payout_queue_id: rows[0].payout_queue_id.map(PayoutQueueId::from),
             bitcoin_tx_id,
             unsigned_psbt,
             signed_tx,
             wallet_summaries,
+            fee_bumps,
+            cancelled: rows[0].cancelled_at.is_some(),
         })
     }
 
+    async fn list_fee_bumps(&self, batch_id: BatchId) -> Result<Vec<BatchFeeBump>, BatchError> {
+        let rows = sqlx::query!(
+            r#"SELECT b.id, b.bitcoin_tx_id, b.total_fee_sats, b.replaced_bitcoin_tx_id, b.replaced_total_fee_sats,
+                      s.wallet_id, s.fee_sats, s.change_sats, s.replaced_fee_sats, s.replaced_change_sats,
+                      s.replaced_ledger_tx_id, s.batch_fee_bumped_ledger_tx_id, s.reverted_ledger_tx_id
+            FROM bria_batch_fee_bumps b
+            JOIN bria_batch_fee_bump_wallet_summaries s ON b.id = s.fee_bump_id
+            WHERE b.batch_id = $1
+            ORDER BY b.created_at, b.id"#,
+            batch_id as BatchId,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut fee_bumps: Vec<BatchFeeBump> = Vec::new();
+        for row in rows {
+            let id = BatchFeeBumpId::from(row.id);
+            if fee_bumps.last().map(|bump| bump.id) != Some(id) {
+                fee_bumps.push(BatchFeeBump {
+                    id,
+                    bitcoin_tx_id: bitcoin::consensus::deserialize(&row.bitcoin_tx_id)?,
+                    total_fee_sats: Satoshis::from(row.total_fee_sats),
+                    replaced_bitcoin_tx_id: bitcoin::consensus::deserialize(
+                        &row.replaced_bitcoin_tx_id,
+                    )?,
+                    replaced_total_fee_sats: Satoshis::from(row.replaced_total_fee_sats),
+                    wallet_summaries: HashMap::new(),
+                });
+            }
+            let wallet_id = WalletId::from(row.wallet_id);
+            fee_bumps
+                .last_mut()
+                .expect("fee bump should exist")
+                .wallet_summaries
+                .insert(
+                    wallet_id,
+                    WalletFeeBumpSummary {
+                        wallet_id,
+                        fee_sats: Satoshis::from(row.fee_sats),
+                        change_sats: Satoshis::from(row.change_sats),
+                        replaced_fee_sats: Satoshis::from(row.replaced_fee_sats),
+                        replaced_change_sats: Satoshis::from(row.replaced_change_sats),
+                        replaced_ledger_tx_id: LedgerTxId::from(row.replaced_ledger_tx_id),
+                        batch_fee_bumped_ledger_tx_id: row
+                            .batch_fee_bumped_ledger_tx_id
+                            .map(LedgerTxId::from),
+                        reverted_ledger_tx_id: row.reverted_ledger_tx_id.map(LedgerTxId::from),
+                    },
+                );
+        }
+        Ok(fee_bumps)
+    }
+
+    #[instrument(name = "batches.create_fee_bump_in_tx", skip_all)]
+    pub async fn create_fee_bump_in_tx<'a>(
+        &self,
+        tx: &mut Transaction<'a, Postgres>,
+        fee_bump: NewBatchFeeBump,
+    ) -> Result<BatchFeeBumpId, BatchError> {
+        let rows_affected = sqlx::query!(
+            r#"UPDATE bria_batches
+               SET bitcoin_tx_id = $1, unsigned_psbt = $2, total_fee_sats = $3, signed_tx = NULL, modified_at = NOW()
+               WHERE id = $4 AND bitcoin_tx_id = $5"#,
+            fee_bump.bitcoin_tx_id.as_ref(),
+            bitcoin::consensus::encode::serialize(&fee_bump.unsigned_psbt),
+            i64::from(fee_bump.total_fee_sats),
+            fee_bump.batch_id as BatchId,
+            fee_bump.replaced_bitcoin_tx_id.as_ref(),
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if rows_affected == 0 {
+            return Err(BatchError::BatchModifiedConcurrently(fee_bump.batch_id));
+        }
+
+        sqlx::query!(
+            r#"INSERT INTO bria_batch_fee_bumps
+               (id, batch_id, bitcoin_tx_id, total_fee_sats, replaced_bitcoin_tx_id, replaced_total_fee_sats, replaced_unsigned_psbt, replaced_signed_tx)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+            fee_bump.id as BatchFeeBumpId,
+            fee_bump.batch_id as BatchId,
+            fee_bump.bitcoin_tx_id.as_ref(),
+            i64::from(fee_bump.total_fee_sats),
+            fee_bump.replaced_bitcoin_tx_id.as_ref(),
+            i64::from(fee_bump.replaced_total_fee_sats),
+            bitcoin::consensus::encode::serialize(&fee_bump.replaced_unsigned_psbt),
+            bitcoin::consensus::encode::serialize(&fee_bump.replaced_signed_tx),
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for (wallet_id, summary) in fee_bump.wallet_summaries {
+            sqlx::query!(
+                r#"INSERT INTO bria_batch_fee_bump_wallet_summaries
+                   (fee_bump_id, wallet_id, fee_sats, change_sats, replaced_fee_sats, replaced_change_sats, replaced_ledger_tx_id)
+                   SELECT $1, $2, $3, $4, $5, $6, COALESCE(
+                     (SELECT s.batch_fee_bumped_ledger_tx_id
+                      FROM bria_batch_fee_bump_wallet_summaries s
+                      JOIN bria_batch_fee_bumps b ON b.id = s.fee_bump_id
+                      WHERE b.batch_id = $7 AND s.wallet_id = $2 AND b.id != $1
+                        AND s.batch_fee_bumped_ledger_tx_id IS NOT NULL AND s.reverted_ledger_tx_id IS NULL
+                      ORDER BY b.created_at DESC LIMIT 1),
+                     (SELECT batch_broadcast_ledger_tx_id
+                      FROM bria_batch_wallet_summaries
+                      WHERE batch_id = $7 AND wallet_id = $2)
+                   )"#,
+                fee_bump.id as BatchFeeBumpId,
+                wallet_id as WalletId,
+                i64::from(summary.fee_sats),
+                i64::from(summary.change_sats),
+                i64::from(summary.replaced_fee_sats),
+                i64::from(summary.replaced_change_sats),
+                fee_bump.batch_id as BatchId,
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!(
+                r#"UPDATE bria_batch_wallet_summaries
+                   SET fee_sats = $1, change_sats = $2, modified_at = NOW()
+                   WHERE batch_id = $3 AND wallet_id = $4"#,
+                i64::from(summary.fee_sats),
+                i64::from(summary.change_sats),
+                fee_bump.batch_id as BatchId,
+                wallet_id as WalletId,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        Ok(fee_bump.id)
+    }
+
     #[instrument(name = "batches.set_signed_tx", skip(self))]
     pub async fn set_signed_tx(
         &self,
         batch_id: BatchId,
         bitcoin_tx: bitcoin::Transaction,
     ) -> Result<(), BatchError> {
+        let bitcoin_tx_id = bitcoin_tx.txid();
         sqlx::query!(
-            r#"UPDATE bria_batches SET signed_tx = $1 WHERE id = $2"#,
+            r#"UPDATE bria_batches SET signed_tx = $1
+            WHERE id = $2 AND bitcoin_tx_id = $3 AND cancelled_at IS NULL"#,
             bitcoin::consensus::encode::serialize(&bitcoin_tx),
             batch_id as BatchId,
+            bitcoin_tx_id.as_ref(),
         )
         .execute(&self.pool)
         .await?;
@@ -156,6 +297,48 @@ impl Batches {
         Ok(())
     }
 
+    /// Marks an unsigned batch as cancelled and returns the BATCH_CREATED
+    /// ledger transactions that need to be reversed
+    #[instrument(name = "batches.cancel", skip(self, tx))]
+    pub async fn cancel(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        batch_id: BatchId,
+        cancelled_by: ProfileId,
+    ) -> Result<Vec<(WalletId, LedgerTxId)>, BatchError> {
+        let rows_affected = sqlx::query!(
+            r#"UPDATE bria_batches
+            SET cancelled_at = NOW(), cancelled_by_profile_id = $1, modified_at = NOW()
+            WHERE id = $2 AND cancelled_at IS NULL AND signed_tx IS NULL"#,
+            cancelled_by as ProfileId,
+            batch_id as BatchId,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if rows_affected == 0 {
+            return Err(BatchError::BatchNotCancellable(batch_id));
+        }
+
+        let rows = sqlx::query!(
+            r#"SELECT wallet_id, batch_created_ledger_tx_id AS "batch_created_ledger_tx_id!"
+            FROM bria_batch_wallet_summaries
+            WHERE batch_id = $1 AND batch_created_ledger_tx_id IS NOT NULL"#,
+            batch_id as BatchId,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    WalletId::from(row.wallet_id),
+                    LedgerTxId::from(row.batch_created_ledger_tx_id),
+                )
+            })
+            .collect())
+    }
+
     #[instrument(name = "batches.set_batch_created_ledger_tx_id", skip(self))]
     pub async fn set_batch_created_ledger_tx_id(
         &self,
@@ -163,6 +346,17 @@ impl Batches {
         wallet_id: WalletId,
     ) -> Result<Option<(Transaction<'_, Postgres>, LedgerTxId)>, BatchError> {
         let mut tx = self.pool.begin().await?;
+        // Blocks a concurrent cancel until the accounting is committed so it can be reversed
+        let batch = sqlx::query!(
+            r#"SELECT cancelled_at IS NOT NULL AS "cancelled!"
+               FROM bria_batches WHERE id = $1 FOR SHARE"#,
+            batch_id as BatchId,
+        )
+        .fetch_one(&mut tx)
+        .await?;
+        if batch.cancelled {
+            return Ok(None);
+        }
         let ledger_transaction_id = LedgerTxId::new();
         let rows_affected = sqlx::query!(
             r#"UPDATE bria_batch_wallet_summaries
@@ -241,4 +435,173 @@ impl Batches {
             ledger_transaction_id,
         )))
     }
+
+    #[instrument(name = "batches.set_batch_fee_bumped_ledger_tx_id", skip(self))]
+    pub async fn set_batch_fee_bumped_ledger_tx_id(
+        &self,
+        fee_bump_id: BatchFeeBumpId,
+        wallet_id: WalletId,
+    ) -> Result<Option<(Transaction<'_, Postgres>, LedgerTxId)>, BatchError> {
+        let mut tx = self.pool.begin().await?;
+        let ledger_transaction_id = LedgerTxId::new();
+        let rows_affected = sqlx::query!(
+            r#"UPDATE bria_batch_fee_bump_wallet_summaries
+               SET batch_fee_bumped_ledger_tx_id = $1, modified_at = NOW()
+               WHERE fee_bump_id = $2 AND wallet_id = $3
+                 AND batch_fee_bumped_ledger_tx_id IS NULL AND reverted_ledger_tx_id IS NULL"#,
+            ledger_transaction_id as LedgerTxId,
+            fee_bump_id as BatchFeeBumpId,
+            wallet_id as WalletId,
+        )
+        .execute(&mut tx)
+        .await?
+        .rows_affected();
+
+        if rows_affected > 0 {
+            Ok(Some((tx, ledger_transaction_id)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[instrument(name = "batches.revert_fee_bumps", skip(self))]
+    pub async fn revert_fee_bumps(
+        &self,
+        bitcoin_tx_id: bitcoin::Txid,
+        wallet_id: WalletId,
+    ) -> Result<Option<(Transaction<'_, Postgres>, RevertedFeeBumps)>, BatchError> {
+        let mut tx = self.pool.begin().await?;
+        let first_bump = sqlx::query!(
+            r#"SELECT id, batch_id, created_at FROM bria_batch_fee_bumps
+               WHERE replaced_bitcoin_tx_id = $1
+               ORDER BY created_at LIMIT 1"#,
+            bitcoin_tx_id.as_ref(),
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+        let first_bump = match first_bump {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+        let rows = sqlx::query!(
+            r#"SELECT b.id, b.bitcoin_tx_id, s.replaced_fee_sats, s.replaced_change_sats, s.replaced_ledger_tx_id, s.batch_fee_bumped_ledger_tx_id
+               FROM bria_batch_fee_bump_wallet_summaries s
+               JOIN bria_batch_fee_bumps b ON b.id = s.fee_bump_id
+               WHERE b.batch_id = $1 AND b.created_at >= $2 AND s.wallet_id = $3
+                 AND s.reverted_ledger_tx_id IS NULL
+               ORDER BY b.created_at
+               FOR UPDATE OF s"#,
+            first_bump.batch_id,
+            first_bump.created_at,
+            wallet_id as WalletId,
+        )
+        .fetch_all(&mut tx)
+        .await?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let ledger_tx_id = LedgerTxId::new();
+        sqlx::query!(
+            r#"UPDATE bria_batch_fee_bump_wallet_summaries
+               SET reverted_ledger_tx_id = $1, modified_at = NOW()
+               WHERE fee_bump_id = ANY($2) AND wallet_id = $3"#,
+            ledger_tx_id as LedgerTxId,
+            &rows.iter().map(|row| row.id).collect::<Vec<_>>(),
+            wallet_id as WalletId,
+        )
+        .execute(&mut tx)
+        .await?;
+        sqlx::query!(
+            r#"UPDATE bria_batches
+               SET bitcoin_tx_id = b.replaced_bitcoin_tx_id,
+                   unsigned_psbt = b.replaced_unsigned_psbt,
+                   signed_tx = b.replaced_signed_tx,
+                   total_fee_sats = b.replaced_total_fee_sats,
+                   modified_at = NOW()
+               FROM bria_batch_fee_bumps b
+               WHERE b.id = $1 AND bria_batches.id = b.batch_id"#,
+            first_bump.id,
+        )
+        .execute(&mut tx)
+        .await?;
+        let summary = sqlx::query!(
+            r#"UPDATE bria_batch_wallet_summaries
+               SET fee_sats = $1, change_sats = $2, modified_at = NOW()
+               WHERE batch_id = $3 AND wallet_id = $4
+               RETURNING current_keychain_id, change_vout"#,
+            rows[0].replaced_fee_sats,
+            rows[0].replaced_change_sats,
+            first_bump.batch_id,
+            wallet_id as WalletId,
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        let latest_accounted = rows
+            .iter()
+            .rev()
+            .find(|row| row.batch_fee_bumped_ledger_tx_id.is_some());
+        let reverted = if let Some(row) = latest_accounted {
+            Some((
+                bitcoin::consensus::deserialize(&row.bitcoin_tx_id)?,
+                LedgerTxId::from(
+                    row.batch_fee_bumped_ledger_tx_id
+                        .expect("ledger tx id should be set"),
+                ),
+            ))
+        } else {
+            None
+        };
+
+        Ok(Some((
+            tx,
+            RevertedFeeBumps {
+                batch_id: BatchId::from(first_bump.batch_id),
+                wallet_id,
+                current_keychain_id: KeychainId::from(summary.current_keychain_id),
+                change_vout: summary.change_vout.map(|vout| vout as u32),
+                bitcoin_tx_id,
+                fee_sats: Satoshis::from(rows[0].replaced_fee_sats),
+                change_sats: Satoshis::from(rows[0].replaced_change_sats),
+                restored_ledger_tx_id: LedgerTxId::from(rows[0].replaced_ledger_tx_id),
+                reverted,
+                ledger_tx_id,
+            },
+        )))
+    }
+
+    /// Whether the tx is the replacement tx of a fee bump
+    #[instrument(name = "batches.is_fee_bump_tx", skip(self))]
+    pub async fn is_fee_bump_tx(&self, bitcoin_tx_id: bitcoin::Txid) -> Result<bool, BatchError> {
+        let row = sqlx::query!(
+            r#"SELECT EXISTS (
+                 SELECT 1 FROM bria_batch_fee_bumps
+                 WHERE bitcoin_tx_id = $1
+               ) as "exists!""#,
+            bitcoin_tx_id.as_ref(),
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.exists)
+    }
+
+    /// Whether the tx is either side of a fee bump, ie. its change utxo may have been
+    /// moved to the other tx
+    #[instrument(name = "batches.is_fee_bump_involved_tx", skip(self))]
+    pub async fn is_fee_bump_involved_tx(
+        &self,
+        bitcoin_tx_id: bitcoin::Txid,
+    ) -> Result<bool, BatchError> {
+        let row = sqlx::query!(
+            r#"SELECT EXISTS (
+                 SELECT 1 FROM bria_batch_fee_bumps
+                 WHERE bitcoin_tx_id = $1 OR replaced_bitcoin_tx_id = $1
+               ) as "exists!""#,
+            bitcoin_tx_id.as_ref(),
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.exists)
+    }
 }
@@ -13,6 +13,7 @@ pub struct UnsyncedTransaction {
     pub fee_sats: Satoshis,
     pub inputs: Vec<(LocalUtxo, u32)>,
     pub outputs: Vec<(LocalUtxo, u32)>,
+    pub previous_outputs: Vec<bitcoin::OutPoint>,
 }
 
 pub struct ConfirmedSpendTransaction {
@@ -149,6 +150,7 @@ impl Transactions {
         let mut tx_id = None;
         let mut confirmation_time = None;
         let mut sats_per_vbyte_when_created = 0.0;
+        let mut previous_outputs = Vec::new();
 
         let mut total_utxo_in_sats = Satoshis::ZERO;
         let mut fee_sats = Satoshis::ZERO;
@@ -165,8 +167,14 @@ impl Transactions {
                 let details: TransactionDetails = serde_json::from_value(row.details_json)?;
                 total_utxo_in_sats = Satoshis::from(details.sent);
                 fee_sats = Satoshis::from(details.fee.expect("Fee"));
-                sats_per_vbyte_when_created = details.fee.expect("Fee") as f32
-                    / details.transaction.expect("transaction").vsize() as f32;
+                let transaction = details.transaction.expect("transaction");
+                sats_per_vbyte_when_created =
+                    details.fee.expect("Fee") as f32 / transaction.vsize() as f32;
+                previous_outputs = transaction
+                    .input
+                    .iter()
+                    .map(|input| input.previous_output)
+                    .collect();
                 confirmation_time = details.confirmation_time;
             }
         }
@@ -178,6 +186,7 @@ impl Transactions {
             sats_per_vbyte_when_created,
             inputs,
             outputs,
+            previous_outputs,
         }))
     }
 
@@ -33,9 +33,13 @@ fn extract_addresses(file_contents: &str) -> HashSet<bitcoin::Address> {
     addresses
 }
 
-pub fn read_and_parse_addresses(file_path: impl AsRef<Path>) -> anyhow::Result<()> {
+pub fn read_addresses(file_path: impl AsRef<Path>) -> anyhow::Result<HashSet<bitcoin::Address>> {
     let s = std::fs::read_to_string(file_path).context("Couldn't read file")?;
-    let blocked_addresses = extract_addresses(&s).into_iter().collect();
+    Ok(extract_addresses(&s))
+}
+
+pub fn read_and_parse_addresses(file_path: impl AsRef<Path>) -> anyhow::Result<()> {
+    let blocked_addresses = read_addresses(file_path)?.into_iter().collect();
 
     let app_output = AppOutput {
         app: SecurityOutput {
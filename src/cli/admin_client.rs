@@ -6,6 +6,8 @@ type ProtoClient = proto::admin_service_client::AdminServiceClient<tonic::transp
 
 use super::token_store;
 
+const IMPORT_CHUNK_SIZE: usize = 1000;
+
 pub struct AdminApiClientConfig {
     pub url: Url,
 }
@@ -114,6 +116,216 @@ impl AdminApiClient {
             .await?;
         output_json(response)
     }
+
+    pub async fn rename_account(&self, id: String, name: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::RenameAccountRequest { id, name });
+        let response = self
+            .connect()
+            .await?
+            .rename_account(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn deactivate_account(&self, id: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::DeactivateAccountRequest { id });
+        let response = self
+            .connect()
+            .await?
+            .deactivate_account(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn update_account_config(
+        &self,
+        id: String,
+        blocked_addresses: Vec<String>,
+        fees: Option<serde_json::Value>,
+    ) -> anyhow::Result<()> {
+        let fees = fees
+            .map(serde_json::from_value)
+            .transpose()
+            .context("Fees config must be an object")?;
+        let request = tonic::Request::new(proto::UpdateAccountConfigRequest {
+            id,
+            config: Some(proto::AccountConfig {
+                blocked_addresses,
+                fees,
+            }),
+        });
+        let response = self
+            .connect()
+            .await?
+            .update_account_config(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn add_blocked_addresses(
+        &self,
+        addresses: Vec<String>,
+        reason: Option<String>,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::AddBlockedAddressesRequest { addresses, reason });
+        let response = self
+            .connect()
+            .await?
+            .add_blocked_addresses(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn import_blocked_addresses(
+        &self,
+        addresses: Vec<String>,
+        reason: Option<String>,
+    ) -> anyhow::Result<()> {
+        let mut client = self.connect().await?;
+        let mut n_added = 0;
+        for chunk in addresses.chunks(IMPORT_CHUNK_SIZE) {
+            let request = tonic::Request::new(proto::AddBlockedAddressesRequest {
+                addresses: chunk.to_vec(),
+                reason: reason.clone(),
+            });
+            n_added += client
+                .add_blocked_addresses(self.inject_admin_auth_token(request)?)
+                .await?
+                .into_inner()
+                .n_added;
+        }
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&proto::AddBlockedAddressesResponse { n_added })?
+        );
+        Ok(())
+    }
+
+    pub async fn remove_blocked_address(&self, address: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::RemoveBlockedAddressRequest { address });
+        let response = self
+            .connect()
+            .await?
+            .remove_blocked_address(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn list_blocked_addresses(&self) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ListBlockedAddressesRequest {});
+        let response = self
+            .connect()
+            .await?
+            .list_blocked_addresses(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn list_blocked_payout_attempts(
+        &self,
+        account_id: Option<String>,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ListBlockedPayoutAttemptsRequest { account_id });
+        let response = self
+            .connect()
+            .await?
+            .list_blocked_payout_attempts(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn list_quarantined_utxos(&self, account_id: Option<String>) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ListQuarantinedUtxosRequest { account_id });
+        let response = self
+            .connect()
+            .await?
+            .list_quarantined_utxos(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn release_quarantined_utxo(
+        &self,
+        account_id: String,
+        outpoint: String,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ReleaseQuarantinedUtxoRequest {
+            account_id,
+            outpoint,
+        });
+        let response = self
+            .connect()
+            .await?
+            .release_quarantined_utxo(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn create_admin_api_key(&self, name: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::CreateAdminApiKeyRequest { name });
+        let response = self
+            .connect()
+            .await?
+            .create_admin_api_key(self.inject_admin_auth_token(request)?)
+            .await?;
+        let key = response.into_inner().key.context("No key in response")?;
+        print_admin_api_key(key);
+        Ok(())
+    }
+
+    pub async fn list_admin_api_keys(&self) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ListAdminApiKeysRequest {});
+        let response = self
+            .connect()
+            .await?
+            .list_admin_api_keys(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn revoke_admin_api_key(&self, id: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::RevokeAdminApiKeyRequest { id });
+        let response = self
+            .connect()
+            .await?
+            .revoke_admin_api_key(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn list_audit_events(
+        &self,
+        account_id: Option<String>,
+        rpc: Option<String>,
+        after_sequence: Option<u64>,
+        limit: Option<u32>,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ListAuditEventsRequest {
+            account_id,
+            rpc,
+            after_sequence,
+            limit,
+        });
+        let response = self
+            .connect()
+            .await?
+            .list_audit_events(self.inject_admin_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn rotate_bootstrap_key(&self) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::RotateBootstrapKeyRequest {});
+        let response = self
+            .connect()
+            .await?
+            .rotate_bootstrap_key(self.inject_admin_auth_token(request)?)
+            .await?;
+        let key = response.into_inner().key.context("No key in response")?;
+        token_store::store_admin_token(&self.bria_home, &key.key)?;
+        print_admin_api_key(key);
+        Ok(())
+    }
 }
 
 pub fn print_admin_api_key(key: proto::AdminApiKey) {
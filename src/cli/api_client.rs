@@ -1,7 +1,7 @@
 use anyhow::Context;
 use url::Url;
 
-use crate::{api::proto, primitives::TxPriority};
+use crate::{api::proto, primitives::TxPriority, profile::ApiKeyScope};
 type ProtoClient = proto::bria_service_client::BriaServiceClient<tonic::transport::Channel>;
 
 use super::token_store;
@@ -62,8 +62,17 @@ impl ApiClient {
         Ok(request)
     }
 
-    pub async fn create_profile(&self, name: String) -> anyhow::Result<()> {
-        let request = tonic::Request::new(proto::CreateProfileRequest { name });
+    pub async fn create_profile(
+        &self,
+        name: String,
+        policy: Option<proto::ProfilePolicy>,
+        payout_approver: bool,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::CreateProfileRequest {
+            name,
+            policy,
+            payout_approver,
+        });
         let response = self
             .connect()
             .await?
@@ -72,6 +81,20 @@ impl ApiClient {
         output_json(response)
     }
 
+    pub async fn update_profile(
+        &self,
+        id: String,
+        policy: Option<proto::ProfilePolicy>,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::UpdateProfileRequest { id, policy });
+        let response = self
+            .connect()
+            .await?
+            .update_profile(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
     pub async fn list_profiles(&self) -> anyhow::Result<()> {
         let request = tonic::Request::new(proto::ListProfilesRequest {});
         let response = self
@@ -82,8 +105,20 @@ impl ApiClient {
         output_json(response)
     }
 
-    pub async fn create_profile_api_key(&self, profile_name: String) -> anyhow::Result<()> {
-        let request = tonic::Request::new(proto::CreateProfileApiKeyRequest { profile_name });
+    pub async fn create_profile_api_key(
+        &self,
+        profile_name: String,
+        scopes: Vec<ApiKeyScope>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::CreateProfileApiKeyRequest {
+            profile_name,
+            scopes: scopes
+                .into_iter()
+                .map(|scope| proto::ApiKeyScope::from(scope) as i32)
+                .collect(),
+            expires_at: expires_at.map(|expires_at| expires_at.timestamp() as u32),
+        });
         let response = self
             .connect()
             .await?
@@ -92,6 +127,47 @@ impl ApiClient {
         output_json(response)
     }
 
+    pub async fn list_profile_api_keys(&self, profile_name: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ListProfileApiKeysRequest { profile_name });
+        let response = self
+            .connect()
+            .await?
+            .list_profile_api_keys(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn revoke_profile_api_key(&self, id: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::RevokeProfileApiKeyRequest { id });
+        let response = self
+            .connect()
+            .await?
+            .revoke_profile_api_key(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn list_audit_events(
+        &self,
+        profile_id: Option<String>,
+        rpc: Option<String>,
+        after_sequence: Option<u64>,
+        limit: Option<u32>,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ListAuditEventsRequest {
+            profile_id,
+            rpc,
+            after_sequence,
+            limit,
+        });
+        let response = self
+            .connect()
+            .await?
+            .list_audit_events(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
     pub async fn import_xpub(
         &self,
         name: String,
@@ -139,6 +215,16 @@ impl ApiClient {
         output_json(response)
     }
 
+    pub async fn test_signer_config(&self, xpub_ref: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::TestSignerConfigRequest { xpub_ref });
+        let response = self
+            .connect()
+            .await?
+            .test_signer_config(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
     pub async fn submit_signed_psbt(
         &self,
         batch_id: String,
@@ -158,6 +244,49 @@ impl ApiClient {
         output_json(response)
     }
 
+    pub async fn list_signing_sessions(
+        &self,
+        batch_id: Option<String>,
+        xpub_ref: Option<String>,
+        state: Option<String>,
+        after_id: Option<String>,
+        limit: Option<u32>,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ListSigningSessionsRequest {
+            batch_id,
+            xpub_ref,
+            state,
+            after_id,
+            limit,
+        });
+        let response = self
+            .connect()
+            .await?
+            .list_signing_sessions(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn retry_signing_session(&self, id: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::RetrySigningSessionRequest { id });
+        let response = self
+            .connect()
+            .await?
+            .retry_signing_session(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn abort_batch_signing(&self, batch_id: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::AbortBatchSigningRequest { batch_id });
+        let response = self
+            .connect()
+            .await?
+            .abort_batch_signing(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
     pub async fn create_wallet(
         &self,
         name: String,
@@ -297,6 +426,12 @@ impl ApiClient {
         consolidate_deprecated_keychains: bool,
         interval_trigger: Option<u32>,
         manual_trigger: Option<bool>,
+        max_fee_rate: Option<u64>,
+        fee_strategy: Option<proto::payout_queue_config::FeeStrategy>,
+        threshold_trigger: Option<proto::payout_queue_config::Trigger>,
+        max_payouts_per_batch: Option<u32>,
+        max_tx_vbytes: Option<u64>,
+        approval: Option<proto::PayoutApprovalConfig>,
     ) -> anyhow::Result<()> {
         let tx_priority = match tx_priority {
             TxPriority::NextBlock => proto::TxPriority::NextBlock as i32,
@@ -315,11 +450,17 @@ impl ApiClient {
             }
             _ => None,
         };
+        let trigger = threshold_trigger.or(trigger);
 
         let config = proto::PayoutQueueConfig {
             tx_priority,
             consolidate_deprecated_keychains,
             trigger,
+            max_fee_rate_sats_per_vbyte: max_fee_rate,
+            fee_strategy,
+            max_payouts_per_batch,
+            max_tx_vbytes,
+            approval,
         };
 
         let request = tonic::Request::new(proto::CreatePayoutQueueRequest {
@@ -370,6 +511,7 @@ impl ApiClient {
         output_json(response)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn submit_payout(
         &self,
         wallet_name: String,
@@ -378,6 +520,8 @@ impl ApiClient {
         satoshis: u64,
         external_id: Option<String>,
         metadata: Option<serde_json::Value>,
+        priority: Option<u32>,
+        execute_after: Option<chrono::DateTime<chrono::Utc>>,
     ) -> anyhow::Result<()> {
         let request = tonic::Request::new(proto::SubmitPayoutRequest {
             wallet_name,
@@ -388,6 +532,8 @@ impl ApiClient {
             satoshis,
             external_id,
             metadata: metadata.map(serde_json::from_value).transpose()?,
+            priority,
+            execute_after: execute_after.map(|execute_after| execute_after.timestamp() as u32),
         });
         let response = self
             .connect()
@@ -407,6 +553,27 @@ impl ApiClient {
         output_json(response)
     }
 
+    pub async fn update_wallet(
+        &self,
+        wallet_name: String,
+        settle_income_after_n_confs: Option<u32>,
+        settle_change_after_n_confs: Option<u32>,
+        dust_threshold_sats: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::UpdateWalletRequest {
+            wallet_name,
+            settle_income_after_n_confs,
+            settle_change_after_n_confs,
+            dust_threshold_sats,
+        });
+        let response = self
+            .connect()
+            .await?
+            .update_wallet(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
     pub async fn list_payouts(&self, wallet: String) -> anyhow::Result<()> {
         let request = tonic::Request::new(proto::ListPayoutsRequest {
             wallet_name: wallet,
@@ -448,6 +615,26 @@ impl ApiClient {
         output_json(response)
     }
 
+    pub async fn approve_payout(&self, id: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::ApprovePayoutRequest { id });
+        let response = self
+            .connect()
+            .await?
+            .approve_payout(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn reject_payout(&self, id: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::RejectPayoutRequest { id });
+        let response = self
+            .connect()
+            .await?
+            .reject_payout(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
     pub async fn cancel_payout(&self, id: String) -> anyhow::Result<()> {
         let request = tonic::Request::new(proto::CancelPayoutRequest { id });
         let response = self
@@ -501,6 +688,12 @@ impl ApiClient {
         tx_priority: Option<TxPriority>,
         consolidate_deprecated_keychains: Option<bool>,
         interval_trigger: Option<u32>,
+        max_fee_rate: Option<u64>,
+        fee_strategy: Option<proto::payout_queue_config::FeeStrategy>,
+        threshold_trigger: Option<proto::payout_queue_config::Trigger>,
+        max_payouts_per_batch: Option<u32>,
+        max_tx_vbytes: Option<u64>,
+        approval: Option<proto::PayoutApprovalConfig>,
     ) -> anyhow::Result<()> {
         let tx_priority = tx_priority.map(|priority| match priority {
             TxPriority::NextBlock => proto::TxPriority::NextBlock as i32,
@@ -508,7 +701,9 @@ impl ApiClient {
             TxPriority::OneHour => proto::TxPriority::OneHour as i32,
         });
 
-        let trigger = interval_trigger.map(proto::payout_queue_config::Trigger::IntervalSecs);
+        let trigger = interval_trigger
+            .map(proto::payout_queue_config::Trigger::IntervalSecs)
+            .or(threshold_trigger);
 
         let config = if let (Some(tx_priority), Some(consolidate_deprecated_keychains)) =
             (tx_priority, consolidate_deprecated_keychains)
@@ -517,6 +712,11 @@ impl ApiClient {
                 tx_priority,
                 consolidate_deprecated_keychains,
                 trigger,
+                max_fee_rate_sats_per_vbyte: max_fee_rate,
+                fee_strategy,
+                max_payouts_per_batch,
+                max_tx_vbytes,
+                approval,
             })
         } else {
             None
@@ -544,6 +744,77 @@ impl ApiClient {
         output_json(response)
     }
 
+    pub async fn get_batch_signing_status(&self, batch_id: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::GetBatchSigningStatusRequest { batch_id });
+        let response = self
+            .connect()
+            .await?
+            .get_batch_signing_status(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn bump_batch_fee(&self, id: String, sats_per_vbyte: f32) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::BumpBatchFeeRequest { id, sats_per_vbyte });
+        let response = self
+            .connect()
+            .await?
+            .bump_batch_fee(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn accelerate_utxo(
+        &self,
+        wallet_name: String,
+        outpoint: String,
+        sats_per_vbyte: f32,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::AccelerateUtxoRequest {
+            wallet_name,
+            outpoint,
+            sats_per_vbyte,
+        });
+        let response = self
+            .connect()
+            .await?
+            .accelerate_utxo(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn freeze_utxo(
+        &self,
+        wallet_name: String,
+        outpoint: String,
+        reason: Option<String>,
+    ) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::FreezeUtxoRequest {
+            wallet_name,
+            outpoint,
+            reason,
+        });
+        let response = self
+            .connect()
+            .await?
+            .freeze_utxo(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
+    pub async fn unfreeze_utxo(&self, wallet_name: String, outpoint: String) -> anyhow::Result<()> {
+        let request = tonic::Request::new(proto::UnfreezeUtxoRequest {
+            wallet_name,
+            outpoint,
+        });
+        let response = self
+            .connect()
+            .await?
+            .unfreeze_utxo(self.inject_auth_token(request)?)
+            .await?;
+        output_json(response)
+    }
+
     pub async fn watch_events(
         &self,
         one_shot: bool,
@@ -0,0 +1,25 @@
+use super::db::*;
+use crate::audit::AuditLog;
+
+pub async fn verify(db_con: String) -> anyhow::Result<()> {
+    let pool = init_pool(&DbConfig {
+        pg_con: db_con,
+        pool_size: 1,
+        migrate_on_start: false,
+    })
+    .await?;
+    let verification = AuditLog::new(&pool).verify().await?;
+    match verification.first_invalid_sequence {
+        None => {
+            println!(
+                "Audit log intact: {} entries, head hash {}",
+                verification.n_entries, verification.last_hash
+            );
+            Ok(())
+        }
+        Some(sequence) => Err(anyhow::anyhow!(
+            "Audit log chain broken at sequence {sequence} (after {} valid entries)",
+            verification.n_entries - 1
+        )),
+    }
+}
@@ -1,6 +1,7 @@
 mod address_extractor;
 mod admin_client;
 mod api_client;
+mod audit;
 mod config;
 mod db;
 mod gen;
@@ -16,6 +17,7 @@ use crate::{
     api::proto,
     dev_constants,
     primitives::{bitcoin, TxPriority},
+    profile::ApiKeyScope,
 };
 use config::*;
 
@@ -71,6 +73,14 @@ enum Command {
         #[clap(subcommand)]
         command: UtilsCommand,
     },
+    /// Subcommand for inspecting the audit log
+    Audit {
+        /// Connection string for the Postgres
+        #[clap(env = "PG_CON")]
+        db_con: String,
+        #[clap(subcommand)]
+        command: AuditCommand,
+    },
     /// Create a new profile
     CreateProfile {
         #[clap(
@@ -85,6 +95,54 @@ enum Command {
         api_key: String,
         #[clap(short, long)]
         name: String,
+        /// Restrict the profile to the given wallet ids
+        #[clap(long = "wallet-id")]
+        wallet_ids: Vec<String>,
+        /// Restrict the profile to the given payout queue ids
+        #[clap(long = "payout-queue-id")]
+        payout_queue_ids: Vec<String>,
+        /// Maximum amount of a single payout submitted by the profile
+        #[clap(long)]
+        max_payout_sats: Option<u64>,
+        /// Maximum amount of payouts submitted by the profile within 24 hours
+        #[clap(long)]
+        max_daily_payout_sats: Option<u64>,
+        /// Restrict payouts submitted by the profile to the given destinations
+        #[clap(long = "allowed-destination")]
+        allowed_destinations: Vec<String>,
+        /// Allow the profile to approve payouts awaiting approval
+        #[clap(long)]
+        payout_approver: bool,
+    },
+    /// Replace the policy of a profile
+    UpdateProfile {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        id: String,
+        /// Restrict the profile to the given wallet ids
+        #[clap(long = "wallet-id")]
+        wallet_ids: Vec<String>,
+        /// Restrict the profile to the given payout queue ids
+        #[clap(long = "payout-queue-id")]
+        payout_queue_ids: Vec<String>,
+        /// Maximum amount of a single payout submitted by the profile
+        #[clap(long)]
+        max_payout_sats: Option<u64>,
+        /// Maximum amount of payouts submitted by the profile within 24 hours
+        #[clap(long)]
+        max_daily_payout_sats: Option<u64>,
+        /// Restrict payouts submitted by the profile to the given destinations
+        #[clap(long = "allowed-destination")]
+        allowed_destinations: Vec<String>,
     },
     /// List all profiles
     ListProfiles {
@@ -113,6 +171,42 @@ enum Command {
         api_key: String,
         #[clap(short, long)]
         profile: String,
+        /// Restrict what the key may be used for (full access if omitted)
+        #[clap(long = "scope", value_enum)]
+        scopes: Vec<ApiKeyScope>,
+        /// Reject the key after this time (RFC 3339)
+        #[clap(long)]
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    },
+    /// List the Api Keys of the given profile name
+    ListApiKeys {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        profile: String,
+    },
+    /// Revoke an Api Key
+    RevokeApiKey {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        id: String,
     },
     /// Import an xpub
     ImportXpub {
@@ -165,6 +259,21 @@ enum Command {
         #[clap(subcommand)]
         command: SetSignerConfigCommand,
     },
+    /// Check that the configured signer can sign for the xpub
+    TestSignerConfig {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        xpub: String,
+    },
     /// Submit a signed psbt
     SubmitSignedPsbt {
         #[clap(
@@ -184,6 +293,61 @@ enum Command {
         #[clap(short, long)]
         signed_psbt: String,
     },
+    /// List signing sessions
+    ListSigningSessions {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        batch_id: Option<String>,
+        #[clap(short, long)]
+        xpub_ref: Option<String>,
+        /// One of initialized, failed, complete or aborted
+        #[clap(short, long)]
+        state: Option<String>,
+        /// The id of the session after which to list
+        #[clap(short, long)]
+        after: Option<String>,
+        #[clap(short, long)]
+        limit: Option<u32>,
+    },
+    /// Trigger an immediate signing attempt for a signing session
+    RetrySigningSession {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        id: String,
+    },
+    /// Stop all further signing attempts for a batch
+    AbortBatchSigning {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        batch_id: String,
+    },
     /// Create a wallet from imported xpubs
     CreateWallet {
         #[clap(
@@ -342,6 +506,48 @@ enum Command {
         interval_trigger: Option<u32>,
         #[clap(short = 'm', long = "manual")]
         manual_trigger: Option<bool>,
+        /// Refuse to batch while the fee rate is above this many sats/vbyte
+        #[clap(long = "max-fee-rate")]
+        max_fee_rate: Option<u64>,
+        /// Multiply the fee rate estimated for the tx priority
+        #[clap(long, conflicts_with_all = ["fixed_fee_rate", "target_blocks"])]
+        fee_multiplier: Option<f64>,
+        /// Always pay this many sats/vbyte
+        #[clap(long, conflicts_with = "target_blocks")]
+        fixed_fee_rate: Option<u64>,
+        /// Estimate the fee rate for confirmation within this many blocks
+        #[clap(long)]
+        target_blocks: Option<u32>,
+        /// Trigger once the unbatched payouts add up to this many sats
+        #[clap(long, conflicts_with_all = ["min_payouts", "max_payout_age", "interval_trigger", "manual_trigger"])]
+        min_total_sats: Option<u64>,
+        /// Trigger once there are at least this many unbatched payouts
+        #[clap(long, conflicts_with_all = ["max_payout_age", "interval_trigger", "manual_trigger"])]
+        min_payouts: Option<u32>,
+        /// Trigger once the oldest unbatched payout is older than this many seconds
+        #[clap(long, conflicts_with_all = ["interval_trigger", "manual_trigger"])]
+        max_payout_age: Option<u32>,
+        /// Trigger once the fee rate is at or below this many sats/vbyte
+        #[clap(long, requires = "fee_window_deadline", conflicts_with_all = ["min_total_sats", "min_payouts", "max_payout_age", "interval_trigger", "manual_trigger"])]
+        fee_window_max_rate: Option<u64>,
+        /// Trigger regardless of the fee rate once the oldest unbatched payout is older than this many seconds
+        #[clap(long, requires = "fee_window_max_rate")]
+        fee_window_deadline: Option<u32>,
+        /// Process at least this often (in seconds) when using a threshold trigger
+        #[clap(long)]
+        max_interval: Option<u32>,
+        /// Leave payouts beyond this count for a follow-up batch
+        #[clap(long)]
+        max_payouts_per_batch: Option<u32>,
+        /// Leave payouts for a follow-up batch when the tx would exceed this many vbytes
+        #[clap(long)]
+        max_tx_vbytes: Option<u64>,
+        /// Require approval for payouts of at least this many sats
+        #[clap(long, requires = "required_approvals")]
+        approval_min_sats: Option<u64>,
+        /// Number of distinct profiles that must approve a large payout
+        #[clap(long, requires = "approval_min_sats")]
+        required_approvals: Option<u32>,
     },
     /// Trigger Payout Queue
     TriggerPayoutQueue {
@@ -395,6 +601,12 @@ enum Command {
         external_id: Option<String>,
         #[clap(short, long, value_parser = parse_json)]
         metadata: Option<serde_json::Value>,
+        /// Payouts with a higher priority are batched first
+        #[clap(long)]
+        priority: Option<u32>,
+        /// Keep the payout out of batches until this time (RFC 3339)
+        #[clap(long)]
+        execute_after: Option<chrono::DateTime<chrono::Utc>>,
     },
     /// List pending Payouts
     ListPayouts {
@@ -428,6 +640,36 @@ enum Command {
         #[clap(short = 'e', long, group = "identifier")]
         external_id: Option<String>,
     },
+    /// Approve a payout that is awaiting approval
+    ApprovePayout {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short = 'i', long)]
+        id: String,
+    },
+    /// Reject a payout that is awaiting approval
+    RejectPayout {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short = 'i', long)]
+        id: String,
+    },
     CancelPayout {
         #[clap(
             short,
@@ -455,6 +697,28 @@ enum Command {
         #[clap(env = "BRIA_API_KEY", default_value = "")]
         api_key: String,
     },
+    /// Update the config of a wallet
+    UpdateWallet {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        wallet: String,
+        #[clap(long)]
+        settle_income_after_n_confs: Option<u32>,
+        #[clap(long)]
+        settle_change_after_n_confs: Option<u32>,
+        /// Incoming utxos below this value are treated as dust
+        #[clap(long)]
+        dust_threshold_sats: Option<u64>,
+    },
 
     /// List Payout Queue
     ListPayoutQueues {
@@ -493,6 +757,48 @@ enum Command {
         consolidate_deprecated_keychains: Option<bool>,
         #[clap(long = "interval-trigger")]
         interval_trigger: Option<u32>,
+        /// Refuse to batch while the fee rate is above this many sats/vbyte
+        #[clap(long = "max-fee-rate")]
+        max_fee_rate: Option<u64>,
+        /// Multiply the fee rate estimated for the tx priority
+        #[clap(long, conflicts_with_all = ["fixed_fee_rate", "target_blocks"])]
+        fee_multiplier: Option<f64>,
+        /// Always pay this many sats/vbyte
+        #[clap(long, conflicts_with = "target_blocks")]
+        fixed_fee_rate: Option<u64>,
+        /// Estimate the fee rate for confirmation within this many blocks
+        #[clap(long)]
+        target_blocks: Option<u32>,
+        /// Trigger once the unbatched payouts add up to this many sats
+        #[clap(long, conflicts_with_all = ["min_payouts", "max_payout_age", "interval_trigger"])]
+        min_total_sats: Option<u64>,
+        /// Trigger once there are at least this many unbatched payouts
+        #[clap(long, conflicts_with_all = ["max_payout_age", "interval_trigger"])]
+        min_payouts: Option<u32>,
+        /// Trigger once the oldest unbatched payout is older than this many seconds
+        #[clap(long, conflicts_with_all = ["interval_trigger"])]
+        max_payout_age: Option<u32>,
+        /// Trigger once the fee rate is at or below this many sats/vbyte
+        #[clap(long, requires = "fee_window_deadline", conflicts_with_all = ["min_total_sats", "min_payouts", "max_payout_age", "interval_trigger"])]
+        fee_window_max_rate: Option<u64>,
+        /// Trigger regardless of the fee rate once the oldest unbatched payout is older than this many seconds
+        #[clap(long, requires = "fee_window_max_rate")]
+        fee_window_deadline: Option<u32>,
+        /// Process at least this often (in seconds) when using a threshold trigger
+        #[clap(long)]
+        max_interval: Option<u32>,
+        /// Leave payouts beyond this count for a follow-up batch
+        #[clap(long)]
+        max_payouts_per_batch: Option<u32>,
+        /// Leave payouts for a follow-up batch when the tx would exceed this many vbytes
+        #[clap(long)]
+        max_tx_vbytes: Option<u64>,
+        /// Require approval for payouts of at least this many sats
+        #[clap(long, requires = "required_approvals")]
+        approval_min_sats: Option<u64>,
+        /// Number of distinct profiles that must approve a large payout
+        #[clap(long, requires = "approval_min_sats")]
+        required_approvals: Option<u32>,
     },
     /// Get Batch details
     GetBatch {
@@ -509,6 +815,93 @@ enum Command {
         #[clap(short, long)]
         batch_id: String,
     },
+    /// Show which signatures are still missing for each input of a batch
+    GetBatchSigningStatus {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        batch_id: String,
+    },
+    /// Replace a broadcast batch with a higher fee rate (RBF)
+    BumpBatchFee {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        batch_id: String,
+        #[clap(short, long)]
+        sats_per_vbyte: f32,
+    },
+    /// Accelerate an unconfirmed utxo by spending it at a higher fee rate (CPFP)
+    AccelerateUtxo {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        wallet: String,
+        #[clap(short, long)]
+        outpoint: String,
+        #[clap(short, long)]
+        sats_per_vbyte: f32,
+    },
+    /// Prevent a utxo from being spent in future batches
+    FreezeUtxo {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        wallet: String,
+        #[clap(short, long)]
+        outpoint: String,
+        #[clap(short, long)]
+        reason: Option<String>,
+    },
+    /// Allow a frozen utxo to be spent again
+    UnfreezeUtxo {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        #[clap(short, long)]
+        wallet: String,
+        #[clap(short, long)]
+        outpoint: String,
+    },
     /// Watch or fetch events
     WatchEvents {
         #[clap(
@@ -531,6 +924,36 @@ enum Command {
         #[clap(long, default_value = "false")]
         augment: bool,
     },
+    /// List entries of the audit log
+    ListAuditEvents {
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value = "http://localhost:2742",
+            env = "BRIA_API_URL"
+        )]
+        url: Option<Url>,
+        #[clap(env = "BRIA_API_KEY", default_value = "")]
+        api_key: String,
+        /// Only list calls made by this profile
+        #[clap(short, long)]
+        profile_id: Option<String>,
+        /// Only list calls of this rpc (eg. SubmitPayout)
+        #[clap(short, long)]
+        rpc: Option<String>,
+        /// The sequence number after which to list
+        #[clap(short, long)]
+        after: Option<u64>,
+        #[clap(short, long)]
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuditCommand {
+    /// Check that the hash chain of the audit log is intact
+    Verify,
 }
 
 #[derive(Subcommand)]
@@ -580,6 +1003,97 @@ enum AdminCommand {
         name: String,
     },
     ListAccounts {},
+    /// Rename an account
+    RenameAccount {
+        #[clap(short, long)]
+        id: String,
+        #[clap(short, long)]
+        name: String,
+    },
+    /// Deactivate an account, rejecting its api keys and stopping its jobs
+    DeactivateAccount {
+        #[clap(short, long)]
+        id: String,
+    },
+    /// Replace the per account config overrides
+    UpdateAccountConfig {
+        #[clap(short, long)]
+        id: String,
+        /// Block payouts to this address for the account (can be repeated)
+        #[clap(long = "blocked-address")]
+        blocked_addresses: Vec<String>,
+        /// Yaml file with a fees config replacing the global one for the account
+        #[clap(long, value_name = "FILE")]
+        fees_config: Option<PathBuf>,
+    },
+    /// Block payouts to the given addresses for all accounts
+    AddBlockedAddresses {
+        /// Address to block (can be repeated)
+        #[clap(long = "address", required = true)]
+        addresses: Vec<String>,
+        #[clap(short, long)]
+        reason: Option<String>,
+    },
+    /// Block all addresses found in a file (eg. a sanctions list)
+    ImportBlockedAddresses {
+        #[clap(short, long)]
+        path: PathBuf,
+        #[clap(short, long)]
+        reason: Option<String>,
+    },
+    /// Unblock an address
+    RemoveBlockedAddress {
+        #[clap(short, long)]
+        address: String,
+    },
+    /// List all globally blocked addresses
+    ListBlockedAddresses {},
+    /// List payouts that were rejected because of a blocked address
+    ListBlockedPayoutAttempts {
+        #[clap(long)]
+        account_id: Option<String>,
+    },
+    /// List utxos that were funded from a blocked address
+    ListQuarantinedUtxos {
+        #[clap(long)]
+        account_id: Option<String>,
+    },
+    /// Allow a quarantined utxo to be spent again
+    ReleaseQuarantinedUtxo {
+        #[clap(long)]
+        account_id: String,
+        /// The outpoint of the utxo (<tx_id>:<vout>)
+        #[clap(short, long)]
+        outpoint: String,
+    },
+    /// Create an additional admin api key
+    CreateApiKey {
+        #[clap(short, long)]
+        name: String,
+    },
+    /// List admin api keys and when they were last used
+    ListApiKeys {},
+    /// Revoke an admin api key
+    RevokeApiKey {
+        #[clap(short, long)]
+        id: String,
+    },
+    /// Replace the bootstrap key with a new one
+    RotateBootstrapKey,
+    /// List entries of the audit log, including calls made with admin api keys
+    ListAuditEvents {
+        /// Only list calls made on behalf of this account
+        #[clap(long)]
+        account_id: Option<String>,
+        /// Only list calls of this rpc (eg. CreateAccount)
+        #[clap(short, long)]
+        rpc: Option<String>,
+        /// The sequence number after which to list
+        #[clap(short, long)]
+        after: Option<u64>,
+        #[clap(short, long)]
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -628,6 +1142,20 @@ enum SetSignerConfigCommand {
         #[clap(short = 'p', long)]
         rpc_password: String,
     },
+    /// Sign in process with the xpriv of the xpub
+    LocalXpriv {
+        /// File containing the base58 encoded xpriv
+        #[clap(short = 'f', long)]
+        xpriv_file: PathBuf,
+    },
+    /// Send the psbt and batch context to a signing service over http
+    Webhook {
+        #[clap(short, long)]
+        endpoint: String,
+        /// File containing the secret used to sign the request body
+        #[clap(short = 's', long)]
+        hmac_secret_file: PathBuf,
+    },
 }
 
 pub async fn run() -> anyhow::Result<()> {
@@ -695,6 +1223,9 @@ pub async fn run() -> anyhow::Result<()> {
                 gen::rotate_signer_encryption_key(old_key)?
             }
         },
+        Command::Audit { db_con, command } => match command {
+            AuditCommand::Verify => audit::verify(db_con).await?,
+        },
         Command::Admin {
             command,
             url,
@@ -716,11 +1247,128 @@ pub async fn run() -> anyhow::Result<()> {
                 AdminCommand::ListAccounts {} => {
                     client.list_accounts().await?;
                 }
+                AdminCommand::RenameAccount { id, name } => {
+                    client.rename_account(id, name).await?;
+                }
+                AdminCommand::DeactivateAccount { id } => {
+                    client.deactivate_account(id).await?;
+                }
+                AdminCommand::UpdateAccountConfig {
+                    id,
+                    blocked_addresses,
+                    fees_config,
+                } => {
+                    client
+                        .update_account_config(
+                            id,
+                            blocked_addresses,
+                            read_fees_config(fees_config)?,
+                        )
+                        .await?;
+                }
+                AdminCommand::AddBlockedAddresses { addresses, reason } => {
+                    client.add_blocked_addresses(addresses, reason).await?;
+                }
+                AdminCommand::ImportBlockedAddresses { path, reason } => {
+                    let addresses = address_extractor::read_addresses(path)?
+                        .into_iter()
+                        .map(|a| a.to_string())
+                        .collect();
+                    client.import_blocked_addresses(addresses, reason).await?;
+                }
+                AdminCommand::RemoveBlockedAddress { address } => {
+                    client.remove_blocked_address(address).await?;
+                }
+                AdminCommand::ListBlockedAddresses {} => {
+                    client.list_blocked_addresses().await?;
+                }
+                AdminCommand::ListBlockedPayoutAttempts { account_id } => {
+                    client.list_blocked_payout_attempts(account_id).await?;
+                }
+                AdminCommand::ListQuarantinedUtxos { account_id } => {
+                    client.list_quarantined_utxos(account_id).await?;
+                }
+                AdminCommand::ReleaseQuarantinedUtxo {
+                    account_id,
+                    outpoint,
+                } => {
+                    client
+                        .release_quarantined_utxo(account_id, outpoint)
+                        .await?;
+                }
+                AdminCommand::CreateApiKey { name } => {
+                    client.create_admin_api_key(name).await?;
+                }
+                AdminCommand::ListApiKeys {} => {
+                    client.list_admin_api_keys().await?;
+                }
+                AdminCommand::RevokeApiKey { id } => {
+                    client.revoke_admin_api_key(id).await?;
+                }
+                AdminCommand::RotateBootstrapKey => {
+                    client.rotate_bootstrap_key().await?;
+                }
+                AdminCommand::ListAuditEvents {
+                    account_id,
+                    rpc,
+                    after,
+                    limit,
+                } => {
+                    client
+                        .list_audit_events(account_id, rpc, after, limit)
+                        .await?;
+                }
             }
         }
-        Command::CreateProfile { url, api_key, name } => {
+        Command::CreateProfile {
+            url,
+            api_key,
+            name,
+            wallet_ids,
+            payout_queue_ids,
+            max_payout_sats,
+            max_daily_payout_sats,
+            allowed_destinations,
+            payout_approver,
+        } => {
             let client = api_client(cli.bria_home, url, api_key);
-            client.create_profile(name).await?;
+            client
+                .create_profile(
+                    name,
+                    profile_policy(
+                        wallet_ids,
+                        payout_queue_ids,
+                        max_payout_sats,
+                        max_daily_payout_sats,
+                        allowed_destinations,
+                    ),
+                    payout_approver,
+                )
+                .await?;
+        }
+        Command::UpdateProfile {
+            url,
+            api_key,
+            id,
+            wallet_ids,
+            payout_queue_ids,
+            max_payout_sats,
+            max_daily_payout_sats,
+            allowed_destinations,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client
+                .update_profile(
+                    id,
+                    profile_policy(
+                        wallet_ids,
+                        payout_queue_ids,
+                        max_payout_sats,
+                        max_daily_payout_sats,
+                        allowed_destinations,
+                    ),
+                )
+                .await?;
         }
         Command::ListProfiles { url, api_key } => {
             let client = api_client(cli.bria_home, url, api_key);
@@ -730,9 +1378,25 @@ pub async fn run() -> anyhow::Result<()> {
             url,
             api_key,
             profile,
+            scopes,
+            expires_at,
         } => {
             let client = api_client(cli.bria_home, url, api_key);
-            client.create_profile_api_key(profile).await?;
+            client
+                .create_profile_api_key(profile, scopes, expires_at)
+                .await?;
+        }
+        Command::ListApiKeys {
+            url,
+            api_key,
+            profile,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.list_profile_api_keys(profile).await?;
+        }
+        Command::RevokeApiKey { url, api_key, id } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.revoke_profile_api_key(id).await?;
         }
         Command::ImportXpub {
             url,
@@ -753,6 +1417,10 @@ pub async fn run() -> anyhow::Result<()> {
             let client = api_client(cli.bria_home, url, api_key);
             client.set_signer_config(xpub, command).await?;
         }
+        Command::TestSignerConfig { url, api_key, xpub } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.test_signer_config(xpub).await?;
+        }
         Command::SubmitSignedPsbt {
             url,
             api_key,
@@ -765,6 +1433,32 @@ pub async fn run() -> anyhow::Result<()> {
                 .submit_signed_psbt(batch_id, xpub_ref, signed_psbt)
                 .await?;
         }
+        Command::ListSigningSessions {
+            url,
+            api_key,
+            batch_id,
+            xpub_ref,
+            state,
+            after,
+            limit,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client
+                .list_signing_sessions(batch_id, xpub_ref, state, after, limit)
+                .await?;
+        }
+        Command::RetrySigningSession { url, api_key, id } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.retry_signing_session(id).await?;
+        }
+        Command::AbortBatchSigning {
+            url,
+            api_key,
+            batch_id,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.abort_batch_signing(batch_id).await?;
+        }
         Command::CreateWallet {
             url,
             api_key,
@@ -842,6 +1536,20 @@ pub async fn run() -> anyhow::Result<()> {
             consolidate_deprecated_keychains,
             interval_trigger,
             manual_trigger,
+            max_fee_rate,
+            fee_multiplier,
+            fixed_fee_rate,
+            target_blocks,
+            min_total_sats,
+            min_payouts,
+            max_payout_age,
+            fee_window_max_rate,
+            fee_window_deadline,
+            max_interval,
+            max_payouts_per_batch,
+            max_tx_vbytes,
+            approval_min_sats,
+            required_approvals,
         } => {
             let client = api_client(cli.bria_home, url, api_key);
             client
@@ -852,6 +1560,16 @@ pub async fn run() -> anyhow::Result<()> {
                     consolidate_deprecated_keychains,
                     interval_trigger,
                     manual_trigger,
+                    max_fee_rate,
+                    fee_strategy(fee_multiplier, fixed_fee_rate, target_blocks),
+                    threshold_trigger(min_total_sats, min_payouts, max_payout_age, max_interval)
+                        .or(fee_rate_window_trigger(
+                            fee_window_max_rate,
+                            fee_window_deadline,
+                        )),
+                    max_payouts_per_batch,
+                    max_tx_vbytes,
+                    payout_approval(approval_min_sats, required_approvals),
                 )
                 .await?;
         }
@@ -881,6 +1599,8 @@ pub async fn run() -> anyhow::Result<()> {
             amount,
             external_id,
             metadata,
+            priority,
+            execute_after,
         } => {
             let client = api_client(cli.bria_home, url, api_key);
             client
@@ -891,6 +1611,8 @@ pub async fn run() -> anyhow::Result<()> {
                     amount,
                     external_id,
                     metadata,
+                    priority,
+                    execute_after,
                 )
                 .await?;
         }
@@ -911,6 +1633,14 @@ pub async fn run() -> anyhow::Result<()> {
             let client = api_client(cli.bria_home, url, api_key);
             client.get_payout(id, external_id).await?;
         }
+        Command::ApprovePayout { url, api_key, id } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.approve_payout(id).await?;
+        }
+        Command::RejectPayout { url, api_key, id } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.reject_payout(id).await?;
+        }
         Command::CancelPayout { url, api_key, id } => {
             let client = api_client(cli.bria_home, url, api_key);
             client.cancel_payout(id).await?;
@@ -919,6 +1649,24 @@ pub async fn run() -> anyhow::Result<()> {
             let client = api_client(cli.bria_home, url, api_key);
             client.list_wallets().await?;
         }
+        Command::UpdateWallet {
+            url,
+            api_key,
+            wallet,
+            settle_income_after_n_confs,
+            settle_change_after_n_confs,
+            dust_threshold_sats,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client
+                .update_wallet(
+                    wallet,
+                    settle_income_after_n_confs,
+                    settle_change_after_n_confs,
+                    dust_threshold_sats,
+                )
+                .await?;
+        }
         Command::ListPayoutQueues { url, api_key } => {
             let client = api_client(cli.bria_home, url, api_key);
             client.list_payout_queues().await?;
@@ -931,6 +1679,20 @@ pub async fn run() -> anyhow::Result<()> {
             tx_priority,
             consolidate_deprecated_keychains,
             interval_trigger,
+            max_fee_rate,
+            fee_multiplier,
+            fixed_fee_rate,
+            target_blocks,
+            min_total_sats,
+            min_payouts,
+            max_payout_age,
+            fee_window_max_rate,
+            fee_window_deadline,
+            max_interval,
+            max_payouts_per_batch,
+            max_tx_vbytes,
+            approval_min_sats,
+            required_approvals,
         } => {
             let client = api_client(cli.bria_home, url, api_key);
             client
@@ -940,6 +1702,16 @@ pub async fn run() -> anyhow::Result<()> {
                     tx_priority,
                     consolidate_deprecated_keychains,
                     interval_trigger,
+                    max_fee_rate,
+                    fee_strategy(fee_multiplier, fixed_fee_rate, target_blocks),
+                    threshold_trigger(min_total_sats, min_payouts, max_payout_age, max_interval)
+                        .or(fee_rate_window_trigger(
+                            fee_window_max_rate,
+                            fee_window_deadline,
+                        )),
+                    max_payouts_per_batch,
+                    max_tx_vbytes,
+                    payout_approval(approval_min_sats, required_approvals),
                 )
                 .await?;
         }
@@ -955,6 +1727,54 @@ pub async fn run() -> anyhow::Result<()> {
             let client = api_client(cli.bria_home, url, api_key);
             client.get_batch(batch_id).await?;
         }
+        Command::GetBatchSigningStatus {
+            url,
+            api_key,
+            batch_id,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.get_batch_signing_status(batch_id).await?;
+        }
+        Command::BumpBatchFee {
+            url,
+            api_key,
+            batch_id,
+            sats_per_vbyte,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.bump_batch_fee(batch_id, sats_per_vbyte).await?;
+        }
+        Command::AccelerateUtxo {
+            url,
+            api_key,
+            wallet,
+            outpoint,
+            sats_per_vbyte,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client
+                .accelerate_utxo(wallet, outpoint, sats_per_vbyte)
+                .await?;
+        }
+        Command::FreezeUtxo {
+            url,
+            api_key,
+            wallet,
+            outpoint,
+            reason,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.freeze_utxo(wallet, outpoint, reason).await?;
+        }
+        Command::UnfreezeUtxo {
+            url,
+            api_key,
+            wallet,
+            outpoint,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client.unfreeze_utxo(wallet, outpoint).await?;
+        }
         Command::WatchEvents {
             url,
             api_key,
@@ -965,10 +1785,31 @@ pub async fn run() -> anyhow::Result<()> {
             let client = api_client(cli.bria_home, url, api_key);
             client.watch_events(one_shot, after, augment).await?;
         }
+        Command::ListAuditEvents {
+            url,
+            api_key,
+            profile_id,
+            rpc,
+            after,
+            limit,
+        } => {
+            let client = api_client(cli.bria_home, url, api_key);
+            client
+                .list_audit_events(profile_id, rpc, after, limit)
+                .await?;
+        }
     }
     Ok(())
 }
 
+fn read_fees_config(path: Option<PathBuf>) -> anyhow::Result<Option<serde_json::Value>> {
+    path.map(|path| {
+        let contents = std::fs::read_to_string(path).context("Couldn't read fees config file")?;
+        serde_yaml::from_str(&contents).context("Couldn't parse fees config file")
+    })
+    .transpose()
+}
+
 fn api_client(bria_home: String, url: Option<Url>, api_key: String) -> api_client::ApiClient {
     api_client::ApiClient::new(
         bria_home,
@@ -1118,6 +1959,12 @@ async fn run_cmd(
                             true,
                             Some(5),
                             None,
+                            None,
+                            None,
+                            None,
+                            None,
+                            None,
+                            None,
                         )
                         .await
                     {
@@ -1141,6 +1988,98 @@ async fn run_cmd(
     reason
 }
 
+fn fee_strategy(
+    fee_multiplier: Option<f64>,
+    fixed_fee_rate: Option<u64>,
+    target_blocks: Option<u32>,
+) -> Option<proto::payout_queue_config::FeeStrategy> {
+    use proto::payout_queue_config::FeeStrategy;
+    fee_multiplier
+        .map(FeeStrategy::PriorityMultiplier)
+        .or(fixed_fee_rate.map(FeeStrategy::FixedSatsPerVbyte))
+        .or(target_blocks.map(FeeStrategy::TargetBlocks))
+}
+
+fn threshold_trigger(
+    min_total_sats: Option<u64>,
+    min_payouts: Option<u32>,
+    max_payout_age: Option<u32>,
+    max_interval: Option<u32>,
+) -> Option<proto::payout_queue_config::Trigger> {
+    use proto::payout_queue_config::Trigger;
+    min_total_sats
+        .map(|min_total_sats| {
+            Trigger::PayoutTotal(proto::PayoutTotalTrigger {
+                min_total_sats,
+                max_interval_secs: max_interval,
+            })
+        })
+        .or(min_payouts.map(|min_payouts| {
+            Trigger::PayoutCount(proto::PayoutCountTrigger {
+                min_payouts,
+                max_interval_secs: max_interval,
+            })
+        }))
+        .or(max_payout_age.map(|max_age_secs| {
+            Trigger::PayoutAge(proto::PayoutAgeTrigger {
+                max_age_secs,
+                max_interval_secs: max_interval,
+            })
+        }))
+}
+
+fn fee_rate_window_trigger(
+    max_sats_per_vbyte: Option<u64>,
+    deadline_secs: Option<u32>,
+) -> Option<proto::payout_queue_config::Trigger> {
+    max_sats_per_vbyte
+        .zip(deadline_secs)
+        .map(|(max_sats_per_vbyte, deadline_secs)| {
+            proto::payout_queue_config::Trigger::FeeRateWindow(proto::FeeRateWindowTrigger {
+                max_sats_per_vbyte,
+                deadline_secs,
+            })
+        })
+}
+
+fn payout_approval(
+    min_sats: Option<u64>,
+    required_approvals: Option<u32>,
+) -> Option<proto::PayoutApprovalConfig> {
+    min_sats
+        .zip(required_approvals)
+        .map(
+            |(min_sats, required_approvals)| proto::PayoutApprovalConfig {
+                min_sats,
+                required_approvals,
+            },
+        )
+}
+
+fn profile_policy(
+    allowed_wallet_ids: Vec<String>,
+    allowed_payout_queue_ids: Vec<String>,
+    max_payout_sats: Option<u64>,
+    max_daily_payout_sats: Option<u64>,
+    allowed_destinations: Vec<String>,
+) -> Option<proto::ProfilePolicy> {
+    if allowed_wallet_ids.is_empty()
+        && allowed_payout_queue_ids.is_empty()
+        && max_payout_sats.is_none()
+        && max_daily_payout_sats.is_none()
+        && allowed_destinations.is_empty()
+    {
+        return None;
+    }
+    Some(proto::ProfilePolicy {
+        allowed_wallet_ids,
+        allowed_payout_queue_ids,
+        max_payout_sats,
+        max_daily_payout_sats,
+        allowed_destinations,
+    })
+}
+
 fn read_to_base64(path: PathBuf) -> anyhow::Result<String> {
     use std::fs::File;
     use std::io::BufReader;
@@ -1185,6 +2124,22 @@ impl TryFrom<SetSignerConfigCommand> for crate::api::proto::set_signer_config_re
                 rpc_user,
                 rpc_password,
             }),
+            SetSignerConfigCommand::LocalXpriv { xpriv_file } => {
+                let xpriv = std::fs::read_to_string(xpriv_file)?.trim().to_string();
+                Config::LocalXpriv(crate::api::proto::LocalXprivSignerConfig { xpriv })
+            }
+            SetSignerConfigCommand::Webhook {
+                endpoint,
+                hmac_secret_file,
+            } => {
+                let hmac_secret = std::fs::read_to_string(hmac_secret_file)?
+                    .trim()
+                    .to_string();
+                Config::Webhook(crate::api::proto::WebhookSignerConfig {
+                    endpoint,
+                    hmac_secret,
+                })
+            }
         };
         Ok(ret)
     }
@@ -0,0 +1,41 @@
+use tokio::sync::RwLock;
+
+use std::{collections::HashMap, sync::Arc};
+
+use super::{chain::*, config::*};
+use crate::{account::Account, primitives::AccountId};
+
+/// Hands out the estimator chain to use for an account, honouring per account fee overrides.
+/// Chains for overridden accounts are cached so their rates cache survives between calls.
+#[derive(Clone)]
+pub struct AccountFeeEstimators {
+    default: FeeEstimatorChain,
+    overrides: Arc<RwLock<HashMap<AccountId, (FeesConfig, FeeEstimatorChain)>>>,
+}
+
+impl AccountFeeEstimators {
+    pub fn new(default: FeeEstimatorChain) -> Self {
+        Self {
+            default,
+            overrides: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub async fn for_account(&self, account: &Account) -> FeeEstimatorChain {
+        let config = match account.config.fees.as_ref() {
+            Some(config) => config,
+            None => return self.default.clone(),
+        };
+        if let Some((cached_config, chain)) = self.overrides.read().await.get(&account.id) {
+            if cached_config == config {
+                return chain.clone();
+            }
+        }
+        let chain = FeeEstimatorChain::new(config);
+        self.overrides
+            .write()
+            .await
+            .insert(account.id, (config.clone(), chain.clone()));
+        chain
+    }
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use bdk::FeeRate;
+use bitcoincore_rpc::{Auth, Client, RpcApi};
+use serde::{Deserialize, Serialize};
+
+use super::{error::FeeEstimationError, estimator::FeeEstimator};
+use crate::primitives::TxPriority;
+
+#[derive(Clone, Debug)]
+pub struct BitcoindFeeEstimator {
+    config: BitcoindFeeEstimatorConfig,
+}
+
+impl BitcoindFeeEstimator {
+    pub fn new(config: BitcoindFeeEstimatorConfig) -> Self {
+        Self { config }
+    }
+
+    fn client(&self) -> Result<Client, FeeEstimationError> {
+        let auth = match (&self.config.rpc_user, &self.config.rpc_password) {
+            (Some(user), Some(password)) => Auth::UserPass(user.clone(), password.clone()),
+            _ => Auth::None,
+        };
+        Ok(Client::new(&self.config.endpoint, auth)?)
+    }
+}
+
+#[async_trait]
+impl FeeEstimator for BitcoindFeeEstimator {
+    fn name(&self) -> &'static str {
+        "bitcoind"
+    }
+
+    async fn fee_rate(&self, priority: TxPriority) -> Result<FeeRate, FeeEstimationError> {
+        self.fee_rate_for_blocks(priority.n_blocks()).await
+    }
+
+    async fn fee_rate_for_blocks(&self, n_blocks: usize) -> Result<FeeRate, FeeEstimationError> {
+        let estimate = self.client()?.estimate_smart_fee(n_blocks as u16, None)?;
+        match estimate.fee_rate {
+            Some(btc_per_kvb) => Ok(FeeRate::from_btc_per_kvb(btc_per_kvb.to_btc() as f32)),
+            None => Err(FeeEstimationError::EstimateUnavailable(
+                estimate.errors.unwrap_or_default().join(", "),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitcoindFeeEstimatorConfig {
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    pub rpc_user: Option<String>,
+    pub rpc_password: Option<String>,
+}
+
+impl Default for BitcoindFeeEstimatorConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: default_endpoint(),
+            rpc_user: None,
+            rpc_password: None,
+        }
+    }
+}
+
+fn default_endpoint() -> String {
+    "http://127.0.0.1:18443".to_string()
+}
@@ -0,0 +1,191 @@
+use async_trait::async_trait;
+use bdk::FeeRate;
+use tokio::sync::RwLock;
+use tracing::instrument;
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use super::{
+    bitcoind::*, config::*, electrum::*, error::FeeEstimationError, estimator::*, mempool_space::*,
+    static_rates::*,
+};
+use crate::primitives::TxPriority;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum FeeTarget {
+    Priority(TxPriority),
+    Blocks(usize),
+}
+
+impl FeeTarget {
+    fn priority(&self) -> TxPriority {
+        match self {
+            Self::Priority(priority) => *priority,
+            Self::Blocks(n_blocks) => TxPriority::closest_to_n_blocks(*n_blocks),
+        }
+    }
+}
+
+type FeeRateCache = HashMap<FeeTarget, (Instant, FeeRate)>;
+
+#[derive(Clone)]
+pub struct FeeEstimatorChain {
+    estimators: Arc<Vec<Box<dyn FeeEstimator>>>,
+    cache: Arc<RwLock<FeeRateCache>>,
+    cache_ttl: Duration,
+    bounds: FeeRateBoundsConfig,
+}
+
+impl FeeEstimatorChain {
+    pub fn new(config: &FeesConfig) -> Self {
+        let estimators = config
+            .estimators
+            .iter()
+            .map(|estimator| match estimator {
+                FeeEstimatorType::MempoolSpace => {
+                    Box::new(MempoolSpaceClient::new(config.mempool_space.clone()))
+                        as Box<dyn FeeEstimator>
+                }
+                FeeEstimatorType::Bitcoind => {
+                    Box::new(BitcoindFeeEstimator::new(config.bitcoind.clone()))
+                }
+                FeeEstimatorType::Electrum => {
+                    Box::new(ElectrumFeeEstimator::new(config.electrum.clone()))
+                }
+                FeeEstimatorType::Static => {
+                    Box::new(StaticFeeEstimator::new(config.static_rates.clone()))
+                }
+            })
+            .collect();
+        Self::from_estimators(estimators, config)
+    }
+
+    fn from_estimators(estimators: Vec<Box<dyn FeeEstimator>>, config: &FeesConfig) -> Self {
+        Self {
+            estimators: Arc::new(estimators),
+            cache: Arc::new(RwLock::new(HashMap::new())),
+            cache_ttl: config.cache_ttl,
+            bounds: config.bounds.clone(),
+        }
+    }
+
+    async fn cached(&self, target: FeeTarget) -> Option<FeeRate> {
+        self.cache
+            .read()
+            .await
+            .get(&target)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.cache_ttl)
+            .map(|(_, fee_rate)| *fee_rate)
+    }
+
+    async fn estimate(&self, target: FeeTarget) -> Result<FeeRate, FeeEstimationError> {
+        let mut last_err = FeeEstimationError::NoEstimatorConfigured;
+        for estimator in self.estimators.iter() {
+            let estimate = match target {
+                FeeTarget::Priority(priority) => estimator.fee_rate(priority).await,
+                FeeTarget::Blocks(n_blocks) => estimator.fee_rate_for_blocks(n_blocks).await,
+            };
+            match estimate {
+                Ok(fee_rate) => return Ok(fee_rate),
+                Err(err) => {
+                    tracing::warn!(estimator = estimator.name(), %err, "fee_estimator_failed");
+                    last_err = err;
+                }
+            }
+        }
+        Err(last_err)
+    }
+
+    async fn bounded_fee_rate(&self, target: FeeTarget) -> Result<FeeRate, FeeEstimationError> {
+        if let Some(fee_rate) = self.cached(target).await {
+            return Ok(fee_rate);
+        }
+        let fee_rate = self
+            .bounds
+            .for_priority(target.priority())
+            .clamp(self.estimate(target).await?);
+        self.cache
+            .write()
+            .await
+            .insert(target, (Instant::now(), fee_rate));
+        Ok(fee_rate)
+    }
+}
+
+#[async_trait]
+impl FeeEstimator for FeeEstimatorChain {
+    fn name(&self) -> &'static str {
+        "chain"
+    }
+
+    #[instrument(name = "fees.fee_rate", skip(self), err)]
+    async fn fee_rate(&self, priority: TxPriority) -> Result<FeeRate, FeeEstimationError> {
+        self.bounded_fee_rate(FeeTarget::Priority(priority)).await
+    }
+
+    #[instrument(name = "fees.fee_rate_for_blocks", skip(self), err)]
+    async fn fee_rate_for_blocks(&self, n_blocks: usize) -> Result<FeeRate, FeeEstimationError> {
+        self.bounded_fee_rate(FeeTarget::Blocks(n_blocks)).await
+    }
+
+    fn bounded(&self, priority: TxPriority, fee_rate: FeeRate) -> FeeRate {
+        self.bounds.for_priority(priority).clamp(fee_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    struct CountingEstimator(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl FeeEstimator for CountingEstimator {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn fee_rate(&self, _: TxPriority) -> Result<FeeRate, FeeEstimationError> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(FeeRate::from_sat_per_vb(n as f32 * 1000.))
+        }
+    }
+
+    #[tokio::test]
+    async fn caches_and_clamps_fee_rates() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut config = FeesConfig::default();
+        config.bounds.next_block.max_sats_per_vbyte = Some(1500.);
+        let chain = FeeEstimatorChain::from_estimators(
+            vec![Box::new(CountingEstimator(Arc::clone(&calls)))],
+            &config,
+        );
+
+        let fee_rate = chain.fee_rate(TxPriority::NextBlock).await.unwrap();
+        assert_eq!(fee_rate.as_sat_per_vb(), 1000.);
+        chain.fee_rate(TxPriority::NextBlock).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let fee_rate = chain.fee_rate(TxPriority::OneHour).await.unwrap();
+        assert_eq!(fee_rate.as_sat_per_vb(), 2000.);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        chain.cache.write().await.clear();
+        let fee_rate = chain.fee_rate(TxPriority::NextBlock).await.unwrap();
+        assert_eq!(fee_rate.as_sat_per_vb(), 1500.);
+
+        let fixed = chain.bounded(TxPriority::NextBlock, FeeRate::from_sat_per_vb(2000.));
+        assert_eq!(fixed.as_sat_per_vb(), 1500.);
+        let fixed = chain.bounded(TxPriority::NextBlock, FeeRate::from_sat_per_vb(0.5));
+        assert_eq!(
+            fixed.as_sat_per_vb(),
+            config.bounds.next_block.min_sats_per_vbyte
+        );
+    }
+}
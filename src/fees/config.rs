@@ -0,0 +1,108 @@
+use bdk::FeeRate;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::{bitcoind::*, electrum::*, mempool_space::*, static_rates::*};
+use crate::primitives::TxPriority;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeEstimatorType {
+    MempoolSpace,
+    Bitcoind,
+    Electrum,
+    Static,
+}
+
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeesConfig {
+    /// Estimators are queried in order, falling back to the next one when a source errors.
+    #[serde(default = "default_estimators")]
+    pub estimators: Vec<FeeEstimatorType>,
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl: Duration,
+    #[serde(default)]
+    pub bounds: FeeRateBoundsConfig,
+    #[serde(default)]
+    pub mempool_space: MempoolSpaceConfig,
+    #[serde(default)]
+    pub bitcoind: BitcoindFeeEstimatorConfig,
+    #[serde(default)]
+    pub electrum: ElectrumFeeEstimatorConfig,
+    #[serde(default, rename = "static")]
+    pub static_rates: StaticFeeEstimatorConfig,
+}
+
+impl Default for FeesConfig {
+    fn default() -> Self {
+        Self {
+            estimators: default_estimators(),
+            cache_ttl: default_cache_ttl(),
+            bounds: FeeRateBoundsConfig::default(),
+            mempool_space: MempoolSpaceConfig::default(),
+            bitcoind: BitcoindFeeEstimatorConfig::default(),
+            electrum: ElectrumFeeEstimatorConfig::default(),
+            static_rates: StaticFeeEstimatorConfig::default(),
+        }
+    }
+}
+
+fn default_estimators() -> Vec<FeeEstimatorType> {
+    vec![FeeEstimatorType::MempoolSpace]
+}
+
+fn default_cache_ttl() -> Duration {
+    Duration::from_secs(30)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeRateBoundsConfig {
+    #[serde(default)]
+    pub next_block: FeeRateBounds,
+    #[serde(default)]
+    pub half_hour: FeeRateBounds,
+    #[serde(default)]
+    pub one_hour: FeeRateBounds,
+}
+
+impl FeeRateBoundsConfig {
+    pub fn for_priority(&self, priority: TxPriority) -> &FeeRateBounds {
+        match priority {
+            TxPriority::NextBlock => &self.next_block,
+            TxPriority::HalfHour => &self.half_hour,
+            TxPriority::OneHour => &self.one_hour,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeRateBounds {
+    #[serde(default = "default_min_sats_per_vbyte")]
+    pub min_sats_per_vbyte: f32,
+    pub max_sats_per_vbyte: Option<f32>,
+}
+
+impl FeeRateBounds {
+    pub fn clamp(&self, fee_rate: FeeRate) -> FeeRate {
+        let mut sats_per_vbyte = fee_rate.as_sat_per_vb().max(self.min_sats_per_vbyte);
+        if let Some(max) = self.max_sats_per_vbyte {
+            sats_per_vbyte = sats_per_vbyte.min(max);
+        }
+        FeeRate::from_sat_per_vb(sats_per_vbyte)
+    }
+}
+
+impl Default for FeeRateBounds {
+    fn default() -> Self {
+        Self {
+            min_sats_per_vbyte: default_min_sats_per_vbyte(),
+            max_sats_per_vbyte: None,
+        }
+    }
+}
+
+fn default_min_sats_per_vbyte() -> f32 {
+    1.0
+}
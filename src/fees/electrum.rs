@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use bdk::FeeRate;
+use electrum_client::{Client, ElectrumApi};
+use serde::{Deserialize, Serialize};
+
+use super::{error::FeeEstimationError, estimator::FeeEstimator};
+use crate::primitives::TxPriority;
+
+#[derive(Clone, Debug)]
+pub struct ElectrumFeeEstimator {
+    url: String,
+}
+
+impl ElectrumFeeEstimator {
+    pub fn new(config: ElectrumFeeEstimatorConfig) -> Self {
+        Self { url: config.url }
+    }
+}
+
+#[async_trait]
+impl FeeEstimator for ElectrumFeeEstimator {
+    fn name(&self) -> &'static str {
+        "electrum"
+    }
+
+    async fn fee_rate(&self, priority: TxPriority) -> Result<FeeRate, FeeEstimationError> {
+        self.fee_rate_for_blocks(priority.n_blocks()).await
+    }
+
+    async fn fee_rate_for_blocks(&self, n_blocks: usize) -> Result<FeeRate, FeeEstimationError> {
+        let client = Client::new(&self.url)?;
+        // blockchain.estimatefee returns BTC/kvB or -1 if no estimate is available
+        let btc_per_kvb = client.estimate_fee(n_blocks)?;
+        if btc_per_kvb <= 0. {
+            return Err(FeeEstimationError::EstimateUnavailable(format!(
+                "electrum returned {btc_per_kvb}"
+            )));
+        }
+        Ok(FeeRate::from_btc_per_kvb(btc_per_kvb as f32))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElectrumFeeEstimatorConfig {
+    #[serde(default = "default_url")]
+    pub url: String,
+}
+
+impl Default for ElectrumFeeEstimatorConfig {
+    fn default() -> Self {
+        Self { url: default_url() }
+    }
+}
+
+fn default_url() -> String {
+    "127.0.0.1:50001".to_string()
+}
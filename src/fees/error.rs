@@ -4,4 +4,12 @@ use thiserror::Error;
 pub enum FeeEstimationError {
     #[error("FeeEstimationError - FeeEstimation: {0}")]
     FeeEstimation(reqwest::Error),
+    #[error("FeeEstimationError - BitcoindRpc: {0}")]
+    BitcoindRpc(#[from] bitcoincore_rpc::Error),
+    #[error("FeeEstimationError - Electrum: {0}")]
+    Electrum(#[from] electrum_client::Error),
+    #[error("FeeEstimationError - EstimateUnavailable: {0}")]
+    EstimateUnavailable(String),
+    #[error("FeeEstimationError - NoEstimatorConfigured")]
+    NoEstimatorConfigured,
 }
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use bdk::FeeRate;
+
+use super::error::FeeEstimationError;
+use crate::primitives::TxPriority;
+
+#[async_trait]
+pub trait FeeEstimator: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+
+    async fn fee_rate(&self, priority: TxPriority) -> Result<FeeRate, FeeEstimationError>;
+
+    /// Applies the configured floor and ceiling to a rate that was not estimated
+    fn bounded(&self, _priority: TxPriority, fee_rate: FeeRate) -> FeeRate {
+        fee_rate
+    }
+
+    /// Sources that only know priority buckets estimate for the closest bucket.
+    async fn fee_rate_for_blocks(&self, n_blocks: usize) -> Result<FeeRate, FeeEstimationError> {
+        self.fee_rate(TxPriority::closest_to_n_blocks(n_blocks))
+            .await
+    }
+}
@@ -1,7 +1,8 @@
+use async_trait::async_trait;
 use bdk::FeeRate;
 use serde::{Deserialize, Serialize};
 
-use super::error::FeeEstimationError;
+use super::{error::FeeEstimationError, estimator::*};
 use crate::primitives::TxPriority;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -23,8 +24,15 @@ impl MempoolSpaceClient {
     pub fn new(config: MempoolSpaceConfig) -> Self {
         Self { url: config.url }
     }
+}
+
+#[async_trait]
+impl FeeEstimator for MempoolSpaceClient {
+    fn name(&self) -> &'static str {
+        "mempool_space"
+    }
 
-    pub async fn fee_rate(&self, priority: TxPriority) -> Result<FeeRate, FeeEstimationError> {
+    async fn fee_rate(&self, priority: TxPriority) -> Result<FeeRate, FeeEstimationError> {
         let url = format!("{}{}", self.url, "/api/v1/fees/recommended");
         let resp = reqwest::get(url)
             .await
@@ -45,7 +53,7 @@ impl MempoolSpaceClient {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MempoolSpaceConfig {
     #[serde(default = "default_url")]
     pub url: String,
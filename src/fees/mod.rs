@@ -1,11 +1,25 @@
+mod account;
+mod bitcoind;
+mod chain;
+mod config;
+mod electrum;
 pub mod error;
+mod estimator;
 mod mempool_space;
+mod static_rates;
 
 use bdk::bitcoin::{LockTime, Transaction, TxOut};
 use std::collections::HashMap;
 
 use crate::primitives::*;
+pub use account::*;
+pub use bitcoind::*;
+pub use chain::*;
+pub use config::*;
+pub use electrum::*;
+pub use estimator::FeeEstimator;
 pub use mempool_space::*;
+pub use static_rates::*;
 
 use error::FeeEstimationError;
 
@@ -14,10 +28,10 @@ use error::FeeEstimationError;
 const TXIN_BASE_WEIGHT: usize = (32 + 4 + 4) * 4;
 
 pub async fn fees_to_encumber(
-    mempool_space: &MempoolSpaceClient,
+    fee_estimator: &impl FeeEstimator,
     satisfaction_weight: usize,
 ) -> Result<Satoshis, FeeEstimationError> {
-    let fee_rate = mempool_space.fee_rate(TxPriority::NextBlock).await?;
+    let fee_rate = fee_estimator.fee_rate(TxPriority::NextBlock).await?;
     Ok(Satoshis::from(
         fee_rate.fee_wu(TXIN_BASE_WEIGHT + satisfaction_weight),
     ))
@@ -75,6 +89,34 @@ pub fn estimate_proportional_fee(
     )
 }
 
+/// Fee the child of a CPFP package has to pay so that parent + child reach `fee_rate`.
+/// The child always pays at least enough to reach `fee_rate` on its own.
+pub fn cpfp_child_fee(
+    fee_rate: bitcoin::FeeRate,
+    parent_weight: usize,
+    parent_fee: Satoshis,
+    input_satisfaction_weight: usize,
+    drain_address: &bitcoin::Address,
+) -> Satoshis {
+    let tx = Transaction {
+        input: vec![],
+        version: 1,
+        lock_time: LockTime::ZERO.into(),
+        output: vec![TxOut {
+            value: 0,
+            script_pubkey: drain_address.script_pubkey(),
+        }],
+    };
+    let child_weight = tx.weight() + TXIN_BASE_WEIGHT + input_satisfaction_weight + 2; // 2 for segwit marker and flag
+    let package_fee = Satoshis::from(fee_rate.fee_wu(parent_weight + child_weight));
+    let child_fee = Satoshis::from(fee_rate.fee_wu(child_weight));
+    if package_fee > parent_fee + child_fee {
+        package_fee - parent_fee
+    } else {
+        child_fee
+    }
+}
+
 pub fn allocate_proportional_fees(
     fees: Satoshis,
     amounts: impl Iterator<Item = (PayoutId, Satoshis)>,
@@ -140,6 +182,33 @@ mod tests {
         assert_eq!(estimate, total_fee);
     }
 
+    #[test]
+    fn test_cpfp_child_fee() {
+        let fee_rate = bitcoin::FeeRate::from_sat_per_vb(10.);
+        let descriptor : bdk::descriptor::ExtendedDescriptor = "wpkh([6f2fa1b2/84'/0'/0']tpubDDDDGYiFda8HfJRc2AHFJDxVzzEtBPrKsbh35EaW2UGd5qfzrF2G87ewAgeeRyHEz4iB3kvhAYW1sH6dpLepTkFUzAktumBN8AXeXWE9nd1/0/*)#l6n08zmr".parse().unwrap();
+        let satisfaction_weight = descriptor.max_satisfaction_weight().unwrap();
+        let address = "bc1qc7yu0g5qplddngesxuarkkp3na9hkrugpydqs0"
+            .parse()
+            .unwrap();
+
+        let stand_alone = cpfp_child_fee(
+            fee_rate,
+            560,
+            Satoshis::from(100_000),
+            satisfaction_weight,
+            &address,
+        );
+        let package = cpfp_child_fee(
+            fee_rate,
+            560,
+            Satoshis::from(141),
+            satisfaction_weight,
+            &address,
+        );
+
+        assert_eq!(package - stand_alone, Satoshis::from(1_400 - 141));
+    }
+
     #[test]
     fn test_allocate_proportional_fees() {
         let fees = Satoshis::from(1000);
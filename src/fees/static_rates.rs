@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use bdk::FeeRate;
+use serde::{Deserialize, Serialize};
+
+use super::{error::FeeEstimationError, estimator::*};
+use crate::primitives::TxPriority;
+
+#[derive(Clone, Debug)]
+pub struct StaticFeeEstimator {
+    config: StaticFeeEstimatorConfig,
+}
+
+impl StaticFeeEstimator {
+    pub fn new(config: StaticFeeEstimatorConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl FeeEstimator for StaticFeeEstimator {
+    fn name(&self) -> &'static str {
+        "static"
+    }
+
+    async fn fee_rate(&self, priority: TxPriority) -> Result<FeeRate, FeeEstimationError> {
+        let sats_per_vbyte = match priority {
+            TxPriority::NextBlock => self.config.next_block,
+            TxPriority::HalfHour => self.config.half_hour,
+            TxPriority::OneHour => self.config.one_hour,
+        };
+        Ok(FeeRate::from_sat_per_vb(sats_per_vbyte))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StaticFeeEstimatorConfig {
+    #[serde(default = "default_next_block")]
+    pub next_block: f32,
+    #[serde(default = "default_half_hour")]
+    pub half_hour: f32,
+    #[serde(default = "default_one_hour")]
+    pub one_hour: f32,
+}
+
+impl Default for StaticFeeEstimatorConfig {
+    fn default() -> Self {
+        Self {
+            next_block: default_next_block(),
+            half_hour: default_half_hour(),
+            one_hour: default_one_hour(),
+        }
+    }
+}
+
+fn default_next_block() -> f32 {
+    3.0
+}
+
+fn default_half_hour() -> f32 {
+    2.0
+}
+
+fn default_one_hour() -> f32 {
+    1.0
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::instrument;
+
+use super::error::JobError;
+use crate::{batch::*, ledger::*, primitives::*, utxo::*, wallet::*};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFeeBumpAccountingData {
+    pub(super) account_id: AccountId,
+    pub(super) wallet_id: WalletId,
+    pub(super) batch_id: BatchId,
+    pub(super) fee_bump_id: BatchFeeBumpId,
+    #[serde(flatten)]
+    pub(super) tracing_data: HashMap<String, String>,
+}
+
+#[instrument(
+    name = "job.batch_fee_bump_accounting",
+    skip(wallets, batches, ledger, bria_utxos),
+    err
+)]
+pub async fn execute(
+    data: BatchFeeBumpAccountingData,
+    ledger: Ledger,
+    wallets: Wallets,
+    bria_utxos: Utxos,
+    batches: Batches,
+) -> Result<BatchFeeBumpAccountingData, JobError> {
+    let batch = batches.find_by_id(data.account_id, data.batch_id).await?;
+    let fee_bump = match batch
+        .fee_bumps
+        .iter()
+        .find(|bump| bump.id == data.fee_bump_id)
+    {
+        Some(fee_bump) => fee_bump,
+        None => return Ok(data),
+    };
+    // Nothing to account for if the bump has since been replaced and reverted
+    let bump_summary = match fee_bump.wallet_summaries.get(&data.wallet_id) {
+        Some(summary) if summary.reverted_ledger_tx_id.is_none() => summary,
+        _ => return Ok(data),
+    };
+    let wallet_summary = batch
+        .wallet_summaries
+        .get(&data.wallet_id)
+        .ok_or(JobError::WalletSummaryMissingInBatch(data.batch_id))?;
+    let wallet = wallets.find_by_id(data.wallet_id).await?;
+
+    if let Some((mut tx, tx_id)) = batches
+        .set_batch_fee_bumped_ledger_tx_id(data.fee_bump_id, data.wallet_id)
+        .await?
+    {
+        if let Some(change_outpoint) = wallet_summary.change_outpoint {
+            bria_utxos
+                .batch_spend_replaced(
+                    &mut tx,
+                    data.wallet_id,
+                    data.batch_id,
+                    wallet_summary.current_keychain_id,
+                    bitcoin::OutPoint {
+                        txid: fee_bump.replaced_bitcoin_tx_id,
+                        vout: change_outpoint.vout,
+                    },
+                    bitcoin::OutPoint {
+                        txid: fee_bump.bitcoin_tx_id,
+                        vout: change_outpoint.vout,
+                    },
+                    bump_summary.change_sats,
+                    tx_id,
+                )
+                .await?;
+        }
+        ledger
+            .batch_fee_bumped(
+                tx,
+                tx_id,
+                bump_summary.replaced_ledger_tx_id,
+                wallet.ledger_account_ids,
+                fee_bump.bitcoin_tx_id,
+                bump_summary.fee_sats,
+                bump_summary.change_sats,
+            )
+            .await?;
+    }
+    Ok(data)
+}
@@ -5,7 +5,8 @@ use std::collections::HashMap;
 
 use super::error::JobError;
 use crate::{
-    app::BlockchainConfig, batch::*, primitives::*, signing_session::*, wallet::*, xpub::*,
+    app::BlockchainConfig, batch::*, outbox::*, payout::*, primitives::*, signing_session::*,
+    wallet::*, xpub::*,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,7 +24,9 @@ pub struct BatchSigningData {
         wallets,
         signing_sessions,
         batches,
+        payouts,
         xpubs,
+        outbox,
         signer_encryption_config
     ),
     fields(stalled, txid, finalization_status),
@@ -35,9 +38,11 @@ pub async fn execute(
     data: BatchSigningData,
     blockchain_cfg: BlockchainConfig,
     batches: Batches,
+    payouts: Payouts,
     signing_sessions: SigningSessions,
     wallets: Wallets,
     xpubs: XPubs,
+    outbox: Outbox,
     signer_encryption_config: SignerEncryptionConfig,
 ) -> Result<(BatchSigningData, bool), JobError> {
     let span = tracing::Span::current();
@@ -86,7 +91,35 @@ pub async fn execute(
         )
     };
 
+    if sessions.values().any(|s| s.is_aborted()) {
+        span.record("finalization_status", "aborted");
+        return Ok((data, false));
+    }
+
+    let context = SigningContext {
+        account_id: data.account_id,
+        batch_id: Some(data.batch_id),
+        payouts: payouts
+            .list_for_batch(data.account_id, data.batch_id)
+            .await?
+            .into_values()
+            .flatten()
+            .map(|payout| SigningContextPayout {
+                id: payout.id,
+                wallet_id: payout.wallet_id,
+                destination: payout.destination.to_string(),
+                satoshis: u64::from(payout.satoshis),
+                external_id: payout.external_id,
+            })
+            .collect(),
+    };
+    let previously_failed: Vec<_> = sessions
+        .iter()
+        .filter(|(_, s)| s.state() == SigningSessionState::Failed)
+        .map(|(xpub_id, _)| *xpub_id)
+        .collect();
     let mut any_updated = false;
+    let mut failures = Vec::new();
     for (xpub_id, session) in sessions.iter_mut().filter(|(_, s)| !s.is_completed()) {
         any_updated = true;
         let account_xpub = if let Some(xpub) = account_xpub_cache.remove(xpub_id) {
@@ -95,18 +128,24 @@ pub async fn execute(
             xpubs.find_from_ref(data.account_id, xpub_id).await?
         };
         let mut client = match account_xpub
-            .remote_signing_client(signer_encryption_config.key)
+            .remote_signing_client(signer_encryption_config.key, &context)
             .await
         {
             Ok(Some(client)) => client,
             Ok(None) => {
                 session.attempt_failed(SigningFailureReason::SignerConfigMissing);
+                failures.push((
+                    session.id,
+                    *xpub_id,
+                    SigningFailureReason::SignerConfigMissing,
+                ));
                 stalled = true;
                 tracing::warn!("signer_config_missing");
                 continue;
             }
             Err(err) => {
                 session.attempt_failed(&err);
+                failures.push((session.id, *xpub_id, SigningFailureReason::from(&err)));
                 tracing::error!("{}", err.to_string());
                 last_err = Some(err);
                 continue;
@@ -118,6 +157,7 @@ pub async fn execute(
             }
             Err(err) => {
                 session.attempt_failed(&err);
+                failures.push((session.id, *xpub_id, SigningFailureReason::from(&err)));
                 tracing::error!("{}", err.to_string());
                 last_err = Some(err);
                 continue;
@@ -130,15 +170,28 @@ pub async fn execute(
         signing_sessions.update_sessions(&mut tx, &sessions).await?;
         tx.commit().await?;
     }
-    let mut sessions = sessions.into_values();
+    for (id, xpub_id, reason) in failures
+        .into_iter()
+        .filter(|(_, xpub_id, _)| !previously_failed.contains(xpub_id))
+    {
+        outbox
+            .add_event(
+                data.account_id,
+                OutboxEventPayload::SigningSessionFailed {
+                    id,
+                    batch_id: data.batch_id,
+                    xpub_id,
+                    reason: reason.to_string(),
+                },
+            )
+            .await?;
+    }
+    let batch_session = BatchSigningSession {
+        xpub_sessions: sessions,
+    };
 
     span.record("stalled", &tracing::field::display(stalled));
-    if let Some(mut first_signed_psbt) = sessions.find_map(|s| s.signed_psbt().cloned()) {
-        for s in sessions {
-            if let Some(psbt) = s.signed_psbt() {
-                let _ = first_signed_psbt.combine(psbt.clone());
-            }
-        }
+    if let Some(combined_psbt) = batch_session.combined_psbt() {
         if current_keychain.is_none() {
             let batch = batches.find_by_id(data.account_id, data.batch_id).await?;
             span.record("txid", &tracing::field::display(batch.bitcoin_tx_id));
@@ -149,7 +202,7 @@ pub async fn execute(
         match (
             current_keychain
                 .expect("keychain should always exist")
-                .finalize_psbt(first_signed_psbt)
+                .finalize_psbt(combined_psbt)
                 .await,
             last_err,
         ) {
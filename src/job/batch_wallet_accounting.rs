@@ -52,7 +52,7 @@ pub async fn execute(
         .list_for_batch(data.account_id, data.batch_id)
         .await?
         .remove(&data.wallet_id)
-        .expect("payouts not found");
+        .unwrap_or_default();
     if let Some((tx, tx_id)) = batches
         .set_batch_created_ledger_tx_id(data.batch_id, data.wallet_id)
         .await?
@@ -13,6 +13,9 @@ pub struct JobsConfig {
     #[serde_as(as = "serde_with::DurationSeconds<u64>")]
     #[serde(default = "default_respawn_all_outbox_handlers_delay")]
     pub respawn_all_outbox_handlers_delay: Duration,
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(default = "default_check_all_signers_delay")]
+    pub check_all_signers_delay: Duration,
     #[serde(default)]
     pub signing: SigningJobConfig,
 }
@@ -35,6 +38,7 @@ impl Default for JobsConfig {
             sync_all_wallets_delay: default_sync_all_wallets_delay(),
             process_all_payout_queues_delay: default_process_all_payout_queues_delay(),
             respawn_all_outbox_handlers_delay: default_respawn_all_outbox_handlers_delay(),
+            check_all_signers_delay: default_check_all_signers_delay(),
             signing: SigningJobConfig::default(),
         }
     }
@@ -62,6 +66,10 @@ fn default_respawn_all_outbox_handlers_delay() -> Duration {
     Duration::from_secs(5)
 }
 
+fn default_check_all_signers_delay() -> Duration {
+    Duration::from_secs(600)
+}
+
 fn default_signing_warn_retries() -> u32 {
     9 // About 8 minutes
 }
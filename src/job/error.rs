@@ -11,8 +11,9 @@ use crate::{
     outbox::error::OutboxError,
     payout::error::PayoutError,
     payout_queue::error::PayoutQueueError,
-    primitives::bitcoin::psbt,
+    primitives::{bitcoin::psbt, BatchId},
     profile::error::ProfileError,
+    security::error::SecurityError,
     signing_session::error::SigningSessionError,
     utxo::error::UtxoError,
     wallet::error::WalletError,
@@ -32,6 +33,8 @@ pub enum JobError {
     #[error("{0}")]
     ProfileError(#[from] ProfileError),
     #[error("{0}")]
+    SecurityError(#[from] SecurityError),
+    #[error("{0}")]
     PayoutQueueError(#[from] PayoutQueueError),
     #[error("{0}")]
     LedgerError(#[from] LedgerError),
@@ -55,6 +58,8 @@ pub enum JobError {
     Sqlx(#[from] sqlx::Error),
     #[error("JobError - PsbtMissingInSigningSessions")]
     PsbtMissingInSigningSessions,
+    #[error("JobError - WalletSummaryMissingInBatch: {0}")]
+    WalletSummaryMissingInBatch(BatchId),
     #[error("JobError - psbt::Error: {0}")]
     PsbtError(#[from] psbt::Error),
 }
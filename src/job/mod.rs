@@ -1,4 +1,5 @@
 mod batch_broadcasting;
+mod batch_fee_bump_accounting;
 mod batch_signing;
 mod batch_wallet_accounting;
 mod config;
@@ -11,16 +12,32 @@ pub mod process_payout_queue;
 
 pub use config::*;
 
+use futures::{StreamExt, TryStreamExt};
 use sqlxmq::{job, CurrentJob, JobBuilder, JobRegistry, OwnedHandle};
 use tracing::instrument;
 use uuid::{uuid, Uuid};
 
+use std::collections::HashMap;
+
 use crate::{
-    account::*, address::Addresses, app::BlockchainConfig, batch::*, fees::MempoolSpaceClient,
-    ledger::Ledger, outbox::*, payout::*, payout_queue::*, primitives::*, signing_session::*,
-    utxo::Utxos, wallet::*, xpub::*,
+    account::*,
+    address::Addresses,
+    app::{BlockchainConfig, SecurityConfig},
+    batch::*,
+    fees::AccountFeeEstimators,
+    ledger::Ledger,
+    outbox::*,
+    payout::*,
+    payout_queue::*,
+    primitives::*,
+    security::BlockedAddresses,
+    signing_session::*,
+    utxo::Utxos,
+    wallet::*,
+    xpub::*,
 };
 use batch_broadcasting::BatchBroadcastingData;
+use batch_fee_bump_accounting::BatchFeeBumpAccountingData;
 use batch_signing::BatchSigningData;
 use batch_wallet_accounting::BatchWalletAccountingData;
 use error::JobError;
@@ -33,11 +50,14 @@ use sync_wallet::SyncWalletData;
 const SYNC_ALL_WALLETS_ID: Uuid = uuid!("00000000-0000-0000-0000-000000000001");
 const PROCESS_ALL_PAYOUT_QUEUES_ID: Uuid = uuid!("00000000-0000-0000-0000-000000000002");
 const RESPAWN_ALL_OUTBOX_ID: Uuid = uuid!("00000000-0000-0000-0000-000000000003");
+const CHECK_ALL_SIGNERS_ID: Uuid = uuid!("00000000-0000-0000-0000-000000000004");
+const CONCURRENT_SIGNER_CHECKS: usize = 10;
 
 #[allow(clippy::too_many_arguments)]
 pub async fn start_job_runner(
     pool: &sqlx::PgPool,
     outbox: Outbox,
+    accounts: Accounts,
     wallets: Wallets,
     xpubs: XPubs,
     payout_queues: PayoutQueues,
@@ -50,7 +70,9 @@ pub async fn start_job_runner(
     config: JobsConfig,
     blockchain_cfg: BlockchainConfig,
     signer_encryption_config: SignerEncryptionConfig,
-    mempool_space_client: MempoolSpaceClient,
+    fee_estimators: AccountFeeEstimators,
+    security_config: SecurityConfig,
+    blocked_addresses: BlockedAddresses,
 ) -> Result<OwnedHandle, JobError> {
     let mut registry = JobRegistry::new(&[
         sync_all_wallets,
@@ -59,14 +81,17 @@ pub async fn start_job_runner(
         schedule_process_payout_queue,
         process_payout_queue,
         batch_wallet_accounting,
+        batch_fee_bump_accounting,
         batch_signing,
         batch_broadcasting,
         respawn_all_outbox_handlers,
         populate_outbox,
+        check_all_signers,
     ]);
     registry.set_context(config);
     registry.set_context(blockchain_cfg);
     registry.set_context(outbox);
+    registry.set_context(accounts);
     registry.set_context(wallets);
     registry.set_context(xpubs);
     registry.set_context(payout_queues);
@@ -77,7 +102,9 @@ pub async fn start_job_runner(
     registry.set_context(utxos);
     registry.set_context(addresses);
     registry.set_context(signer_encryption_config);
-    registry.set_context(mempool_space_client);
+    registry.set_context(fee_estimators);
+    registry.set_context(security_config);
+    registry.set_context(blocked_addresses);
 
     Ok(registry.runner(pool).set_keep_alive(false).run().await?)
 }
@@ -85,6 +112,7 @@ pub async fn start_job_runner(
 #[job(name = "sync_all_wallets")]
 async fn sync_all_wallets(
     mut current_job: CurrentJob,
+    accounts: Accounts,
     wallets: Wallets,
     JobsConfig {
         sync_all_wallets_delay: delay,
@@ -96,7 +124,11 @@ async fn sync_all_wallets(
         .build()
         .expect("couldn't build JobExecutor")
         .execute(|_| async move {
+            let inactive_account_ids = accounts.inactive_ids().await?;
             for (account_id, wallet_id) in wallets.all_ids().await? {
+                if inactive_account_ids.contains(&account_id) {
+                    continue;
+                }
                 let _ = spawn_sync_wallet(&pool, SyncWalletData::new(account_id, wallet_id)).await;
             }
             Ok::<(), JobError>(())
@@ -109,7 +141,10 @@ async fn sync_all_wallets(
 #[job(name = "process_all_payout_queues")]
 async fn process_all_payout_queues(
     mut current_job: CurrentJob,
+    accounts: Accounts,
     payout_queues: PayoutQueues,
+    payouts: Payouts,
+    fee_estimators: AccountFeeEstimators,
     JobsConfig {
         process_all_payout_queues_delay: delay,
         ..
@@ -120,7 +155,34 @@ async fn process_all_payout_queues(
         .build()
         .expect("couldn't build JobExecutor")
         .execute(|_| async move {
+            let accounts: HashMap<_, _> = accounts
+                .list()
+                .await?
+                .into_iter()
+                .map(|account| (account.id, account))
+                .collect();
             for group in payout_queues.all().await? {
+                let account = match accounts.get(&group.account_id) {
+                    Some(account) if account.active => account,
+                    _ => continue,
+                };
+                if group.config.trigger.is_threshold() {
+                    if let Ok(unbatched_payouts) =
+                        payouts.list_unbatched(group.account_id, group.id).await
+                    {
+                        let current_fee_rate = if group.config.trigger.needs_fee_rate() {
+                            let fee_estimator = fee_estimators.for_account(account).await;
+                            group.config.fee_rate(&fee_estimator).await.ok()
+                        } else {
+                            None
+                        };
+                        if group.threshold_reached(&unbatched_payouts, current_fee_rate) {
+                            let _ = spawn_process_payout_queue(&pool, (group.account_id, group.id))
+                                .await;
+                            continue;
+                        }
+                    }
+                }
                 if let Some(delay) = group.spawn_in() {
                     let _ = spawn_schedule_process_payout_queue(
                         &pool,
@@ -161,13 +223,13 @@ async fn populate_outbox(
 #[job(name = "respawn_all_outbox_handlers")]
 async fn respawn_all_outbox_handlers(
     mut current_job: CurrentJob,
+    accounts: Accounts,
     JobsConfig {
         respawn_all_outbox_handlers_delay: delay,
         ..
     }: JobsConfig,
 ) -> Result<(), JobError> {
     let pool = current_job.pool().clone();
-    let accounts = Accounts::new(&pool);
     JobExecutor::builder(&mut current_job)
         .build()
         .expect("couldn't build JobExecutor")
@@ -182,6 +244,52 @@ async fn respawn_all_outbox_handlers(
     Ok(())
 }
 
+#[job(name = "check_all_signers")]
+async fn check_all_signers(
+    mut current_job: CurrentJob,
+    accounts: Accounts,
+    xpubs: XPubs,
+    signer_encryption_config: SignerEncryptionConfig,
+    JobsConfig {
+        check_all_signers_delay: delay,
+        ..
+    }: JobsConfig,
+) -> Result<(), JobError> {
+    JobExecutor::builder(&mut current_job)
+        .build()
+        .expect("couldn't build JobExecutor")
+        .execute(|_| async move {
+            let inactive_account_ids = accounts.inactive_ids().await?;
+            let checks = xpubs
+                .list_all_xpubs()
+                .await?
+                .into_iter()
+                .filter(|xpub| {
+                    xpub.has_signer_config() && !inactive_account_ids.contains(&xpub.account_id)
+                })
+                .map(|mut xpub| {
+                    let xpubs = &xpubs;
+                    async move {
+                        match xpub.check_signer_health(signer_encryption_config.key).await {
+                            Ok(_) => xpubs.record_signer_health(&xpub).await?,
+                            Err(err) => {
+                                tracing::warn!(%err, xpub_id = %xpub.id(), "could not check signer health")
+                            }
+                        }
+                        Ok::<(), JobError>(())
+                    }
+                });
+            futures::stream::iter(checks)
+                .buffer_unordered(CONCURRENT_SIGNER_CHECKS)
+                .try_collect::<()>()
+                .await?;
+            Ok::<(), JobError>(())
+        })
+        .await?;
+    spawn_check_all_signers(current_job.pool(), delay).await?;
+    Ok(())
+}
+
 #[job(name = "sync_wallet")]
 #[allow(clippy::too_many_arguments)]
 async fn sync_wallet(
@@ -192,7 +300,11 @@ async fn sync_wallet(
     utxos: Utxos,
     ledger: Ledger,
     batches: Batches,
-    mempool_space_client: MempoolSpaceClient,
+    accounts: Accounts,
+    fee_estimators: AccountFeeEstimators,
+    outbox: Outbox,
+    blocked_addresses: BlockedAddresses,
+    security_config: SecurityConfig,
 ) -> Result<(), JobError> {
     let pool = current_job.pool().clone();
     let mut has_more = false;
@@ -212,7 +324,11 @@ async fn sync_wallet(
                 ledger,
                 batches,
                 data,
-                mempool_space_client,
+                accounts,
+                fee_estimators,
+                outbox,
+                blocked_addresses,
+                security_config,
             )
             .await?;
             *more_ref = more;
@@ -228,6 +344,22 @@ async fn sync_wallet(
 pub async fn spawn_process_payout_queue(
     pool: &sqlx::PgPool,
     data: impl Into<ProcessPayoutQueueData>,
+) -> Result<ProcessPayoutQueueData, JobError> {
+    let data = data.into();
+    onto_account_main_channel(
+        pool,
+        data.account_id,
+        process_payout_queue_job_id(data.payout_queue_id),
+        "process_payout_queue",
+        data,
+    )
+    .await
+}
+
+/// Used by a running process_payout_queue job whose own id is still taken
+async fn spawn_next_process_payout_queue(
+    pool: &sqlx::PgPool,
+    data: impl Into<ProcessPayoutQueueData>,
 ) -> Result<ProcessPayoutQueueData, JobError> {
     let data = data.into();
     onto_account_main_channel(
@@ -256,6 +388,7 @@ async fn schedule_process_payout_queue(mut current_job: CurrentJob) -> Result<()
 }
 
 #[job(name = "process_payout_queue")]
+#[allow(clippy::too_many_arguments)]
 async fn process_payout_queue(
     mut current_job: CurrentJob,
     payouts: Payouts,
@@ -263,7 +396,11 @@ async fn process_payout_queue(
     utxos: Utxos,
     payout_queues: PayoutQueues,
     batches: Batches,
-    mempool_space_client: MempoolSpaceClient,
+    fee_estimators: AccountFeeEstimators,
+    outbox: Outbox,
+    accounts: Accounts,
+    security_config: SecurityConfig,
+    blocked_addresses: BlockedAddresses,
 ) -> Result<(), JobError> {
     let pool = current_job.pool().clone();
     JobExecutor::builder(&mut current_job)
@@ -273,21 +410,29 @@ async fn process_payout_queue(
         .execute(|data| async move {
             let data: ProcessPayoutQueueData = data.expect("no ProcessPayoutQueueData available");
             let (data, res) = process_payout_queue::execute(
-                pool,
+                pool.clone(),
                 payouts,
                 wallets,
                 payout_queues,
                 batches,
                 utxos,
                 data,
-                mempool_space_client,
+                fee_estimators,
+                outbox,
+                accounts,
+                security_config,
+                blocked_addresses,
             )
             .await?;
-            if let Some((mut tx, wallet_ids)) = res {
+            if let Some((mut tx, wallet_ids, has_more)) = res {
                 for id in wallet_ids {
                     spawn_batch_wallet_accounting(&mut tx, (&data, id)).await?;
                 }
                 spawn_batch_signing(tx, &data).await?;
+                if has_more {
+                    spawn_next_process_payout_queue(&pool, (data.account_id, data.payout_queue_id))
+                        .await?;
+                }
             }
 
             Ok::<_, JobError>(data)
@@ -335,6 +480,35 @@ async fn batch_wallet_accounting(
     Ok(())
 }
 
+#[job(
+    name = "batch_fee_bump_accounting",
+    channel_name = "wallet_accounting",
+    retries = 20,
+    ordered = true
+)]
+async fn batch_fee_bump_accounting(
+    mut current_job: CurrentJob,
+    ledger: Ledger,
+    wallets: Wallets,
+    utxos: Utxos,
+    batches: Batches,
+) -> Result<(), JobError> {
+    let pool = current_job.pool().clone();
+    JobExecutor::builder(&mut current_job)
+        .build()
+        .expect("couldn't build JobExecutor")
+        .execute(|data| async move {
+            let data: BatchFeeBumpAccountingData =
+                data.expect("no BatchFeeBumpAccountingData available");
+            let data =
+                batch_fee_bump_accounting::execute(data, ledger, wallets, utxos, batches).await?;
+            spawn_batch_broadcasting(pool.begin().await?, data.clone()).await?;
+            Ok::<_, JobError>(data)
+        })
+        .await?;
+    Ok(())
+}
+
 #[job(name = "batch_signing", channel_name = "batch_signing")]
 #[allow(clippy::too_many_arguments)]
 async fn batch_signing(
@@ -343,8 +517,10 @@ async fn batch_signing(
     blockchain_cfg: BlockchainConfig,
     signer_encryption_config: SignerEncryptionConfig,
     batches: Batches,
+    payouts: Payouts,
     wallets: Wallets,
     xpubs: XPubs,
+    outbox: Outbox,
     signing_sessions: SigningSessions,
 ) -> Result<(), JobError> {
     let pool = current_job.pool().clone();
@@ -361,9 +537,11 @@ async fn batch_signing(
                 data,
                 blockchain_cfg,
                 batches,
+                payouts,
                 signing_sessions,
                 wallets,
                 xpubs,
+                outbox,
                 signer_encryption_config,
             )
             .await?;
@@ -497,6 +675,48 @@ async fn spawn_batch_wallet_accounting(
     }
 }
 
+#[instrument(name = "job.spawn_batch_fee_bump", skip_all, fields(error, error.level, error.message), err)]
+pub async fn spawn_batch_fee_bump(
+    mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
+    account_id: AccountId,
+    batch_id: BatchId,
+    fee_bump_id: BatchFeeBumpId,
+    wallet_ids: impl Iterator<Item = WalletId>,
+) -> Result<(), JobError> {
+    for wallet_id in wallet_ids {
+        let data = BatchFeeBumpAccountingData {
+            account_id,
+            wallet_id,
+            batch_id,
+            fee_bump_id,
+            tracing_data: crate::tracing::extract_tracing_data(),
+        };
+        if let Err(e) = batch_fee_bump_accounting
+            .builder()
+            .set_json(&data)
+            .expect("Couldn't set json")
+            .set_channel_args(&format!("wallet_id:{}", data.wallet_id))
+            .spawn(&mut tx)
+            .await
+        {
+            crate::tracing::insert_error_fields(tracing::Level::ERROR, &e);
+            return Err(e.into());
+        }
+    }
+    spawn_batch_signing(tx, (account_id, batch_id)).await
+}
+
+#[instrument(name = "job.spawn_utxo_acceleration", skip_all, fields(error, error.level, error.message), err)]
+pub async fn spawn_utxo_acceleration(
+    mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
+    account_id: AccountId,
+    batch_id: BatchId,
+    wallet_id: WalletId,
+) -> Result<(), JobError> {
+    spawn_batch_wallet_accounting(&mut tx, (account_id, batch_id, wallet_id)).await?;
+    spawn_batch_signing(tx, (account_id, batch_id)).await
+}
+
 #[instrument(name = "job.spawn_batch_signing", skip_all, fields(error, error.level, error.message), err)]
 async fn spawn_batch_signing(
     mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
@@ -543,6 +763,30 @@ pub async fn spawn_all_batch_signings(
     Ok(())
 }
 
+/// Pulls the pending signing attempt of a batch forward so it runs right away
+/// instead of waiting out its retry backoff. Spawns a new attempt if none is pending.
+#[instrument(name = "job.retry_batch_signing", skip_all, fields(error, error.level, error.message), err)]
+pub async fn retry_batch_signing(
+    mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
+    account_id: AccountId,
+    batch_id: BatchId,
+) -> Result<(), JobError> {
+    let result = sqlx::query!(
+        r#"UPDATE mq_msgs SET attempt_at = NOW()
+           WHERE channel_name = 'batch_signing' AND channel_args = $1
+             AND id != uuid_nil() AND NOT mq_uuid_exists(after_message_id)
+             AND attempt_at > NOW()"#,
+        format!("batch_id:{batch_id}"),
+    )
+    .execute(&mut *tx)
+    .await?;
+    if result.rows_affected() > 0 {
+        tx.commit().await?;
+        return Ok(());
+    }
+    spawn_batch_signing(tx, (account_id, batch_id)).await
+}
+
 #[instrument(name = "job.spawn_batch_broadcasting", skip_all, fields(error, error.level, error.message), err)]
 async fn spawn_batch_broadcasting(
     mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
@@ -611,6 +855,33 @@ pub async fn spawn_respawn_all_outbox_handlers(
     }
 }
 
+#[instrument(name = "job.spawn_check_all_signers", skip_all, fields(error, error.level, error.message), err)]
+pub async fn spawn_check_all_signers(
+    pool: &sqlx::PgPool,
+    duration: std::time::Duration,
+) -> Result<(), JobError> {
+    match JobBuilder::new_with_id(CHECK_ALL_SIGNERS_ID, "check_all_signers")
+        .set_channel_name("check_all_signers")
+        .set_delay(duration)
+        .spawn(pool)
+        .await
+    {
+        Err(sqlx::Error::Database(err)) if err.message().contains("duplicate key") => Ok(()),
+        Err(e) => {
+            crate::tracing::insert_error_fields(tracing::Level::ERROR, &e);
+            Err(e.into())
+        }
+        Ok(_) => Ok(()),
+    }
+}
+
+// One pending process_payout_queue job per queue. The queue id itself is taken
+// by schedule_process_payout_queue so a fixed mask is applied to it.
+fn process_payout_queue_job_id(payout_queue_id: PayoutQueueId) -> Uuid {
+    const MASK: u128 = 0xffff_ffff_ffff_0000_0000_0000_0000_0000;
+    Uuid::from_u128(Uuid::from(payout_queue_id).as_u128() ^ MASK)
+}
+
 fn schedule_payout_queue_channel_arg(payout_queue_id: PayoutQueueId) -> String {
     format!("payout_queue_id:{payout_queue_id}")
 }
@@ -676,6 +947,17 @@ impl From<(&ProcessPayoutQueueData, WalletId)> for BatchWalletAccountingData {
     }
 }
 
+impl From<(AccountId, BatchId, WalletId)> for BatchWalletAccountingData {
+    fn from((account_id, batch_id, wallet_id): (AccountId, BatchId, WalletId)) -> Self {
+        Self {
+            tracing_data: crate::tracing::extract_tracing_data(),
+            account_id,
+            batch_id,
+            wallet_id,
+        }
+    }
+}
+
 impl From<&ProcessPayoutQueueData> for BatchSigningData {
     fn from(data: &ProcessPayoutQueueData) -> Self {
         Self {
@@ -704,6 +986,15 @@ impl From<BatchWalletAccountingData> for BatchBroadcastingData {
     }
 }
 
+impl From<BatchFeeBumpAccountingData> for BatchBroadcastingData {
+    fn from(data: BatchFeeBumpAccountingData) -> Self {
+        Self {
+            account_id: data.account_id,
+            batch_id: data.batch_id,
+        }
+    }
+}
+
 impl From<BatchSigningData> for BatchBroadcastingData {
     fn from(data: BatchSigningData) -> Self {
         Self {
@@ -4,8 +4,8 @@ use tracing::instrument;
 
 use super::error::JobError;
 use crate::{
-    batch::*, fees::MempoolSpaceClient, payout::*, payout_queue::*, primitives::*, utxo::*,
-    wallet::*,
+    account::Accounts, app::SecurityConfig, batch::*, fees::*, outbox::*, payout::*,
+    payout_queue::*, primitives::*, security::*, utxo::*, wallet::*,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +22,7 @@ pub struct ProcessPayoutQueueData {
     skip_all,
     fields(
         n_unbatched_payouts,
+        n_deferred_payouts,
         payout_queue_name,
         n_reserved_utxos,
         txid,
@@ -40,44 +41,172 @@ pub(super) async fn execute<'a>(
     batches: Batches,
     utxos: Utxos,
     data: ProcessPayoutQueueData,
-    mempool_space_client: MempoolSpaceClient,
+    fee_estimators: AccountFeeEstimators,
+    outbox: Outbox,
+    accounts: Accounts,
+    security_config: SecurityConfig,
+    blocked_addresses: BlockedAddresses,
 ) -> Result<
     (
         ProcessPayoutQueueData,
-        Option<(sqlx::Transaction<'a, sqlx::Postgres>, Vec<WalletId>)>,
+        Option<(sqlx::Transaction<'a, sqlx::Postgres>, Vec<WalletId>, bool)>,
     ),
     JobError,
 > {
-    let payout_queue = payout_queues
+    let account = accounts.find_by_id(data.account_id).await?;
+    if !account.active {
+        return Ok((data, None));
+    }
+    let mut payout_queue = payout_queues
         .find_by_id(data.account_id, data.payout_queue_id)
         .await?;
     let mut unbatched_payouts = payouts
         .list_unbatched(data.account_id, data.payout_queue_id)
         .await?;
-    let fee_rate = mempool_space_client
-        .fee_rate(payout_queue.config.tx_priority)
-        .await?;
+    let fee_estimator = fee_estimators.for_account(&account).await;
+    let fee_rate = payout_queue.config.fee_rate(&fee_estimator).await?;
+    let max_fee_rate_sats_per_vbyte = payout_queue.config.max_fee_rate_sats_per_vbyte;
+    let fee_rate_exceeded = max_fee_rate_sats_per_vbyte
+        .map(|max| unbatched_payouts.n_payouts() > 0 && fee_rate.as_sat_per_vb() > max as f32)
+        .unwrap_or(false);
+    // Only notify when the queue enters the exceeded state, not on every run
+    if payout_queue.update_fee_rate_exceeded(fee_rate, fee_rate_exceeded) {
+        payout_queues.update(payout_queue).await?;
+        if let (true, Some(max_fee_rate_sats_per_vbyte)) =
+            (fee_rate_exceeded, max_fee_rate_sats_per_vbyte)
+        {
+            outbox
+                .add_event(
+                    data.account_id,
+                    OutboxEventPayload::PayoutQueueFeeRateExceeded {
+                        payout_queue_id: data.payout_queue_id,
+                        fee_rate_sats_per_vbyte: fee_rate.as_sat_per_vb(),
+                        max_fee_rate_sats_per_vbyte,
+                    },
+                )
+                .await?;
+            return Ok((data, None));
+        }
+        payout_queue = payout_queues
+            .find_by_id(data.account_id, data.payout_queue_id)
+            .await?;
+    } else if fee_rate_exceeded {
+        return Ok((data, None));
+    }
     let mut tx = pool.begin().await?;
+    let db_blocked = blocked_addresses
+        .find_blocked(&unbatched_payouts.onchain_destinations())
+        .await?;
+    let mut blocked_payouts = unbatched_payouts.exclude(|p| {
+        security_config.is_blocked(&p.destination)
+            || account.config.is_blocked(&p.destination)
+            || p.destination
+                .onchain_address()
+                .map(|addr| db_blocked.contains(&addr))
+                .unwrap_or(false)
+    });
+    for payout in blocked_payouts.iter() {
+        if let Some(address) = payout.destination.onchain_address() {
+            blocked_addresses
+                .record_payout_attempt(NewBlockedPayoutAttempt {
+                    account_id: data.account_id,
+                    profile_id: None,
+                    payout_id: Some(payout.id),
+                    address,
+                    satoshis: payout.satoshis,
+                })
+                .await?;
+        }
+    }
+    let newly_blocked: Vec<_> = blocked_payouts
+        .iter_mut()
+        .filter_map(|p| {
+            p.skipped(PayoutSkipReason::Blocked).then(|| {
+                (
+                    p.id,
+                    p.wallet_id,
+                    p.satoshis,
+                    p.destination.clone(),
+                    PayoutSkipReason::Blocked,
+                )
+            })
+        })
+        .collect();
+    payouts.update_skipped(&mut tx, blocked_payouts).await?;
+    emit_skipped_payouts(
+        &outbox,
+        data.account_id,
+        data.payout_queue_id,
+        newly_blocked.into_iter(),
+    )
+    .await?;
+    let max_tx_vbytes = payout_queue.config.max_tx_vbytes;
+    let mut n_deferred = payout_queue
+        .config
+        .max_payouts_per_batch
+        .map(|max_payouts| unbatched_payouts.truncate(max_payouts))
+        .unwrap_or(0);
     let FinishedPsbtBuild {
         psbt,
         included_payouts,
+        skipped_payouts,
         included_utxos,
         wallet_totals,
         tx_id,
         fee_satoshis,
         ..
-    } = construct_psbt(
-        &pool,
-        &mut tx,
-        &unbatched_payouts,
-        &utxos,
-        &wallets,
-        payout_queue,
-        fee_rate,
+    } = loop {
+        let build = construct_psbt(
+            &pool,
+            &mut tx,
+            &unbatched_payouts,
+            &utxos,
+            &wallets,
+            &payout_queue,
+            fee_rate,
+        )
+        .await?;
+        let n_payouts = unbatched_payouts.n_payouts();
+        match (max_tx_vbytes, build.tx_id) {
+            (Some(max_tx_vbytes), Some(_)) => {
+                let tx_vbytes = estimated_tx_vbytes(build.fee_satoshis, fee_rate);
+                match n_payouts_within_vbytes(n_payouts, tx_vbytes, max_tx_vbytes) {
+                    Some(max_payouts) => n_deferred += unbatched_payouts.truncate(max_payouts),
+                    None => break build,
+                }
+            }
+            _ => break build,
+        }
+    };
+
+    // Only emit when the skip reason changed to avoid an event on every run
+    let newly_skipped: Vec<_> = skipped_payouts
+        .into_iter()
+        .flat_map(|(wallet_id, payouts)| {
+            payouts
+                .into_iter()
+                .map(move |((id, address, satoshis), reason)| {
+                    (
+                        PayoutId::from(id),
+                        wallet_id,
+                        satoshis,
+                        PayoutDestination::OnchainAddress { value: address },
+                        reason,
+                    )
+                })
+        })
+        .filter(|(id, _, _, _, reason)| unbatched_payouts.record_skip(*id, *reason))
+        .collect();
+    emit_skipped_payouts(
+        &outbox,
+        data.account_id,
+        data.payout_queue_id,
+        newly_skipped.into_iter(),
     )
     .await?;
 
     let span = tracing::Span::current();
+    span.record("n_deferred_payouts", n_deferred);
     if let (Some(tx_id), Some(psbt)) = (tx_id, psbt) {
         span.record("txid", &tracing::field::display(tx_id));
         span.record("psbt", &tracing::field::display(&psbt));
@@ -119,7 +248,7 @@ pub(super) async fn execute<'a>(
                 &mut tx,
                 data.account_id,
                 batch_id,
-                data.payout_queue_id,
+                Some(data.payout_queue_id),
                 fee_rate,
                 included_utxos,
             )
@@ -139,11 +268,13 @@ pub(super) async fn execute<'a>(
 
         payouts.update_unbatched(&mut tx, unbatched_payouts).await?;
 
-        Ok((data, Some((tx, wallet_ids))))
+        Ok((data, Some((tx, wallet_ids, n_deferred > 0))))
     } else {
         if unbatched_payouts.n_not_batched() > 0 {
             queue_drain_error(unbatched_payouts.n_not_batched());
         }
+        payouts.update_unbatched(&mut tx, unbatched_payouts).await?;
+        tx.commit().await?;
         Ok((data, None))
     }
 }
@@ -154,7 +285,7 @@ pub async fn construct_psbt(
     unbatched_payouts: &UnbatchedPayouts,
     utxos: &Utxos,
     wallets: &Wallets,
-    payout_queue: PayoutQueue,
+    payout_queue: &PayoutQueue,
     fee_rate: bitcoin::FeeRate,
 ) -> Result<FinishedPsbtBuild, JobError> {
     let span = tracing::Span::current();
@@ -164,7 +295,7 @@ pub async fn construct_psbt(
         name: queue_name,
         ..
     } = payout_queue;
-    span.record("payout_queue_name", queue_name);
+    span.record("payout_queue_name", queue_name.as_str());
     span.record("payout_queue_id", &tracing::field::display(queue_id));
     span.record("n_unbatched_payouts", unbatched_payouts.n_payouts());
 
@@ -192,6 +323,52 @@ pub async fn construct_psbt(
     .await?)
 }
 
+async fn emit_skipped_payouts(
+    outbox: &Outbox,
+    account_id: AccountId,
+    payout_queue_id: PayoutQueueId,
+    skipped: impl Iterator<
+        Item = (
+            PayoutId,
+            WalletId,
+            Satoshis,
+            PayoutDestination,
+            PayoutSkipReason,
+        ),
+    >,
+) -> Result<(), JobError> {
+    for (id, wallet_id, satoshis, destination, reason) in skipped {
+        outbox
+            .add_event(
+                account_id,
+                OutboxEventPayload::PayoutSkipped {
+                    id,
+                    wallet_id,
+                    payout_queue_id,
+                    satoshis,
+                    destination,
+                    reason,
+                },
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+fn estimated_tx_vbytes(fee_satoshis: Satoshis, fee_rate: bitcoin::FeeRate) -> u64 {
+    (u64::from(fee_satoshis) as f32 / fee_rate.as_sat_per_vb()).ceil() as u64
+}
+
+/// How many payouts to keep when a tx with `n_payouts` is over the vbytes limit.
+/// Always makes progress by deferring at least one payout but never all of them.
+fn n_payouts_within_vbytes(n_payouts: usize, tx_vbytes: u64, max_tx_vbytes: u64) -> Option<usize> {
+    if n_payouts <= 1 || tx_vbytes <= max_tx_vbytes {
+        return None;
+    }
+    let max_payouts = (n_payouts as u64 * max_tx_vbytes / tx_vbytes) as usize;
+    Some(max_payouts.clamp(1, n_payouts - 1))
+}
+
 #[instrument(name = "job.queue_drain_error", fields(error = true, error.level, error.message))]
 fn queue_drain_error(n_not_batched: usize) {
     let span = tracing::Span::current();
@@ -219,3 +396,21 @@ impl From<WalletTotals> for WalletSummary {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_all_payouts_within_vbytes() {
+        assert_eq!(n_payouts_within_vbytes(3, 200, 200), None);
+        assert_eq!(n_payouts_within_vbytes(1, 500, 200), None);
+    }
+
+    #[test]
+    fn defers_payouts_over_vbytes() {
+        assert_eq!(n_payouts_within_vbytes(10, 400, 200), Some(5));
+        assert_eq!(n_payouts_within_vbytes(2, 201, 200), Some(1));
+        assert_eq!(n_payouts_within_vbytes(3, 10_000, 200), Some(1));
+    }
+}
@@ -1,22 +1,28 @@
 use bdk::blockchain::{ElectrumBlockchain, GetHeight};
-use electrum_client::{Client, ConfigBuilder};
+use electrum_client::{Client, ConfigBuilder, ElectrumApi};
 use serde::{Deserialize, Serialize};
 use tracing::{info, instrument};
 
 use super::error::JobError;
 use crate::{
+    account::{Account, Accounts},
     address::*,
-    app::BlockchainConfig,
+    app::{BlockchainConfig, SecurityConfig},
     batch::*,
     bdk::error::BdkError,
     bdk::pg::{ConfirmedIncomeUtxo, ConfirmedSpendTransaction, Transactions, Utxos as BdkUtxos},
-    fees::{self, MempoolSpaceClient},
+    fees::{self, AccountFeeEstimators},
     ledger::*,
+    outbox::*,
     primitives::*,
-    utxo::{Utxos, WalletUtxo},
+    security::BlockedAddresses,
+    utxo::{error::UtxoError, Utxos, WalletUtxo},
     wallet::*,
 };
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncWalletData {
@@ -53,13 +59,28 @@ struct Deps {
     bria_addresses: Addresses,
     bria_utxos: Utxos,
     ledger: Ledger,
+    outbox: Outbox,
+    blocked_addresses: BlockedAddresses,
+    security_config: SecurityConfig,
 }
 
 const MAX_TXS_PER_SYNC: usize = 100;
 
 #[instrument(
     name = "job.sync_wallet",
-    skip(pool, wallets, batches, bria_utxos, bria_addresses, ledger),
+    skip(
+        pool,
+        wallets,
+        batches,
+        bria_utxos,
+        bria_addresses,
+        ledger,
+        accounts,
+        fee_estimators,
+        outbox,
+        blocked_addresses,
+        security_config
+    ),
     fields(
         n_pending_utxos,
         n_confirmed_utxos,
@@ -79,34 +100,45 @@ pub async fn execute(
     ledger: Ledger,
     batches: Batches,
     data: SyncWalletData,
-    mempool_space_client: MempoolSpaceClient,
+    accounts: Accounts,
+    fee_estimators: AccountFeeEstimators,
+    outbox: Outbox,
+    blocked_addresses: BlockedAddresses,
+    security_config: SecurityConfig,
 ) -> Result<(bool, SyncWalletData), JobError> {
     info!("Starting sync_wallet job: {:?}", data);
     let span = tracing::Span::current();
     let wallet = wallets.find_by_id(data.wallet_id).await?;
+    let account = accounts.find_by_id(data.account_id).await?;
+    let fee_estimator = fee_estimators.for_account(&account).await;
     let mut trackers = InstrumentationTrackers::new();
     let deps = Deps {
         blockchain_cfg,
         bria_addresses,
         bria_utxos,
         ledger,
+        outbox,
+        blocked_addresses,
+        security_config,
     };
+    let check_funding_sources = deps.security_config.has_blocked_addresses()
+        || !account.config.blocked_addresses.is_empty()
+        || !deps.blocked_addresses.is_empty().await?;
     let mut utxos_to_fetch = HashMap::new();
     let mut income_bria_utxos = Vec::new();
     for keychain_wallet in wallet.keychain_wallets(pool.clone()) {
         info!("Syncing keychain '{}'", keychain_wallet.keychain_id);
-        let fees_to_encumber = fees::fees_to_encumber(
-            &mempool_space_client,
-            keychain_wallet.max_satisfaction_weight(),
-        )
-        .await?;
+        let fees_to_encumber =
+            fees::fees_to_encumber(&fee_estimator, keychain_wallet.max_satisfaction_weight())
+                .await?;
         let keychain_id = keychain_wallet.keychain_id;
         utxos_to_fetch.clear();
         utxos_to_fetch.insert(keychain_id, Vec::<bitcoin::OutPoint>::new());
         let (blockchain, current_height) = init_electrum(&deps.blockchain_cfg.electrum_url).await?;
+        let blockchain = Arc::new(blockchain);
         span.record("current_height", current_height);
         let latest_change_settle_height = wallet.config.latest_change_settle_height(current_height);
-        keychain_wallet.sync(blockchain).await?;
+        keychain_wallet.sync(Arc::clone(&blockchain)).await?;
         let bdk_txs = Transactions::new(keychain_id, pool.clone());
         let bdk_utxos = BdkUtxos::new(keychain_id, pool.clone());
         let mut txs_to_skip = Vec::new();
@@ -139,12 +171,35 @@ pub async fn execute(
                 }
             }
             txs_to_skip.clear();
+            if spend_tx && batches.is_fee_bump_tx(unsynced_tx.tx_id).await? {
+                bdk_txs.mark_as_synced(unsynced_tx.tx_id).await?;
+                continue;
+            }
+            let quarantine_source = if spend_tx || !check_funding_sources {
+                None
+            } else {
+                find_blocked_funding_source(
+                    &deps,
+                    &account,
+                    &blockchain,
+                    wallet.network,
+                    &unsynced_tx.previous_outputs,
+                )
+                .await?
+            };
             for output in unsynced_tx.outputs.drain(..) {
                 if output.0.keychain == bitcoin::KeychainKind::Internal {
                     change.push(output);
                     continue;
                 }
                 let (local_utxo, path) = output;
+                let dust = !spend_tx
+                    && wallet
+                        .config
+                        .is_dust(Satoshis::from(local_utxo.txout.value));
+                let ledger_account_ids = wallet
+                    .ledger_account_ids
+                    .for_income(dust, quarantine_source.is_some());
                 let address_info = keychain_wallet
                     .find_address_from_path(path, local_utxo.keychain)
                     .await?;
@@ -168,6 +223,8 @@ pub async fn execute(
                         &local_utxo,
                         unsynced_tx.sats_per_vbyte_when_created,
                         spend_tx,
+                        quarantine_source.as_ref(),
+                        dust,
                     )
                     .await?
                 {
@@ -185,8 +242,7 @@ pub async fn execute(
                                 onchain_incoming_account_id: wallet
                                     .ledger_account_ids
                                     .onchain_incoming_id,
-                                effective_incoming_account_id: wallet
-                                    .ledger_account_ids
+                                effective_incoming_account_id: ledger_account_ids
                                     .effective_incoming_id,
                                 onchain_fee_account_id: wallet.ledger_account_ids.fee_id,
                                 meta: UtxoDetectedMeta {
@@ -195,10 +251,14 @@ pub async fn execute(
                                     keychain_id,
                                     outpoint: local_utxo.outpoint,
                                     satoshis: local_utxo.txout.value.into(),
-                                    address: address_info.address,
+                                    address: address_info.address.clone(),
                                     encumbered_spending_fees: std::iter::once((
                                         local_utxo.outpoint,
-                                        fees_to_encumber,
+                                        if dust {
+                                            Satoshis::ZERO
+                                        } else {
+                                            fees_to_encumber
+                                        },
                                     ))
                                     .collect(),
                                     confirmation_time: unsynced_tx.confirmation_time.clone(),
@@ -206,6 +266,37 @@ pub async fn execute(
                             },
                         )
                         .await?;
+                    if let Some(source_address) = quarantine_source.as_ref() {
+                        deps.outbox
+                            .add_event(
+                                data.account_id,
+                                OutboxEventPayload::UtxoQuarantined {
+                                    tx_id: local_utxo.outpoint.txid,
+                                    vout: local_utxo.outpoint.vout,
+                                    satoshis: local_utxo.txout.value.into(),
+                                    address: address_info.address.clone(),
+                                    wallet_id: data.wallet_id,
+                                    keychain_id,
+                                    source_address: source_address.clone(),
+                                },
+                            )
+                            .await?;
+                    }
+                    if dust {
+                        deps.outbox
+                            .add_event(
+                                data.account_id,
+                                OutboxEventPayload::UtxoDustDetected {
+                                    tx_id: local_utxo.outpoint.txid,
+                                    vout: local_utxo.outpoint.vout,
+                                    satoshis: local_utxo.txout.value.into(),
+                                    address: address_info.address,
+                                    wallet_id: data.wallet_id,
+                                    keychain_id,
+                                },
+                            )
+                            .await?;
+                    }
                     let conf_time = match unsynced_tx.confirmation_time.as_ref() {
                         Some(t)
                             if t.height
@@ -236,7 +327,9 @@ pub async fn execute(
                                 utxo.utxo_settled_ledger_tx_id,
                                 UtxoSettledParams {
                                     journal_id: wallet.journal_id,
-                                    ledger_account_ids: wallet.ledger_account_ids,
+                                    ledger_account_ids: wallet
+                                        .ledger_account_ids
+                                        .for_income(utxo.dust, utxo.quarantined || utxo.frozen),
                                     pending_id: utxo.utxo_detected_ledger_tx_id,
                                     meta: UtxoSettledMeta {
                                         account_id: data.account_id,
@@ -369,6 +462,7 @@ pub async fn execute(
                 if !spend_tx || conf_time.height > latest_change_settle_height {
                     continue;
                 }
+                revert_fee_bumps(&deps, &batches, &wallet, unsynced_tx.tx_id).await?;
                 let mut tx = pool.begin().await?;
                 if let Some((pending_out_id, confirmed_out_id, change_spent)) = deps
                     .bria_utxos
@@ -429,7 +523,9 @@ pub async fn execute(
                         utxo.utxo_settled_ledger_tx_id,
                         UtxoSettledParams {
                             journal_id: wallet.journal_id,
-                            ledger_account_ids: wallet.ledger_account_ids,
+                            ledger_account_ids: wallet
+                                .ledger_account_ids
+                                .for_income(utxo.dust, utxo.quarantined || utxo.frozen),
                             pending_id: utxo.utxo_detected_ledger_tx_id,
                             meta: UtxoSettledMeta {
                                 account_id: data.account_id,
@@ -453,12 +549,13 @@ pub async fn execute(
             let mut tx = pool.begin().await?;
             let min_height = wallet.config.latest_change_settle_height(current_height);
             if let Ok(Some(ConfirmedSpendTransaction {
+                tx_id,
                 confirmation_time,
                 inputs,
                 outputs,
-                ..
             })) = bdk_txs.find_confirmed_spend_tx(&mut tx, min_height).await
             {
+                revert_fee_bumps(&deps, &batches, &wallet, tx_id).await?;
                 let change_utxo = outputs
                     .into_iter()
                     .find(|u| u.keychain == bitcoin::KeychainKind::Internal);
@@ -498,10 +595,19 @@ pub async fn execute(
                 bdk_txs
                     .delete_transaction_if_no_more_utxos_exist(&mut tx, outpoint)
                     .await?;
-                let detected_txn_id = deps
+                let detected_txn_id = match deps
                     .bria_utxos
                     .delete_utxo(&mut tx, outpoint, keychain_id)
-                    .await?;
+                    .await
+                {
+                    Err(UtxoError::UtxoDoesNotExistError)
+                        if batches.is_fee_bump_involved_tx(outpoint.txid).await? =>
+                    {
+                        tx.commit().await?;
+                        continue;
+                    }
+                    res => res?,
+                };
                 deps.ledger
                     .utxo_dropped(tx, LedgerTransactionId::new(), detected_txn_id)
                     .await?;
@@ -520,6 +626,99 @@ pub async fn execute(
     Ok((has_more, data))
 }
 
+async fn revert_fee_bumps(
+    deps: &Deps,
+    batches: &Batches,
+    wallet: &Wallet,
+    confirmed_tx_id: bitcoin::Txid,
+) -> Result<(), JobError> {
+    if let Some((mut tx, reverted)) = batches.revert_fee_bumps(confirmed_tx_id, wallet.id).await? {
+        if let Some((bumped_tx_id, bumped_ledger_tx_id)) = reverted.reverted {
+            if let Some(vout) = reverted.change_vout {
+                deps.bria_utxos
+                    .batch_spend_replaced(
+                        &mut tx,
+                        wallet.id,
+                        reverted.batch_id,
+                        reverted.current_keychain_id,
+                        bitcoin::OutPoint {
+                            txid: bumped_tx_id,
+                            vout,
+                        },
+                        bitcoin::OutPoint {
+                            txid: reverted.bitcoin_tx_id,
+                            vout,
+                        },
+                        reverted.change_sats,
+                        reverted.restored_ledger_tx_id,
+                    )
+                    .await?;
+            }
+            deps.ledger
+                .batch_fee_bumped(
+                    tx,
+                    reverted.ledger_tx_id,
+                    bumped_ledger_tx_id,
+                    wallet.ledger_account_ids,
+                    reverted.bitcoin_tx_id,
+                    reverted.fee_sats,
+                    reverted.change_sats,
+                )
+                .await?;
+        } else {
+            tx.commit().await?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns a blocked address the funding transaction spends from (if any).
+/// Lookup failures fail the job so the deposit is screened again on retry.
+async fn find_blocked_funding_source(
+    deps: &Deps,
+    account: &Account,
+    blockchain: &Arc<ElectrumBlockchain>,
+    network: bitcoin::Network,
+    previous_outputs: &[bitcoin::OutPoint],
+) -> Result<Option<bitcoin::Address>, JobError> {
+    let previous_outputs: Vec<_> = previous_outputs
+        .iter()
+        .filter(|outpoint| !outpoint.is_null())
+        .copied()
+        .collect();
+    if previous_outputs.is_empty() {
+        return Ok(None);
+    }
+    let txids: Vec<_> = previous_outputs
+        .iter()
+        .map(|outpoint| outpoint.txid)
+        .collect();
+    let blockchain = Arc::clone(blockchain);
+    let prev_txs =
+        tokio::task::spawn_blocking(move || blockchain.batch_transaction_get(txids.iter()))
+            .await
+            .map_err(BdkError::from)?
+            .map_err(BdkError::from)?;
+    let source_addresses: HashSet<_> = previous_outputs
+        .iter()
+        .zip(prev_txs)
+        .filter_map(|(outpoint, tx)| tx.output.into_iter().nth(outpoint.vout as usize))
+        .filter_map(|output| bitcoin::Address::from_script(&output.script_pubkey, network).ok())
+        .collect();
+    if let Some(address) = source_addresses.iter().find(|addr| {
+        deps.security_config.is_address_blocked(addr)
+            || account.config.blocked_addresses.contains(addr)
+    }) {
+        return Ok(Some(address.clone()));
+    }
+    Ok(deps
+        .blocked_addresses
+        .find_blocked(&source_addresses)
+        .await?
+        .into_iter()
+        .next())
+}
+
 async fn init_electrum(electrum_url: &str) -> Result<(ElectrumBlockchain, u32), BdkError> {
     let blockchain = ElectrumBlockchain::from(Client::from_config(
         electrum_url,
@@ -37,6 +37,18 @@ pub(super) const BATCH_CREATED_ID: Uuid = uuid!("10000000-0000-0000-0000-0000000
 pub(super) const BATCH_BROADCAST_CODE: &str = "BATCH_BROADCAST";
 pub(super) const BATCH_BROADCAST_ID: Uuid = uuid!("00000000-0000-0000-0000-000000000008");
 
+pub(super) const BATCH_FEE_BUMPED_CODE: &str = "BATCH_FEE_BUMPED";
+pub(super) const BATCH_FEE_BUMPED_ID: Uuid = uuid!("00000000-0000-0000-0000-000000000011");
+
+pub(super) const BATCH_CANCELLED_CODE: &str = "BATCH_CANCELLED";
+pub(super) const BATCH_CANCELLED_ID: Uuid = uuid!("00000000-0000-0000-0000-000000000014");
+
+pub(super) const UTXO_FROZEN_CODE: &str = "UTXO_FROZEN";
+pub(super) const UTXO_FROZEN_ID: Uuid = uuid!("00000000-0000-0000-0000-000000000013");
+
+pub(super) const UTXO_UNFROZEN_CODE: &str = "UTXO_UNFROZEN";
+pub(super) const UTXO_UNFROZEN_ID: Uuid = uuid!("00000000-0000-0000-0000-000000000012");
+
 // Onchain/Omnibus Ledger Accounts
 pub(super) const ONCHAIN_UTXO_INCOMING_CODE: &str = "ONCHAIN_UTXO_INCOMING";
 pub(super) const ONCHAIN_UTXO_INCOMING_ID: Uuid = uuid!("00000000-1910-0000-1000-000000000000");
@@ -105,6 +117,7 @@ pub enum Category {
     Effective,
     Fee,
     Dust,
+    Frozen,
 }
 
 impl Category {
@@ -114,6 +127,7 @@ impl Category {
             Category::Effective => "2000",
             Category::Fee => "3000",
             Category::Dust => "0000",
+            Category::Frozen => "4000",
         }
     }
 }
@@ -25,6 +25,7 @@ pub enum JournalEventMetadata {
     PayoutCancelled(PayoutCancelledMeta),
     BatchCreated(BatchCreatedMeta),
     BatchBroadcast(BatchBroadcastMeta),
+    BatchFeeBumped(BatchFeeBumpedMeta),
     UnknownTransaction(Option<serde_json::Value>),
 }
 
@@ -78,6 +79,10 @@ impl TryFrom<SqlxLedgerEvent> for MaybeIgnored {
                         tx.metadata::<BatchBroadcastMeta>()?
                             .ok_or(LedgerError::MissingTxMetadata)?,
                     ),
+                    BATCH_FEE_BUMPED_ID => JournalEventMetadata::BatchFeeBumped(
+                        tx.metadata::<BatchFeeBumpedMeta>()?
+                            .ok_or(LedgerError::MissingTxMetadata)?,
+                    ),
                     _ => JournalEventMetadata::UnknownTransaction(tx.metadata_json),
                 },
             ),
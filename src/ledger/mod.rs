@@ -59,6 +59,10 @@ impl Ledger {
             templates::fix::legacy_batch_created(&inner).await?;
         }
         templates::BatchBroadcast::init(&inner).await?;
+        templates::BatchCancelled::init(&inner).await?;
+        templates::BatchFeeBumped::init(&inner).await?;
+        templates::UtxoFrozen::init(&inner).await?;
+        templates::UtxoUnfrozen::init(&inner).await?;
 
         Ok(Self {
             inner,
@@ -235,6 +239,32 @@ impl Ledger {
         Ok(())
     }
 
+    #[instrument(name = "ledger.utxo_frozen", skip(self, tx))]
+    pub async fn utxo_frozen(
+        &self,
+        tx: Transaction<'_, Postgres>,
+        tx_id: LedgerTransactionId,
+        params: UtxoFrozenParams,
+    ) -> Result<(), LedgerError> {
+        self.inner
+            .post_transaction_in_tx(tx, tx_id, UTXO_FROZEN_CODE, Some(params))
+            .await?;
+        Ok(())
+    }
+
+    #[instrument(name = "ledger.utxo_unfrozen", skip(self, tx))]
+    pub async fn utxo_unfrozen(
+        &self,
+        tx: Transaction<'_, Postgres>,
+        tx_id: LedgerTransactionId,
+        params: UtxoUnfrozenParams,
+    ) -> Result<(), LedgerError> {
+        self.inner
+            .post_transaction_in_tx(tx, tx_id, UTXO_UNFROZEN_CODE, Some(params))
+            .await?;
+        Ok(())
+    }
+
     #[instrument(name = "ledger.payout_submitted", skip(self, tx))]
     pub async fn payout_submitted(
         &self,
@@ -319,6 +349,48 @@ impl Ledger {
         Ok(())
     }
 
+    #[instrument(name = "ledger.batch_cancelled", skip(self, tx))]
+    pub async fn batch_cancelled(
+        &self,
+        tx: Transaction<'_, Postgres>,
+        tx_id: LedgerTransactionId,
+        batch_created_tx_id: LedgerTransactionId,
+        ledger_account_ids: WalletLedgerAccountIds,
+    ) -> Result<(), LedgerError> {
+        let txs = self
+            .inner
+            .transactions()
+            .list_by_ids(std::iter::once(batch_created_tx_id))
+            .await?;
+        let txn = txs.first().ok_or(LedgerError::TransactionNotFound)?;
+        let meta: BatchCreatedMeta = txn.metadata()?.ok_or(LedgerError::MissingTxMetadata)?;
+        let entries = self
+            .inner
+            .entries()
+            .list_by_transaction_ids(std::iter::once(batch_created_tx_id))
+            .await?;
+        let encumbered_fees = entries
+            .into_values()
+            .flatten()
+            .find_map(|entry| match entry.entry_type.as_str() {
+                "BATCH_CREATED_FR_ENC_CR" => Some(Satoshis::from_btc(entry.units)),
+                _ => None,
+            })
+            .ok_or(LedgerError::ExpectedEntryNotFoundInTx(
+                "Encumbered fees not found",
+            ))?;
+        let params = BatchCreatedParams {
+            journal_id: txn.journal_id,
+            ledger_account_ids,
+            encumbered_fees,
+            meta,
+        };
+        self.inner
+            .post_transaction_in_tx(tx, tx_id, BATCH_CANCELLED_CODE, Some(params))
+            .await?;
+        Ok(())
+    }
+
     #[instrument(name = "ledger.batch_broadcast", skip(self, tx))]
     pub async fn batch_broadcast(
         &self,
@@ -359,6 +431,83 @@ impl Ledger {
         Ok(())
     }
 
+    #[instrument(name = "ledger.batch_fee_bumped", skip(self, tx))]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn batch_fee_bumped(
+        &self,
+        tx: Transaction<'_, Postgres>,
+        tx_id: LedgerTransactionId,
+        replaced_tx_id: LedgerTransactionId,
+        ledger_account_ids: WalletLedgerAccountIds,
+        bitcoin_tx_id: bitcoin::Txid,
+        fee_sats: Satoshis,
+        change_sats: Satoshis,
+    ) -> Result<(), LedgerError> {
+        #[derive(serde::Deserialize)]
+        struct ExtractBatchTxSummary {
+            batch_info: BatchWalletInfo,
+            #[serde(default)]
+            encumbered_spending_fees: EncumberedSpendingFees,
+            tx_summary: WalletTransactionSummary,
+            #[serde(default)]
+            withdraw_from_effective_when_settled: HashMap<bitcoin::OutPoint, Satoshis>,
+        }
+        let txs = self
+            .inner
+            .transactions()
+            .list_by_ids(std::iter::once(replaced_tx_id))
+            .await?;
+        let txn = txs.first().ok_or(LedgerError::TransactionNotFound)?;
+        if let Some(ExtractBatchTxSummary {
+            batch_info,
+            encumbered_spending_fees,
+            mut tx_summary,
+            withdraw_from_effective_when_settled,
+        }) = txn.metadata()?
+        {
+            let replaced_bitcoin_tx_id = tx_summary.bitcoin_tx_id;
+            let (additional_fee_sats, reverted) = if fee_sats >= tx_summary.fee_sats {
+                (fee_sats - tx_summary.fee_sats, false)
+            } else {
+                (tx_summary.fee_sats - fee_sats, true)
+            };
+            tx_summary.bitcoin_tx_id = bitcoin_tx_id;
+            tx_summary.fee_sats = fee_sats;
+            for change in tx_summary.change_utxos.iter_mut() {
+                change.outpoint.txid = bitcoin_tx_id;
+                change.satoshis = change_sats;
+            }
+            let params = BatchFeeBumpedParams {
+                journal_id: txn.journal_id,
+                ledger_account_ids,
+                meta: BatchFeeBumpedMeta {
+                    batch_info,
+                    encumbered_spending_fees: encumbered_spending_fees
+                        .into_iter()
+                        .map(|(outpoint, fees)| {
+                            (
+                                bitcoin::OutPoint {
+                                    txid: bitcoin_tx_id,
+                                    vout: outpoint.vout,
+                                },
+                                fees,
+                            )
+                        })
+                        .collect(),
+                    tx_summary,
+                    withdraw_from_effective_when_settled,
+                    replaced_bitcoin_tx_id,
+                    additional_fee_sats,
+                    reverted,
+                },
+            };
+            self.inner
+                .post_transaction_in_tx(tx, tx_id, BATCH_FEE_BUMPED_CODE, Some(params))
+                .await?;
+        }
+        Ok(())
+    }
+
     #[instrument(name = "ledger.spend_detected", skip(self, tx))]
     pub async fn spend_detected(
         &self,
@@ -462,6 +611,7 @@ impl Ledger {
             effective_outgoing_id,
             fee_id,
             dust_id,
+            frozen_id,
         }: WalletLedgerAccountIds,
     ) -> Result<WalletLedgerAccountBalances, LedgerError> {
         let mut balances = self
@@ -478,6 +628,7 @@ impl Ledger {
                     effective_outgoing_id,
                     fee_id,
                     dust_id,
+                    frozen_id,
                 ],
             )
             .await?;
@@ -502,6 +653,9 @@ impl Ledger {
                 .and_then(|b| b.remove(&self.btc)),
             fee: balances.get_mut(&fee_id).and_then(|b| b.remove(&self.btc)),
             dust: balances.get_mut(&dust_id).and_then(|b| b.remove(&self.btc)),
+            frozen: balances
+                .get_mut(&frozen_id)
+                .and_then(|b| b.remove(&self.btc)),
         })
     }
 
@@ -657,6 +811,16 @@ impl Ledger {
                     DebitOrCredit::Credit,
                 )
                 .await?,
+            frozen_id: self
+                .create_account_for_wallet(
+                    tx,
+                    &prefix,
+                    wallet_ledger_account_ids.frozen_id,
+                    format!("WALLET_{prefix}_FROZEN"),
+                    format!("{prefix}-frozen"),
+                    DebitOrCredit::Credit,
+                )
+                .await?,
         };
         Ok(account_ids)
     }
@@ -0,0 +1,187 @@
+use sqlx_ledger::{tx_template::*, SqlxLedger, SqlxLedgerError};
+use tracing::instrument;
+
+use super::BatchCreatedParams;
+use crate::ledger::{constants::*, error::LedgerError};
+
+/// Reverses BATCH_CREATED for a batch that was cancelled before being signed.
+/// Takes the same params as the BATCH_CREATED transaction it undoes.
+pub struct BatchCancelled {}
+
+impl BatchCancelled {
+    #[instrument(name = "ledger.batch_cancelled.init", skip_all)]
+    pub async fn init(ledger: &SqlxLedger) -> Result<(), LedgerError> {
+        let tx_input = TxInput::builder()
+            .journal_id("params.journal_id")
+            .effective("params.effective")
+            .correlation_id("params.correlation_id")
+            .metadata("params.meta")
+            .description("'Cancel Batch'")
+            .build()
+            .expect("Couldn't build TxInput");
+        let entries = vec![
+            // EFFECTIVE
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_LOG_OUT_ENC_CR'")
+                .currency("'BTC'")
+                .account_id("params.effective_outgoing_account_id")
+                .direction("CREDIT")
+                .layer("ENCUMBERED")
+                .units("params.total_utxo_in - params.change - params.fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_LOG_OUT_ENC_DR'")
+                .currency("'BTC'")
+                .account_id(format!("uuid('{EFFECTIVE_OUTGOING_ID}')"))
+                .direction("DEBIT")
+                .layer("ENCUMBERED")
+                .units("params.total_utxo_in - params.change - params.fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_LOG_OUT_PEN_DR'")
+                .currency("'BTC'")
+                .account_id("params.effective_outgoing_account_id")
+                .direction("DEBIT")
+                .layer("PENDING")
+                .units("params.total_utxo_in - params.change - params.fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_LOG_OUT_PEN_CR'")
+                .currency("'BTC'")
+                .account_id(format!("uuid('{EFFECTIVE_OUTGOING_ID}')"))
+                .direction("CREDIT")
+                .layer("PENDING")
+                .units("params.total_utxo_in - params.change - params.fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_LOG_SET_CR'")
+                .currency("'BTC'")
+                .account_id("params.effective_at_rest_account_id")
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.total_utxo_in - params.change")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_LOG_SET_DR'")
+                .currency("'BTC'")
+                .account_id(format!("uuid('{EFFECTIVE_AT_REST_ID}')"))
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.total_utxo_in - params.change")
+                .build()
+                .expect("Couldn't build entry"),
+            // FEES
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_FEE_PEN_CR'")
+                .currency("'BTC'")
+                .account_id("params.onchain_fee_account_id")
+                .direction("CREDIT")
+                .layer("PENDING")
+                .units("params.fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_FEE_PEN_DR'")
+                .currency("'BTC'")
+                .account_id(format!("uuid('{ONCHAIN_FEE_ID}')"))
+                .direction("DEBIT")
+                .layer("PENDING")
+                .units("params.fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_FR_ENC_DR'")
+                .currency("'BTC'")
+                .account_id("params.onchain_fee_account_id")
+                .direction("DEBIT")
+                .layer("ENCUMBERED")
+                .units("params.encumbered_fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_FR_ENC_CR'")
+                .currency("'BTC'")
+                .account_id(format!("uuid('{ONCHAIN_FEE_ID}')"))
+                .direction("CREDIT")
+                .layer("ENCUMBERED")
+                .units("params.encumbered_fees")
+                .build()
+                .expect("Couldn't build entry"),
+            // UTXO
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_UTX_OUT_PEN_CR'")
+                .currency("'BTC'")
+                .account_id(format!("uuid('{ONCHAIN_UTXO_OUTGOING_ID}')"))
+                .direction("CREDIT")
+                .layer("PENDING")
+                .units("params.total_utxo_in - params.fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_UTX_OUT_PEN_DR'")
+                .currency("'BTC'")
+                .account_id("params.onchain_outgoing_account_id")
+                .direction("DEBIT")
+                .layer("PENDING")
+                .units("params.total_utxo_in - params.fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_UTX_SET_CR'")
+                .currency("'BTC'")
+                .account_id("params.onchain_at_rest_account_id")
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.total_utxo_settled_in")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_UTX_SET_DR'")
+                .currency("'BTC'")
+                .account_id(format!("uuid('{ONCHAIN_UTXO_AT_REST_ID}')"))
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.total_utxo_settled_in")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_CHG_ENC_CR'")
+                .currency("'BTC'")
+                .account_id(format!("uuid('{ONCHAIN_UTXO_INCOMING_ID}')"))
+                .direction("CREDIT")
+                .layer("ENCUMBERED")
+                .units("params.change")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_CANCELLED_CHG_ENC_DR'")
+                .currency("'BTC'")
+                .account_id("params.onchain_income_account_id")
+                .direction("DEBIT")
+                .layer("ENCUMBERED")
+                .units("params.change")
+                .build()
+                .expect("Couldn't build entry"),
+        ];
+
+        let params = BatchCreatedParams::defs();
+        let template = NewTxTemplate::builder()
+            .id(BATCH_CANCELLED_ID)
+            .code(BATCH_CANCELLED_CODE)
+            .tx_input(tx_input)
+            .entries(entries)
+            .params(params)
+            .build()
+            .expect("Couldn't build BATCH_CANCELLED_CODE");
+        match ledger.tx_templates().create(template).await {
+            Err(SqlxLedgerError::DuplicateKey(_)) => Ok(()),
+            Err(e) => Err(e.into()),
+            Ok(_) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx_ledger::{tx_template::*, JournalId, SqlxLedger, SqlxLedgerError};
+use tracing::instrument;
+use uuid::Uuid;
+
+use std::collections::HashMap;
+
+use super::shared_meta::*;
+use crate::{
+    ledger::{constants::*, error::LedgerError, WalletLedgerAccountIds},
+    primitives::*,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFeeBumpedMeta {
+    pub batch_info: BatchWalletInfo,
+    pub encumbered_spending_fees: EncumberedSpendingFees,
+    pub tx_summary: WalletTransactionSummary,
+    pub withdraw_from_effective_when_settled: HashMap<bitcoin::OutPoint, Satoshis>,
+    pub replaced_bitcoin_tx_id: bitcoin::Txid,
+    pub additional_fee_sats: Satoshis,
+    pub reverted: bool,
+}
+
+#[derive(Debug)]
+pub struct BatchFeeBumpedParams {
+    pub journal_id: JournalId,
+    pub ledger_account_ids: WalletLedgerAccountIds,
+    pub meta: BatchFeeBumpedMeta,
+}
+
+impl BatchFeeBumpedParams {
+    pub fn defs() -> Vec<ParamDefinition> {
+        vec![
+            ParamDefinition::builder()
+                .name("journal_id")
+                .r#type(ParamDataType::UUID)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("onchain_fee_account_id")
+                .r#type(ParamDataType::UUID)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("effective_at_rest_account_id")
+                .r#type(ParamDataType::UUID)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("onchain_outgoing_account_id")
+                .r#type(ParamDataType::UUID)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("onchain_income_account_id")
+                .r#type(ParamDataType::UUID)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("additional_fees")
+                .r#type(ParamDataType::DECIMAL)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("reverted")
+                .r#type(ParamDataType::BOOLEAN)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("correlation_id")
+                .r#type(ParamDataType::UUID)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("meta")
+                .r#type(ParamDataType::JSON)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("effective")
+                .r#type(ParamDataType::DATE)
+                .build()
+                .unwrap(),
+        ]
+    }
+}
+
+impl From<BatchFeeBumpedParams> for TxParams {
+    fn from(
+        BatchFeeBumpedParams {
+            journal_id,
+            ledger_account_ids,
+            meta,
+        }: BatchFeeBumpedParams,
+    ) -> Self {
+        let effective = Utc::now().date_naive();
+        let additional_fees = meta.additional_fee_sats.to_btc();
+        let reverted = meta.reverted;
+        let batch_id = Uuid::from(meta.batch_info.batch_id);
+        let meta = serde_json::to_value(meta).expect("Couldn't serialize meta");
+        let mut params = Self::default();
+        params.insert("journal_id", journal_id);
+        params.insert("onchain_fee_account_id", ledger_account_ids.fee_id);
+        params.insert(
+            "effective_at_rest_account_id",
+            ledger_account_ids.effective_at_rest_id,
+        );
+        params.insert(
+            "onchain_outgoing_account_id",
+            ledger_account_ids.onchain_outgoing_id,
+        );
+        params.insert(
+            "onchain_income_account_id",
+            ledger_account_ids.onchain_incoming_id,
+        );
+        params.insert("additional_fees", additional_fees);
+        params.insert("reverted", reverted);
+        params.insert("correlation_id", batch_id);
+        params.insert("meta", meta);
+        params.insert("effective", effective);
+        params
+    }
+}
+
+pub struct BatchFeeBumped {}
+
+impl BatchFeeBumped {
+    #[instrument(name = "ledger.batch_fee_bumped.init", skip_all)]
+    pub async fn init(ledger: &SqlxLedger) -> Result<(), LedgerError> {
+        let tx_input = TxInput::builder()
+            .journal_id("params.journal_id")
+            .effective("params.effective")
+            .correlation_id("params.correlation_id")
+            .metadata("params.meta")
+            .description("'Bump Batch fee'")
+            .build()
+            .expect("Couldn't build TxInput");
+        let entries = vec![
+            // EFFECTIVE
+            EntryInput::builder()
+                .entry_type("'BATCH_FEE_BUMPED_LOG_SET_DR'")
+                .currency("'BTC'")
+                .account_id(format!(
+                    "params.reverted ? uuid('{EFFECTIVE_AT_REST_ID}') : params.effective_at_rest_account_id"
+                ))
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.additional_fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_FEE_BUMPED_LOG_SET_CR'")
+                .currency("'BTC'")
+                .account_id(format!(
+                    "params.reverted ? params.effective_at_rest_account_id : uuid('{EFFECTIVE_AT_REST_ID}')"
+                ))
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.additional_fees")
+                .build()
+                .expect("Couldn't build entry"),
+            // FEES
+            EntryInput::builder()
+                .entry_type("'BATCH_FEE_BUMPED_FEE_PEN_DR'")
+                .currency("'BTC'")
+                .account_id(format!(
+                    "params.reverted ? uuid('{ONCHAIN_FEE_ID}') : params.onchain_fee_account_id"
+                ))
+                .direction("DEBIT")
+                .layer("PENDING")
+                .units("params.additional_fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_FEE_BUMPED_FEE_PEN_CR'")
+                .currency("'BTC'")
+                .account_id(format!(
+                    "params.reverted ? params.onchain_fee_account_id : uuid('{ONCHAIN_FEE_ID}')"
+                ))
+                .direction("CREDIT")
+                .layer("PENDING")
+                .units("params.additional_fees")
+                .build()
+                .expect("Couldn't build entry"),
+            // UTXO
+            EntryInput::builder()
+                .entry_type("'BATCH_FEE_BUMPED_UTX_OUT_PEN_DR'")
+                .currency("'BTC'")
+                .account_id(format!(
+                    "params.reverted ? uuid('{ONCHAIN_UTXO_OUTGOING_ID}') : params.onchain_outgoing_account_id"
+                ))
+                .direction("DEBIT")
+                .layer("PENDING")
+                .units("params.additional_fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_FEE_BUMPED_UTX_OUT_PEN_CR'")
+                .currency("'BTC'")
+                .account_id(format!(
+                    "params.reverted ? params.onchain_outgoing_account_id : uuid('{ONCHAIN_UTXO_OUTGOING_ID}')"
+                ))
+                .direction("CREDIT")
+                .layer("PENDING")
+                .units("params.additional_fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_FEE_BUMPED_CHG_PEN_DR'")
+                .currency("'BTC'")
+                .account_id(format!(
+                    "params.reverted ? uuid('{ONCHAIN_UTXO_INCOMING_ID}') : params.onchain_income_account_id"
+                ))
+                .direction("DEBIT")
+                .layer("PENDING")
+                .units("params.additional_fees")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'BATCH_FEE_BUMPED_CHG_PEN_CR'")
+                .currency("'BTC'")
+                .account_id(format!(
+                    "params.reverted ? params.onchain_income_account_id : uuid('{ONCHAIN_UTXO_INCOMING_ID}')"
+                ))
+                .direction("CREDIT")
+                .layer("PENDING")
+                .units("params.additional_fees")
+                .build()
+                .expect("Couldn't build entry"),
+        ];
+
+        let params = BatchFeeBumpedParams::defs();
+        let template = NewTxTemplate::builder()
+            .id(BATCH_FEE_BUMPED_ID)
+            .code(BATCH_FEE_BUMPED_CODE)
+            .tx_input(tx_input)
+            .entries(entries)
+            .params(params)
+            .build()
+            .expect("Couldn't build template");
+        match ledger.tx_templates().create(template).await {
+            Err(SqlxLedgerError::DuplicateKey(_)) => Ok(()),
+            Err(e) => Err(e.into()),
+            Ok(_) => Ok(()),
+        }
+    }
+}
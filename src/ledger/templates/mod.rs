@@ -1,5 +1,7 @@
 mod batch_broadcast;
+mod batch_cancelled;
 mod batch_created;
+mod batch_fee_bumped;
 mod payout_cancelled;
 mod payout_submitted;
 mod shared_meta;
@@ -8,10 +10,14 @@ mod spend_settled;
 mod spent_utxo_settled;
 mod utxo_detected;
 mod utxo_dropped;
+mod utxo_frozen;
 mod utxo_settled;
+mod utxo_unfrozen;
 
 pub use batch_broadcast::*;
+pub use batch_cancelled::*;
 pub use batch_created::*;
+pub use batch_fee_bumped::*;
 pub use payout_cancelled::*;
 pub use payout_submitted::*;
 pub use shared_meta::*;
@@ -20,6 +26,8 @@ pub use spend_settled::*;
 pub use spent_utxo_settled::*;
 pub use utxo_detected::*;
 pub use utxo_dropped::*;
+pub use utxo_frozen::*;
 pub use utxo_settled::*;
+pub use utxo_unfrozen::*;
 
 pub mod fix;
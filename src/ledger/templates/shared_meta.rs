@@ -28,7 +28,7 @@ pub struct ChangeOutput {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchWalletInfo {
     pub account_id: AccountId,
-    pub payout_queue_id: PayoutQueueId,
+    pub payout_queue_id: Option<PayoutQueueId>,
     pub batch_id: BatchId,
     pub wallet_id: WalletId,
     pub included_payouts: Vec<PayoutInfo>,
@@ -0,0 +1,148 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx_ledger::{tx_template::*, JournalId, SqlxLedger, SqlxLedgerError};
+use tracing::instrument;
+
+use crate::{
+    ledger::{constants::*, error::LedgerError, WalletLedgerAccountIds},
+    primitives::*,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UtxoFrozenMeta {
+    pub account_id: AccountId,
+    pub wallet_id: WalletId,
+    pub keychain_id: KeychainId,
+    pub outpoint: bitcoin::OutPoint,
+    pub satoshis: Satoshis,
+    pub address: bitcoin::Address,
+    pub settled: bool,
+    pub frozen_by: ProfileId,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct UtxoFrozenParams {
+    pub journal_id: JournalId,
+    pub ledger_account_ids: WalletLedgerAccountIds,
+    pub meta: UtxoFrozenMeta,
+}
+
+impl UtxoFrozenParams {
+    pub fn defs() -> Vec<ParamDefinition> {
+        vec![
+            ParamDefinition::builder()
+                .name("journal_id")
+                .r#type(ParamDataType::UUID)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("frozen_account_id")
+                .r#type(ParamDataType::UUID)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("effective_account_id")
+                .r#type(ParamDataType::UUID)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("layer")
+                .r#type(ParamDataType::STRING)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("amount")
+                .r#type(ParamDataType::DECIMAL)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("meta")
+                .r#type(ParamDataType::JSON)
+                .build()
+                .unwrap(),
+            ParamDefinition::builder()
+                .name("effective")
+                .r#type(ParamDataType::DATE)
+                .build()
+                .unwrap(),
+        ]
+    }
+}
+
+impl From<UtxoFrozenParams> for TxParams {
+    fn from(
+        UtxoFrozenParams {
+            journal_id,
+            ledger_account_ids: accounts,
+            meta,
+        }: UtxoFrozenParams,
+    ) -> Self {
+        let amount = meta.satoshis.to_btc();
+        let (effective_account_id, layer) = if meta.settled {
+            (accounts.effective_at_rest_id, "SETTLED")
+        } else {
+            (accounts.effective_incoming_id, "PENDING")
+        };
+        let meta = serde_json::to_value(meta).expect("Couldn't serialize meta");
+        let mut params = Self::default();
+        params.insert("journal_id", journal_id);
+        params.insert("frozen_account_id", accounts.frozen_id);
+        params.insert("effective_account_id", effective_account_id);
+        params.insert("layer", layer);
+        params.insert("amount", amount);
+        params.insert("meta", meta);
+        params.insert("effective", Utc::now().date_naive());
+        params
+    }
+}
+
+pub struct UtxoFrozen {}
+
+impl UtxoFrozen {
+    #[instrument(name = "ledger.utxo_frozen.init", skip_all)]
+    pub async fn init(ledger: &SqlxLedger) -> Result<(), LedgerError> {
+        let tx_input = TxInput::builder()
+            .journal_id("params.journal_id")
+            .effective("params.effective")
+            .metadata("params.meta")
+            .description("'Utxo moved to the frozen balance'")
+            .build()
+            .expect("Couldn't build TxInput");
+        let entries = vec![
+            EntryInput::builder()
+                .entry_type("'UTXO_FROZEN_LOG_DR'")
+                .currency("'BTC'")
+                .account_id("params.effective_account_id")
+                .direction("DEBIT")
+                .layer("params.layer")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+            EntryInput::builder()
+                .entry_type("'UTXO_FROZEN_FRZ_CR'")
+                .currency("'BTC'")
+                .account_id("params.frozen_account_id")
+                .direction("CREDIT")
+                .layer("params.layer")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+        ];
+
+        let params = UtxoFrozenParams::defs();
+        let template = NewTxTemplate::builder()
+            .id(UTXO_FROZEN_ID)
+            .code(UTXO_FROZEN_CODE)
+            .tx_input(tx_input)
+            .entries(entries)
+            .params(params)
+            .build()
+            .expect("Couldn't build UTXO_FROZEN_CODE");
+        match ledger.tx_templates().create(template).await {
+            Err(SqlxLedgerError::DuplicateKey(_)) => Ok(()),
+            Err(e) => Err(e.into()),
+            Ok(_) => Ok(()),
+        }
+    }
+}
@@ -14,6 +14,7 @@ pub struct WalletLedgerAccountBalances {
     pub effective_outgoing: Option<AccountBalance>,
     pub fee: Option<AccountBalance>,
     pub dust: Option<AccountBalance>,
+    pub frozen: Option<AccountBalance>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -26,6 +27,7 @@ pub struct WalletLedgerAccountIds {
     pub effective_outgoing_id: LedgerAccountId,
     pub fee_id: LedgerAccountId,
     pub dust_id: LedgerAccountId,
+    pub frozen_id: LedgerAccountId,
 }
 
 impl WalletLedgerAccountIds {
@@ -34,6 +36,35 @@ impl WalletLedgerAccountIds {
         let (_, suffix) = uuid_string.split_at(24);
         suffix.to_owned()
     }
+
+    /// Account ids to use for income that should not count towards the effective balance
+    pub fn for_dust_income(&self) -> Self {
+        Self {
+            effective_incoming_id: self.dust_id,
+            effective_at_rest_id: self.dust_id,
+            ..*self
+        }
+    }
+
+    /// Account ids to use for income that is held back until it gets released
+    pub fn for_frozen_income(&self) -> Self {
+        Self {
+            effective_incoming_id: self.frozen_id,
+            effective_at_rest_id: self.frozen_id,
+            ..*self
+        }
+    }
+
+    /// Account ids to use for income depending on whether it is dust or held back
+    pub fn for_income(&self, dust: bool, held: bool) -> Self {
+        if dust {
+            self.for_dust_income()
+        } else if held {
+            self.for_frozen_income()
+        } else {
+            *self
+        }
+    }
 }
 
 fn derive_wallet_ledger_account_code(
@@ -148,6 +179,17 @@ impl From<WalletId> for WalletLedgerAccountIds {
         )
         .expect("Invalid Wallet_Id");
 
+        let frozen_id = Uuid::parse_str(
+            derive_wallet_ledger_account_code(
+                Element::Liability,
+                SubGroup::AtRest,
+                Category::Frozen,
+                suffix,
+            )
+            .as_str(),
+        )
+        .expect("Invalid Wallet_Id");
+
         Self {
             onchain_incoming_id: LedgerAccountId::from(onchain_incoming_id),
             onchain_at_rest_id: LedgerAccountId::from(onchain_at_rest_id),
@@ -157,6 +199,7 @@ impl From<WalletId> for WalletLedgerAccountIds {
             effective_outgoing_id: LedgerAccountId::from(effective_outgoing_id),
             fee_id: LedgerAccountId::from(fee_id),
             dust_id: LedgerAccountId::from(dust_id),
+            frozen_id: LedgerAccountId::from(frozen_id),
         }
     }
 }
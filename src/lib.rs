@@ -6,6 +6,7 @@ pub mod address;
 pub mod admin;
 mod api;
 pub mod app;
+pub mod audit;
 pub mod batch;
 pub mod bdk;
 pub mod cli;
@@ -20,6 +21,7 @@ pub mod payout;
 pub mod payout_queue;
 pub mod primitives;
 pub mod profile;
+pub mod security;
 pub mod signing_session;
 mod tracing;
 pub mod utxo;
@@ -69,6 +69,18 @@ impl Augmenter {
             }
             OutboxEventPayload::UtxoDropped {
                 address, wallet_id, ..
+            }
+            | OutboxEventPayload::UtxoQuarantined {
+                address, wallet_id, ..
+            }
+            | OutboxEventPayload::UtxoDustDetected {
+                address, wallet_id, ..
+            }
+            | OutboxEventPayload::UtxoFrozen {
+                address, wallet_id, ..
+            }
+            | OutboxEventPayload::UtxoUnfrozen {
+                address, wallet_id, ..
             } => {
                 let address_info = self
                     .addresses
@@ -88,13 +100,23 @@ impl Augmenter {
             | OutboxEventPayload::PayoutCancelled { id, .. }
             | OutboxEventPayload::PayoutCommitted { id, .. }
             | OutboxEventPayload::PayoutBroadcast { id, .. }
-            | OutboxEventPayload::PayoutSettled { id, .. } => {
+            | OutboxEventPayload::PayoutFeeBumped { id, .. }
+            | OutboxEventPayload::PayoutSettled { id, .. }
+            | OutboxEventPayload::PayoutSkipped { id, .. }
+            | OutboxEventPayload::PayoutApprovalRequested { id, .. }
+            | OutboxEventPayload::PayoutApproved { id, .. }
+            | OutboxEventPayload::PayoutRejected { id, .. } => {
                 let payout = self.payouts.find_by_id(account_id, id).await?;
                 Ok(Augmentation {
                     payout: Some(payout),
                     address: None,
                 })
             }
+            OutboxEventPayload::PayoutQueueFeeRateExceeded { .. }
+            | OutboxEventPayload::SigningSessionFailed { .. } => Ok(Augmentation {
+                payout: None,
+                address: None,
+            }),
         }
     }
 }
@@ -3,7 +3,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     fees,
-    ledger::{BatchBroadcastMeta, BatchCreatedMeta, JournalEventMetadata, SpendSettledMeta},
+    ledger::{
+        BatchBroadcastMeta, BatchCreatedMeta, BatchFeeBumpedMeta, JournalEventMetadata,
+        SpendSettledMeta,
+    },
     primitives::*,
 };
 
@@ -75,6 +78,42 @@ pub enum OutboxEventPayload {
         wallet_id: WalletId,
         keychain_id: KeychainId,
     },
+    UtxoQuarantined {
+        tx_id: bitcoin::Txid,
+        vout: u32,
+        satoshis: Satoshis,
+        address: bitcoin::Address,
+        wallet_id: WalletId,
+        keychain_id: KeychainId,
+        source_address: bitcoin::Address,
+    },
+    UtxoDustDetected {
+        tx_id: bitcoin::Txid,
+        vout: u32,
+        satoshis: Satoshis,
+        address: bitcoin::Address,
+        wallet_id: WalletId,
+        keychain_id: KeychainId,
+    },
+    UtxoFrozen {
+        tx_id: bitcoin::Txid,
+        vout: u32,
+        satoshis: Satoshis,
+        address: bitcoin::Address,
+        wallet_id: WalletId,
+        keychain_id: KeychainId,
+        profile_id: ProfileId,
+        reason: Option<String>,
+    },
+    UtxoUnfrozen {
+        tx_id: bitcoin::Txid,
+        vout: u32,
+        satoshis: Satoshis,
+        address: bitcoin::Address,
+        wallet_id: WalletId,
+        keychain_id: KeychainId,
+        profile_id: ProfileId,
+    },
     PayoutSubmitted {
         id: PayoutId,
         profile_id: ProfileId,
@@ -115,6 +154,19 @@ pub enum OutboxEventPayload {
         destination: PayoutDestination,
         proportional_fee: Satoshis,
     },
+    PayoutFeeBumped {
+        id: PayoutId,
+        vout: u32,
+        profile_id: ProfileId,
+        wallet_id: WalletId,
+        payout_queue_id: PayoutQueueId,
+        batch_id: BatchId,
+        tx_id: bitcoin::Txid,
+        replaced_tx_id: bitcoin::Txid,
+        satoshis: Satoshis,
+        destination: PayoutDestination,
+        proportional_fee: Satoshis,
+    },
     PayoutSettled {
         id: PayoutId,
         vout: u32,
@@ -127,6 +179,51 @@ pub enum OutboxEventPayload {
         destination: PayoutDestination,
         proportional_fee: Satoshis,
     },
+    PayoutQueueFeeRateExceeded {
+        payout_queue_id: PayoutQueueId,
+        fee_rate_sats_per_vbyte: f32,
+        max_fee_rate_sats_per_vbyte: u64,
+    },
+    PayoutSkipped {
+        id: PayoutId,
+        wallet_id: WalletId,
+        payout_queue_id: PayoutQueueId,
+        satoshis: Satoshis,
+        destination: PayoutDestination,
+        reason: PayoutSkipReason,
+    },
+    PayoutApprovalRequested {
+        id: PayoutId,
+        wallet_id: WalletId,
+        payout_queue_id: PayoutQueueId,
+        satoshis: Satoshis,
+        destination: PayoutDestination,
+        required_approvals: u32,
+    },
+    PayoutApproved {
+        id: PayoutId,
+        wallet_id: WalletId,
+        payout_queue_id: PayoutQueueId,
+        satoshis: Satoshis,
+        destination: PayoutDestination,
+        approved_by: ProfileId,
+        approvals: u32,
+        required_approvals: u32,
+    },
+    PayoutRejected {
+        id: PayoutId,
+        wallet_id: WalletId,
+        payout_queue_id: PayoutQueueId,
+        satoshis: Satoshis,
+        destination: PayoutDestination,
+        rejected_by: ProfileId,
+    },
+    SigningSessionFailed {
+        id: SigningSessionId,
+        batch_id: BatchId,
+        xpub_id: XPubId,
+        reason: String,
+    },
 }
 
 impl From<JournalEventMetadata> for Vec<OutboxEventPayload> {
@@ -191,7 +288,9 @@ impl From<JournalEventMetadata> for Vec<OutboxEventPayload> {
                         id: payout.id,
                         vout: payout.vout_in_tx,
                         wallet_id: batch_info.wallet_id,
-                        payout_queue_id: batch_info.payout_queue_id,
+                        payout_queue_id: batch_info
+                            .payout_queue_id
+                            .expect("payouts are always batched via a payout queue"),
                         batch_id: batch_info.batch_id,
                         profile_id: payout.profile_id,
                         tx_id: tx_summary.bitcoin_tx_id,
@@ -220,10 +319,45 @@ impl From<JournalEventMetadata> for Vec<OutboxEventPayload> {
                         id: payout.id,
                         vout: payout.vout_in_tx,
                         wallet_id: batch_info.wallet_id,
-                        payout_queue_id: batch_info.payout_queue_id,
+                        payout_queue_id: batch_info
+                            .payout_queue_id
+                            .expect("payouts are always batched via a payout queue"),
+                        batch_id: batch_info.batch_id,
+                        profile_id: payout.profile_id,
+                        tx_id: tx_summary.bitcoin_tx_id,
+                        satoshis: payout.satoshis,
+                        destination: payout.destination,
+                        proportional_fee: proportional_fees
+                            .remove(&payout.id)
+                            .expect("couldn't find proportional fee"),
+                    })
+                }
+            }
+            BatchFeeBumped(BatchFeeBumpedMeta {
+                batch_info,
+                tx_summary,
+                replaced_bitcoin_tx_id,
+                ..
+            }) => {
+                let mut proportional_fees = fees::allocate_proportional_fees(
+                    tx_summary.fee_sats,
+                    batch_info
+                        .included_payouts
+                        .iter()
+                        .map(|p| (p.id, p.satoshis)),
+                );
+                for payout in batch_info.included_payouts {
+                    res.push(OutboxEventPayload::PayoutFeeBumped {
+                        id: payout.id,
+                        vout: payout.vout_in_tx,
+                        wallet_id: batch_info.wallet_id,
+                        payout_queue_id: batch_info
+                            .payout_queue_id
+                            .expect("payouts are always batched via a payout queue"),
                         batch_id: batch_info.batch_id,
                         profile_id: payout.profile_id,
                         tx_id: tx_summary.bitcoin_tx_id,
+                        replaced_tx_id: replaced_bitcoin_tx_id,
                         satoshis: payout.satoshis,
                         destination: payout.destination,
                         proportional_fee: proportional_fees
@@ -249,7 +383,9 @@ impl From<JournalEventMetadata> for Vec<OutboxEventPayload> {
                         id: payout.id,
                         vout: payout.vout_in_tx,
                         wallet_id: batch_info.wallet_id,
-                        payout_queue_id: batch_info.payout_queue_id,
+                        payout_queue_id: batch_info
+                            .payout_queue_id
+                            .expect("payouts are always batched via a payout queue"),
                         batch_id: batch_info.batch_id,
                         profile_id: payout.profile_id,
                         tx_id: tx_summary.bitcoin_tx_id,
@@ -104,6 +104,37 @@ impl Outbox {
         Ok(())
     }
 
+    #[instrument("outbox.add_event", skip(self))]
+    pub async fn add_event(
+        &self,
+        account_id: AccountId,
+        payload: OutboxEventPayload,
+    ) -> Result<(), OutboxError> {
+        let sequences = self.sequences_for(account_id).await?;
+        let mut write_sequences = sequences.write().await;
+        let sequence = write_sequences.0.next();
+        let event = OutboxEvent::builder()
+            .account_id(account_id)
+            .sequence(sequence)
+            .payload(payload)
+            .recorded_at(chrono::Utc::now())
+            .build()
+            .expect("Could not build OutboxEvent");
+
+        if let Err(res) = self.repo.persist_events(std::slice::from_ref(&event)).await {
+            let mut write_seqs = self.sequences.write().await;
+            write_seqs.remove(&account_id);
+            return Err(res);
+        }
+        self.event_sender
+            .send(event)
+            .map_err(|_| OutboxError::SendEventError)?;
+
+        write_sequences.0 = sequence;
+
+        Ok(())
+    }
+
     pub async fn register_listener(
         &self,
         account_id: AccountId,
@@ -1,6 +1,7 @@
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
+use super::error::PayoutError;
 use crate::{entity::*, primitives::*};
 
 #[derive(Serialize, Deserialize)]
@@ -13,6 +14,10 @@ pub enum PayoutEvent {
         profile_id: ProfileId,
         destination: PayoutDestination,
         satoshis: Satoshis,
+        #[serde(default)]
+        priority: u32,
+        #[serde(default)]
+        execute_after: Option<chrono::DateTime<chrono::Utc>>,
     },
     ExternalIdUpdated {
         external_id: String,
@@ -24,9 +29,25 @@ pub enum PayoutEvent {
         batch_id: BatchId,
         outpoint: bitcoin::OutPoint,
     },
+    /// The batch was cancelled before being signed, the payout is queued again
+    RemovedFromBatch {
+        batch_id: BatchId,
+    },
     Cancelled {
         executed_by: ProfileId,
     },
+    ApprovalRequested {
+        required_approvals: u32,
+    },
+    Approved {
+        approved_by: ProfileId,
+    },
+    Rejected {
+        rejected_by: ProfileId,
+    },
+    Skipped {
+        reason: PayoutSkipReason,
+    },
 }
 
 #[derive(Builder)]
@@ -42,20 +63,103 @@ pub struct Payout {
     pub outpoint: Option<bitcoin::OutPoint>,
     pub satoshis: Satoshis,
     pub destination: PayoutDestination,
+    pub priority: u32,
+    #[builder(default)]
+    pub execute_after: Option<chrono::DateTime<chrono::Utc>>,
     pub external_id: String,
     #[builder(setter(into), default)]
     pub metadata: Option<serde_json::Value>,
+    #[builder(default)]
+    pub required_approvals: u32,
+    #[builder(default)]
+    pub approved_by: Vec<ProfileId>,
+    #[builder(default)]
+    pub rejected_by: Option<ProfileId>,
 
     pub(super) events: EntityEvents<PayoutEvent>,
 }
 
 impl Payout {
+    pub(super) fn remove_from_batch(&mut self) {
+        if let Some(batch_id) = self.batch_id.take() {
+            self.outpoint = None;
+            self.events.push(PayoutEvent::RemovedFromBatch { batch_id });
+        }
+    }
+
     pub fn cancel_payout(&mut self, profile_id: ProfileId) {
         self.events.push(PayoutEvent::Cancelled {
             executed_by: profile_id,
         })
     }
 
+    /// Scheduled payouts are left out of batches until `execute_after` has passed
+    pub fn is_scheduled(&self) -> bool {
+        self.execute_after
+            .map(|execute_after| execute_after > chrono::Utc::now())
+            .unwrap_or(false)
+    }
+
+    /// Payouts awaiting approval are left out of batches
+    pub fn is_awaiting_approval(&self) -> bool {
+        self.rejected_by.is_none() && (self.approved_by.len() as u32) < self.required_approvals
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.rejected_by.is_some()
+    }
+
+    /// Whether the payout may be picked up by the next batch
+    pub fn is_batchable(&self) -> bool {
+        self.batch_id.is_none()
+            && !self.is_cancelled()
+            && !self.is_rejected()
+            && !self.is_scheduled()
+            && !self.is_awaiting_approval()
+    }
+
+    /// Records the approval of `profile_id` and returns whether the payout is now fully approved.
+    /// `creator_chain` lists the profiles that (transitively) created the approver.
+    pub fn approve(
+        &mut self,
+        profile_id: ProfileId,
+        creator_chain: &[ProfileId],
+    ) -> Result<bool, PayoutError> {
+        self.check_awaiting_approval()?;
+        if profile_id == self.profile_id {
+            return Err(PayoutError::SubmitterCannotApprove);
+        }
+        if creator_chain.contains(&self.profile_id) {
+            return Err(PayoutError::ApproverCreatedBySubmitter);
+        }
+        if self.approved_by.contains(&profile_id) {
+            return Err(PayoutError::AlreadyApprovedByProfile(
+                profile_id.to_string(),
+            ));
+        }
+        self.approved_by.push(profile_id);
+        self.events.push(PayoutEvent::Approved {
+            approved_by: profile_id,
+        });
+        Ok(!self.is_awaiting_approval())
+    }
+
+    pub fn reject(&mut self, profile_id: ProfileId) -> Result<(), PayoutError> {
+        self.check_awaiting_approval()?;
+        self.rejected_by = Some(profile_id);
+        self.events.push(PayoutEvent::Rejected {
+            rejected_by: profile_id,
+        });
+        Ok(())
+    }
+
+    fn check_awaiting_approval(&self) -> Result<(), PayoutError> {
+        if self.is_cancelled() || !self.is_awaiting_approval() {
+            return Err(PayoutError::PayoutNotAwaitingApproval(self.id.to_string()));
+        }
+        Ok(())
+    }
+
     pub fn is_cancelled(&self) -> bool {
         for event in self.events.iter() {
             if let PayoutEvent::Cancelled { .. } = event {
@@ -79,10 +183,17 @@ pub struct NewPayout {
     pub(super) profile_id: ProfileId,
     pub(super) satoshis: Satoshis,
     pub(super) destination: PayoutDestination,
+    /// Payouts with a higher priority are batched first
+    #[builder(default)]
+    pub(super) priority: u32,
+    #[builder(default, setter(into))]
+    pub(super) execute_after: Option<chrono::DateTime<chrono::Utc>>,
     #[builder(setter(into))]
     pub(super) external_id: String,
     #[builder(default, setter(into))]
     pub(super) metadata: Option<serde_json::Value>,
+    #[builder(default, setter(into))]
+    pub(super) required_approvals: Option<u32>,
 }
 
 impl NewPayout {
@@ -102,6 +213,8 @@ impl NewPayout {
                 profile_id: self.profile_id,
                 destination: self.destination,
                 satoshis: self.satoshis,
+                priority: self.priority,
+                execute_after: self.execute_after,
             },
             PayoutEvent::ExternalIdUpdated {
                 external_id: self.external_id,
@@ -110,6 +223,9 @@ impl NewPayout {
         if let Some(metadata) = self.metadata {
             events.push(PayoutEvent::MetadataUpdated { metadata });
         }
+        if let Some(required_approvals) = self.required_approvals {
+            events.push(PayoutEvent::ApprovalRequested { required_approvals });
+        }
         events
     }
 }
@@ -119,6 +235,7 @@ impl TryFrom<EntityEvents<PayoutEvent>> for Payout {
 
     fn try_from(events: EntityEvents<PayoutEvent>) -> Result<Self, Self::Error> {
         let mut builder = PayoutBuilder::default();
+        let mut approved_by = Vec::new();
         for event in events.iter() {
             match event {
                 PayoutEvent::Initialized {
@@ -128,7 +245,8 @@ impl TryFrom<EntityEvents<PayoutEvent>> for Payout {
                     payout_queue_id,
                     destination,
                     satoshis,
-                    ..
+                    priority,
+                    execute_after,
                 } => {
                     builder = builder
                         .id(*id)
@@ -136,7 +254,9 @@ impl TryFrom<EntityEvents<PayoutEvent>> for Payout {
                         .profile_id(*profile_id)
                         .payout_queue_id(*payout_queue_id)
                         .destination(destination.clone())
-                        .satoshis(*satoshis);
+                        .satoshis(*satoshis)
+                        .priority(*priority)
+                        .execute_after(*execute_after);
                 }
 
                 PayoutEvent::ExternalIdUpdated { external_id } => {
@@ -148,9 +268,21 @@ impl TryFrom<EntityEvents<PayoutEvent>> for Payout {
                 PayoutEvent::CommittedToBatch { batch_id, outpoint } => {
                     builder = builder.batch_id(*batch_id).outpoint(*outpoint);
                 }
+                PayoutEvent::RemovedFromBatch { .. } => {
+                    builder = builder.batch_id(None).outpoint(None);
+                }
+                PayoutEvent::ApprovalRequested { required_approvals } => {
+                    builder = builder.required_approvals(*required_approvals);
+                }
+                PayoutEvent::Approved { approved_by: id } => {
+                    approved_by.push(*id);
+                }
+                PayoutEvent::Rejected { rejected_by } => {
+                    builder = builder.rejected_by(Some(*rejected_by));
+                }
                 _ => (),
             }
         }
-        builder.events(events).build()
+        builder.approved_by(approved_by).events(events).build()
     }
 }
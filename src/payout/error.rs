@@ -10,4 +10,12 @@ pub enum PayoutError {
     PayoutIdNotFound(String),
     #[error("PayoutError - External Id does not exists")]
     ExternalIdNotFound,
+    #[error("PayoutError - Payout {0} is not awaiting approval")]
+    PayoutNotAwaitingApproval(String),
+    #[error("PayoutError - Payout has already been approved by profile {0}")]
+    AlreadyApprovedByProfile(String),
+    #[error("PayoutError - A payout cannot be approved by the profile that submitted it")]
+    SubmitterCannotApprove,
+    #[error("PayoutError - A payout cannot be approved by a profile created by its submitter")]
+    ApproverCreatedBySubmitter,
 }
@@ -126,35 +126,59 @@ impl Payouts {
         for row in rows {
             let wallet_id = WalletId::from(row.wallet_id);
             let id = WalletId::from(row.id);
-            wallet_payouts.push((id, wallet_id));
+            wallet_payouts.push((id, wallet_id, row.created_at));
             let events = entity_events.entry(id).or_insert_with(EntityEvents::new);
             events.load_event(row.sequence as usize, row.event)?;
         }
         let mut payouts: HashMap<WalletId, Vec<UnbatchedPayout>> = HashMap::new();
-        for (id, wallet_id) in wallet_payouts {
+        for (id, wallet_id, created_at) in wallet_payouts {
             if let Some(events) = entity_events.remove(&id) {
-                payouts
-                    .entry(wallet_id)
-                    .or_default()
-                    .push(UnbatchedPayout::try_from(events)?);
+                let payout = Payout::try_from(events)?;
+                if !payout.is_batchable() {
+                    continue;
+                }
+                let mut unbatched = UnbatchedPayout::try_from(payout.events)?;
+                unbatched.created_at = created_at;
+                payouts.entry(wallet_id).or_default().push(unbatched);
             }
         }
-        let filtered_payouts: HashMap<WalletId, Vec<UnbatchedPayout>> = payouts
-            .into_iter()
-            .map(|(wallet_id, unbatched_payouts)| {
-                let filtered_unbatched_payouts = unbatched_payouts
-                    .into_iter()
-                    .filter(|payout| {
-                        !payout
-                            .events
-                            .iter()
-                            .any(|event| matches!(event, PayoutEvent::Cancelled { .. }))
-                    })
-                    .collect();
-                (wallet_id, filtered_unbatched_payouts)
-            })
-            .collect();
-        Ok(UnbatchedPayouts::new(filtered_payouts))
+        Ok(UnbatchedPayouts::new(payouts))
+    }
+
+    /// Locks the profile's submissions for the rest of the tx so concurrent
+    /// submissions can't both pass a limit check against the same total
+    #[instrument(name = "payouts.submitted_total_since_in_tx", skip(self, tx))]
+    pub async fn submitted_total_since_in_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account_id: AccountId,
+        profile_id: ProfileId,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Satoshis, PayoutError> {
+        sqlx::query!(
+            "SELECT pg_advisory_xact_lock(hashtextextended($1::TEXT, 0))",
+            profile_id as ProfileId,
+        )
+        .execute(&mut *tx)
+        .await?;
+        let row = sqlx::query!(
+            r#"
+              SELECT COALESCE(SUM((e.event->>'satoshis')::NUMERIC), 0) AS "total!"
+              FROM bria_payouts b
+              JOIN bria_payout_events e ON b.id = e.id
+              WHERE b.account_id = $1 AND b.profile_id = $2 AND b.created_at > $3
+                AND e.event_type = 'initialized'
+                AND NOT EXISTS (
+                  SELECT 1 FROM bria_payout_events c
+                  WHERE c.id = b.id AND c.event_type IN ('cancelled', 'rejected')
+                )"#,
+            account_id as AccountId,
+            profile_id as ProfileId,
+            since,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        Ok(Satoshis::from(row.total))
     }
 
     #[instrument(name = "payouts.list_for_wallet", skip(self))]
@@ -228,11 +252,50 @@ impl Payouts {
         Ok(payouts)
     }
 
+    /// Returns the payouts of a cancelled batch to their queue
+    #[instrument(name = "payouts.remove_from_batch", skip(self, tx))]
+    pub async fn remove_from_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account_id: AccountId,
+        batch_id: BatchId,
+    ) -> Result<(), PayoutError> {
+        let mut payouts: Vec<_> = self
+            .list_for_batch(account_id, batch_id)
+            .await?
+            .into_values()
+            .flatten()
+            .collect();
+        if payouts.is_empty() {
+            return Ok(());
+        }
+        for payout in payouts.iter_mut() {
+            payout.remove_from_batch();
+        }
+        EntityEvents::<PayoutEvent>::persist(
+            "bria_payout_events",
+            tx,
+            payouts
+                .into_iter()
+                .flat_map(|p| p.events.into_new_serialized_events(p.id)),
+        )
+        .await?;
+        sqlx::query!(
+            r#"UPDATE bria_payouts SET batch_id = NULL WHERE account_id = $1 AND batch_id = $2"#,
+            account_id as AccountId,
+            batch_id as BatchId,
+        )
+        .execute(&mut *tx)
+        .await?;
+        Ok(())
+    }
+
     pub async fn update_unbatched(
         &self,
         tx: &mut Transaction<'_, Postgres>,
-        payouts: UnbatchedPayouts,
+        mut payouts: UnbatchedPayouts,
     ) -> Result<(), PayoutError> {
+        self.update_skipped(tx, payouts.take_skipped()).await?;
         if payouts.batch_id.is_none() || payouts.batched.is_empty() {
             return Ok(());
         }
@@ -256,6 +319,27 @@ impl Payouts {
         Ok(())
     }
 
+    pub async fn update_skipped(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        payouts: Vec<UnbatchedPayout>,
+    ) -> Result<(), PayoutError> {
+        let mut payouts = payouts
+            .into_iter()
+            .filter(|p| p.events.is_dirty())
+            .peekable();
+        if payouts.peek().is_none() {
+            return Ok(());
+        }
+        EntityEvents::<PayoutEvent>::persist(
+            "bria_payout_events",
+            tx,
+            payouts.flat_map(|p| p.events.into_new_serialized_events(p.id)),
+        )
+        .await?;
+        Ok(())
+    }
+
     pub async fn average_payout_per_batch(
         &self,
         wallet_id: WalletId,
@@ -290,8 +374,8 @@ impl Payouts {
         ))
     }
 
-    #[instrument(name = "payouts.find_by_id_for_cancellation", skip(self))]
-    pub async fn find_by_id_for_cancellation(
+    #[instrument(name = "payouts.find_by_id_for_update", skip(self, tx))]
+    pub async fn find_by_id_for_update(
         &self,
         tx: &mut Transaction<'_, Postgres>,
         account_id: AccountId,
@@ -326,15 +410,26 @@ impl Payouts {
         if !payout.events.is_dirty() {
             return Ok(());
         }
-
         let mut tx = self.pool.begin().await?;
+        self.update_in_tx(&mut tx, payout).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn update_in_tx(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        payout: Payout,
+    ) -> Result<(), PayoutError> {
+        if !payout.events.is_dirty() {
+            return Ok(());
+        }
         EntityEvents::<PayoutEvent>::persist(
             "bria_payout_events",
-            &mut tx,
+            tx,
             payout.events.new_serialized_events(payout.id),
         )
         .await?;
-        tx.commit().await?;
         Ok(())
     }
 }
@@ -32,10 +32,92 @@ impl UnbatchedPayouts {
             .collect()
     }
 
+    pub fn onchain_destinations(&self) -> HashSet<bitcoin::Address> {
+        self.inner
+            .values()
+            .flatten()
+            .filter_map(|p| p.destination.onchain_address())
+            .collect()
+    }
+
     pub fn n_payouts(&self) -> usize {
         self.inner.values().fold(0, |acc, v| acc + v.len())
     }
 
+    pub fn total_sats(&self) -> Satoshis {
+        self.inner.values().flatten().map(|p| p.satoshis).sum()
+    }
+
+    pub fn oldest_created_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.inner.values().flatten().map(|p| p.created_at).min()
+    }
+
+    /// Keeps the first `max_payouts` payouts in priority-then-age order and
+    /// returns how many were deferred
+    pub fn truncate(&mut self, max_payouts: usize) -> usize {
+        let n_payouts = self.n_payouts();
+        if n_payouts <= max_payouts {
+            return 0;
+        }
+        let mut ordered: Vec<_> = self.inner.values().flatten().collect();
+        ordered.sort_by_key(|p| p.batching_order());
+        let keep: HashSet<_> = ordered
+            .into_iter()
+            .take(max_payouts)
+            .map(|p| p.id)
+            .collect();
+        for payouts in self.inner.values_mut() {
+            payouts.retain(|p| keep.contains(&p.id));
+        }
+        self.inner.retain(|_, payouts| !payouts.is_empty());
+        n_payouts - max_payouts
+    }
+
+    pub fn exclude(
+        &mut self,
+        predicate: impl Fn(&UnbatchedPayout) -> bool,
+    ) -> Vec<UnbatchedPayout> {
+        let mut excluded = Vec::new();
+        for payouts in self.inner.values_mut() {
+            let (matching, remaining) = payouts.drain(..).partition(|p| predicate(p));
+            *payouts = remaining;
+            excluded.extend::<Vec<_>>(matching);
+        }
+        self.inner.retain(|_, payouts| !payouts.is_empty());
+        excluded
+    }
+
+    /// Records why the payout could not be batched and returns whether the
+    /// reason changed since it was last recorded
+    pub fn record_skip(&mut self, id: PayoutId, reason: PayoutSkipReason) -> bool {
+        self.inner
+            .values_mut()
+            .flatten()
+            .chain(self.shifted.values_mut())
+            .find(|p| p.id == id)
+            .map(|p| p.skipped(reason))
+            .unwrap_or(false)
+    }
+
+    pub(super) fn take_skipped(&mut self) -> Vec<UnbatchedPayout> {
+        let mut skipped = Vec::new();
+        for payouts in self.inner.values_mut() {
+            let (dirty, clean) = payouts.drain(..).partition(|p| p.events.is_dirty());
+            *payouts = clean;
+            skipped.extend::<Vec<_>>(dirty);
+        }
+        let dirty_ids: Vec<_> = self
+            .shifted
+            .values()
+            .filter(|p| p.events.is_dirty())
+            .map(|p| p.id)
+            .collect();
+        for id in dirty_ids {
+            skipped.extend(self.shifted.remove(&id));
+        }
+        skipped
+    }
+
     pub fn include_simulated_payout(&mut self, wallet_id: WalletId, payout: TxPayout) {
         self.simulated_payout = Some((wallet_id, payout));
     }
@@ -79,7 +161,14 @@ impl UnbatchedPayouts {
         let mut ret: HashMap<WalletId, Vec<TxPayout>> = self
             .inner
             .iter()
-            .map(|(wallet_id, payouts)| (*wallet_id, payouts.iter().map(TxPayout::from).collect()))
+            .map(|(wallet_id, payouts)| {
+                let mut payouts: Vec<_> = payouts.iter().collect();
+                payouts.sort_by_key(|p| p.batching_order());
+                (
+                    *wallet_id,
+                    payouts.into_iter().map(TxPayout::from).collect(),
+                )
+            })
             .collect();
         if let Some((wallet_id, payout)) = &self.simulated_payout {
             let entry = ret.entry(*wallet_id).or_default();
@@ -96,11 +185,37 @@ pub struct UnbatchedPayout {
     pub wallet_id: WalletId,
     pub destination: PayoutDestination,
     pub satoshis: Satoshis,
+    pub priority: u32,
+    #[builder(setter(skip))]
+    pub created_at: chrono::DateTime<chrono::Utc>,
 
     pub(super) events: EntityEvents<PayoutEvent>,
 }
 
 impl UnbatchedPayout {
+    fn batching_order(
+        &self,
+    ) -> (
+        std::cmp::Reverse<u32>,
+        chrono::DateTime<chrono::Utc>,
+        PayoutId,
+    ) {
+        (std::cmp::Reverse(self.priority), self.created_at, self.id)
+    }
+
+    /// Records the skip reason and returns whether it differs from the last recorded one
+    pub fn skipped(&mut self, reason: PayoutSkipReason) -> bool {
+        let last_reason = self.events.iter().rev().find_map(|event| match event {
+            PayoutEvent::Skipped { reason } => Some(*reason),
+            _ => None,
+        });
+        if last_reason == Some(reason) {
+            return false;
+        }
+        self.events.push(PayoutEvent::Skipped { reason });
+        true
+    }
+
     pub(super) fn commit_to_batch(&mut self, batch_id: BatchId, outpoint: bitcoin::OutPoint) {
         self.events
             .push(PayoutEvent::CommittedToBatch { batch_id, outpoint });
@@ -118,6 +233,7 @@ impl TryFrom<EntityEvents<PayoutEvent>> for UnbatchedPayout {
                 wallet_id,
                 destination,
                 satoshis,
+                priority,
                 ..
             } = event
             {
@@ -125,7 +241,8 @@ impl TryFrom<EntityEvents<PayoutEvent>> for UnbatchedPayout {
                     .id(*id)
                     .wallet_id(*wallet_id)
                     .destination(destination.clone())
-                    .satoshis(*satoshis);
+                    .satoshis(*satoshis)
+                    .priority(*priority);
             }
         }
         builder.events(events).build()
@@ -144,3 +261,41 @@ impl From<&UnbatchedPayout> for TxPayout {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unbatched_payout() -> UnbatchedPayout {
+        let id = PayoutId::new();
+        UnbatchedPayout::try_from(EntityEvents::init([PayoutEvent::Initialized {
+            id,
+            wallet_id: WalletId::new(),
+            payout_queue_id: PayoutQueueId::new(),
+            profile_id: ProfileId::new(),
+            destination: PayoutDestination::OnchainAddress {
+                value: "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap(),
+            },
+            satoshis: Satoshis::from(10_000),
+            priority: 0,
+            execute_after: None,
+        }]))
+        .unwrap()
+    }
+
+    #[test]
+    fn skip_is_only_recorded_when_reason_changes() {
+        let payout = unbatched_payout();
+        let id = payout.id;
+        let mut payouts = UnbatchedPayouts::new(HashMap::from([(payout.wallet_id, vec![payout])]));
+
+        assert!(payouts.record_skip(id, PayoutSkipReason::InsufficientFunds));
+        assert!(!payouts.record_skip(id, PayoutSkipReason::InsufficientFunds));
+        assert!(payouts.record_skip(id, PayoutSkipReason::Dust));
+        assert!(!payouts.record_skip(PayoutId::new(), PayoutSkipReason::Dust));
+
+        let skipped = payouts.take_skipped();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(payouts.n_payouts(), 0);
+    }
+}
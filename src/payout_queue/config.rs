@@ -1,13 +1,82 @@
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use crate::primitives::TxPriority;
+use crate::{
+    fees::{error::FeeEstimationError, FeeEstimator},
+    primitives::{bitcoin::FeeRate, Satoshis, TxPriority},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PayoutQueueConfig {
     pub tx_priority: TxPriority,
     pub consolidate_deprecated_keychains: bool,
     pub trigger: PayoutQueueTrigger,
+    #[serde(default)]
+    pub max_fee_rate_sats_per_vbyte: Option<u64>,
+    #[serde(default)]
+    pub fee_strategy: FeeStrategy,
+    #[serde(default)]
+    pub max_payouts_per_batch: Option<usize>,
+    #[serde(default)]
+    pub max_tx_vbytes: Option<u64>,
+    #[serde(default)]
+    pub approval: Option<PayoutApprovalConfig>,
+}
+
+impl PayoutQueueConfig {
+    pub fn required_approvals(&self, sats: Satoshis) -> Option<u32> {
+        self.approval
+            .filter(|approval| approval.required_approvals > 0 && sats >= approval.min_sats)
+            .map(|approval| approval.required_approvals)
+    }
+
+    pub async fn fee_rate(
+        &self,
+        fee_estimator: &impl FeeEstimator,
+    ) -> Result<FeeRate, FeeEstimationError> {
+        match self.fee_strategy {
+            FeeStrategy::PriorityMultiplier { multiplier } => {
+                let fee_rate = fee_estimator.fee_rate(self.tx_priority).await?;
+                Ok(FeeRate::from_sat_per_vb(
+                    fee_rate.as_sat_per_vb() * multiplier.to_f32().expect("multiplier overflow"),
+                ))
+            }
+            FeeStrategy::FixedRate { sats_per_vbyte } => Ok(fee_estimator.bounded(
+                self.tx_priority,
+                FeeRate::from_sat_per_vb(sats_per_vbyte as f32),
+            )),
+            FeeStrategy::TargetBlocks { n_blocks } => {
+                fee_estimator.fee_rate_for_blocks(n_blocks as usize).await
+            }
+        }
+    }
+}
+
+/// The furthest confirmation target fee estimators (eg. bitcoind) will estimate for
+pub const MAX_TARGET_BLOCKS: u32 = 1008;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeeStrategy {
+    /// The estimate for the queue's `tx_priority` scaled by `multiplier`
+    PriorityMultiplier {
+        multiplier: Decimal,
+    },
+    FixedRate {
+        sats_per_vbyte: u64,
+    },
+    TargetBlocks {
+        n_blocks: u32,
+    },
+}
+
+impl Default for FeeStrategy {
+    fn default() -> Self {
+        Self::PriorityMultiplier {
+            multiplier: Decimal::ONE,
+        }
+    }
 }
 
 #[serde_with::serde_as]
@@ -20,6 +89,51 @@ pub enum PayoutQueueTrigger {
         seconds: Duration,
     },
     Manual,
+    /// Fires once the unbatched payouts add up to `min_total_sats`
+    PayoutTotal {
+        min_total_sats: Satoshis,
+        #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+        #[serde(default)]
+        max_interval: Option<Duration>,
+    },
+    /// Fires once there are at least `min_payouts` unbatched payouts
+    PayoutCount {
+        min_payouts: usize,
+        #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+        #[serde(default)]
+        max_interval: Option<Duration>,
+    },
+    /// Fires once the oldest unbatched payout has waited longer than `max_age`
+    PayoutAge {
+        #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+        max_age: Duration,
+        #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+        #[serde(default)]
+        max_interval: Option<Duration>,
+    },
+    /// Fires once the queue's fee rate drops to `max_sats_per_vbyte` or below,
+    /// or unconditionally once the oldest unbatched payout has waited `deadline`
+    FeeRateWindow {
+        max_sats_per_vbyte: u64,
+        #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+        deadline: Duration,
+    },
+}
+
+impl PayoutQueueTrigger {
+    pub fn is_threshold(&self) -> bool {
+        matches!(
+            self,
+            Self::PayoutTotal { .. }
+                | Self::PayoutCount { .. }
+                | Self::PayoutAge { .. }
+                | Self::FeeRateWindow { .. }
+        )
+    }
+
+    pub fn needs_fee_rate(&self) -> bool {
+        matches!(self, Self::FeeRateWindow { .. })
+    }
 }
 
 impl Default for PayoutQueueConfig {
@@ -30,10 +144,23 @@ impl Default for PayoutQueueConfig {
             trigger: PayoutQueueTrigger::Interval {
                 seconds: default_interval(),
             },
+            max_fee_rate_sats_per_vbyte: None,
+            fee_strategy: FeeStrategy::default(),
+            max_payouts_per_batch: None,
+            max_tx_vbytes: None,
+            approval: None,
         }
     }
 }
 
+/// Payouts of at least `min_sats` are held back from batching until
+/// `required_approvals` distinct profiles have approved them
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PayoutApprovalConfig {
+    pub min_sats: Satoshis,
+    pub required_approvals: u32,
+}
+
 fn default_interval() -> Duration {
     Duration::from_secs(60)
 }
@@ -3,7 +3,11 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use super::config::*;
-use crate::{entity::*, primitives::*};
+use crate::{
+    entity::*,
+    payout::UnbatchedPayouts,
+    primitives::{bitcoin::FeeRate, *},
+};
 
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -21,6 +25,10 @@ pub enum PayoutQueueEvent {
     ConfigUpdated {
         config: PayoutQueueConfig,
     },
+    FeeRateExceeded {
+        fee_rate_sats_per_vbyte: f32,
+    },
+    FeeRateRecovered,
 }
 
 #[derive(Builder)]
@@ -40,6 +48,46 @@ impl PayoutQueue {
         match self.config.trigger {
             Interval { seconds } => Some(seconds),
             Manual => None,
+            PayoutTotal { max_interval, .. }
+            | PayoutCount { max_interval, .. }
+            | PayoutAge { max_interval, .. } => max_interval,
+            FeeRateWindow { .. } => None,
+        }
+    }
+
+    pub fn threshold_reached(
+        &self,
+        unbatched_payouts: &UnbatchedPayouts,
+        current_fee_rate: Option<FeeRate>,
+    ) -> bool {
+        use PayoutQueueTrigger::*;
+        match self.config.trigger {
+            PayoutTotal { min_total_sats, .. } => {
+                unbatched_payouts.n_payouts() > 0
+                    && unbatched_payouts.total_sats() >= min_total_sats
+            }
+            PayoutCount { min_payouts, .. } => {
+                unbatched_payouts.n_payouts() > 0 && unbatched_payouts.n_payouts() >= min_payouts
+            }
+            PayoutAge { max_age, .. } => oldest_payout_age(unbatched_payouts)
+                .map(|age| age >= max_age)
+                .unwrap_or(false),
+            FeeRateWindow {
+                max_sats_per_vbyte,
+                deadline,
+            } => {
+                if unbatched_payouts.n_payouts() == 0 {
+                    return false;
+                }
+                let cheap_enough = current_fee_rate
+                    .map(|fee_rate| fee_rate.as_sat_per_vb() <= max_sats_per_vbyte as f32)
+                    .unwrap_or(false);
+                cheap_enough
+                    || oldest_payout_age(unbatched_payouts)
+                        .map(|age| age >= deadline)
+                        .unwrap_or(false)
+            }
+            Interval { .. } | Manual => false,
         }
     }
 
@@ -60,6 +108,34 @@ impl PayoutQueue {
         }
     }
 
+    pub fn is_fee_rate_exceeded(&self) -> bool {
+        let mut ret = false;
+        for event in self.events.iter() {
+            match event {
+                PayoutQueueEvent::FeeRateExceeded { .. } => ret = true,
+                PayoutQueueEvent::FeeRateRecovered => ret = false,
+                _ => (),
+            }
+        }
+        ret
+    }
+
+    /// Records whether the current fee rate exceeds the configured max and returns
+    /// whether that changed the queue's state
+    pub fn update_fee_rate_exceeded(&mut self, fee_rate: FeeRate, exceeded: bool) -> bool {
+        if self.is_fee_rate_exceeded() == exceeded {
+            return false;
+        }
+        if exceeded {
+            self.events.push(PayoutQueueEvent::FeeRateExceeded {
+                fee_rate_sats_per_vbyte: fee_rate.as_sat_per_vb(),
+            });
+        } else {
+            self.events.push(PayoutQueueEvent::FeeRateRecovered);
+        }
+        true
+    }
+
     pub fn update_config(&mut self, config: PayoutQueueConfig) {
         if self.config != config {
             self.events.push(PayoutQueueEvent::ConfigUpdated { config });
@@ -80,6 +156,15 @@ pub struct NewPayoutQueue {
     pub(super) config: PayoutQueueConfig,
 }
 
+fn oldest_payout_age(unbatched_payouts: &UnbatchedPayouts) -> Option<Duration> {
+    unbatched_payouts.oldest_created_at().map(|created_at| {
+        chrono::Utc::now()
+            .signed_duration_since(created_at)
+            .to_std()
+            .unwrap_or_default()
+    })
+}
+
 impl NewPayoutQueue {
     pub fn builder() -> NewPayoutQueueBuilder {
         let mut builder = NewPayoutQueueBuilder::default();
@@ -128,3 +213,26 @@ impl TryFrom<EntityEvents<PayoutQueueEvent>> for PayoutQueue {
         builder.events(events).build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_rate_exceeded_only_changes_on_transition() {
+        let new_queue = NewPayoutQueue::builder()
+            .account_id(AccountId::new())
+            .name("queue")
+            .build()
+            .unwrap();
+        let mut queue = PayoutQueue::try_from(new_queue.initial_events()).unwrap();
+        let fee_rate = FeeRate::from_sat_per_vb(50.0);
+
+        assert!(!queue.update_fee_rate_exceeded(fee_rate, false));
+        assert!(queue.update_fee_rate_exceeded(fee_rate, true));
+        assert!(!queue.update_fee_rate_exceeded(fee_rate, true));
+        assert!(queue.is_fee_rate_exceeded());
+        assert!(queue.update_fee_rate_exceeded(fee_rate, false));
+        assert!(!queue.is_fee_rate_exceeded());
+    }
+}
@@ -9,6 +9,7 @@ pub use sqlx_ledger::{
 use std::fmt;
 
 crate::entity_id! { AdminApiKeyId }
+crate::entity_id! { AuditEntryId }
 crate::entity_id! { AccountId }
 impl From<LedgerJournalId> for AccountId {
     fn from(id: LedgerJournalId) -> Self {
@@ -39,6 +40,7 @@ impl From<LedgerTransactionId> for PayoutId {
     }
 }
 crate::entity_id! { BatchId }
+crate::entity_id! { BatchFeeBumpId }
 crate::entity_id! { OutboxEventId }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Copy, Serialize, Deserialize)]
@@ -79,7 +81,7 @@ pub mod bitcoin {
         bitcoin::{
             blockdata::{
                 script::Script,
-                transaction::{OutPoint, Transaction, TxOut},
+                transaction::{OutPoint, ParseOutPointError, Transaction, TxOut},
             },
             consensus,
             hash_types::Txid,
@@ -119,7 +121,7 @@ pub mod bitcoin {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq, Hash, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum TxPriority {
     NextBlock,
@@ -135,10 +137,26 @@ impl TxPriority {
             Self::OneHour => 6,
         }
     }
+
+    pub fn closest_to_n_blocks(n_blocks: usize) -> Self {
+        match n_blocks {
+            0..=1 => Self::NextBlock,
+            2..=3 => Self::HalfHour,
+            _ => Self::OneHour,
+        }
+    }
 }
 
 pub type TxPayout = (uuid::Uuid, bitcoin::Address, Satoshis);
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutSkipReason {
+    InsufficientFunds,
+    Dust,
+    Blocked,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum PayoutDestination {
@@ -1,3 +1,4 @@
+use super::{error::ProfileError, policy::ProfilePolicy, scope::ApiKeyScope};
 use crate::primitives::*;
 
 #[derive(Clone, Debug)]
@@ -5,6 +6,84 @@ pub struct Profile {
     pub id: ProfileId,
     pub account_id: AccountId,
     pub name: String,
+    pub policy: Option<ProfilePolicy>,
+    /// Set on creation, only approver profiles can approve or reject payouts
+    pub payout_approver: bool,
+}
+
+impl Profile {
+    pub fn check_payout_approver(&self) -> Result<(), ProfileError> {
+        if !self.payout_approver {
+            return Err(ProfileError::NotAPayoutApprover(self.id));
+        }
+        Ok(())
+    }
+
+    pub fn check_can_manage_profiles(&self) -> Result<(), ProfileError> {
+        if self.policy.is_some() {
+            return Err(ProfileError::ProfileManagementNotAllowed);
+        }
+        Ok(())
+    }
+
+    /// Restricted profiles only get access to the wallets and queues in their policy
+    pub fn check_account_level_access(&self) -> Result<(), ProfileError> {
+        if self.policy.is_some() {
+            return Err(ProfileError::AccountLevelAccessNotAllowed);
+        }
+        Ok(())
+    }
+
+    pub fn check_wallet(&self, wallet_id: WalletId) -> Result<(), ProfileError> {
+        match &self.policy {
+            Some(policy) if !policy.may_use_wallet(wallet_id) => {
+                Err(ProfileError::WalletNotAllowed(wallet_id))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_payout_queue(&self, payout_queue_id: PayoutQueueId) -> Result<(), ProfileError> {
+        match &self.policy {
+            Some(policy) if !policy.may_use_payout_queue(payout_queue_id) => {
+                Err(ProfileError::PayoutQueueNotAllowed(payout_queue_id))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_payout(
+        &self,
+        destination: &PayoutDestination,
+        sats: Satoshis,
+    ) -> Result<(), ProfileError> {
+        if let Some(policy) = &self.policy {
+            if !policy.may_pay_to(destination) {
+                return Err(ProfileError::DestinationNotAllowed(destination.clone()));
+            }
+            if let Some(max_payout_sats) = policy.max_payout_sats {
+                if sats > max_payout_sats {
+                    return Err(ProfileError::PayoutLimitExceeded(u64::from(
+                        max_payout_sats,
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn check_daily_payout_total(&self, daily_total: Satoshis) -> Result<(), ProfileError> {
+        match self
+            .policy
+            .as_ref()
+            .and_then(|policy| policy.max_daily_payout_sats)
+        {
+            Some(max_daily_payout_sats) if daily_total > max_daily_payout_sats => Err(
+                ProfileError::DailyPayoutLimitExceeded(u64::from(max_daily_payout_sats)),
+            ),
+            _ => Ok(()),
+        }
+    }
 }
 
 pub struct ProfileApiKey {
@@ -12,4 +91,16 @@ pub struct ProfileApiKey {
     pub id: ProfileApiKeyId,
     pub profile_id: ProfileId,
     pub account_id: AccountId,
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// An api key as listed, without the secret
+pub struct ProfileApiKeySummary {
+    pub id: ProfileApiKeyId,
+    pub profile_id: ProfileId,
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
 }
@@ -1,11 +1,48 @@
 use thiserror::Error;
 
+use super::scope::ApiAccess;
+use crate::primitives::*;
+
 #[derive(Error, Debug)]
 pub enum ProfileError {
     #[error("ProfileError - Api key does not exist")]
     ProfileKeyNotFound,
+    #[error("ProfileError - Api key has expired")]
+    ProfileKeyExpired,
+    #[error("ProfileError - Account {0} has been deactivated")]
+    AccountDeactivated(AccountId),
+    #[error("ProfileError - Could not find api key with id: {0}")]
+    ProfileApiKeyIdNotFound(ProfileApiKeyId),
+    #[error("ProfileError - Api key expiry {0} is in the past")]
+    ApiKeyExpiryInPast(chrono::DateTime<chrono::Utc>),
+    #[error("ProfileError - Api key scopes do not permit {0:?} access")]
+    ApiKeyScopeNotPermitted(ApiAccess),
     #[error("ProfileError - Could not find profile with name: {0}")]
     ProfileNameNotFound(String),
+    #[error("ProfileError - Could not find profile with id: {0}")]
+    ProfileIdNotFound(ProfileId),
+    #[error("ProfileError - Profile policy does not allow managing profiles")]
+    ProfileManagementNotAllowed,
+    #[error("ProfileError - Profile {0} is not a payout approver")]
+    NotAPayoutApprover(ProfileId),
+    #[error(
+        "ProfileError - Keys for approver profile {0} can only be created along its creator chain"
+    )]
+    ApproverKeyNotAllowed(ProfileId),
+    #[error("ProfileError - Profile policy does not allow account level access")]
+    AccountLevelAccessNotAllowed,
+    #[error("ProfileError - Profile policy does not allow using wallet {0}")]
+    WalletNotAllowed(WalletId),
+    #[error("ProfileError - Profile policy does not allow using payout queue {0}")]
+    PayoutQueueNotAllowed(PayoutQueueId),
+    #[error("ProfileError - Profile policy does not allow paying to '{0}'")]
+    DestinationNotAllowed(PayoutDestination),
+    #[error("ProfileError - Payout exceeds the profile limit of {0} sats")]
+    PayoutLimitExceeded(u64),
+    #[error("ProfileError - Payout exceeds the profile daily limit of {0} sats")]
+    DailyPayoutLimitExceeded(u64),
+    #[error("ProfileError - Could not deserialize policy: {0}")]
+    CouldNotDeserializePolicy(#[from] serde_json::Error),
     #[error("ProfileError - Sqlx: {0}")]
     Sqlx(#[from] sqlx::Error),
     #[error("ProfileError - EntityError: {0}")]
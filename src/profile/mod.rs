@@ -1,6 +1,10 @@
 mod entity;
 pub mod error;
+mod policy;
 mod repo;
+mod scope;
 
 pub use entity::*;
+pub use policy::*;
 pub use repo::*;
+pub use scope::*;
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::primitives::*;
+
+/// Restrictions on what a profile may do within its account.
+/// `None` for any of the fields means unrestricted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProfilePolicy {
+    #[serde(default)]
+    pub allowed_wallet_ids: Option<HashSet<WalletId>>,
+    #[serde(default)]
+    pub allowed_payout_queue_ids: Option<HashSet<PayoutQueueId>>,
+    #[serde(default)]
+    pub max_payout_sats: Option<Satoshis>,
+    /// Limit on the sats submitted over the last 24 hours
+    #[serde(default)]
+    pub max_daily_payout_sats: Option<Satoshis>,
+    #[serde(default)]
+    pub allowed_destinations: Option<HashSet<bitcoin::Address>>,
+}
+
+impl ProfilePolicy {
+    pub fn may_use_wallet(&self, wallet_id: WalletId) -> bool {
+        self.allowed_wallet_ids
+            .as_ref()
+            .map(|ids| ids.contains(&wallet_id))
+            .unwrap_or(true)
+    }
+
+    pub fn may_use_payout_queue(&self, payout_queue_id: PayoutQueueId) -> bool {
+        self.allowed_payout_queue_ids
+            .as_ref()
+            .map(|ids| ids.contains(&payout_queue_id))
+            .unwrap_or(true)
+    }
+
+    pub fn may_pay_to(&self, destination: &PayoutDestination) -> bool {
+        match (
+            self.allowed_destinations.as_ref(),
+            destination.onchain_address(),
+        ) {
+            (None, _) => true,
+            (Some(allowed), Some(address)) => allowed.contains(&address),
+            (Some(_), None) => false,
+        }
+    }
+}
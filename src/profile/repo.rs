@@ -2,7 +2,7 @@ use rand::distributions::{Alphanumeric, DistString};
 use sqlx::{Pool, Postgres, Transaction};
 use uuid::Uuid;
 
-use super::{entity::*, error::ProfileError};
+use super::{entity::*, error::ProfileError, policy::ProfilePolicy, scope::ApiKeyScope};
 use crate::{dev_constants, primitives::*};
 
 pub struct Profiles {
@@ -19,15 +19,21 @@ impl Profiles {
         tx: &mut Transaction<'_, Postgres>,
         account_id: AccountId,
         profile_name: String,
+        policy: Option<ProfilePolicy>,
+        payout_approver: bool,
+        created_by: Option<ProfileId>,
     ) -> Result<Profile, ProfileError> {
         let id = Uuid::new_v4();
         let record = sqlx::query!(
-            r#"INSERT INTO bria_profiles (id, account_id, name)
-            VALUES ($1, $2, $3)
+            r#"INSERT INTO bria_profiles (id, account_id, name, policy, payout_approver, created_by_profile_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING (id)"#,
             id,
             Uuid::from(account_id),
             profile_name,
+            policy.as_ref().map(serde_json::to_value).transpose()?,
+            payout_approver,
+            created_by.map(Uuid::from),
         )
         .fetch_one(&mut *tx)
         .await?;
@@ -35,28 +41,76 @@ impl Profiles {
             id: ProfileId::from(record.id),
             account_id,
             name: profile_name,
+            policy,
+            payout_approver,
         })
     }
 
+    /// The profile followed by the profile that created it, its creator and so on
+    pub async fn creator_chain(
+        &self,
+        profile_id: ProfileId,
+    ) -> Result<Vec<ProfileId>, ProfileError> {
+        let records = sqlx::query!(
+            r#"WITH RECURSIVE chain(id, created_by_profile_id, depth) AS (
+                 SELECT id, created_by_profile_id, 0 FROM bria_profiles WHERE id = $1
+                 UNION ALL
+                 SELECT p.id, p.created_by_profile_id, c.depth + 1
+                 FROM bria_profiles p JOIN chain c ON p.id = c.created_by_profile_id
+               )
+               SELECT id AS "id!" FROM chain ORDER BY depth"#,
+            Uuid::from(profile_id),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(records
+            .into_iter()
+            .map(|record| ProfileId::from(record.id))
+            .collect())
+    }
+
+    pub async fn update_policy(
+        &self,
+        account_id: AccountId,
+        profile_id: ProfileId,
+        policy: Option<ProfilePolicy>,
+    ) -> Result<(), ProfileError> {
+        let result = sqlx::query!(
+            r#"UPDATE bria_profiles SET policy = $3, modified_at = NOW()
+            WHERE account_id = $1 AND id = $2"#,
+            Uuid::from(account_id),
+            Uuid::from(profile_id),
+            policy.as_ref().map(serde_json::to_value).transpose()?,
+        )
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(ProfileError::ProfileIdNotFound(profile_id));
+        }
+        Ok(())
+    }
+
     pub async fn list_for_account(
         &self,
         account_id: AccountId,
     ) -> Result<Vec<Profile>, ProfileError> {
         let records = sqlx::query!(
-            r#"SELECT id, name FROM bria_profiles WHERE account_id = $1"#,
+            r#"SELECT id, name, policy, payout_approver FROM bria_profiles WHERE account_id = $1"#,
             account_id as AccountId
         )
         .fetch_all(&self.pool)
         .await?;
 
-        let profiles = records
-            .into_iter()
-            .map(|record| Profile {
+        let mut profiles = Vec::new();
+        for record in records {
+            profiles.push(Profile {
                 id: ProfileId::from(record.id),
                 account_id,
                 name: record.name,
-            })
-            .collect();
+                policy: record.policy.map(serde_json::from_value).transpose()?,
+                payout_approver: record.payout_approver,
+            });
+        }
 
         Ok(profiles)
     }
@@ -67,20 +121,24 @@ impl Profiles {
         name: String,
     ) -> Result<Profile, ProfileError> {
         let record = sqlx::query!(
-            r#"SELECT id, name FROM bria_profiles WHERE account_id = $1 AND name = $2"#,
+            r#"SELECT id, name, policy, payout_approver FROM bria_profiles WHERE account_id = $1 AND name = $2"#,
             Uuid::from(account_id),
             name
         )
         .fetch_optional(&self.pool)
         .await?;
 
-        record
-            .map(|row| Profile {
+        if let Some(row) = record {
+            Ok(Profile {
                 id: ProfileId::from(row.id),
                 account_id,
                 name: row.name,
+                policy: row.policy.map(serde_json::from_value).transpose()?,
+                payout_approver: row.payout_approver,
             })
-            .ok_or(ProfileError::ProfileNameNotFound(name))
+        } else {
+            Err(ProfileError::ProfileNameNotFound(name))
+        }
     }
 
     pub async fn create_key_for_profile_in_tx(
@@ -88,6 +146,8 @@ impl Profiles {
         tx: &mut sqlx::Transaction<'_, Postgres>,
         profile: Profile,
         dev: bool,
+        scopes: Vec<ApiKeyScope>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<ProfileApiKey, ProfileError> {
         let key = if dev {
             dev_constants::BRIA_DEV_KEY.to_string()
@@ -96,10 +156,12 @@ impl Profiles {
             format!("bria_{code}")
         };
         let record = sqlx::query!(
-            r#"INSERT INTO bria_profile_api_keys (encrypted_key, profile_id)
-            VALUES (crypt($1, gen_salt('bf')), (SELECT id FROM bria_profiles WHERE id = $2)) RETURNING (id)"#,
+            r#"INSERT INTO bria_profile_api_keys (encrypted_key, profile_id, scopes, expires_at)
+            VALUES (crypt($1, gen_salt('bf')), (SELECT id FROM bria_profiles WHERE id = $2), $3, $4) RETURNING (id)"#,
             key,
             Uuid::from(profile.id),
+            serde_json::to_value(&scopes)?,
+            expires_at,
         )
         .fetch_one(&mut *tx)
         .await?;
@@ -108,14 +170,71 @@ impl Profiles {
             id: ProfileApiKeyId::from(record.id),
             profile_id: profile.id,
             account_id: profile.account_id,
+            scopes,
+            expires_at,
         })
     }
 
-    pub async fn find_by_key(&self, key: &str) -> Result<Profile, ProfileError> {
+    pub async fn list_keys_for_profile(
+        &self,
+        account_id: AccountId,
+        profile_id: ProfileId,
+    ) -> Result<Vec<ProfileApiKeySummary>, ProfileError> {
+        let records = sqlx::query!(
+            r#"SELECT k.id, k.scopes, k.expires_at, k.active, k.created_at
+               FROM bria_profile_api_keys k
+               JOIN bria_profiles p ON k.profile_id = p.id
+               WHERE p.account_id = $1 AND p.id = $2
+               ORDER BY k.created_at"#,
+            Uuid::from(account_id),
+            Uuid::from(profile_id),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut keys = Vec::new();
+        for record in records {
+            keys.push(ProfileApiKeySummary {
+                id: ProfileApiKeyId::from(record.id),
+                profile_id,
+                scopes: serde_json::from_value(record.scopes)?,
+                expires_at: record.expires_at,
+                revoked: !record.active,
+                created_at: record.created_at,
+            });
+        }
+        Ok(keys)
+    }
+
+    pub async fn revoke_key(
+        &self,
+        account_id: AccountId,
+        key_id: ProfileApiKeyId,
+    ) -> Result<(), ProfileError> {
+        let result = sqlx::query!(
+            r#"UPDATE bria_profile_api_keys k SET active = false, modified_at = NOW()
+               FROM bria_profiles p
+               WHERE k.profile_id = p.id AND p.account_id = $1 AND k.id = $2"#,
+            Uuid::from(account_id),
+            Uuid::from(key_id),
+        )
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(ProfileError::ProfileApiKeyIdNotFound(key_id));
+        }
+        Ok(())
+    }
+
+    pub async fn find_by_key(
+        &self,
+        key: &str,
+    ) -> Result<(Profile, Vec<ApiKeyScope>), ProfileError> {
         let record = sqlx::query!(
-            r#"SELECT p.id, p.account_id, p.name
+            r#"SELECT p.id, p.account_id, p.name, p.policy, p.payout_approver, k.scopes, k.expires_at, a.active AS account_active
                FROM bria_profiles p
                JOIN bria_profile_api_keys k ON k.profile_id = p.id
+               JOIN bria_accounts a ON a.id = p.account_id
                WHERE k.active = true AND k.encrypted_key = crypt($1, encrypted_key)"#,
             key
         )
@@ -123,11 +242,28 @@ impl Profiles {
         .await?;
 
         if let Some(record) = record {
-            Ok(Profile {
-                id: ProfileId::from(record.id),
-                account_id: AccountId::from(record.account_id),
-                name: record.name,
-            })
+            if record
+                .expires_at
+                .map(|expires_at| expires_at <= chrono::Utc::now())
+                .unwrap_or(false)
+            {
+                return Err(ProfileError::ProfileKeyExpired);
+            }
+            if !record.account_active {
+                return Err(ProfileError::AccountDeactivated(AccountId::from(
+                    record.account_id,
+                )));
+            }
+            Ok((
+                Profile {
+                    id: ProfileId::from(record.id),
+                    account_id: AccountId::from(record.account_id),
+                    name: record.name,
+                    policy: record.policy.map(serde_json::from_value).transpose()?,
+                    payout_approver: record.payout_approver,
+                },
+                serde_json::from_value(record.scopes)?,
+            ))
         } else {
             Err(ProfileError::ProfileKeyNotFound)
         }
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use super::error::ProfileError;
+
+/// What a profile api key is allowed to do
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    Full,
+    ReadOnly,
+    Addresses,
+    SubmitPayouts,
+    SubscribeEvents,
+}
+
+/// The kind of access an api call requires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiAccess {
+    Read,
+    Write,
+    ReadAddresses,
+    WriteAddresses,
+    SubmitPayout,
+    SubscribeEvents,
+}
+
+impl ApiKeyScope {
+    pub fn permits(&self, access: ApiAccess) -> bool {
+        matches!(
+            (self, access),
+            (ApiKeyScope::Full, _)
+                | (
+                    ApiKeyScope::ReadOnly,
+                    ApiAccess::Read | ApiAccess::ReadAddresses | ApiAccess::SubscribeEvents
+                )
+                | (
+                    ApiKeyScope::Addresses,
+                    ApiAccess::ReadAddresses | ApiAccess::WriteAddresses
+                )
+                | (ApiKeyScope::SubmitPayouts, ApiAccess::SubmitPayout)
+                | (ApiKeyScope::SubscribeEvents, ApiAccess::SubscribeEvents)
+        )
+    }
+}
+
+pub fn check_scopes(scopes: &[ApiKeyScope], access: ApiAccess) -> Result<(), ProfileError> {
+    if scopes.iter().any(|scope| scope.permits(access)) {
+        Ok(())
+    } else {
+        Err(ProfileError::ApiKeyScopeNotPermitted(access))
+    }
+}
@@ -0,0 +1,28 @@
+use crate::primitives::*;
+
+#[derive(Debug, Clone)]
+pub struct BlockedAddress {
+    pub address: bitcoin::Address,
+    pub reason: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewBlockedPayoutAttempt {
+    pub account_id: AccountId,
+    pub profile_id: Option<ProfileId>,
+    pub payout_id: Option<PayoutId>,
+    pub address: bitcoin::Address,
+    pub satoshis: Satoshis,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockedPayoutAttempt {
+    pub id: uuid::Uuid,
+    pub account_id: AccountId,
+    pub profile_id: Option<ProfileId>,
+    pub payout_id: Option<PayoutId>,
+    pub address: bitcoin::Address,
+    pub satoshis: Satoshis,
+    pub attempted_at: chrono::DateTime<chrono::Utc>,
+}
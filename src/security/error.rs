@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+use crate::primitives::bitcoin;
+
+#[derive(Error, Debug)]
+pub enum SecurityError {
+    #[error("SecurityError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("SecurityError - Address {0} is not blocked")]
+    BlockedAddressNotFound(bitcoin::Address),
+    #[error("SecurityError - Could not parse stored address: {0}")]
+    CouldNotParseAddress(#[from] bitcoin::AddressError),
+}
@@ -0,0 +1,152 @@
+use sqlx::{Pool, Postgres};
+use tracing::instrument;
+
+use std::collections::HashSet;
+
+use super::{entity::*, error::*};
+use crate::primitives::*;
+
+#[derive(Debug, Clone)]
+pub struct BlockedAddresses {
+    pool: Pool<Postgres>,
+}
+
+impl BlockedAddresses {
+    pub fn new(pool: &Pool<Postgres>) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    #[instrument(name = "blocked_addresses.add", skip(self, addresses))]
+    pub async fn add(
+        &self,
+        addresses: impl IntoIterator<Item = bitcoin::Address>,
+        reason: Option<String>,
+    ) -> Result<u64, SecurityError> {
+        let addresses: Vec<String> = addresses.into_iter().map(|addr| addr.to_string()).collect();
+        let res = sqlx::query!(
+            r#"INSERT INTO bria_blocked_addresses (address, reason)
+            SELECT unnest($1::varchar[]), $2
+            ON CONFLICT (address) DO NOTHING"#,
+            &addresses[..],
+            reason,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(res.rows_affected())
+    }
+
+    #[instrument(name = "blocked_addresses.remove", skip(self))]
+    pub async fn remove(&self, address: bitcoin::Address) -> Result<(), SecurityError> {
+        let res = sqlx::query!(
+            r#"DELETE FROM bria_blocked_addresses WHERE address = $1"#,
+            address.to_string(),
+        )
+        .execute(&self.pool)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(SecurityError::BlockedAddressNotFound(address));
+        }
+        Ok(())
+    }
+
+    pub async fn list(&self) -> Result<Vec<BlockedAddress>, SecurityError> {
+        let rows = sqlx::query!(
+            r#"SELECT address, reason, created_at
+            FROM bria_blocked_addresses ORDER BY created_at, address"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(BlockedAddress {
+                    address: row.address.parse()?,
+                    reason: row.reason,
+                    created_at: row.created_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the subset of `addresses` that is currently blocked
+    pub async fn find_blocked(
+        &self,
+        addresses: impl IntoIterator<Item = &bitcoin::Address>,
+    ) -> Result<HashSet<bitcoin::Address>, SecurityError> {
+        let addresses: Vec<String> = addresses.into_iter().map(|addr| addr.to_string()).collect();
+        if addresses.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let rows = sqlx::query!(
+            r#"SELECT address FROM bria_blocked_addresses WHERE address = ANY($1)"#,
+            &addresses[..],
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter()
+            .map(|row| Ok(row.address.parse()?))
+            .collect()
+    }
+
+    pub async fn is_empty(&self) -> Result<bool, SecurityError> {
+        let row =
+            sqlx::query!(r#"SELECT NOT EXISTS (SELECT 1 FROM bria_blocked_addresses) AS "empty!""#)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(row.empty)
+    }
+
+    pub async fn is_blocked(&self, destination: &PayoutDestination) -> Result<bool, SecurityError> {
+        match destination.onchain_address() {
+            Some(addr) => Ok(!self.find_blocked([&addr]).await?.is_empty()),
+            None => Ok(false),
+        }
+    }
+
+    #[instrument(name = "blocked_addresses.record_payout_attempt", skip(self))]
+    pub async fn record_payout_attempt(
+        &self,
+        attempt: NewBlockedPayoutAttempt,
+    ) -> Result<(), SecurityError> {
+        sqlx::query!(
+            r#"INSERT INTO bria_blocked_payout_attempts (account_id, profile_id, payout_id, address, satoshis)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING"#,
+            attempt.account_id as AccountId,
+            attempt.profile_id.map(uuid::Uuid::from),
+            attempt.payout_id.map(uuid::Uuid::from),
+            attempt.address.to_string(),
+            i64::from(attempt.satoshis),
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_payout_attempts(
+        &self,
+        account_id: Option<AccountId>,
+    ) -> Result<Vec<BlockedPayoutAttempt>, SecurityError> {
+        let rows = sqlx::query!(
+            r#"SELECT id, account_id, profile_id, payout_id, address, satoshis, created_at
+            FROM bria_blocked_payout_attempts
+            WHERE $1::uuid IS NULL OR account_id = $1
+            ORDER BY created_at"#,
+            account_id.map(uuid::Uuid::from),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(BlockedPayoutAttempt {
+                    id: row.id,
+                    account_id: AccountId::from(row.account_id),
+                    profile_id: row.profile_id.map(ProfileId::from),
+                    payout_id: row.payout_id.map(PayoutId::from),
+                    address: row.address.parse()?,
+                    satoshis: Satoshis::from(row.satoshis),
+                    attempted_at: row.created_at,
+                })
+            })
+            .collect()
+    }
+}
@@ -4,10 +4,11 @@ use thiserror::Error;
 
 use std::collections::HashMap;
 
+use super::{error::SigningSessionError, status::is_signed_by_xpub};
 use crate::{
     entity::*,
     primitives::{bitcoin::psbt, *},
-    xpub::SigningClientError,
+    xpub::{SigningClientError, XPub},
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,16 +27,60 @@ pub enum SigningSessionEvent {
     ExternallySignedPsbtSubmitted {
         signed_psbt: psbt::PartiallySignedTransaction,
     },
+    PartiallySignedPsbtSubmitted {
+        signed_psbt: psbt::PartiallySignedTransaction,
+    },
     RemoteSigningCompleted {
         signed_psbt: psbt::PartiallySignedTransaction,
     },
+    UnsignedPsbtReplaced {
+        unsigned_psbt: psbt::PartiallySignedTransaction,
+    },
+    SigningAborted {
+        aborted_by: ProfileId,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SigningSessionState {
     Initialized,
     Failed,
     Complete,
+    Aborted,
+}
+
+impl std::str::FromStr for SigningSessionState {
+    type Err = SigningSessionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "initialized" => Ok(Self::Initialized),
+            "failed" => Ok(Self::Failed),
+            "complete" => Ok(Self::Complete),
+            "aborted" => Ok(Self::Aborted),
+            _ => Err(SigningSessionError::UnknownState(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for SigningSessionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Initialized => write!(f, "initialized"),
+            Self::Failed => write!(f, "failed"),
+            Self::Complete => write!(f, "complete"),
+            Self::Aborted => write!(f, "aborted"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SigningSessionFilter {
+    pub batch_id: Option<BatchId>,
+    pub xpub_id: Option<XPubId>,
+    pub state: Option<SigningSessionState>,
+    pub after_id: Option<SigningSessionId>,
+    pub limit: Option<u32>,
 }
 
 #[derive(Builder)]
@@ -61,13 +106,62 @@ impl SigningSession {
             .push(SigningSessionEvent::RemoteSigningCompleted { signed_psbt })
     }
 
-    pub fn submit_externally_signed_psbt(&mut self, signed_psbt: psbt::PartiallySignedTransaction) {
+    /// Merges the submission into previously submitted signatures. The session only
+    /// completes once `xpub` has signed every input it is a cosigner of.
+    pub fn submit_externally_signed_psbt(
+        &mut self,
+        signed_psbt: psbt::PartiallySignedTransaction,
+        xpub: &XPub,
+    ) -> Result<(), SigningSessionError> {
+        let signed_psbt = match self.signed_psbt() {
+            Some(existing) => {
+                let mut combined = existing.clone();
+                combined
+                    .combine(signed_psbt)
+                    .map_err(SigningSessionError::CouldNotCombinePsbt)?;
+                combined
+            }
+            None => signed_psbt,
+        };
+        if is_signed_by_xpub(&self.unsigned_psbt, &signed_psbt, xpub) {
+            self.events
+                .push(SigningSessionEvent::ExternallySignedPsbtSubmitted { signed_psbt })
+        } else {
+            self.events
+                .push(SigningSessionEvent::PartiallySignedPsbtSubmitted { signed_psbt })
+        }
+        Ok(())
+    }
+
+    pub fn replace_unsigned_psbt(&mut self, unsigned_psbt: psbt::PartiallySignedTransaction) {
+        self.unsigned_psbt = unsigned_psbt.clone();
+        self.events
+            .push(SigningSessionEvent::UnsignedPsbtReplaced { unsigned_psbt })
+    }
+
+    pub fn abort(&mut self, aborted_by: ProfileId) {
         self.events
-            .push(SigningSessionEvent::ExternallySignedPsbtSubmitted { signed_psbt })
+            .push(SigningSessionEvent::SigningAborted { aborted_by })
     }
 
     pub fn is_completed(&self) -> bool {
-        self.signed_psbt().is_some()
+        let mut ret = false;
+        for event in self.events.iter() {
+            ret = match event {
+                SigningSessionEvent::RemoteSigningCompleted { .. }
+                | SigningSessionEvent::ExternallySignedPsbtSubmitted { .. } => true,
+                SigningSessionEvent::PartiallySignedPsbtSubmitted { .. }
+                | SigningSessionEvent::UnsignedPsbtReplaced { .. } => false,
+                _ => ret,
+            };
+        }
+        ret
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.events
+            .iter()
+            .any(|event| matches!(event, SigningSessionEvent::SigningAborted { .. }))
     }
 
     pub fn signed_psbt(&self) -> Option<&psbt::PartiallySignedTransaction> {
@@ -75,9 +169,13 @@ impl SigningSession {
         for event in self.events.iter() {
             match event {
                 SigningSessionEvent::RemoteSigningCompleted { signed_psbt }
-                | SigningSessionEvent::ExternallySignedPsbtSubmitted { signed_psbt } => {
+                | SigningSessionEvent::ExternallySignedPsbtSubmitted { signed_psbt }
+                | SigningSessionEvent::PartiallySignedPsbtSubmitted { signed_psbt } => {
                     ret = Some(signed_psbt);
                 }
+                SigningSessionEvent::UnsignedPsbtReplaced { .. } => {
+                    ret = None;
+                }
                 _ => (),
             }
         }
@@ -94,6 +192,7 @@ impl SigningSession {
                 SigningSessionEvent::SigningAttemptFailed { reason } => Some(reason),
                 SigningSessionEvent::RemoteSigningCompleted { .. } => None,
                 SigningSessionEvent::ExternallySignedPsbtSubmitted { .. } => None,
+                SigningSessionEvent::UnsignedPsbtReplaced { .. } => None,
                 _ => ret,
             };
         }
@@ -101,6 +200,9 @@ impl SigningSession {
     }
 
     pub fn state(&self) -> SigningSessionState {
+        if self.is_aborted() {
+            return SigningSessionState::Aborted;
+        }
         let mut ret = SigningSessionState::Initialized;
         for event in self.events.iter() {
             ret = match event {
@@ -109,6 +211,9 @@ impl SigningSession {
                 SigningSessionEvent::ExternallySignedPsbtSubmitted { .. } => {
                     SigningSessionState::Complete
                 }
+                SigningSessionEvent::UnsignedPsbtReplaced { .. } => {
+                    SigningSessionState::Initialized
+                }
                 _ => ret,
             };
         }
@@ -171,22 +276,69 @@ impl TryFrom<EntityEvents<SigningSessionEvent>> for SigningSession {
     fn try_from(events: EntityEvents<SigningSessionEvent>) -> Result<Self, Self::Error> {
         let mut builder = SigningSessionBuilder::default();
         for event in events.iter() {
-            if let SigningSessionEvent::Initialized {
-                id,
-                account_id,
-                batch_id,
-                unsigned_psbt,
-                xpub_id,
-            } = event
-            {
-                builder = builder
-                    .id(*id)
-                    .account_id(*account_id)
-                    .batch_id(*batch_id)
-                    .xpub_id(*xpub_id)
-                    .unsigned_psbt(unsigned_psbt.clone());
+            match event {
+                SigningSessionEvent::Initialized {
+                    id,
+                    account_id,
+                    batch_id,
+                    unsigned_psbt,
+                    xpub_id,
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .account_id(*account_id)
+                        .batch_id(*batch_id)
+                        .xpub_id(*xpub_id)
+                        .unsigned_psbt(unsigned_psbt.clone());
+                }
+                SigningSessionEvent::UnsignedPsbtReplaced { unsigned_psbt } => {
+                    builder = builder.unsigned_psbt(unsigned_psbt.clone());
+                }
+                _ => (),
             }
         }
         builder.events(events).build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bdk::bitcoin::{PackedLockTime, Transaction};
+
+    use super::*;
+
+    fn session() -> SigningSession {
+        let unsigned_psbt = psbt::PartiallySignedTransaction::from_unsigned_tx(Transaction {
+            version: 2,
+            lock_time: PackedLockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        })
+        .unwrap();
+        let new_session = NewSigningSession::builder()
+            .account_id(AccountId::new())
+            .batch_id(BatchId::new())
+            .xpub_id(XPubId::from(bitcoin::Fingerprint::default()))
+            .unsigned_psbt(unsigned_psbt)
+            .build()
+            .unwrap();
+        SigningSession::try_from(new_session.initial_events()).unwrap()
+    }
+
+    #[test]
+    fn aborted_sessions_stay_aborted() {
+        let mut session = session();
+        session.attempt_failed(SigningFailureReason::SignerConfigMissing);
+        assert_eq!(session.state(), SigningSessionState::Failed);
+
+        session.abort(ProfileId::new());
+        let unsigned_psbt = session.unsigned_psbt.clone();
+        session.replace_unsigned_psbt(unsigned_psbt);
+        assert!(session.is_aborted());
+        assert_eq!(session.state(), SigningSessionState::Aborted);
+        assert_eq!(
+            "Aborted".parse::<SigningSessionState>().unwrap(),
+            session.state()
+        );
+    }
+}
@@ -6,4 +6,16 @@ pub enum SigningSessionError {
     Sqlx(#[from] sqlx::Error),
     #[error("SigningSessionError - EntityError: {0}")]
     EntityError(#[from] crate::entity::EntityError),
+    #[error("SigningSessionError - NotFound: {0}")]
+    NotFound(crate::primitives::SigningSessionId),
+    #[error("SigningSessionError - UnknownState: {0}")]
+    UnknownState(String),
+    #[error("SigningSessionError - AlreadyComplete: {0}")]
+    AlreadyComplete(crate::primitives::SigningSessionId),
+    #[error("SigningSessionError - Aborted: {0}")]
+    Aborted(crate::primitives::SigningSessionId),
+    #[error("SigningSessionError - NothingToAbort: {0}")]
+    NothingToAbort(crate::primitives::BatchId),
+    #[error("SigningSessionError - CouldNotCombinePsbt: {0}")]
+    CouldNotCombinePsbt(crate::primitives::bitcoin::psbt::Error),
 }
@@ -1,6 +1,8 @@
 mod entity;
 pub mod error;
 mod repo;
+mod status;
 
 pub use entity::*;
 pub use repo::*;
+pub use status::*;
@@ -6,6 +6,8 @@ use std::collections::HashMap;
 use super::{entity::*, error::SigningSessionError};
 use crate::{entity::EntityEvents, primitives::*};
 
+const DEFAULT_LIST_LIMIT: u32 = 100;
+
 #[derive(Clone)]
 pub struct SigningSessions {
     pool: Pool<Postgres>,
@@ -64,6 +66,15 @@ impl SigningSessions {
         tx: &mut Transaction<'_, Postgres>,
         sessions: &HashMap<XPubId, SigningSession>,
     ) -> Result<(), SigningSessionError> {
+        for session in sessions.values().filter(|s| s.events.is_dirty()) {
+            sqlx::query!(
+                r#"UPDATE bria_signing_sessions SET state = $1 WHERE id = $2"#,
+                session.state().to_string(),
+                Uuid::from(session.id),
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
         EntityEvents::<SigningSessionEvent>::persist(
             "bria_signing_session_events",
             tx,
@@ -113,6 +124,86 @@ impl SigningSessions {
         }
     }
 
+    pub async fn find_by_id(
+        &self,
+        account_id: AccountId,
+        id: SigningSessionId,
+    ) -> Result<SigningSession, SigningSessionError> {
+        let rows = sqlx::query!(
+            r#"
+              SELECT b.*, e.sequence, e.event_type, e.event
+              FROM bria_signing_sessions b
+              JOIN bria_signing_session_events e ON b.id = e.id
+              WHERE account_id = $1 AND b.id = $2
+              ORDER BY sequence"#,
+            Uuid::from(account_id),
+            Uuid::from(id)
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        if rows.is_empty() {
+            return Err(SigningSessionError::NotFound(id));
+        }
+        let mut events = EntityEvents::new();
+        for row in rows {
+            events.load_event(row.sequence as usize, row.event)?;
+        }
+        Ok(SigningSession::try_from(events)?)
+    }
+
+    pub async fn list(
+        &self,
+        account_id: AccountId,
+        filter: SigningSessionFilter,
+    ) -> Result<Vec<SigningSession>, SigningSessionError> {
+        let xpub_fingerprint = filter.xpub_id.map(|id| id.as_bytes().to_owned());
+        let rows = sqlx::query!(
+            r#"
+              WITH sessions AS (
+                SELECT id, created_at
+                FROM bria_signing_sessions
+                WHERE account_id = $1
+                  AND ($2::UUID IS NULL OR batch_id = $2)
+                  AND ($3::BYTEA IS NULL OR xpub_fingerprint = $3)
+                  AND ($4::VARCHAR IS NULL OR state = $4)
+                  AND ($5::UUID IS NULL OR (created_at, id) > (
+                    SELECT created_at, id FROM bria_signing_sessions WHERE id = $5
+                  ))
+                ORDER BY created_at, id
+                LIMIT $6
+              )
+              SELECT s.id, e.sequence, e.event
+              FROM sessions s
+              JOIN bria_signing_session_events e ON s.id = e.id
+              ORDER BY s.created_at, s.id, e.sequence"#,
+            Uuid::from(account_id),
+            filter.batch_id.map(Uuid::from),
+            xpub_fingerprint.as_ref().map(|fp| fp.as_slice()),
+            filter.state.map(|state| state.to_string()),
+            filter.after_id.map(Uuid::from),
+            filter.limit.unwrap_or(DEFAULT_LIST_LIMIT) as i64,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let mut session_ids = Vec::new();
+        let mut entity_events = HashMap::new();
+        for row in rows {
+            let id = SigningSessionId::from(row.id);
+            let events = entity_events.entry(id).or_insert_with(|| {
+                session_ids.push(id);
+                EntityEvents::new()
+            });
+            events.load_event(row.sequence as usize, row.event)?;
+        }
+        let mut sessions = Vec::new();
+        for id in session_ids {
+            if let Some(events) = entity_events.remove(&id) {
+                sessions.push(SigningSession::try_from(events)?);
+            }
+        }
+        Ok(sessions)
+    }
+
     pub async fn list_batch_ids_for(
         &self,
         tx: &mut Transaction<'_, Postgres>,
@@ -0,0 +1,403 @@
+use bdk::{
+    bitcoin::{
+        secp256k1::{self, Secp256k1, VerifyOnly},
+        PublicKey,
+    },
+    miniscript::{Miniscript, Segwitv0, Terminal},
+};
+
+use super::entity::*;
+use crate::{
+    primitives::{
+        bitcoin::{psbt, DerivationPath, OutPoint},
+        *,
+    },
+    xpub::XPub,
+};
+
+#[derive(Debug, Clone)]
+pub struct BatchSigningStatus {
+    pub inputs: Vec<InputSigningStatus>,
+}
+
+impl BatchSigningStatus {
+    pub fn threshold_met(&self) -> bool {
+        self.inputs.iter().all(|input| input.threshold_met())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InputSigningStatus {
+    pub outpoint: OutPoint,
+    pub required_signatures: usize,
+    pub signed_by: Vec<XPubId>,
+    pub missing: Vec<XPubId>,
+    pub finalized: bool,
+}
+
+impl InputSigningStatus {
+    pub fn threshold_met(&self) -> bool {
+        self.finalized || self.signed_by.len() >= self.required_signatures
+    }
+}
+
+impl BatchSigningSession {
+    pub fn combined_psbt(&self) -> Option<psbt::PartiallySignedTransaction> {
+        let mut psbts = self
+            .xpub_sessions
+            .values()
+            .filter_map(|session| session.signed_psbt());
+        let mut combined = psbts.next()?.clone();
+        for psbt in psbts {
+            let _ = combined.combine(psbt.clone());
+        }
+        Some(combined)
+    }
+
+    pub fn signing_status(&self, xpubs: &[XPub]) -> BatchSigningStatus {
+        let unsigned_psbt = match self.xpub_sessions.values().next() {
+            Some(session) => &session.unsigned_psbt,
+            None => return BatchSigningStatus { inputs: Vec::new() },
+        };
+        let combined = self.combined_psbt();
+        let secp = Secp256k1::verification_only();
+        let inputs = unsigned_psbt
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(idx, input)| {
+                let signed_input = combined.as_ref().map(|psbt| &psbt.inputs[idx]);
+                let finalized = signed_input.map(is_finalized).unwrap_or(false);
+                let mut signed_by = Vec::new();
+                let mut missing = Vec::new();
+                for xpub in xpubs {
+                    let mut keys = xpub_keys(&secp, input, xpub).peekable();
+                    if keys.peek().is_none() {
+                        continue;
+                    }
+                    if signed_input
+                        .map(|i| keys.any(|pk| i.partial_sigs.keys().any(|key| key.inner == *pk)))
+                        .unwrap_or(false)
+                    {
+                        signed_by.push(xpub.id());
+                    } else if !finalized {
+                        // Finalized inputs no longer carry partial sigs but need no more signatures
+                        missing.push(xpub.id());
+                    }
+                }
+                InputSigningStatus {
+                    outpoint: unsigned_psbt.unsigned_tx.input[idx].previous_output,
+                    required_signatures: required_signatures(input),
+                    signed_by,
+                    missing,
+                    finalized,
+                }
+            })
+            .collect();
+        BatchSigningStatus { inputs }
+    }
+}
+
+/// Whether `xpub` has signed (or the signed psbt finalized) every input it is a cosigner of
+pub(super) fn is_signed_by_xpub(
+    unsigned_psbt: &psbt::PartiallySignedTransaction,
+    signed_psbt: &psbt::PartiallySignedTransaction,
+    xpub: &XPub,
+) -> bool {
+    let secp = Secp256k1::verification_only();
+    unsigned_psbt
+        .inputs
+        .iter()
+        .zip(signed_psbt.inputs.iter())
+        .all(|(input, signed_input)| {
+            let mut keys = xpub_keys(&secp, input, xpub).peekable();
+            keys.peek().is_none()
+                || is_finalized(signed_input)
+                || keys.any(|pk| signed_input.partial_sigs.keys().any(|key| key.inner == *pk))
+        })
+}
+
+fn is_finalized(input: &psbt::Input) -> bool {
+    input.final_script_witness.is_some() || input.final_script_sig.is_some()
+}
+
+/// Keys of the input that derive from `xpub`
+fn xpub_keys<'a>(
+    secp: &'a Secp256k1<VerifyOnly>,
+    input: &'a psbt::Input,
+    xpub: &'a XPub,
+) -> impl Iterator<Item = &'a secp256k1::PublicKey> {
+    input
+        .bip32_derivation
+        .iter()
+        .filter(move |(pk, (_, path))| {
+            // Keychain paths end in <branch>/<index> relative to the xpub
+            let relative_path = DerivationPath::from(&path[path.len().saturating_sub(2)..]);
+            xpub.inner()
+                .derive_pub(secp, &relative_path)
+                .map(|derived| derived.public_key == **pk)
+                .unwrap_or(false)
+        })
+        .map(|(pk, _)| pk)
+}
+
+fn required_signatures(input: &psbt::Input) -> usize {
+    input
+        .witness_script
+        .as_ref()
+        .and_then(|script| Miniscript::<PublicKey, Segwitv0>::parse_insane(script).ok())
+        .map(|ms| match ms.node {
+            Terminal::Multi(k, _) => k,
+            _ => 1,
+        })
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use bdk::{
+        bitcoin::{
+            blockdata::{opcodes, script::Builder},
+            util::bip32::{ExtendedPrivKey, ExtendedPubKey},
+            Network, PackedLockTime, Script, Sequence, Transaction, TxIn, TxOut, Witness,
+        },
+        signer::{InputSigner, SignerContext, SignerWrapper},
+        SignOptions,
+    };
+
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::signing_session::error::SigningSessionError;
+
+    #[test]
+    fn signing_status() {
+        let xpub = XPub::try_from(("tpubDE8HT914zGpxhJhgoMX35xgNyjHy5d1neGXHjTLAtuUssTA7tNWNs177JsFPbJwD5FBXCHJYbwUC9AzSEpYHC4hKgaCvZyZTuCbWfNUWXoM", Some("m/48h/1h/0h/2h"))).unwrap();
+        let unsigned_psbt = "cHNidP8BAH0BAAAAASNihqnLFfz7pHt1zDeB/iB7ku75Ah6EFaFhQZnbErt9AAAAAAD+////Ap13fQEAAAAAIgAgO37beKyitaViJwyjZ3oTIwdBU0JTbBRa32V1zvdifQzAaHgEAAAAABYAFFPOvhKDbGzCHM0LNEHgSPJjuf7RzQAAAAABAPYCAAAAAAEBTEYh+JWYBjbSBgwY+QxYOE25/vFk5zdS61jKtc1HJjYAAAAAAP3///8CAOH1BQAAAAAiACCOipWPCjso1EpZQctqUeF6N4QjTNQ3c+15axzGinwSVhwEECQBAAAAIlEgzmi+Ha7O7p08hrHEzLrq68MJlSDW40V39kbqS+ArmTMCRzBEAiB5fcQ8lx7fp+Calgy7o9jQEsHEPho0zfP13TQsCC2/GgIgSL/zyp0nz5PzdMXxhgBJ59O2t7tUhAfKxBYtVjMYXR0BIQN39pz1kuRtgfVu5SMba1rXL5HXDIKq4/rq7I/342+/GsgAAAABASsA4fUFAAAAACIAII6KlY8KOyjUSllBy2pR4Xo3hCNM1Ddz7XlrHMaKfBJWAQMEAQAAAAEFR1EhAlBn4VwHril4Da/2rGzF/FZnM0gnAi5M7A7iHMxMA4tIIQKXjwOvzjfb1Y0HMvH2Bc2Eqtukx+dxd4V8qOus23qGhlKuIgYCUGfhXAeuKXgNr/asbMX8VmczSCcCLkzsDuIczEwDi0gcmFPdqTAAAIABAACAAAAAgAIAAIAAAAAAAAAAACIGApePA6/ON9vVjQcy8fYFzYSq26TH53F3hXyo66zbeoaGHB3opBwwAACAAQAAgAAAAIACAACAAAAAAAAAAAAAAQFHUSECXDnAvMuAqtaBxRvWWRK4cOeJCmnxrHmzX7Ys+TOgLkMhAsl2+NBf0WNXB5Dyu/j0+luIVYCV+21GR7hPI2AUvisiUq4iAgJcOcC8y4Cq1oHFG9ZZErhw54kKafGsebNftiz5M6AuQxyYU92pMAAAgAEAAIAAAACAAgAAgAEAAAAAAAAAIgICyXb40F/RY1cHkPK7+PT6W4hVgJX7bUZHuE8jYBS+KyIcHeikHDAAAIABAACAAAAAgAIAAIABAAAAAAAAAAAA".parse::<psbt::PartiallySignedTransaction>().unwrap();
+        let signed_psbt = "cHNidP8BAH0BAAAAASNihqnLFfz7pHt1zDeB/iB7ku75Ah6EFaFhQZnbErt9AAAAAAD+////Ap13fQEAAAAAIgAgO37beKyitaViJwyjZ3oTIwdBU0JTbBRa32V1zvdifQzAaHgEAAAAABYAFFPOvhKDbGzCHM0LNEHgSPJjuf7RzQAAAAABAIkCAAAAAUxGIfiVmAY20gYMGPkMWDhNuf7xZOc3UutYyrXNRyY2AAAAAAD9////AgDh9QUAAAAAIgAgjoqVjwo7KNRKWUHLalHhejeEI0zUN3PteWscxop8ElYcBBAkAQAAACJRIM5ovh2uzu6dPIaxxMy66uvDCZUg1uNFd/ZG6kvgK5kzyAAAAAEBKwDh9QUAAAAAIgAgjoqVjwo7KNRKWUHLalHhejeEI0zUN3PteWscxop8ElYBCJIDAEcwRAIgGZdgjGq/M/51nE9WtP69BZBhQtho22JcoIQHSWEXI00CIA7/Mj5A906MFjd+sm+EawhjTALyR5jsPyT6Qa7TEJQDAUdRIQJQZ+FcB64peA2v9qxsxfxWZzNIJwIuTOwO4hzMTAOLSCECl48Dr84329WNBzLx9gXNhKrbpMfncXeFfKjrrNt6hoZSrgABAUdRIQJcOcC8y4Cq1oHFG9ZZErhw54kKafGsebNftiz5M6AuQyECyXb40F/RY1cHkPK7+PT6W4hVgJX7bUZHuE8jYBS+KyJSriICAlw5wLzLgKrWgcUb1lkSuHDniQpp8ax5s1+2LPkzoC5DHJhT3akwAACAAQAAgAAAAIACAACAAQAAAAAAAAAiAgLJdvjQX9FjVweQ8rv49PpbiFWAlfttRke4TyNgFL4rIhwd6KQcMAAAgAEAAIAAAACAAgAAgAEAAAAAAAAAAAA=".parse::<psbt::PartiallySignedTransaction>().unwrap();
+        let new_session = NewSigningSession::builder()
+            .account_id(AccountId::new())
+            .batch_id(BatchId::new())
+            .xpub_id(xpub.id())
+            .unsigned_psbt(unsigned_psbt)
+            .build()
+            .unwrap();
+        let mut batch_session = BatchSigningSession {
+            xpub_sessions: HashMap::from([(
+                xpub.id(),
+                SigningSession::try_from(new_session.initial_events()).unwrap(),
+            )]),
+        };
+
+        let status = batch_session.signing_status(std::slice::from_ref(&xpub));
+        assert_eq!(status.inputs.len(), 1);
+        assert_eq!(status.inputs[0].required_signatures, 1);
+        assert_eq!(status.inputs[0].missing, vec![xpub.id()]);
+        assert!(!status.threshold_met());
+
+        batch_session
+            .xpub_sessions
+            .get_mut(&xpub.id())
+            .unwrap()
+            .submit_externally_signed_psbt(signed_psbt, &xpub)
+            .unwrap();
+        let status = batch_session.signing_status(std::slice::from_ref(&xpub));
+        assert!(status.inputs[0].finalized);
+        assert!(status.inputs[0].missing.is_empty());
+        assert!(status.threshold_met());
+        assert!(batch_session.xpub_sessions[&xpub.id()].is_completed());
+    }
+
+    #[test]
+    fn signing_status_2_of_3() {
+        let secp = Secp256k1::new();
+        let xprivs: Vec<_> = (1..=3)
+            .map(|seed| ExtendedPrivKey::new_master(Network::Testnet, &[seed; 32]).unwrap())
+            .collect();
+        let xpubs: Vec<_> = xprivs
+            .iter()
+            .map(|xpriv| {
+                let xpub = ExtendedPubKey::from_priv(&secp, xpriv);
+                XPub::try_from((xpub.to_string(), None::<&str>)).unwrap()
+            })
+            .collect();
+        let path = "m/0/3".parse::<DerivationPath>().unwrap();
+        let keys: Vec<_> = xprivs
+            .iter()
+            .map(|xpriv| xpriv.derive_priv(&secp, &path).unwrap().to_priv())
+            .collect();
+
+        let mut script = Builder::new().push_int(2);
+        for key in keys.iter() {
+            script = script.push_key(&key.public_key(&secp));
+        }
+        let witness_script = script
+            .push_int(3)
+            .push_opcode(opcodes::all::OP_CHECKMULTISIG)
+            .into_script();
+        let mut unsigned_psbt = psbt::PartiallySignedTransaction::from_unsigned_tx(Transaction {
+            version: 2,
+            lock_time: PackedLockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::null(),
+                script_sig: Script::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::new(),
+            }],
+            output: vec![TxOut {
+                value: 90_000,
+                script_pubkey: witness_script.to_v0_p2wsh(),
+            }],
+        })
+        .unwrap();
+        let input = &mut unsigned_psbt.inputs[0];
+        input.witness_utxo = Some(TxOut {
+            value: 100_000,
+            script_pubkey: witness_script.to_v0_p2wsh(),
+        });
+        input.witness_script = Some(witness_script);
+        for (xpriv, key) in xprivs.iter().zip(keys.iter()) {
+            input.bip32_derivation.insert(
+                key.public_key(&secp).inner,
+                (xpriv.fingerprint(&secp), path.clone()),
+            );
+        }
+
+        let sessions = xpubs.iter().map(|xpub| {
+            let new_session = NewSigningSession::builder()
+                .account_id(AccountId::new())
+                .batch_id(BatchId::new())
+                .xpub_id(xpub.id())
+                .unsigned_psbt(unsigned_psbt.clone())
+                .build()
+                .unwrap();
+            (
+                xpub.id(),
+                SigningSession::try_from(new_session.initial_events()).unwrap(),
+            )
+        });
+        let mut batch_session = BatchSigningSession {
+            xpub_sessions: sessions.collect(),
+        };
+        let status = batch_session.signing_status(&xpubs);
+        assert_eq!(status.inputs[0].required_signatures, 2);
+        assert_eq!(status.inputs[0].missing.len(), 3);
+        assert!(!status.threshold_met());
+
+        for (idx, key) in keys.iter().take(2).enumerate() {
+            let mut signed_psbt = unsigned_psbt.clone();
+            SignerWrapper::new(*key, SignerContext::Segwitv0)
+                .sign_input(&mut signed_psbt, 0, &SignOptions::default(), &secp)
+                .unwrap();
+            batch_session
+                .xpub_sessions
+                .get_mut(&xpubs[idx].id())
+                .unwrap()
+                .submit_externally_signed_psbt(signed_psbt, &xpubs[idx])
+                .unwrap();
+            assert!(batch_session.xpub_sessions[&xpubs[idx].id()].is_completed());
+
+            let status = batch_session.signing_status(&xpubs);
+            assert_eq!(status.inputs[0].signed_by.len(), idx + 1);
+            assert_eq!(status.inputs[0].missing.len(), 2 - idx);
+            assert!(!status.inputs[0].missing.contains(&xpubs[idx].id()));
+            assert_eq!(status.threshold_met(), idx == 1);
+        }
+        assert!(!batch_session.xpub_sessions[&xpubs[2].id()].is_completed());
+    }
+
+    #[test]
+    fn partial_submission_does_not_complete_session() {
+        let secp = Secp256k1::new();
+        let xpriv = ExtendedPrivKey::new_master(Network::Testnet, &[7; 32]).unwrap();
+        let xpub = XPub::try_from((
+            ExtendedPubKey::from_priv(&secp, &xpriv).to_string(),
+            None::<&str>,
+        ))
+        .unwrap();
+        let paths: Vec<_> = ["m/0/1", "m/0/2"]
+            .iter()
+            .map(|path| path.parse::<DerivationPath>().unwrap())
+            .collect();
+        let keys: Vec<_> = paths
+            .iter()
+            .map(|path| xpriv.derive_priv(&secp, path).unwrap().to_priv())
+            .collect();
+        let script_pubkeys: Vec<_> = keys
+            .iter()
+            .map(|key| {
+                bdk::bitcoin::Address::p2wpkh(&key.public_key(&secp), Network::Testnet)
+                    .unwrap()
+                    .script_pubkey()
+            })
+            .collect();
+        let mut unsigned_psbt = psbt::PartiallySignedTransaction::from_unsigned_tx(Transaction {
+            version: 2,
+            lock_time: PackedLockTime::ZERO,
+            input: (0..2)
+                .map(|vout| TxIn {
+                    previous_output: OutPoint {
+                        txid: OutPoint::null().txid,
+                        vout,
+                    },
+                    script_sig: Script::new(),
+                    sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: vec![TxOut {
+                value: 190_000,
+                script_pubkey: script_pubkeys[0].clone(),
+            }],
+        })
+        .unwrap();
+        for (idx, input) in unsigned_psbt.inputs.iter_mut().enumerate() {
+            input.witness_utxo = Some(TxOut {
+                value: 100_000,
+                script_pubkey: script_pubkeys[idx].clone(),
+            });
+            input.bip32_derivation.insert(
+                keys[idx].public_key(&secp).inner,
+                (xpriv.fingerprint(&secp), paths[idx].clone()),
+            );
+        }
+        let new_session = NewSigningSession::builder()
+            .account_id(AccountId::new())
+            .batch_id(BatchId::new())
+            .xpub_id(xpub.id())
+            .unsigned_psbt(unsigned_psbt.clone())
+            .build()
+            .unwrap();
+        let mut session = SigningSession::try_from(new_session.initial_events()).unwrap();
+
+        let mut first_input_signed = unsigned_psbt.clone();
+        SignerWrapper::new(keys[0], SignerContext::Segwitv0)
+            .sign_input(&mut first_input_signed, 0, &SignOptions::default(), &secp)
+            .unwrap();
+        session
+            .submit_externally_signed_psbt(first_input_signed, &xpub)
+            .unwrap();
+        assert!(!session.is_completed());
+
+        let mut second_input_signed = unsigned_psbt.clone();
+        SignerWrapper::new(keys[1], SignerContext::Segwitv0)
+            .sign_input(&mut second_input_signed, 1, &SignOptions::default(), &secp)
+            .unwrap();
+        session
+            .submit_externally_signed_psbt(second_input_signed, &xpub)
+            .unwrap();
+        assert!(session.is_completed());
+        let signed_psbt = session.signed_psbt().unwrap();
+        assert!(signed_psbt
+            .inputs
+            .iter()
+            .all(|input| input.partial_sigs.len() == 1));
+
+        let mut other_tx = unsigned_psbt;
+        other_tx.unsigned_tx.output[0].value = 1;
+        assert!(matches!(
+            session.submit_externally_signed_psbt(other_tx, &xpub),
+            Err(SigningSessionError::CouldNotCombinePsbt(_))
+        ));
+    }
+}
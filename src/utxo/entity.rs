@@ -14,6 +14,21 @@ pub struct WalletUtxo {
     pub utxo_detected_ledger_tx_id: LedgerTransactionId,
     pub utxo_settled_ledger_tx_id: Option<LedgerTransactionId>,
     pub spending_batch_id: Option<BatchId>,
+    pub quarantined: bool,
+    pub frozen: bool,
+    pub frozen_reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct QuarantinedUtxo {
+    pub account_id: AccountId,
+    pub wallet_id: WalletId,
+    pub keychain_id: KeychainId,
+    pub outpoint: OutPoint,
+    pub value: Satoshis,
+    pub address: bitcoin::Address,
+    pub source_address: Option<bitcoin::Address>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Debug)]
@@ -24,6 +39,22 @@ pub struct SettledUtxo {
     pub utxo_detected_ledger_tx_id: LedgerTransactionId,
     pub utxo_settled_ledger_tx_id: LedgerTransactionId,
     pub spend_detected_ledger_tx_id: Option<LedgerTransactionId>,
+    pub dust: bool,
+    pub quarantined: bool,
+    pub frozen: bool,
+}
+
+/// A utxo whose quarantine or freeze status was just changed
+#[derive(Debug)]
+pub struct HeldUtxo {
+    pub wallet_id: WalletId,
+    pub keychain_id: KeychainId,
+    pub outpoint: OutPoint,
+    pub value: Satoshis,
+    pub address: bitcoin::Address,
+    pub settled: bool,
+    /// Whether the value has to move in or out of the frozen ledger account
+    pub ledger_hold_changed: bool,
 }
 
 #[derive(Debug)]
@@ -55,6 +86,10 @@ pub struct NewUtxo {
     pub(super) self_pay: bool,
     pub(super) bdk_spent: bool,
     pub(super) utxo_detected_ledger_tx_id: LedgerTransactionId,
+    #[builder(default)]
+    pub(super) quarantined_source_address: Option<String>,
+    #[builder(default)]
+    pub(super) dust: bool,
 }
 
 impl NewUtxo {
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::primitives::bitcoin;
+
 #[derive(Debug, Error)]
 pub enum UtxoError {
     #[error("UtxoError - Sqlx: {0}")]
@@ -10,4 +12,12 @@ pub enum UtxoError {
     UtxoAlreadySettledError,
     #[error("UtxoError - Utxo does not exist")]
     UtxoDoesNotExistError,
+    #[error("UtxoError - No quarantined utxo found for outpoint {0}")]
+    QuarantinedUtxoNotFound(bitcoin::OutPoint),
+    #[error("UtxoError - No unspent and unreserved utxo found for outpoint {0}")]
+    UnspentUtxoNotFound(bitcoin::OutPoint),
+    #[error("UtxoError - No frozen utxo found for outpoint {0}")]
+    FrozenUtxoNotFound(bitcoin::OutPoint),
+    #[error("UtxoError - Utxos are already reserved, frozen or quarantined")]
+    UtxosNotReservable,
 }
@@ -39,6 +39,8 @@ impl Utxos {
         utxo: &LocalUtxo,
         sats_per_vbyte_when_created: f32,
         self_pay: bool,
+        quarantined_source: Option<&bitcoin::Address>,
+        dust: bool,
     ) -> Result<Option<(LedgerTransactionId, Transaction<'_, Postgres>)>, UtxoError> {
         let new_utxo = NewUtxo::builder()
             .account_id(account_id)
@@ -53,6 +55,8 @@ impl Utxos {
             .bdk_spent(utxo.is_spent)
             .sats_per_vbyte_when_created(sats_per_vbyte_when_created)
             .self_pay(self_pay)
+            .quarantined_source_address(quarantined_source.map(|addr| addr.to_string()))
+            .dust(dust)
             .build()
             .expect("Could not build NewUtxo");
         let mut tx = self.pool.begin().await?;
@@ -155,6 +159,34 @@ impl Utxos {
         Ok(pending_spend_tx_id.map(|id| (id, spend_tx_id, change_spent)))
     }
 
+    #[instrument(name = "utxos.batch_spend_replaced", skip(self, tx), err)]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn batch_spend_replaced(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        wallet_id: WalletId,
+        batch_id: BatchId,
+        keychain_id: KeychainId,
+        replaced_change: OutPoint,
+        change: OutPoint,
+        change_sats: Satoshis,
+        ledger_tx_id: LedgerTransactionId,
+    ) -> Result<(), UtxoError> {
+        self.utxos
+            .replace_change_utxo(
+                tx,
+                keychain_id,
+                replaced_change,
+                change,
+                change_sats,
+                ledger_tx_id,
+            )
+            .await?;
+        self.utxos
+            .update_batch_spend_detected(tx, wallet_id, batch_id, ledger_tx_id)
+            .await
+    }
+
     #[instrument(name = "utxos.find_keychain_utxos", skip_all, err)]
     pub async fn find_keychain_utxos(
         &self,
@@ -176,11 +208,14 @@ impl Utxos {
 
         // We need to tell bdk which utxos not to select.
         // If we have included it in a batch OR
-        // it is an income address and not recorded as settled yet
+        // it is an income address and not recorded as settled yet OR
+        // it has been quarantined or frozen
         // we need to flag it to bdk
         let filtered_utxos = reservable_utxos.into_iter().filter_map(|utxo| {
             if utxo.spending_batch_id.is_some()
                 || (utxo.income_address && utxo.utxo_settled_ledger_tx_id.is_none())
+                || utxo.quarantined
+                || utxo.frozen
             {
                 Some((utxo.keychain_id, utxo.outpoint))
             } else {
@@ -205,7 +240,7 @@ impl Utxos {
         tx: &mut Transaction<'_, Postgres>,
         account_id: AccountId,
         batch_id: BatchId,
-        payout_queue_id: PayoutQueueId,
+        payout_queue_id: Option<PayoutQueueId>,
         fee_rate: bitcoin::FeeRate,
         utxos: impl IntoIterator<Item = (KeychainId, OutPoint)>,
     ) -> Result<(), UtxoError> {
@@ -214,6 +249,15 @@ impl Utxos {
             .await
     }
 
+    #[instrument(name = "utxos.unreserve_utxos_in_batch", skip_all, err)]
+    pub async fn unreserve_utxos_in_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        batch_id: BatchId,
+    ) -> Result<(), UtxoError> {
+        self.utxos.unreserve_utxos_in_batch(tx, batch_id).await
+    }
+
     pub async fn average_utxo_value(
         &self,
         wallet_id: WalletId,
@@ -247,6 +291,55 @@ impl Utxos {
         self.utxos.list_utxos_by_outpoint(utxos).await
     }
 
+    #[instrument(name = "utxos.list_quarantined", skip(self), err)]
+    pub async fn list_quarantined(
+        &self,
+        account_id: Option<AccountId>,
+    ) -> Result<Vec<QuarantinedUtxo>, UtxoError> {
+        self.utxos.list_quarantined(account_id).await
+    }
+
+    #[instrument(name = "utxos.release_quarantined", skip(self), err)]
+    pub async fn release_quarantined(
+        &self,
+        account_id: AccountId,
+        outpoint: OutPoint,
+    ) -> Result<(HeldUtxo, Transaction<'_, Postgres>), UtxoError> {
+        let mut tx = self.pool.begin().await?;
+        let utxo = self
+            .utxos
+            .release_quarantined(&mut tx, account_id, outpoint)
+            .await?;
+        Ok((utxo, tx))
+    }
+
+    #[instrument(name = "utxos.freeze", skip(self), err)]
+    pub async fn freeze(
+        &self,
+        wallet_id: WalletId,
+        outpoint: OutPoint,
+        reason: Option<String>,
+        profile_id: ProfileId,
+    ) -> Result<(HeldUtxo, Transaction<'_, Postgres>), UtxoError> {
+        let mut tx = self.pool.begin().await?;
+        let utxo = self
+            .utxos
+            .freeze(&mut tx, wallet_id, outpoint, reason, profile_id)
+            .await?;
+        Ok((utxo, tx))
+    }
+
+    #[instrument(name = "utxos.unfreeze", skip(self), err)]
+    pub async fn unfreeze(
+        &self,
+        wallet_id: WalletId,
+        outpoint: OutPoint,
+    ) -> Result<(HeldUtxo, Transaction<'_, Postgres>), UtxoError> {
+        let mut tx = self.pool.begin().await?;
+        let utxo = self.utxos.unfreeze(&mut tx, wallet_id, outpoint).await?;
+        Ok((utxo, tx))
+    }
+
     #[instrument(name = "utxos.delete_utxo", skip(self), err)]
     pub async fn delete_utxo(
         &self,
@@ -12,6 +12,8 @@ pub struct ReservableUtxo {
     pub outpoint: OutPoint,
     pub spending_batch_id: Option<BatchId>,
     pub utxo_settled_ledger_tx_id: Option<LedgerTransactionId>,
+    pub quarantined: bool,
+    pub frozen: bool,
 }
 
 #[derive(Clone)]
@@ -31,8 +33,8 @@ impl UtxoRepo {
     ) -> Result<Option<LedgerTransactionId>, UtxoError> {
         let result = sqlx::query!(
             r#"INSERT INTO bria_utxos
-               (account_id, wallet_id, keychain_id, tx_id, vout, sats_per_vbyte_when_created, self_pay, kind, address_idx, value, address, script_hex, income_detected_ledger_tx_id, bdk_spent)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+               (account_id, wallet_id, keychain_id, tx_id, vout, sats_per_vbyte_when_created, self_pay, kind, address_idx, value, address, script_hex, income_detected_ledger_tx_id, bdk_spent, quarantined, quarantined_source_address, dust, frozen, frozen_reason)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15::varchar IS NOT NULL, $15, $16, $16, CASE WHEN $16 THEN 'dust' END)
                ON CONFLICT (keychain_id, tx_id, vout) DO NOTHING"#,
           utxo.account_id as AccountId,
           utxo.wallet_id as WalletId,
@@ -48,6 +50,8 @@ impl UtxoRepo {
           utxo.script_hex,
           utxo.utxo_detected_ledger_tx_id as LedgerTransactionId,
           utxo.bdk_spent,
+          utxo.quarantined_source_address,
+          utxo.dust,
         )
         .execute(&mut *tx)
         .await?;
@@ -78,7 +82,7 @@ impl UtxoRepo {
             WHERE keychain_id = $4
               AND tx_id = $5
               AND vout = $6
-            RETURNING address_idx, value, address, income_detected_ledger_tx_id, spend_detected_ledger_tx_id"#,
+            RETURNING address_idx, value, address, income_detected_ledger_tx_id, spend_detected_ledger_tx_id, dust, quarantined, frozen"#,
             bdk_spent,
             block_height as i32,
             new_confirmed_ledger_tx_id as LedgerTransactionId,
@@ -98,6 +102,9 @@ impl UtxoRepo {
             spend_detected_ledger_tx_id: row
                 .spend_detected_ledger_tx_id
                 .map(LedgerTransactionId::from),
+            dust: row.dust,
+            quarantined: row.quarantined,
+            frozen: row.frozen,
         })
     }
 
@@ -183,6 +190,62 @@ impl UtxoRepo {
         })
     }
 
+    pub async fn replace_change_utxo(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        keychain_id: KeychainId,
+        replaced_change: OutPoint,
+        change: OutPoint,
+        change_sats: Satoshis,
+        ledger_tx_id: LedgerTransactionId,
+    ) -> Result<(), UtxoError> {
+        let result = sqlx::query!(
+            r#"UPDATE bria_utxos
+            SET tx_id = $1, vout = $2, value = $3, income_detected_ledger_tx_id = $4, modified_at = NOW()
+            WHERE keychain_id = $5
+              AND tx_id = $6
+              AND vout = $7
+              AND income_settled_ledger_tx_id IS NULL
+              AND spend_detected_ledger_tx_id IS NULL"#,
+            change.txid.to_string(),
+            change.vout as i32,
+            change_sats.into_inner(),
+            ledger_tx_id as LedgerTransactionId,
+            keychain_id as KeychainId,
+            replaced_change.txid.to_string(),
+            replaced_change.vout as i32,
+        )
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(UtxoError::UtxoDoesNotExistError);
+        }
+        Ok(())
+    }
+
+    pub async fn update_batch_spend_detected(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        wallet_id: WalletId,
+        batch_id: BatchId,
+        ledger_tx_id: LedgerTransactionId,
+    ) -> Result<(), UtxoError> {
+        sqlx::query!(
+            r#"UPDATE bria_utxos
+            SET spend_detected_ledger_tx_id = $1, modified_at = NOW()
+            WHERE wallet_id = $2
+              AND spending_batch_id = $3
+              AND spend_detected_ledger_tx_id IS NOT NULL
+              AND spend_settled_ledger_tx_id IS NULL"#,
+            ledger_tx_id as LedgerTransactionId,
+            wallet_id as WalletId,
+            batch_id as BatchId,
+        )
+        .execute(&mut *tx)
+        .await?;
+        Ok(())
+    }
+
     pub async fn find_keychain_utxos(
         &self,
         keychain_ids: impl Iterator<Item = KeychainId>,
@@ -194,7 +257,7 @@ impl UtxoRepo {
                       WHEN kind = 'external' THEN address
                       ELSE NULL
                   END as optional_address,
-                  block_height, income_detected_ledger_tx_id, income_settled_ledger_tx_id, spending_batch_id
+                  block_height, income_detected_ledger_tx_id, income_settled_ledger_tx_id, spending_batch_id, quarantined, frozen, frozen_reason
            FROM bria_utxos
            WHERE keychain_id = ANY($1) AND bdk_spent = false
            ORDER BY created_at DESC"#,
@@ -228,6 +291,9 @@ impl UtxoRepo {
                     .income_settled_ledger_tx_id
                     .map(LedgerTransactionId::from),
                 spending_batch_id: row.spending_batch_id.map(BatchId::from),
+                quarantined: row.quarantined,
+                frozen: row.frozen,
+                frozen_reason: row.frozen_reason,
             };
 
             let keychain_id = KeychainId::from(row.keychain_id);
@@ -253,7 +319,7 @@ impl UtxoRepo {
         let rows = sqlx::query!(
             r#"SELECT keychain_id,
                CASE WHEN kind = 'external' THEN true ELSE false END as income_address,
-               tx_id, vout, spending_batch_id, income_settled_ledger_tx_id
+               tx_id, vout, spending_batch_id, income_settled_ledger_tx_id, quarantined, frozen
                FROM bria_utxos
                WHERE keychain_id = ANY($1) AND bdk_spent = false
                FOR UPDATE"#,
@@ -275,6 +341,8 @@ impl UtxoRepo {
                 utxo_settled_ledger_tx_id: row
                     .income_settled_ledger_tx_id
                     .map(LedgerTransactionId::from),
+                quarantined: row.quarantined,
+                frozen: row.frozen,
             })
             .collect();
 
@@ -286,7 +354,7 @@ impl UtxoRepo {
         tx: &mut Transaction<'_, Postgres>,
         account_id: AccountId,
         batch_id: BatchId,
-        payout_queue_id: PayoutQueueId,
+        payout_queue_id: Option<PayoutQueueId>,
         fee_rate: bitcoin::FeeRate,
         utxos: impl IntoIterator<Item = (KeychainId, OutPoint)>,
     ) -> Result<(), UtxoError> {
@@ -299,22 +367,44 @@ impl UtxoRepo {
         query_builder.push_bind(payout_queue_id);
         query_builder.push(", spending_sats_per_vbyte = ");
         query_builder.push_bind(fee_rate.as_sat_per_vb());
-        query_builder.push("WHERE account_id = ");
+        query_builder.push(" WHERE account_id = ");
         query_builder.push_bind(account_id);
-        query_builder.push(" AND (keychain_id, tx_id, vout) IN");
-        query_builder.push_tuples(
-            utxos
-                .into_iter()
-                .map(|(keychain_id, utxo)| (keychain_id, utxo.txid.to_string(), utxo.vout as i32)),
-            |mut builder, (keychain_id, tx_id, vout)| {
-                builder.push_bind(keychain_id);
-                builder.push_bind(tx_id);
-                builder.push_bind(vout);
-            },
+        query_builder.push(
+            " AND spending_batch_id IS NULL AND NOT frozen AND NOT quarantined AND (keychain_id, tx_id, vout) IN",
         );
+        let utxos: Vec<_> = utxos
+            .into_iter()
+            .map(|(keychain_id, utxo)| (keychain_id, utxo.txid.to_string(), utxo.vout as i32))
+            .collect();
+        let n_utxos = utxos.len() as u64;
+        query_builder.push_tuples(utxos, |mut builder, (keychain_id, tx_id, vout)| {
+            builder.push_bind(keychain_id);
+            builder.push_bind(tx_id);
+            builder.push_bind(vout);
+        });
 
         let query = query_builder.build();
-        query.execute(&mut *tx).await?;
+        let result = query.execute(&mut *tx).await?;
+        if result.rows_affected() != n_utxos {
+            return Err(UtxoError::UtxosNotReservable);
+        }
+        Ok(())
+    }
+
+    pub async fn unreserve_utxos_in_batch(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        batch_id: BatchId,
+    ) -> Result<(), UtxoError> {
+        sqlx::query!(
+            r#"UPDATE bria_utxos
+            SET spending_batch_id = NULL, spending_payout_queue_id = NULL,
+                spending_sats_per_vbyte = NULL, modified_at = NOW()
+            WHERE spending_batch_id = $1"#,
+            batch_id as BatchId,
+        )
+        .execute(&mut *tx)
+        .await?;
         Ok(())
     }
 
@@ -366,7 +456,7 @@ impl UtxoRepo {
                       WHEN kind = 'external' THEN address
                       ELSE NULL
                   END as optional_address,
-                  block_height, income_detected_ledger_tx_id, income_settled_ledger_tx_id, spending_batch_id
+                  block_height, income_detected_ledger_tx_id, income_settled_ledger_tx_id, spending_batch_id, quarantined, frozen, frozen_reason
             FROM bria_utxos
             WHERE (keychain_id, tx_id, vout) IN"#,
         );
@@ -418,6 +508,9 @@ impl UtxoRepo {
                     .get::<Option<Uuid>, _>("spending_batch_id")
                     .map(BatchId::from),
                 block_height: row.get::<Option<i32>, _>("block_height").map(|h| h as u32),
+                quarantined: row.get("quarantined"),
+                frozen: row.get("frozen"),
+                frozen_reason: row.get("frozen_reason"),
             })
             .collect())
     }
@@ -441,6 +534,131 @@ impl UtxoRepo {
         Ok(row.and_then(|res| res.avg_value.map(Satoshis::from)))
     }
 
+    pub async fn list_quarantined(
+        &self,
+        account_id: Option<AccountId>,
+    ) -> Result<Vec<QuarantinedUtxo>, UtxoError> {
+        let rows = sqlx::query!(
+            r#"SELECT account_id, wallet_id, keychain_id, tx_id, vout, value, address, quarantined_source_address, created_at
+               FROM bria_utxos
+               WHERE quarantined = true AND bdk_spent = false AND ($1::uuid IS NULL OR account_id = $1)
+               ORDER BY created_at"#,
+            account_id.map(Uuid::from),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| QuarantinedUtxo {
+                account_id: AccountId::from(row.account_id),
+                wallet_id: WalletId::from(row.wallet_id),
+                keychain_id: KeychainId::from(row.keychain_id),
+                outpoint: OutPoint {
+                    txid: row.tx_id.parse().unwrap(),
+                    vout: row.vout as u32,
+                },
+                value: Satoshis::from(row.value),
+                address: row.address.parse().expect("couldn't parse address"),
+                source_address: row
+                    .quarantined_source_address
+                    .map(|addr| addr.parse().expect("couldn't parse address")),
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+
+    pub async fn release_quarantined(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account_id: AccountId,
+        outpoint: OutPoint,
+    ) -> Result<HeldUtxo, UtxoError> {
+        let row = sqlx::query!(
+            r#"UPDATE bria_utxos
+            SET quarantined = false, quarantine_released_at = NOW(), modified_at = NOW()
+            WHERE account_id = $1 AND tx_id = $2 AND vout = $3 AND quarantined = true
+            RETURNING wallet_id, keychain_id, value, address, income_settled_ledger_tx_id, dust, frozen"#,
+            account_id as AccountId,
+            outpoint.txid.to_string(),
+            outpoint.vout as i32,
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(UtxoError::QuarantinedUtxoNotFound(outpoint))?;
+        Ok(HeldUtxo {
+            wallet_id: WalletId::from(row.wallet_id),
+            keychain_id: KeychainId::from(row.keychain_id),
+            outpoint,
+            value: Satoshis::from(row.value),
+            address: row.address.parse().expect("couldn't parse address"),
+            settled: row.income_settled_ledger_tx_id.is_some(),
+            ledger_hold_changed: !row.dust && !row.frozen,
+        })
+    }
+
+    pub async fn freeze(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        wallet_id: WalletId,
+        outpoint: OutPoint,
+        reason: Option<String>,
+        profile_id: ProfileId,
+    ) -> Result<HeldUtxo, UtxoError> {
+        let row = sqlx::query!(
+            r#"UPDATE bria_utxos
+            SET frozen = true, frozen_reason = $1, frozen_by_profile_id = $2, frozen_at = NOW(), modified_at = NOW()
+            WHERE wallet_id = $3 AND tx_id = $4 AND vout = $5 AND frozen = false
+              AND bdk_spent = false AND spending_batch_id IS NULL
+            RETURNING keychain_id, value, address, income_settled_ledger_tx_id, dust, quarantined"#,
+            reason,
+            profile_id as ProfileId,
+            wallet_id as WalletId,
+            outpoint.txid.to_string(),
+            outpoint.vout as i32,
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(UtxoError::UnspentUtxoNotFound(outpoint))?;
+        Ok(HeldUtxo {
+            wallet_id,
+            keychain_id: KeychainId::from(row.keychain_id),
+            outpoint,
+            value: Satoshis::from(row.value),
+            address: row.address.parse().expect("couldn't parse address"),
+            settled: row.income_settled_ledger_tx_id.is_some(),
+            ledger_hold_changed: !row.dust && !row.quarantined,
+        })
+    }
+
+    pub async fn unfreeze(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        wallet_id: WalletId,
+        outpoint: OutPoint,
+    ) -> Result<HeldUtxo, UtxoError> {
+        let row = sqlx::query!(
+            r#"UPDATE bria_utxos
+            SET frozen = false, frozen_reason = NULL, frozen_by_profile_id = NULL, frozen_at = NULL, modified_at = NOW()
+            WHERE wallet_id = $1 AND tx_id = $2 AND vout = $3 AND frozen = true AND dust = false
+            RETURNING keychain_id, value, address, income_settled_ledger_tx_id, quarantined"#,
+            wallet_id as WalletId,
+            outpoint.txid.to_string(),
+            outpoint.vout as i32,
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(UtxoError::FrozenUtxoNotFound(outpoint))?;
+        Ok(HeldUtxo {
+            wallet_id,
+            keychain_id: KeychainId::from(row.keychain_id),
+            outpoint,
+            value: Satoshis::from(row.value),
+            address: row.address.parse().expect("couldn't parse address"),
+            settled: row.income_settled_ledger_tx_id.is_some(),
+            ledger_hold_changed: !row.quarantined,
+        })
+    }
+
     pub async fn delete_utxo(
         &self,
         tx: &mut Transaction<'_, Postgres>,
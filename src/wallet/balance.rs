@@ -14,6 +14,10 @@ pub struct WalletBalanceSummary {
     pub effective_pending_income: Satoshis,
     pub effective_pending_outgoing: Satoshis,
     pub effective_encumbered_outgoing: Satoshis,
+    pub dust_pending_income: Satoshis,
+    pub dust_settled: Satoshis,
+    /// Value of the unspent utxos that are held back from the effective balance
+    pub utxo_frozen: Satoshis,
 }
 
 impl From<WalletLedgerAccountBalances> for WalletBalanceSummary {
@@ -83,6 +87,22 @@ impl From<WalletLedgerAccountBalances> for WalletBalanceSummary {
                     .map(|b| b.encumbered())
                     .unwrap_or(Decimal::ZERO),
             ),
+            dust_pending_income: Satoshis::from_btc(
+                balances
+                    .dust
+                    .as_ref()
+                    .map(|b| b.pending())
+                    .unwrap_or(Decimal::ZERO),
+            ),
+            dust_settled: Satoshis::from_btc(
+                balances.dust.map(|b| b.settled()).unwrap_or(Decimal::ZERO),
+            ),
+            utxo_frozen: Satoshis::from_btc(
+                balances
+                    .frozen
+                    .map(|b| b.pending() + b.settled())
+                    .unwrap_or(Decimal::ZERO),
+            ),
         }
     }
 }
@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::primitives::Satoshis;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WalletConfig {
     pub settle_income_after_n_confs: u32,
     pub settle_change_after_n_confs: u32,
+    /// Incoming utxos below this value are routed to the dust account and frozen
+    #[serde(default)]
+    pub dust_threshold_sats: Option<Satoshis>,
 }
 
 impl WalletConfig {
@@ -22,6 +27,34 @@ impl WalletConfig {
             self.latest_income_settle_height(current_height)
         }
     }
+
+    pub fn is_dust(&self, value: Satoshis) -> bool {
+        self.dust_threshold_sats
+            .map(|threshold| value < threshold)
+            .unwrap_or(false)
+    }
+}
+
+/// Changes to a wallet config, fields that are `None` keep their current value
+#[derive(Debug, Clone, Default)]
+pub struct WalletConfigUpdate {
+    pub settle_income_after_n_confs: Option<u32>,
+    pub settle_change_after_n_confs: Option<u32>,
+    pub dust_threshold_sats: Option<Satoshis>,
+}
+
+impl WalletConfig {
+    pub fn merge(&self, update: WalletConfigUpdate) -> Self {
+        Self {
+            settle_income_after_n_confs: update
+                .settle_income_after_n_confs
+                .unwrap_or(self.settle_income_after_n_confs),
+            settle_change_after_n_confs: update
+                .settle_change_after_n_confs
+                .unwrap_or(self.settle_change_after_n_confs),
+            dust_threshold_sats: update.dust_threshold_sats.or(self.dust_threshold_sats),
+        }
+    }
 }
 
 impl Default for WalletConfig {
@@ -29,6 +62,7 @@ impl Default for WalletConfig {
         Self {
             settle_income_after_n_confs: 2,
             settle_change_after_n_confs: 1,
+            dust_threshold_sats: None,
         }
     }
 }
@@ -0,0 +1,104 @@
+use bdk::{
+    database::BatchDatabase,
+    wallet::{AddressIndex, AddressInfo},
+    FeeRate, Wallet,
+};
+
+use super::{keychain::*, psbt_builder::DEFAULT_SIGHASH_TYPE};
+use crate::{
+    bdk::error::BdkError,
+    fees,
+    primitives::{bitcoin::*, *},
+};
+
+pub struct CpfpPsbt {
+    pub keychain_id: KeychainId,
+    pub tx_id: bitcoin::Txid,
+    pub psbt: psbt::PartiallySignedTransaction,
+    pub fee_satoshis: Satoshis,
+    pub change_satoshis: Satoshis,
+    pub change_address: AddressInfo,
+    pub change_outpoint: OutPoint,
+}
+
+pub struct CpfpPsbtBuilder {
+    outpoint: OutPoint,
+    fee_rate: FeeRate,
+    input_satisfaction_weight: usize,
+    result: Option<CpfpPsbt>,
+}
+
+impl CpfpPsbtBuilder {
+    pub fn new(outpoint: OutPoint, fee_rate: FeeRate, input_satisfaction_weight: usize) -> Self {
+        Self {
+            outpoint,
+            fee_rate,
+            input_satisfaction_weight,
+            result: None,
+        }
+    }
+
+    pub fn finish(self) -> Option<CpfpPsbt> {
+        self.result
+    }
+}
+
+impl BdkWalletVisitor for CpfpPsbtBuilder {
+    fn visit_bdk_wallet<D: BatchDatabase>(
+        mut self,
+        keychain_id: KeychainId,
+        wallet: &Wallet<D>,
+    ) -> Result<Self, BdkError> {
+        let parent = match wallet.get_tx(&self.outpoint.txid, true)? {
+            Some(parent) if parent.confirmation_time.is_none() => parent,
+            _ => return Ok(self),
+        };
+        let parent_weight = parent
+            .transaction
+            .as_ref()
+            .map(|tx| tx.weight())
+            .unwrap_or(0);
+        // bdk can't know the fee of a parent funded by foreign inputs,
+        // in which case the child pays for the whole package.
+        let parent_fee = Satoshis::from(parent.fee.unwrap_or(0));
+
+        let change_address = wallet.get_internal_address(AddressIndex::LastUnused)?;
+        let fee = fees::cpfp_child_fee(
+            self.fee_rate,
+            parent_weight,
+            parent_fee,
+            self.input_satisfaction_weight,
+            &change_address.address,
+        );
+
+        let mut builder = wallet.build_tx();
+        builder
+            .add_utxo(self.outpoint)?
+            .manually_selected_only()
+            .fee_absolute(u64::from(fee))
+            .drain_to(change_address.script_pubkey())
+            .sighash(DEFAULT_SIGHASH_TYPE.into())
+            .enable_rbf();
+        let (psbt, details) = builder.finish()?;
+        let (vout, change) = psbt
+            .unsigned_tx
+            .output
+            .iter()
+            .enumerate()
+            .find(|(_, out)| out.script_pubkey == change_address.script_pubkey())
+            .expect("change output disappeared");
+        self.result = Some(CpfpPsbt {
+            keychain_id,
+            tx_id: details.txid,
+            fee_satoshis: Satoshis::from(details.fee.expect("fee must be present")),
+            change_satoshis: Satoshis::from(change.value),
+            change_outpoint: OutPoint {
+                txid: details.txid,
+                vout: vout as u32,
+            },
+            change_address,
+            psbt,
+        });
+        Ok(self)
+    }
+}
@@ -23,6 +23,8 @@ pub enum WalletEvent {
         effective_at_rest_ledger_account_id: LedgerAccountId,
         effective_outgoing_ledger_account_id: LedgerAccountId,
         dust_ledger_account_id: LedgerAccountId,
+        #[serde(default)]
+        frozen_ledger_account_id: Option<LedgerAccountId>,
     },
     NameUpdated {
         name: String,
@@ -50,7 +52,7 @@ pub struct Wallet {
     pub network: bitcoin::Network,
     pub name: String,
 
-    events: EntityEvents<WalletEvent>,
+    pub(super) events: EntityEvents<WalletEvent>,
 }
 
 impl Wallet {
@@ -107,6 +109,15 @@ impl Wallet {
         }
         ret
     }
+
+    pub fn update_config(&mut self, update: WalletConfigUpdate) {
+        let wallet_config = self.config.merge(update);
+        if self.config != wallet_config {
+            self.config = wallet_config.clone();
+            self.events
+                .push(WalletEvent::ConfigUpdated { wallet_config });
+        }
+    }
 }
 
 #[derive(Builder, Clone)]
@@ -148,6 +159,7 @@ impl NewWallet {
                 effective_at_rest_ledger_account_id: self.ledger_account_ids.effective_at_rest_id,
                 effective_outgoing_ledger_account_id: self.ledger_account_ids.effective_outgoing_id,
                 dust_ledger_account_id: self.ledger_account_ids.dust_id,
+                frozen_ledger_account_id: Some(self.ledger_account_ids.frozen_id),
             },
             WalletEvent::NameUpdated { name: self.name },
             WalletEvent::ConfigUpdated {
@@ -183,6 +195,7 @@ impl TryFrom<EntityEvents<WalletEvent>> for Wallet {
                     effective_at_rest_ledger_account_id,
                     effective_outgoing_ledger_account_id,
                     dust_ledger_account_id,
+                    frozen_ledger_account_id,
                     ..
                 } => {
                     builder = builder
@@ -198,6 +211,8 @@ impl TryFrom<EntityEvents<WalletEvent>> for Wallet {
                             effective_at_rest_id: *effective_at_rest_ledger_account_id,
                             effective_outgoing_id: *effective_outgoing_ledger_account_id,
                             dust_id: *dust_ledger_account_id,
+                            frozen_id: frozen_ledger_account_id
+                                .unwrap_or_else(|| WalletLedgerAccountIds::from(*id).frozen_id),
                         });
                 }
                 ConfigUpdated {
@@ -1,5 +1,6 @@
 pub mod balance;
 mod config;
+mod cpfp_psbt_builder;
 mod entity;
 pub mod error;
 mod keychain;
@@ -9,6 +10,7 @@ mod repo;
 
 pub use balance::*;
 pub use config::*;
+pub use cpfp_psbt_builder::*;
 pub use entity::*;
 pub use keychain::*;
 pub use psbt_builder::*;
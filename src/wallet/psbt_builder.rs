@@ -1,7 +1,7 @@
 use bdk::{
     database::BatchDatabase,
     wallet::tx_builder::TxOrdering,
-    wallet::{AddressIndex, AddressInfo},
+    wallet::{AddressIndex, AddressInfo, IsDust},
     FeeRate, Wallet,
 };
 use std::{
@@ -32,6 +32,7 @@ pub struct WalletTotals {
 
 pub struct FinishedPsbtBuild {
     pub included_payouts: HashMap<WalletId, Vec<(TxPayout, u32)>>,
+    pub skipped_payouts: HashMap<WalletId, Vec<(TxPayout, PayoutSkipReason)>>,
     pub included_utxos: HashMap<WalletId, HashMap<KeychainId, Vec<bitcoin::OutPoint>>>,
     pub included_wallet_keychains: HashMap<KeychainId, WalletId>,
     pub wallet_totals: HashMap<WalletId, WalletTotals>,
@@ -185,6 +186,7 @@ impl PsbtBuilder<InitialPsbtBuilderState> {
             input_weights: HashMap::new(),
             result: FinishedPsbtBuild {
                 included_payouts: HashMap::new(),
+                skipped_payouts: HashMap::new(),
                 included_utxos: HashMap::new(),
                 included_wallet_keychains: HashMap::new(),
                 wallet_totals: HashMap::new(),
@@ -334,17 +336,40 @@ impl BdkWalletVisitor for PsbtBuilder<AcceptingCurrentKeychainState> {
             .expect("Unsupported descriptor");
         let change_address = wallet.get_internal_address(AddressIndex::LastUnused)?;
 
-        let mut max_payout = 0;
-        while max_payout < self.current_payouts.len()
-            && self.try_build_current_wallet_psbt(
-                current_keychain_id,
-                &self.current_payouts[..=max_payout],
-                wallet,
-            )?
+        let wallet_id = self.current_wallet.expect("current wallet must be set");
+        let mut payouts = Vec::new();
+        for payout in std::mem::take(&mut self.current_payouts) {
+            if u64::from(payout.2).is_dust(&payout.1.script_pubkey()) {
+                self.result
+                    .skipped_payouts
+                    .entry(wallet_id)
+                    .or_default()
+                    .push((payout, PayoutSkipReason::Dust));
+            } else {
+                payouts.push(payout);
+            }
+        }
+        // Only fall back to adding payouts one by one when they don't all fit
+        if !payouts.is_empty()
+            && self
+                .try_build_current_wallet_psbt(current_keychain_id, &payouts, wallet)?
+                .is_some()
         {
-            max_payout += 1;
+            for payout in std::mem::take(&mut payouts) {
+                payouts.push(payout);
+                if let Some(reason) =
+                    self.try_build_current_wallet_psbt(current_keychain_id, &payouts, wallet)?
+                {
+                    let payout = payouts.pop().expect("payout was just pushed");
+                    self.result
+                        .skipped_payouts
+                        .entry(wallet_id)
+                        .or_default()
+                        .push((payout, reason));
+                }
+            }
         }
-        if max_payout == 0 {
+        if payouts.is_empty() {
             return Ok(self);
         }
 
@@ -363,7 +388,7 @@ impl BdkWalletVisitor for PsbtBuilder<AcceptingCurrentKeychainState> {
         builder.sighash(DEFAULT_SIGHASH_TYPE.into());
 
         let mut total_output_satoshis = Satoshis::from(0);
-        for (payout_id, destination, satoshis) in self.current_payouts.drain(..max_payout) {
+        for (payout_id, destination, satoshis) in payouts {
             total_output_satoshis += satoshis;
             builder.add_recipient(destination.script_pubkey(), u64::from(satoshis));
             self.result
@@ -421,11 +446,11 @@ impl BdkWalletVisitor for PsbtBuilder<AcceptingCurrentKeychainState> {
         }
 
         builder.ordering(TxOrdering::Bip69Lexicographic);
+        builder.enable_rbf();
         match builder.finish() {
             Ok((psbt, details)) => {
                 let fee_satoshis = Satoshis::from(details.fee.expect("fee must be present"));
                 let current_wallet_fee = fee_satoshis - self.result.fee_satoshis;
-                let wallet_id = self.current_wallet.expect("current wallet must be set");
                 let change_satoshis = Satoshis::from(
                     psbt.unsigned_tx
                         .output
@@ -506,7 +531,7 @@ impl PsbtBuilder<AcceptingCurrentKeychainState> {
         keychain_id: KeychainId,
         payouts: &[TxPayout],
         wallet: &Wallet<D>,
-    ) -> Result<bool, BdkError> {
+    ) -> Result<Option<PayoutSkipReason>, BdkError> {
         let mut builder = wallet.build_tx();
         builder.fee_rate(self.fee_rate.expect("fee rate must be set"));
 
@@ -538,8 +563,11 @@ impl PsbtBuilder<AcceptingCurrentKeychainState> {
         }
 
         match builder.finish() {
-            Ok(_) => Ok(true),
-            Err(bdk::Error::InsufficientFunds { .. }) => Ok(false),
+            Ok(_) => Ok(None),
+            Err(bdk::Error::InsufficientFunds { .. }) => {
+                Ok(Some(PayoutSkipReason::InsufficientFunds))
+            }
+            Err(bdk::Error::OutputBelowDustLimit(_)) => Ok(Some(PayoutSkipReason::Dust)),
             Err(e) => Err(e.into()),
         }
     }
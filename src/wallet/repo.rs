@@ -145,4 +145,20 @@ impl Wallets {
         }
         Ok(wallets)
     }
+
+    pub async fn update(&self, wallet: Wallet) -> Result<(), WalletError> {
+        if !wallet.events.is_dirty() {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        EntityEvents::<WalletEvent>::persist(
+            "bria_wallet_events",
+            &mut tx,
+            wallet.events.new_serialized_events(wallet.id),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
 }
@@ -21,6 +21,13 @@ pub enum XPubEvent {
     },
 }
 
+#[derive(Debug, Clone)]
+pub struct SignerHealth {
+    pub healthy: bool,
+    pub error: Option<String>,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Builder)]
 #[builder(pattern = "owned", build_fn(error = "EntityError"))]
 pub struct AccountXPub {
@@ -29,6 +36,8 @@ pub struct AccountXPub {
     pub value: XPubValue,
     pub original: String,
     pub(super) encrypted_signer_config: Option<(ConfigCyper, Nonce)>,
+    #[builder(default)]
+    pub(super) signer_health: Option<SignerHealth>,
     pub(super) db_uuid: uuid::Uuid,
     pub(super) events: EntityEvents<XPubEvent>,
 }
@@ -43,10 +52,50 @@ impl AccountXPub {
         config: SignerConfig,
         secret: &EncryptionKey,
     ) -> Result<(), XPubError> {
+        if let SignerConfig::LocalXpriv(ref cfg) = config {
+            LocalXprivSigner::new(cfg, &self.value)?;
+        }
         self.encrypted_signer_config = Some(config.encrypt(secret)?);
+        self.signer_health = None;
         Ok(())
     }
 
+    pub fn signer_health(&self) -> Option<&SignerHealth> {
+        self.signer_health.as_ref()
+    }
+
+    pub async fn check_signer_health(
+        &mut self,
+        key: EncryptionKey,
+    ) -> Result<&SignerHealth, XPubError> {
+        let check = async {
+            match self
+                .remote_signing_client(key, &SigningContext::health_check(self.account_id))
+                .await
+            {
+                Ok(Some(mut client)) => Some(check_signer(client.as_mut(), &self.value).await),
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            }
+        };
+        let result = match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, check).await {
+            Ok(Some(result)) => result,
+            Ok(None) if !self.has_signer_config() => return Err(XPubError::SignerConfigMissing),
+            Ok(None) => Err(SigningClientError::HealthCheckFailed(
+                "signer config could not be decrypted".to_string(),
+            )),
+            Err(_) => Err(SigningClientError::HealthCheckFailed(format!(
+                "timed out after {}s",
+                HEALTH_CHECK_TIMEOUT.as_secs()
+            ))),
+        };
+        Ok(self.signer_health.insert(SignerHealth {
+            healthy: result.is_ok(),
+            error: result.err().map(|e| e.to_string()),
+            checked_at: chrono::Utc::now(),
+        }))
+    }
+
     pub fn signing_cfg(&self, key: EncryptionKey) -> Option<SignerConfig> {
         self.encrypted_signer_config
             .as_ref()
@@ -64,6 +113,7 @@ impl AccountXPub {
     pub async fn remote_signing_client(
         &self,
         key: EncryptionKey,
+        context: &SigningContext,
     ) -> Result<Option<Box<dyn RemoteSigningClient + 'static>>, SigningClientError> {
         let client = match self.signing_cfg(key) {
             Some(SignerConfig::Lnd(ref cfg)) => {
@@ -74,6 +124,14 @@ impl AccountXPub {
                 let client = BitcoindRemoteSigner::connect(cfg).await?;
                 Some(Box::new(client) as Box<dyn RemoteSigningClient + 'static>)
             }
+            Some(SignerConfig::LocalXpriv(ref cfg)) => {
+                let client = LocalXprivSigner::new(cfg, &self.value)?;
+                Some(Box::new(client) as Box<dyn RemoteSigningClient + 'static>)
+            }
+            Some(SignerConfig::Webhook(ref cfg)) => {
+                let client = WebhookRemoteSigner::new(cfg, context.clone())?;
+                Some(Box::new(client) as Box<dyn RemoteSigningClient + 'static>)
+            }
             None => None,
         };
         Ok(client)
@@ -120,11 +178,21 @@ impl NewAccountXPub {
     }
 }
 
-impl TryFrom<(EntityEvents<XPubEvent>, Option<(ConfigCyper, Nonce)>)> for AccountXPub {
+impl
+    TryFrom<(
+        EntityEvents<XPubEvent>,
+        Option<(ConfigCyper, Nonce)>,
+        Option<SignerHealth>,
+    )> for AccountXPub
+{
     type Error = EntityError;
 
     fn try_from(
-        (events, config): (EntityEvents<XPubEvent>, Option<(ConfigCyper, Nonce)>),
+        (events, config, signer_health): (
+            EntityEvents<XPubEvent>,
+            Option<(ConfigCyper, Nonce)>,
+            Option<SignerHealth>,
+        ),
     ) -> Result<Self, Self::Error> {
         let mut builder = AccountXPubBuilder::default();
         for event in events.iter() {
@@ -156,6 +224,6 @@ impl TryFrom<(EntityEvents<XPubEvent>, Option<(ConfigCyper, Nonce)>)> for Accoun
         } else {
             builder = builder.encrypted_signer_config(None);
         }
-        builder.events(events).build()
+        builder.signer_health(signer_health).events(events).build()
     }
 }
@@ -18,6 +18,10 @@ pub enum XPubError {
     Bip32(#[from] crate::primitives::bitcoin::bip32::Error),
     #[error("XPubError - UnsupportedPubKeyType")]
     UnsupportedPubKeyType,
+    #[error("XPubError - SigningClient: {0}")]
+    SigningClient(#[from] super::signing_client::SigningClientError),
+    #[error("XPubError - SignerConfigMissing")]
+    SignerConfigMissing,
     #[error("Could not decrypt signer config: {0}")]
     CouldNotDecryptSignerConfig(chacha20poly1305::Error),
 }
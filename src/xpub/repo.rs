@@ -75,7 +75,10 @@ impl XPubs {
                 INSERT INTO bria_xpub_signer_configs (id, cypher, nonce, created_at, modified_at)
                 VALUES ($1, $2, $3, NOW(), NOW())
                 ON CONFLICT (id) DO UPDATE 
-                SET cypher = $2, nonce = $3, modified_at = NOW()
+                SET cypher = $2, nonce = $3, modified_at = NOW(),
+                  healthy = CASE WHEN bria_xpub_signer_configs.cypher = $2 THEN bria_xpub_signer_configs.healthy END,
+                  health_error = CASE WHEN bria_xpub_signer_configs.cypher = $2 THEN bria_xpub_signer_configs.health_error END,
+                  health_checked_at = CASE WHEN bria_xpub_signer_configs.cypher = $2 THEN bria_xpub_signer_configs.health_checked_at END
                 "#,
                 xpub.db_uuid,
                 cypher_bytes,
@@ -88,6 +91,29 @@ impl XPubs {
         Ok(())
     }
 
+    pub async fn record_signer_health(&self, xpub: &AccountXPub) -> Result<(), XPubError> {
+        if let (Some((cypher, _)), Some(health)) = (
+            xpub.encrypted_signer_config.as_ref(),
+            xpub.signer_health.as_ref(),
+        ) {
+            sqlx::query!(
+                r#"
+                UPDATE bria_xpub_signer_configs
+                SET healthy = $2, health_error = $3, health_checked_at = $4
+                WHERE id = $1 AND cypher = $5
+                "#,
+                xpub.db_uuid,
+                health.healthy,
+                health.error,
+                health.checked_at,
+                &cypher.0,
+            )
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
     pub async fn find_from_ref(
         &self,
         account_id: AccountId,
@@ -133,7 +159,7 @@ impl XPubs {
 
         let config_row = sqlx::query!(
             r#"
-            SELECT cypher, nonce
+            SELECT cypher, nonce, healthy, health_error, health_checked_at
             FROM bria_xpub_signer_configs
             WHERE id = $1
             "#,
@@ -142,12 +168,15 @@ impl XPubs {
         .fetch_optional(&self.pool)
         .await?;
 
-        let config = match config_row {
-            Some(row) => Some((ConfigCyper(row.cypher), Nonce(row.nonce))),
-            None => None,
+        let (config, health) = match config_row {
+            Some(row) => (
+                Some((ConfigCyper(row.cypher), Nonce(row.nonce))),
+                signer_health(row.healthy, row.health_error, row.health_checked_at),
+            ),
+            None => (None, None),
         };
 
-        Ok(AccountXPub::try_from((events, config))?)
+        Ok(AccountXPub::try_from((events, config, health))?)
     }
 
     pub async fn list_xpubs(&self, account_id: AccountId) -> Result<Vec<AccountXPub>, XPubError> {
@@ -166,7 +195,7 @@ impl XPubs {
 
         let config_rows = sqlx::query!(
             r#"
-            SELECT id, cypher, nonce
+            SELECT id, cypher, nonce, healthy, health_error, health_checked_at
             FROM bria_xpub_signer_configs
             WHERE id = ANY($1)
             "#,
@@ -175,9 +204,17 @@ impl XPubs {
         .fetch_all(&self.pool)
         .await?;
 
-        let mut config_map: HashMap<Uuid, (ConfigCyper, Nonce)> = config_rows
+        let mut config_map: HashMap<Uuid, _> = config_rows
             .into_iter()
-            .map(|row| (row.id, (ConfigCyper(row.cypher), Nonce(row.nonce))))
+            .map(|row| {
+                (
+                    row.id,
+                    (
+                        (ConfigCyper(row.cypher), Nonce(row.nonce)),
+                        signer_health(row.healthy, row.health_error, row.health_checked_at),
+                    ),
+                )
+            })
             .collect();
 
         let mut entity_events = HashMap::new();
@@ -189,8 +226,11 @@ impl XPubs {
 
         let mut xpubs = Vec::new();
         for (id, events) in entity_events {
-            let config = config_map.remove(&id);
-            let xpub = AccountXPub::try_from((events, config))?;
+            let (config, health) = match config_map.remove(&id) {
+                Some((config, health)) => (Some(config), health),
+                None => (None, None),
+            };
+            let xpub = AccountXPub::try_from((events, config, health))?;
             xpubs.push(xpub);
         }
 
@@ -208,16 +248,24 @@ impl XPubs {
         .await?;
         let config_rows = sqlx::query!(
             r#"
-            SELECT id, cypher, nonce
+            SELECT id, cypher, nonce, healthy, health_error, health_checked_at
             FROM bria_xpub_signer_configs
             "#,
         )
         .fetch_all(&self.pool)
         .await?;
 
-        let mut config_map: HashMap<Uuid, (ConfigCyper, Nonce)> = config_rows
+        let mut config_map: HashMap<Uuid, _> = config_rows
             .into_iter()
-            .map(|row| (row.id, (ConfigCyper(row.cypher), Nonce(row.nonce))))
+            .map(|row| {
+                (
+                    row.id,
+                    (
+                        (ConfigCyper(row.cypher), Nonce(row.nonce)),
+                        signer_health(row.healthy, row.health_error, row.health_checked_at),
+                    ),
+                )
+            })
             .collect();
 
         let mut entity_events = HashMap::new();
@@ -229,11 +277,29 @@ impl XPubs {
 
         let mut xpubs = Vec::new();
         for (id, events) in entity_events {
-            let config = config_map.remove(&id);
-            let xpub = AccountXPub::try_from((events, config))?;
+            let (config, health) = match config_map.remove(&id) {
+                Some((config, health)) => (Some(config), health),
+                None => (None, None),
+            };
+            let xpub = AccountXPub::try_from((events, config, health))?;
             xpubs.push(xpub);
         }
 
         Ok(xpubs)
     }
 }
+
+fn signer_health(
+    healthy: Option<bool>,
+    error: Option<String>,
+    checked_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Option<SignerHealth> {
+    match (healthy, checked_at) {
+        (Some(healthy), Some(checked_at)) => Some(SignerHealth {
+            healthy,
+            error,
+            checked_at,
+        }),
+        _ => None,
+    }
+}
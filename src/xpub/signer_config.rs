@@ -23,6 +23,8 @@ pub struct SignerEncryptionConfig {
 pub enum SignerConfig {
     Lnd(LndSignerConfig),
     Bitcoind(BitcoindSignerConfig),
+    LocalXpriv(LocalXprivSignerConfig),
+    Webhook(WebhookSignerConfig),
 }
 
 impl SignerConfig {
@@ -86,6 +88,12 @@ impl std::fmt::Debug for SignerConfig {
             SignerConfig::Bitcoind(config) => {
                 write!(f, "SignerConfig::Bitcoind(endpoint={})", config.endpoint)
             }
+            SignerConfig::LocalXpriv(_) => {
+                write!(f, "SignerConfig::LocalXpriv(xpriv=*******Redacted*******)")
+            }
+            SignerConfig::Webhook(config) => {
+                write!(f, "SignerConfig::Webhook(endpoint={})", config.endpoint)
+            }
         }
     }
 }
@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+use crate::primitives::*;
+
+/// Information about the batch being signed that is made available to signers
+#[derive(Debug, Clone, Serialize)]
+pub struct SigningContext {
+    pub account_id: AccountId,
+    /// None when the signer is only being health checked
+    pub batch_id: Option<BatchId>,
+    pub payouts: Vec<SigningContextPayout>,
+}
+
+impl SigningContext {
+    pub fn health_check(account_id: AccountId) -> Self {
+        Self {
+            account_id,
+            batch_id: None,
+            payouts: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SigningContextPayout {
+    pub id: PayoutId,
+    pub wallet_id: WalletId,
+    pub destination: String,
+    pub satoshis: u64,
+    pub external_id: String,
+}
@@ -16,4 +16,10 @@ pub enum SigningClientError {
     HexConvert(String),
     #[error("SigningClientError - IO: {0}")]
     IO(#[from] std::io::Error),
+    #[error("SigningClientError - InvalidXpriv: {0}")]
+    InvalidXpriv(String),
+    #[error("SigningClientError - HealthCheckFailed: {0}")]
+    HealthCheckFailed(String),
+    #[error("SigningClientError - Signer: {0}")]
+    Signer(#[from] bdk::signer::SignerError),
 }
@@ -0,0 +1,100 @@
+use bdk::bitcoin::{
+    secp256k1::Secp256k1, util::bip32::ChildNumber, OutPoint, PackedLockTime, PublicKey, Script,
+    Sequence, Transaction, TxIn, TxOut, Witness,
+};
+
+use super::{error::*, r#trait::*};
+use crate::{
+    primitives::bitcoin::{psbt, DerivationPath},
+    xpub::XPub,
+};
+
+const DUMMY_UTXO_SATS: u64 = 100_000;
+/// Upper bound on connecting to and getting a signature from a signer during a health check
+pub const HEALTH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Asks the signer to sign a psbt spending a made up utxo locked to the first
+/// external address of the xpub. Nothing is ever broadcast.
+pub async fn check_signer(
+    client: &mut dyn RemoteSigningClient,
+    xpub: &XPub,
+) -> Result<(), SigningClientError> {
+    let (unsigned_psbt, pubkey) = dummy_psbt(xpub)?;
+    let signed_psbt = client.sign_psbt(&unsigned_psbt).await?;
+    let input = &signed_psbt.inputs[0];
+    if input.final_script_witness.is_some()
+        || input.partial_sigs.keys().any(|key| key.inner == pubkey)
+    {
+        Ok(())
+    } else {
+        Err(SigningClientError::HealthCheckFailed(format!(
+            "signer did not sign for xpub {}",
+            xpub.id()
+        )))
+    }
+}
+
+fn dummy_psbt(
+    xpub: &XPub,
+) -> Result<
+    (
+        psbt::PartiallySignedTransaction,
+        bdk::bitcoin::secp256k1::PublicKey,
+    ),
+    SigningClientError,
+> {
+    let secp = Secp256k1::verification_only();
+    let path = DerivationPath::from(vec![
+        ChildNumber::Normal { index: 0 },
+        ChildNumber::Normal { index: 0 },
+    ]);
+    let pubkey = xpub
+        .inner()
+        .derive_pub(&secp, &path)
+        .map_err(|e| SigningClientError::HealthCheckFailed(e.to_string()))?
+        .public_key;
+    let script_pubkey = Script::new_v0_p2wpkh(
+        &PublicKey::new(pubkey)
+            .wpubkey_hash()
+            .expect("key is compressed"),
+    );
+    let funding = Transaction {
+        version: 2,
+        lock_time: PackedLockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: Script::new(),
+            sequence: Sequence::MAX,
+            witness: Witness::default(),
+        }],
+        output: vec![TxOut {
+            value: DUMMY_UTXO_SATS,
+            script_pubkey: script_pubkey.clone(),
+        }],
+    };
+    let spend = Transaction {
+        version: 2,
+        lock_time: PackedLockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::new(funding.txid(), 0),
+            script_sig: Script::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::default(),
+        }],
+        output: vec![TxOut {
+            value: DUMMY_UTXO_SATS / 2,
+            script_pubkey,
+        }],
+    };
+    let mut psbt = psbt::PartiallySignedTransaction::from_unsigned_tx(spend)
+        .expect("unsigned tx has no signatures");
+    // Mirror the key origin that bdk derives from the keychain descriptors
+    let key_source = match xpub.derivation {
+        Some(ref derivation) => (xpub.parent_fingerprint, derivation.extend(path.as_ref())),
+        None => (xpub.fingerprint(), path),
+    };
+    psbt.inputs[0].witness_utxo = Some(funding.output[0].clone());
+    psbt.inputs[0].non_witness_utxo = Some(funding);
+    psbt.inputs[0].bip32_derivation.insert(pubkey, key_source);
+    Ok((psbt, pubkey))
+}
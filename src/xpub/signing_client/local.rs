@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use bdk::{
+    bitcoin::{
+        secp256k1::Secp256k1,
+        util::bip32::{ChildNumber, ExtendedPrivKey},
+    },
+    miniscript::descriptor::{DescriptorXKey, Wildcard},
+    signer::{SignerContext, SignerWrapper, TransactionSigner},
+    SignOptions,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{error::*, r#trait::*};
+use crate::{
+    primitives::bitcoin::{psbt, DerivationPath, ExtendedPubKey},
+    xpub::XPub,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalXprivSignerConfig {
+    pub xpriv: String,
+}
+
+impl LocalXprivSignerConfig {
+    pub fn xpriv(&self) -> Result<ExtendedPrivKey, SigningClientError> {
+        self.xpriv
+            .parse()
+            .map_err(|e| SigningClientError::InvalidXpriv(format!("{e}")))
+    }
+}
+
+pub struct LocalXprivSigner {
+    keychains: Vec<SignerWrapper<DescriptorXKey<ExtendedPrivKey>>>,
+}
+
+impl LocalXprivSigner {
+    pub fn new(cfg: &LocalXprivSignerConfig, xpub: &XPub) -> Result<Self, SigningClientError> {
+        let xpriv = cfg.xpriv()?;
+        if ExtendedPubKey::from_priv(&Secp256k1::new(), &xpriv) != *xpub.inner() {
+            return Err(SigningClientError::InvalidXpriv(format!(
+                "xpriv does not belong to xpub {}",
+                xpub.id()
+            )));
+        }
+        // One signer per keychain branch (<xpub>/0/* and <xpub>/1/*)
+        let keychains = [0, 1]
+            .into_iter()
+            .map(|branch| {
+                let key = DescriptorXKey {
+                    origin: xpub
+                        .derivation
+                        .clone()
+                        .map(|path| (xpub.parent_fingerprint, path)),
+                    xkey: xpriv,
+                    derivation_path: DerivationPath::from(vec![ChildNumber::Normal {
+                        index: branch,
+                    }]),
+                    wildcard: Wildcard::Unhardened,
+                };
+                SignerWrapper::new(key, SignerContext::Segwitv0)
+            })
+            .collect();
+        Ok(Self { keychains })
+    }
+}
+
+#[async_trait]
+impl RemoteSigningClient for LocalXprivSigner {
+    async fn sign_psbt(
+        &mut self,
+        psbt: &psbt::PartiallySignedTransaction,
+    ) -> Result<psbt::PartiallySignedTransaction, SigningClientError> {
+        let mut psbt = psbt.clone();
+        let sign_options = SignOptions {
+            try_finalize: false,
+            ..Default::default()
+        };
+        let secp = Secp256k1::new();
+        for signer in self.keychains.iter() {
+            signer.sign_transaction(&mut psbt, &sign_options, &secp)?;
+        }
+        Ok(psbt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bdk::bitcoin::{
+        OutPoint, PackedLockTime, Script, Sequence, Transaction, TxIn, TxOut, Witness,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn sign_psbt() {
+        let secp = Secp256k1::new();
+        let master: ExtendedPrivKey = "tprv8ZgxMBicQKsPd3krDUsBAmtnRsK3rb8u5yi1zhQgMhF1tR8MW7xfE4rnrbbsrbPR52e7rKapu6ztw1jXveJSCGHEriUGZV7mCe88duLp5pj".parse().unwrap();
+        let derivation: DerivationPath = "m/84'/1'/0'".parse().unwrap();
+        let account = master.derive_priv(&secp, &derivation).unwrap();
+        let xpub = XPub {
+            derivation: Some(derivation.clone()),
+            inner: ExtendedPubKey::from_priv(&secp, &account),
+        };
+
+        let path: DerivationPath = "m/0/0".parse().unwrap();
+        let pubkey = xpub.inner().derive_pub(&secp, &path).unwrap().public_key;
+        let script_pubkey =
+            Script::new_v0_p2wpkh(&bdk::bitcoin::PublicKey::new(pubkey).wpubkey_hash().unwrap());
+        let funding = Transaction {
+            version: 2,
+            lock_time: PackedLockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: 50_000,
+                script_pubkey: script_pubkey.clone(),
+            }],
+        };
+        let spend = Transaction {
+            version: 2,
+            lock_time: PackedLockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(funding.txid(), 0),
+                script_sig: Script::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::default(),
+            }],
+            output: vec![TxOut {
+                value: 49_000,
+                script_pubkey,
+            }],
+        };
+        let mut unsigned_psbt = psbt::PartiallySignedTransaction::from_unsigned_tx(spend).unwrap();
+        unsigned_psbt.inputs[0].witness_utxo = Some(funding.output[0].clone());
+        unsigned_psbt.inputs[0].non_witness_utxo = Some(funding);
+        unsigned_psbt.inputs[0].bip32_derivation.insert(
+            pubkey,
+            (xpub.parent_fingerprint, derivation.extend(path.as_ref())),
+        );
+
+        let cfg = LocalXprivSignerConfig {
+            xpriv: account.to_string(),
+        };
+        let mut signer = LocalXprivSigner::new(&cfg, &xpub).unwrap();
+        let signed_psbt = signer.sign_psbt(&unsigned_psbt).await.unwrap();
+        assert_eq!(signed_psbt.inputs[0].partial_sigs.len(), 1);
+
+        let wrong_key = LocalXprivSignerConfig {
+            xpriv: master.to_string(),
+        };
+        assert!(LocalXprivSigner::new(&wrong_key, &xpub).is_err());
+    }
+}
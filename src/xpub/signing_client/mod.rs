@@ -1,9 +1,17 @@
 mod bitcoind;
+mod context;
 mod error;
+mod health_check;
 mod lnd;
+mod local;
 mod r#trait;
+mod webhook;
 
 pub use bitcoind::*;
+pub use context::*;
 pub use error::*;
+pub use health_check::*;
 pub use lnd::*;
+pub use local::*;
 pub use r#trait::*;
+pub use webhook::*;
@@ -0,0 +1,261 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine};
+use bdk::bitcoin::hashes::{
+    hmac::{Hmac, HmacEngine},
+    sha256, Hash, HashEngine,
+};
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+use super::{context::*, error::*, r#trait::*};
+use crate::primitives::bitcoin::{consensus, psbt};
+
+pub const SIGNATURE_HEADER: &str = "X-Bria-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Bria-Timestamp";
+/// Receivers should reject requests whose timestamp is further than this from their own clock
+pub const TIMESTAMP_TOLERANCE: Duration = Duration::from_secs(300);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookSignerConfig {
+    pub endpoint: String,
+    pub hmac_secret: String,
+}
+
+#[derive(Serialize)]
+struct WebhookSigningRequest<'a> {
+    psbt: String,
+    #[serde(flatten)]
+    context: &'a SigningContext,
+}
+
+#[derive(Deserialize)]
+struct WebhookSigningResponse {
+    signed_psbt: String,
+}
+
+pub struct WebhookRemoteSigner {
+    inner: reqwest::Client,
+    cfg: WebhookSignerConfig,
+    context: SigningContext,
+}
+
+impl WebhookRemoteSigner {
+    pub fn new(
+        cfg: &WebhookSignerConfig,
+        context: SigningContext,
+    ) -> Result<Self, SigningClientError> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                SigningClientError::CouldNotConnect(format!("Failed to build webhook client: {e}"))
+            })?;
+        Ok(Self {
+            inner: client,
+            cfg: cfg.clone(),
+            context,
+        })
+    }
+}
+
+/// Hex encoded HMAC-SHA256 of `<timestamp>.<body>` keyed with the shared secret
+pub fn webhook_signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut engine = HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+    engine.input(timestamp.to_string().as_bytes());
+    engine.input(b".");
+    engine.input(body);
+    hex::encode(Hmac::<sha256::Hash>::from_engine(engine).into_inner())
+}
+
+/// Checks a received request the way a webhook signer is expected to
+pub fn verify_webhook_signature(
+    secret: &str,
+    timestamp: i64,
+    body: &[u8],
+    signature: &str,
+    now: i64,
+) -> bool {
+    timestamp.abs_diff(now) <= TIMESTAMP_TOLERANCE.as_secs()
+        && webhook_signature(secret, timestamp, body) == signature
+}
+
+#[async_trait]
+impl RemoteSigningClient for WebhookRemoteSigner {
+    async fn sign_psbt(
+        &mut self,
+        psbt: &psbt::PartiallySignedTransaction,
+    ) -> Result<psbt::PartiallySignedTransaction, SigningClientError> {
+        let body = serde_json::to_vec(&WebhookSigningRequest {
+            psbt: general_purpose::STANDARD.encode(consensus::encode::serialize(psbt)),
+            context: &self.context,
+        })
+        .expect("Couldn't serialize webhook signing request");
+        let timestamp = chrono::Utc::now().timestamp();
+        let response = self
+            .inner
+            .post(&self.cfg.endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp)
+            .header(
+                SIGNATURE_HEADER,
+                webhook_signature(&self.cfg.hmac_secret, timestamp, &body),
+            )
+            .body(body)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|e| {
+                SigningClientError::RemoteCallFailure(format!(
+                    "Failed to sign psbt via webhook @ {}: {e}",
+                    self.cfg.endpoint
+                ))
+            })?;
+        let response: WebhookSigningResponse = response.json().await.map_err(|e| {
+            SigningClientError::RemoteCallFailure(format!(
+                "Failed to parse webhook signing response: {e}"
+            ))
+        })?;
+
+        let signed_psbt = general_purpose::STANDARD.decode(response.signed_psbt)?;
+        let signed_psbt: psbt::PartiallySignedTransaction =
+            consensus::encode::deserialize(&signed_psbt)?;
+        if signed_psbt.unsigned_tx != psbt.unsigned_tx {
+            return Err(SigningClientError::RemoteCallFailure(format!(
+                "Webhook @ {} returned a psbt for a different transaction",
+                self.cfg.endpoint
+            )));
+        }
+        Ok(signed_psbt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server,
+    };
+
+    use std::convert::Infallible;
+
+    use super::*;
+    use crate::primitives::*;
+
+    const SECRET: &str = "webhook-secret";
+
+    async fn handle(req: Request<Body>) -> Result<Response<Body>, Infallible> {
+        let header = |name| {
+            req.headers()
+                .get(name)
+                .map(|v: &hyper::header::HeaderValue| v.to_str().unwrap().to_string())
+                .unwrap_or_default()
+        };
+        let signature = header(SIGNATURE_HEADER);
+        let timestamp = header(TIMESTAMP_HEADER).parse().unwrap_or_default();
+        let tamper = req.uri().path() == "/tamper";
+        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+        if !verify_webhook_signature(
+            SECRET,
+            timestamp,
+            &body,
+            &signature,
+            chrono::Utc::now().timestamp(),
+        ) {
+            return Ok(Response::builder().status(401).body(Body::empty()).unwrap());
+        }
+        let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(request["payouts"][0]["satoshis"], 10_000);
+        let mut signed_psbt: psbt::PartiallySignedTransaction = consensus::encode::deserialize(
+            &general_purpose::STANDARD
+                .decode(request["psbt"].as_str().unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        if tamper {
+            signed_psbt.unsigned_tx.version = 1;
+        }
+        let response = serde_json::json!({
+            "signed_psbt": general_purpose::STANDARD.encode(consensus::encode::serialize(&signed_psbt))
+        });
+        Ok(Response::new(Body::from(response.to_string())))
+    }
+
+    fn context() -> SigningContext {
+        SigningContext {
+            account_id: AccountId::new(),
+            batch_id: Some(BatchId::new()),
+            payouts: vec![SigningContextPayout {
+                id: PayoutId::new(),
+                wallet_id: WalletId::new(),
+                destination: "bcrt1q9y9ezufqzrxm7qkz3uq9ef7f4a6ed3xhcfq5mn".to_string(),
+                satoshis: 10_000,
+                external_id: "external-id".to_string(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn sign_psbt() {
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(handle))
+        }));
+        let endpoint = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+
+        let unsigned_psbt =
+            psbt::PartiallySignedTransaction::from_unsigned_tx(bdk::bitcoin::Transaction {
+                version: 2,
+                lock_time: bdk::bitcoin::PackedLockTime::ZERO,
+                input: vec![],
+                output: vec![],
+            })
+            .unwrap();
+
+        let cfg = WebhookSignerConfig {
+            endpoint: endpoint.clone(),
+            hmac_secret: SECRET.to_string(),
+        };
+        let mut signer = WebhookRemoteSigner::new(&cfg, context()).unwrap();
+        let signed_psbt = signer.sign_psbt(&unsigned_psbt).await.unwrap();
+        assert_eq!(signed_psbt, unsigned_psbt);
+
+        let tampering = WebhookSignerConfig {
+            endpoint: format!("{endpoint}/tamper"),
+            hmac_secret: SECRET.to_string(),
+        };
+        let mut signer = WebhookRemoteSigner::new(&tampering, context()).unwrap();
+        assert!(matches!(
+            signer.sign_psbt(&unsigned_psbt).await,
+            Err(SigningClientError::RemoteCallFailure(_))
+        ));
+
+        let wrong_secret = WebhookSignerConfig {
+            endpoint,
+            hmac_secret: "wrong-secret".to_string(),
+        };
+        let mut signer = WebhookRemoteSigner::new(&wrong_secret, context()).unwrap();
+        assert!(signer.sign_psbt(&unsigned_psbt).await.is_err());
+    }
+
+    #[test]
+    fn stale_signatures_are_rejected() {
+        let body = b"{}";
+        let now = chrono::Utc::now().timestamp();
+        let signature = webhook_signature(SECRET, now, body);
+        assert!(verify_webhook_signature(SECRET, now, body, &signature, now));
+        assert!(!verify_webhook_signature(
+            SECRET,
+            now - 1,
+            body,
+            &signature,
+            now
+        ));
+        let stale = now - TIMESTAMP_TOLERANCE.as_secs() as i64 - 1;
+        let signature = webhook_signature(SECRET, stale, body);
+        assert!(!verify_webhook_signature(
+            SECRET, stale, body, &signature, now
+        ));
+    }
+}
@@ -0,0 +1,219 @@
+mod helpers;
+
+use rand::distributions::{Alphanumeric, DistString};
+
+use bria::{account::*, admin::*, primitives::*};
+
+#[tokio::test]
+async fn admin_api_key_lifecycle() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let app = AdminApp::new(pool, bitcoin::Network::Regtest);
+
+    let name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let key = app.create_admin_api_key(name.clone()).await?;
+    let other = app
+        .create_admin_api_key(Alphanumeric.sample_string(&mut rand::thread_rng(), 32))
+        .await?;
+    app.authenticate(&key.key).await?;
+    assert!(matches!(
+        app.create_admin_api_key(name.clone()).await,
+        Err(AdminApiError::AdminApiKeyNameAlreadyExists(_))
+    ));
+
+    let keys = app.list_admin_api_keys().await?;
+    let listed = keys.iter().find(|k| k.id == key.id).unwrap();
+    assert_eq!(listed.name, name);
+    assert!(!listed.revoked);
+    assert!(listed.last_used_at.is_some());
+
+    app.revoke_admin_api_key(key.id).await?;
+    assert!(matches!(
+        app.authenticate(&key.key).await,
+        Err(AdminApiError::AdminApiKeyNotFound)
+    ));
+    assert!(matches!(
+        app.revoke_admin_api_key(key.id).await,
+        Err(AdminApiError::AdminApiKeyIdNotFound(_))
+    ));
+    app.authenticate(&other.key).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rotate_bootstrap_key() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let app = AdminApp::new(pool, bitcoin::Network::Regtest);
+
+    let _ = app.bootstrap().await;
+    let old = app.rotate_bootstrap_key().await?;
+    let new = app.rotate_bootstrap_key().await?;
+    assert_eq!(old.id, new.id);
+    assert!(app.authenticate(&old.key).await.is_err());
+    app.authenticate(&new.key).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn account_management() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let app = AdminApp::new(pool.clone(), bitcoin::Network::Regtest);
+    let profiles = bria::profile::Profiles::new(&pool);
+
+    let name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let key = app.create_account(name).await?;
+    profiles.find_by_key(&key.key).await?;
+
+    let new_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    app.rename_account(key.account_id, new_name.clone()).await?;
+    let other = app
+        .create_account(Alphanumeric.sample_string(&mut rand::thread_rng(), 32))
+        .await?;
+    assert!(matches!(
+        app.rename_account(other.account_id, new_name.clone()).await,
+        Err(AdminApiError::AccountError(
+            bria::account::error::AccountError::AccountNameAlreadyExists
+        ))
+    ));
+    assert!(matches!(
+        app.create_account(new_name.clone()).await,
+        Err(AdminApiError::AccountError(
+            bria::account::error::AccountError::AccountNameAlreadyExists
+        ))
+    ));
+
+    let blocked: bitcoin::Address = "bcrt1qcv9xq3me73wsv4scy6qvx3f24e3dnt56h9m9z6".parse()?;
+    let mut fees = bria::fees::FeesConfig::default();
+    fees.bitcoind.rpc_password = Some("secret".to_string());
+    app.update_account_config(
+        key.account_id,
+        AccountConfig {
+            blocked_addresses: [blocked.clone()].into_iter().collect(),
+            fees: Some(fees),
+        },
+    )
+    .await?;
+    let accounts = app.list_accounts().await?;
+    let account = accounts.iter().find(|a| a.id == key.account_id).unwrap();
+    assert_eq!(account.name, new_name);
+    assert!(account.active);
+    assert!(account.config.blocked_addresses.contains(&blocked));
+
+    let redacted = account.config.clone().redacted();
+    assert_eq!(
+        redacted
+            .fees
+            .as_ref()
+            .unwrap()
+            .bitcoind
+            .rpc_password
+            .as_deref(),
+        Some(REDACTED_SECRET)
+    );
+    app.update_account_config(key.account_id, redacted).await?;
+    let accounts = app.list_accounts().await?;
+    let account = accounts.iter().find(|a| a.id == key.account_id).unwrap();
+    assert_eq!(
+        account
+            .config
+            .fees
+            .as_ref()
+            .unwrap()
+            .bitcoind
+            .rpc_password
+            .as_deref(),
+        Some("secret")
+    );
+
+    app.deactivate_account(key.account_id).await?;
+    assert!(matches!(
+        profiles.find_by_key(&key.key).await,
+        Err(bria::profile::error::ProfileError::AccountDeactivated(_))
+    ));
+    let accounts = app.list_accounts().await?;
+    assert!(
+        !accounts
+            .iter()
+            .find(|a| a.id == key.account_id)
+            .unwrap()
+            .active
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn blocked_addresses() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let app = AdminApp::new(pool.clone(), bitcoin::Network::Regtest);
+    let blocked_addresses = bria::security::BlockedAddresses::new(&pool);
+
+    let blocked = helpers::random_bdk_wallet()?
+        .get_address(bdk::wallet::AddressIndex::New)?
+        .address;
+    let other = helpers::random_bdk_wallet()?
+        .get_address(bdk::wallet::AddressIndex::New)?
+        .address;
+
+    let n_added = app
+        .add_blocked_addresses(vec![blocked.clone()], Some("sanctioned".to_string()))
+        .await?;
+    assert_eq!(n_added, 1);
+    let n_added = app
+        .add_blocked_addresses(vec![blocked.clone(), other.clone()], None)
+        .await?;
+    assert_eq!(n_added, 1);
+
+    let listed = app.list_blocked_addresses().await?;
+    let entry = listed.iter().find(|a| a.address == blocked).unwrap();
+    assert_eq!(entry.reason.as_deref(), Some("sanctioned"));
+    let found = blocked_addresses.find_blocked([&blocked, &other]).await?;
+    assert_eq!(found.len(), 2);
+
+    app.remove_blocked_address(other.clone()).await?;
+    let found = blocked_addresses.find_blocked([&blocked, &other]).await?;
+    assert!(found.contains(&blocked));
+    assert!(!found.contains(&other));
+    assert!(matches!(
+        app.remove_blocked_address(other).await,
+        Err(AdminApiError::SecurityError(
+            bria::security::error::SecurityError::BlockedAddressNotFound(_)
+        ))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_audit_events_includes_admin_calls() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let app = AdminApp::new(pool, bitcoin::Network::Regtest);
+
+    let key = app
+        .create_admin_api_key(Alphanumeric.sample_string(&mut rand::thread_rng(), 32))
+        .await?;
+    let actor = bria::audit::AuditActor::AdminApiKey { id: key.id };
+    let rpc = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    app.record_audit_event(
+        actor,
+        &rpc,
+        &serde_json::json!({ "name": "account" }),
+        bria::audit::AuditOutcome::Succeeded,
+    )
+    .await?;
+
+    let events = app
+        .list_audit_events(
+            None,
+            bria::audit::AuditFilter {
+                rpc: Some(rpc),
+                ..Default::default()
+            },
+        )
+        .await?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].actor, actor);
+
+    Ok(())
+}
@@ -152,7 +152,7 @@ async fn profile_name_not_found() -> anyhow::Result<()> {
     let profile = helpers::create_test_account(&pool).await?;
     let app = App::run(pool, AppConfig::default()).await?;
     let err = app
-        .create_profile_api_key(profile, "test".to_string())
+        .create_profile_api_key(profile, "test".to_string(), Vec::new(), None)
         .await;
     assert!(matches!(
         err,
@@ -0,0 +1,80 @@
+mod helpers;
+
+use bria::audit::*;
+
+#[tokio::test]
+async fn audit_log_is_hash_chained() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+    let audit = AuditLog::new(&pool);
+    let actor = AuditActor::Profile {
+        account_id: profile.account_id,
+        profile_id: profile.id,
+    };
+
+    audit
+        .append(
+            actor,
+            "CreateWallet",
+            &serde_json::json!({ "name": "wallet" }),
+            AuditOutcome::Succeeded,
+        )
+        .await?;
+    let id = audit
+        .append(
+            actor,
+            "SetSignerConfig",
+            &serde_json::json!({ "xpubRef": "xpub", "config": { "macaroonBase64": "secret" } }),
+            AuditOutcome::Failed {
+                error: "xpub not found".to_string(),
+            },
+        )
+        .await?;
+
+    let entries = audit
+        .list_for_account(profile.account_id, AuditFilter::default())
+        .await?;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].prev_hash, entries[0].hash);
+    assert_eq!(entries[1].request["config"], "[REDACTED]");
+    assert_eq!(entries[1].actor, actor);
+    assert_eq!(entries[0].outcome, Some(AuditOutcome::Succeeded));
+    assert!(matches!(
+        entries[1].outcome,
+        Some(AuditOutcome::Failed { .. })
+    ));
+
+    let filtered = audit
+        .list_for_account(
+            profile.account_id,
+            AuditFilter {
+                rpc: Some("CreateWallet".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+    assert_eq!(filtered.len(), 1);
+
+    assert!(audit.verify().await?.is_valid());
+
+    let original = entries[1].request.clone();
+    sqlx::query("UPDATE bria_audit_log SET request = $1 WHERE id = $2")
+        .bind(serde_json::json!({ "xpubRef": "tampered" }))
+        .bind(uuid::Uuid::from(id))
+        .execute(&pool)
+        .await?;
+    let verification = audit.verify().await?;
+    assert_eq!(
+        verification.first_invalid_sequence,
+        Some(entries[1].sequence)
+    );
+
+    sqlx::query("UPDATE bria_audit_log SET request = $1 WHERE id = $2")
+        .bind(original)
+        .bind(uuid::Uuid::from(id))
+        .execute(&pool)
+        .await?;
+    assert!(audit.verify().await?.is_valid());
+
+    Ok(())
+}
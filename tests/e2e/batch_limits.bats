@@ -0,0 +1,68 @@
+#!/usr/bin/env bats
+
+load "helpers"
+
+setup_file() {
+  restart_bitcoin_stack
+  reset_pg
+  bitcoind_init
+  start_daemon
+  bria_init
+}
+
+teardown_file() {
+  stop_daemon
+}
+
+wait_for_batch() {
+  for i in {1..30}; do
+    batch_id=$(bria_cmd get-payout --id "$1" | jq -r '.payout.batchId')
+    [[ "${batch_id}" != "null" ]] && break
+    sleep 1
+  done
+  echo "${batch_id}"
+}
+
+@test "batch_limits: Fund the wallet" {
+  # Each batch reserves its own utxo as no signer is configured to broadcast them
+  for i in {1..4}; do
+    bria_address=$(bria_cmd new-address -w default | jq -r '.address')
+    bitcoin_cli -regtest sendtoaddress ${bria_address} 1
+  done
+  bitcoin_cli -generate 10
+
+  for i in {1..30}; do
+    cache_wallet_balance
+    [[ $(cached_effective_settled) == 400000000 ]] && break
+    sleep 1
+  done
+  [[ $(cached_effective_settled) == 400000000 ]] || exit 1
+}
+
+@test "batch_limits: Payouts beyond max-payouts-per-batch go into a follow-up batch" {
+  bria_cmd create-payout-queue -n per_batch -m true --max-payouts-per-batch 1
+  first=$(bria_cmd submit-payout --wallet default --queue-name per_batch --destination bcrt1q208tuy5rd3kvy8xdpv6yrczg7f3mnlk3lql7ej --amount 100000 | jq -r '.id')
+  second=$(bria_cmd submit-payout --wallet default --queue-name per_batch --destination bcrt1q3rr02wkkvkwcj7h0nr9dqr9z3z3066pktat7kv --amount 100000 | jq -r '.id')
+
+  # A manual queue only runs again if the job respawns itself for the deferred payout
+  bria_cmd trigger-payout-queue --name per_batch
+
+  first_batch=$(wait_for_batch ${first})
+  second_batch=$(wait_for_batch ${second})
+  [[ "${first_batch}" != "null" && "${second_batch}" != "null" ]] || exit 1
+  [[ "${first_batch}" != "${second_batch}" ]] || exit 1
+}
+
+@test "batch_limits: Payouts beyond max-tx-vbytes go into a follow-up batch" {
+  # A single payout tx is ~141 vbytes, adding a second output takes it to ~172
+  bria_cmd create-payout-queue -n vbytes -m true --max-tx-vbytes 160
+  first=$(bria_cmd submit-payout --wallet default --queue-name vbytes --destination bcrt1q208tuy5rd3kvy8xdpv6yrczg7f3mnlk3lql7ej --amount 100000 | jq -r '.id')
+  second=$(bria_cmd submit-payout --wallet default --queue-name vbytes --destination bcrt1q3rr02wkkvkwcj7h0nr9dqr9z3z3066pktat7kv --amount 100000 | jq -r '.id')
+
+  bria_cmd trigger-payout-queue --name vbytes
+
+  first_batch=$(wait_for_batch ${first})
+  second_batch=$(wait_for_batch ${second})
+  [[ "${first_batch}" != "null" && "${second_batch}" != "null" ]] || exit 1
+  [[ "${first_batch}" != "${second_batch}" ]] || exit 1
+}
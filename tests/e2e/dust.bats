@@ -0,0 +1,51 @@
+#!/usr/bin/env bats
+
+load "helpers"
+
+setup_file() {
+  restart_bitcoin_stack
+  reset_pg
+  bitcoind_init
+  start_daemon
+  bria_init
+}
+
+teardown_file() {
+  stop_daemon
+}
+
+@test "dust: Routes incoming dust to the dust account" {
+  bria_cmd update-wallet -w default --dust-threshold-sats 10000
+  settle_income=$(bria_cmd list-wallets | jq -r '.wallets[0].config.settleIncomeAfterNConfs')
+  [[ "${settle_income}" == "2" ]] || exit 1
+
+  bria_address=$(bria_cmd new-address -w default | jq -r '.address')
+  bitcoin_cli -regtest sendtoaddress ${bria_address} 0.00005
+
+  for i in {1..30}; do
+    cache_wallet_balance
+    [[ $(cached_dust_pending_income) == 5000 ]] && break
+    sleep 1
+  done
+  [[ $(cached_dust_pending_income) == 5000 ]] || exit 1
+  [[ $(cached_pending_income) == 5000 ]] || exit 1
+  [[ $(cached_utxo_frozen) == 0 ]] || exit 1
+
+  dust_event="null"
+  for i in {0..1}; do
+    event=$(bria_cmd watch-events -a ${i} -o | jq -r '.payload.utxoDustDetected')
+    [[ "${event}" != "null" ]] && dust_event="${event}"
+  done
+  [[ $(jq -r '.satoshis' <<< "${dust_event}") == 5000 ]] || exit 1
+
+  bitcoin_cli -generate 2
+
+  for i in {1..30}; do
+    cache_wallet_balance
+    [[ $(cached_dust_settled) == 5000 ]] && break
+    sleep 1
+  done
+  [[ $(cached_dust_settled) == 5000 ]] || exit 1
+  [[ $(cached_dust_pending_income) == 0 ]] || exit 1
+  [[ $(cached_effective_settled) == 0 ]] || exit 1
+}
@@ -50,6 +50,18 @@ cached_encumbered_outgoing() {
   echo ${balance} | jq -r '.effectiveEncumberedOutgoing'
 }
 
+cached_dust_pending_income() {
+  echo ${balance} | jq -r '.dustPendingIncome'
+}
+
+cached_dust_settled() {
+  echo ${balance} | jq -r '.dustSettled'
+}
+
+cached_utxo_frozen() {
+  echo ${balance} | jq -r '.utxoFrozen'
+}
+
 bitcoin_cli() {
   docker exec "${COMPOSE_PROJECT_NAME}-bitcoind-1" bitcoin-cli $@
 }
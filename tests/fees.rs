@@ -1,4 +1,8 @@
-use bria::{fees::*, primitives::TxPriority};
+use bria::{
+    fees::{error::FeeEstimationError, *},
+    payout_queue::{FeeStrategy, PayoutQueueConfig},
+    primitives::TxPriority,
+};
 
 #[tokio::test]
 async fn mempool_space() -> anyhow::Result<()> {
@@ -8,3 +12,79 @@ async fn mempool_space() -> anyhow::Result<()> {
     assert!(fee_rate.as_sat_per_vb() > 0.0);
     Ok(())
 }
+
+#[tokio::test]
+async fn static_rates() -> anyhow::Result<()> {
+    let estimator = StaticFeeEstimator::new(StaticFeeEstimatorConfig {
+        next_block: 5.0,
+        half_hour: 3.0,
+        one_hour: 1.0,
+    });
+    let fee_rate = estimator.fee_rate(TxPriority::NextBlock).await?;
+    assert_eq!(fee_rate.as_sat_per_vb(), 5.0);
+    let fee_rate = estimator.fee_rate(TxPriority::OneHour).await?;
+    assert_eq!(fee_rate.as_sat_per_vb(), 1.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn chain_falls_back_when_primary_errors() -> anyhow::Result<()> {
+    let config = FeesConfig {
+        estimators: vec![FeeEstimatorType::MempoolSpace, FeeEstimatorType::Static],
+        mempool_space: MempoolSpaceConfig {
+            url: "http://127.0.0.1:1".to_string(),
+        },
+        ..Default::default()
+    };
+    let chain = FeeEstimatorChain::new(&config);
+    let fee_rate = chain.fee_rate(TxPriority::HalfHour).await?;
+    assert_eq!(
+        fee_rate.as_sat_per_vb(),
+        StaticFeeEstimatorConfig::default().half_hour
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn empty_chain_errors() -> anyhow::Result<()> {
+    let config = FeesConfig {
+        estimators: vec![],
+        ..Default::default()
+    };
+    let chain = FeeEstimatorChain::new(&config);
+    assert!(matches!(
+        chain.fee_rate(TxPriority::NextBlock).await,
+        Err(FeeEstimationError::NoEstimatorConfigured)
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn payout_queue_fee_strategies() -> anyhow::Result<()> {
+    let estimator = StaticFeeEstimator::new(StaticFeeEstimatorConfig::default());
+    let mut config = PayoutQueueConfig {
+        fee_strategy: FeeStrategy::PriorityMultiplier {
+            multiplier: rust_decimal_macros::dec!(1.5),
+        },
+        ..Default::default()
+    };
+    assert_eq!(config.fee_rate(&estimator).await?.as_sat_per_vb(), 4.5);
+
+    config.fee_strategy = FeeStrategy::FixedRate { sats_per_vbyte: 7 };
+    assert_eq!(config.fee_rate(&estimator).await?.as_sat_per_vb(), 7.0);
+
+    config.fee_strategy = FeeStrategy::TargetBlocks { n_blocks: 6 };
+    assert_eq!(config.fee_rate(&estimator).await?.as_sat_per_vb(), 1.0);
+    Ok(())
+}
+
+#[test]
+fn payout_queue_config_without_fee_strategy() -> anyhow::Result<()> {
+    let config: PayoutQueueConfig = serde_json::from_value(serde_json::json!({
+        "tx_priority": "half_hour",
+        "consolidate_deprecated_keychains": false,
+        "trigger": { "type": "manual" }
+    }))?;
+    assert_eq!(config.fee_strategy, FeeStrategy::default());
+    Ok(())
+}
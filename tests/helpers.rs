@@ -39,6 +39,8 @@ pub async fn create_test_account(pool: &sqlx::PgPool) -> anyhow::Result<Profile>
         id: profile_key.profile_id,
         account_id: profile_key.account_id,
         name,
+        policy: None,
+        payout_approver: false,
     })
 }
 
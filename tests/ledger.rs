@@ -369,7 +369,7 @@ async fn create_batch() -> anyhow::Result<()> {
                         account_id,
                         wallet_id,
                         batch_id,
-                        payout_queue_id: PayoutQueueId::new(),
+                        payout_queue_id: Some(PayoutQueueId::new()),
                         included_payouts: Vec::new(),
                     },
                     tx_summary: WalletTransactionSummary {
@@ -427,6 +427,270 @@ async fn create_batch() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn cancel_batch() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+
+    let ledger = Ledger::init(&pool).await?;
+
+    let account_id = AccountId::new();
+    let name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let mut tx = pool.begin().await?;
+    let journal_id = ledger
+        .create_journal_for_account(&mut tx, account_id, name.clone())
+        .await?;
+    let wallet_id = WalletId::new();
+    let wallet_ledger_accounts = ledger
+        .create_ledger_accounts_for_wallet(&mut tx, wallet_id)
+        .await?;
+
+    tx.commit().await?;
+
+    let batch_id = BatchId::new();
+    let fee_sats = Satoshis::from(2_346);
+    let total_spent_sats = Satoshis::from(100_000_000);
+    let total_utxo_in_sats = Satoshis::from(200_000_000);
+    let total_utxo_settled_in_sats = Satoshis::from(100_000_000);
+    let change_sats = total_utxo_in_sats - total_spent_sats - fee_sats;
+    let address: bitcoin::Address = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".parse().unwrap();
+    let outpoint = OutPoint {
+        txid: "4010e27ff7dc6d9c66a5657e6b3d94b4c4e394d968398d16fefe4637463d194d"
+            .parse()
+            .unwrap(),
+        vout: 0,
+    };
+    let encumbered_fees = Satoshis::from(12_346);
+
+    let batch_created_tx_id = LedgerTransactionId::new();
+    let tx = pool.begin().await?;
+    ledger
+        .batch_created(
+            tx,
+            batch_created_tx_id,
+            BatchCreatedParams {
+                journal_id,
+                ledger_account_ids: wallet_ledger_accounts,
+                encumbered_fees,
+                meta: BatchCreatedMeta {
+                    batch_info: BatchWalletInfo {
+                        account_id,
+                        wallet_id,
+                        batch_id,
+                        payout_queue_id: Some(PayoutQueueId::new()),
+                        included_payouts: Vec::new(),
+                    },
+                    tx_summary: WalletTransactionSummary {
+                        account_id,
+                        wallet_id,
+                        bitcoin_tx_id:
+                            "4010e27ff7dc6d9c66a5657e6b3d94b4c4e394d968398d16fefe4637463d194d"
+                                .parse()
+                                .unwrap(),
+                        total_utxo_settled_in_sats,
+                        total_utxo_in_sats,
+                        fee_sats,
+                        change_utxos: std::iter::once(ChangeOutput {
+                            outpoint,
+                            satoshis: change_sats,
+                            address,
+                        })
+                        .collect(),
+                        current_keychain_id: KeychainId::new(),
+                    },
+                },
+            },
+        )
+        .await?;
+
+    let tx = pool.begin().await?;
+    ledger
+        .batch_cancelled(
+            tx,
+            LedgerTransactionId::new(),
+            batch_created_tx_id,
+            wallet_ledger_accounts,
+        )
+        .await?;
+
+    let balances = ledger
+        .get_wallet_ledger_account_balances(journal_id, wallet_ledger_accounts)
+        .await?;
+    let summary = WalletBalanceSummary::from(balances);
+
+    assert_eq!(summary.effective_pending_outgoing, Satoshis::ZERO);
+    assert_eq!(summary.effective_settled, Satoshis::ZERO);
+    assert_eq!(summary.effective_encumbered_outgoing, Satoshis::ZERO);
+    assert_eq!(summary.fees_encumbered, Satoshis::ZERO);
+    assert_eq!(summary.fees_pending, Satoshis::ZERO);
+    assert_eq!(summary.utxo_encumbered_incoming, Satoshis::ZERO);
+    assert_eq!(summary.utxo_settled, Satoshis::ZERO);
+    assert_eq!(summary.utxo_pending_outgoing, Satoshis::ZERO);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn batch_fee_bumped() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+
+    let ledger = Ledger::init(&pool).await?;
+
+    let account_id = AccountId::new();
+    let name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let mut tx = pool.begin().await?;
+    let journal_id = ledger
+        .create_journal_for_account(&mut tx, account_id, name.clone())
+        .await?;
+    let wallet_id = WalletId::new();
+    let wallet_ledger_accounts = ledger
+        .create_ledger_accounts_for_wallet(&mut tx, wallet_id)
+        .await?;
+
+    tx.commit().await?;
+
+    let fee_sats = Satoshis::from(2_346);
+    let total_spent_sats = Satoshis::from(100_000_000);
+    let total_utxo_in_sats = Satoshis::from(200_000_000);
+    let total_utxo_settled_in_sats = Satoshis::from(100_000_000);
+    let change_sats = total_utxo_in_sats - total_spent_sats - fee_sats;
+    let address: bitcoin::Address = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".parse().unwrap();
+    let bitcoin_tx_id: bitcoin::Txid =
+        "4010e27ff7dc6d9c66a5657e6b3d94b4c4e394d968398d16fefe4637463d194d"
+            .parse()
+            .unwrap();
+    let outpoint = OutPoint {
+        txid: bitcoin_tx_id,
+        vout: 0,
+    };
+
+    let created_id = LedgerTransactionId::new();
+    let tx = pool.begin().await?;
+    ledger
+        .batch_created(
+            tx,
+            created_id,
+            BatchCreatedParams {
+                journal_id,
+                ledger_account_ids: wallet_ledger_accounts,
+                encumbered_fees: Satoshis::from(12_346),
+                meta: BatchCreatedMeta {
+                    batch_info: BatchWalletInfo {
+                        account_id,
+                        wallet_id,
+                        batch_id: BatchId::new(),
+                        payout_queue_id: Some(PayoutQueueId::new()),
+                        included_payouts: Vec::new(),
+                    },
+                    tx_summary: WalletTransactionSummary {
+                        account_id,
+                        wallet_id,
+                        bitcoin_tx_id,
+                        total_utxo_settled_in_sats,
+                        total_utxo_in_sats,
+                        fee_sats,
+                        change_utxos: std::iter::once(ChangeOutput {
+                            outpoint,
+                            satoshis: change_sats,
+                            address,
+                        })
+                        .collect(),
+                        current_keychain_id: KeychainId::new(),
+                    },
+                },
+            },
+        )
+        .await?;
+    let broadcast_id = LedgerTransactionId::new();
+    let tx = pool.begin().await?;
+    ledger
+        .batch_broadcast(
+            tx,
+            created_id,
+            broadcast_id,
+            Satoshis::from(1_000),
+            wallet_ledger_accounts,
+        )
+        .await?;
+
+    let additional_fee_sats = Satoshis::from(1_500);
+    let bumped_tx_id: bitcoin::Txid =
+        "a5c0b0e9b0e3f6a1b5ac1d0ee4d8c7e0b7aa7e1fb0b5d8fbd2c6cbd6e3a4e1f0"
+            .parse()
+            .unwrap();
+    let bumped_id = LedgerTransactionId::new();
+    let tx = pool.begin().await?;
+    ledger
+        .batch_fee_bumped(
+            tx,
+            bumped_id,
+            broadcast_id,
+            wallet_ledger_accounts,
+            bumped_tx_id,
+            fee_sats + additional_fee_sats,
+            change_sats - additional_fee_sats,
+        )
+        .await?;
+
+    let balances = ledger
+        .get_wallet_ledger_account_balances(journal_id, wallet_ledger_accounts)
+        .await?;
+    let summary = WalletBalanceSummary::from(balances);
+
+    assert_eq!(summary.fees_pending, fee_sats + additional_fee_sats);
+    assert_eq!(
+        summary.utxo_pending_incoming,
+        change_sats - additional_fee_sats
+    );
+    assert_eq!(
+        summary.utxo_pending_outgoing,
+        total_utxo_in_sats - fee_sats - additional_fee_sats
+    );
+    assert_eq!(
+        summary.effective_settled.flip_sign(),
+        total_spent_sats + fee_sats + additional_fee_sats
+    );
+
+    let account_balances = ledger
+        .get_account_ledger_account_balances(journal_id)
+        .await?;
+    let account_summary = AccountBalanceSummary::from(account_balances);
+    assert_summaries_match(summary, account_summary);
+
+    let tx = pool.begin().await?;
+    ledger
+        .batch_fee_bumped(
+            tx,
+            LedgerTransactionId::new(),
+            bumped_id,
+            wallet_ledger_accounts,
+            bitcoin_tx_id,
+            fee_sats,
+            change_sats,
+        )
+        .await?;
+
+    let balances = ledger
+        .get_wallet_ledger_account_balances(journal_id, wallet_ledger_accounts)
+        .await?;
+    let summary = WalletBalanceSummary::from(balances);
+
+    assert_eq!(summary.fees_pending, fee_sats);
+    assert_eq!(summary.utxo_pending_incoming, change_sats);
+    assert_eq!(summary.utxo_pending_outgoing, total_utxo_in_sats - fee_sats);
+    assert_eq!(
+        summary.effective_settled.flip_sign(),
+        total_spent_sats + fee_sats
+    );
+
+    let account_balances = ledger
+        .get_account_ledger_account_balances(journal_id)
+        .await?;
+    let account_summary = AccountBalanceSummary::from(account_balances);
+    assert_summaries_match(summary, account_summary);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn spend_detected() -> anyhow::Result<()> {
     let pool = helpers::init_pool().await?;
@@ -858,3 +1122,131 @@ async fn payout_cancelled() -> anyhow::Result<()> {
     assert_summaries_match(summary, account_summary);
     Ok(())
 }
+
+#[tokio::test]
+async fn quarantined_utxo_is_held_until_released() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+
+    let ledger = Ledger::init(&pool).await?;
+
+    let account_id = AccountId::new();
+    let name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let mut tx = pool.begin().await?;
+    let journal_id = ledger
+        .create_journal_for_account(&mut tx, account_id, name.clone())
+        .await?;
+    let wallet_id = WalletId::new();
+    let wallet_ledger_accounts = ledger
+        .create_ledger_accounts_for_wallet(&mut tx, wallet_id)
+        .await?;
+    let held_accounts = wallet_ledger_accounts.for_income(false, true);
+
+    let one_btc = Satoshis::from(100_000_000);
+    let address: bitcoin::Address = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".parse().unwrap();
+    let outpoint = OutPoint {
+        txid: "4010e27ff7dc6d9c66a5657e6b3d94b4c4e394d968398d16fefe4637463d194d"
+            .parse()
+            .unwrap(),
+        vout: 0,
+    };
+    let keychain_id = KeychainId::new();
+    let pending_id = LedgerTransactionId::new();
+
+    ledger
+        .utxo_detected(
+            tx,
+            pending_id,
+            UtxoDetectedParams {
+                journal_id,
+                onchain_incoming_account_id: held_accounts.onchain_incoming_id,
+                onchain_fee_account_id: held_accounts.fee_id,
+                effective_incoming_account_id: held_accounts.effective_incoming_id,
+                meta: UtxoDetectedMeta {
+                    account_id,
+                    wallet_id,
+                    keychain_id,
+                    outpoint,
+                    satoshis: one_btc,
+                    address: address.clone(),
+                    encumbered_spending_fees: std::iter::once((outpoint, Satoshis::ZERO)).collect(),
+                    confirmation_time: None,
+                },
+            },
+        )
+        .await?;
+
+    let summary = WalletBalanceSummary::from(
+        ledger
+            .get_wallet_ledger_account_balances(journal_id, wallet_ledger_accounts)
+            .await?,
+    );
+    assert_eq!(summary.utxo_pending_incoming, one_btc);
+    assert_eq!(summary.effective_pending_income, Satoshis::ZERO);
+    assert_eq!(summary.utxo_frozen, one_btc);
+
+    let tx = pool.begin().await?;
+    ledger
+        .utxo_settled(
+            tx,
+            LedgerTransactionId::new(),
+            UtxoSettledParams {
+                journal_id,
+                ledger_account_ids: held_accounts,
+                pending_id,
+                meta: UtxoSettledMeta {
+                    account_id,
+                    wallet_id,
+                    keychain_id,
+                    outpoint,
+                    satoshis: one_btc,
+                    address: address.clone(),
+                    confirmation_time: BlockTime {
+                        height: 1,
+                        timestamp: 123409,
+                    },
+                    already_spent_tx_id: None,
+                },
+            },
+        )
+        .await?;
+
+    let summary = WalletBalanceSummary::from(
+        ledger
+            .get_wallet_ledger_account_balances(journal_id, wallet_ledger_accounts)
+            .await?,
+    );
+    assert_eq!(summary.utxo_settled, one_btc);
+    assert_eq!(summary.effective_settled, Satoshis::ZERO);
+    assert_eq!(summary.utxo_frozen, one_btc);
+
+    let tx = pool.begin().await?;
+    ledger
+        .utxo_unfrozen(
+            tx,
+            LedgerTransactionId::new(),
+            UtxoUnfrozenParams {
+                journal_id,
+                ledger_account_ids: wallet_ledger_accounts,
+                meta: UtxoUnfrozenMeta {
+                    account_id,
+                    wallet_id,
+                    keychain_id,
+                    outpoint,
+                    satoshis: one_btc,
+                    address,
+                    settled: true,
+                },
+            },
+        )
+        .await?;
+
+    let summary = WalletBalanceSummary::from(
+        ledger
+            .get_wallet_ledger_account_balances(journal_id, wallet_ledger_accounts)
+            .await?,
+    );
+    assert_eq!(summary.effective_settled, one_btc);
+    assert_eq!(summary.utxo_frozen, Satoshis::ZERO);
+
+    Ok(())
+}
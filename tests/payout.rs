@@ -2,7 +2,14 @@ mod helpers;
 
 use rand::distributions::{Alphanumeric, DistString};
 
-use bria::{app::*, primitives::*, xpub::*};
+use bria::{
+    app::{error::ApplicationError, *},
+    payout::{error::PayoutError, *},
+    payout_queue::*,
+    primitives::*,
+    profile::{error::ProfileError, ProfilePolicy},
+    xpub::*,
+};
 
 #[tokio::test]
 async fn test_payout() -> anyhow::Result<()> {
@@ -47,8 +54,625 @@ async fn test_payout() -> anyhow::Result<()> {
             Satoshis::from(10000),
             None,
             None,
+            None,
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn payout_count_trigger() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+
+    let original = "tpubDD4vFnWuTMEcZiaaZPgvzeGyMzWe6qHW8gALk5Md9kutDvtdDjYFwzauEFFRHgov8pAwup5jX88j5YFyiACsPf3pqn5hBjvuTLRAseaJ6b4";
+    let xpub = XPub::try_from((original, Some("m/84'/0'/0'"))).unwrap();
+    let wallet_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let id = XPubs::new(&pool)
+        .persist(
+            NewAccountXPub::builder()
+                .account_id(profile.account_id)
+                .original(original.to_owned())
+                .key_name(wallet_name.clone())
+                .value(xpub)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+    app.create_wpkh_wallet(profile.clone(), wallet_name.clone(), id.to_string(), None)
+        .await?;
+
+    let queue_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let config = PayoutQueueConfig {
+        trigger: PayoutQueueTrigger::PayoutCount {
+            min_payouts: 2,
+            max_interval: None,
+        },
+        ..Default::default()
+    };
+    let queue_id = app
+        .create_payout_queue(profile.clone(), queue_name.clone(), None, Some(config))
+        .await?;
+    let queue = PayoutQueues::new(&pool)
+        .find_by_name(profile.account_id, queue_name.clone())
+        .await?;
+    assert_eq!(queue.spawn_in(), None);
+
+    let payouts = Payouts::new(&pool);
+    let destination = PayoutDestination::OnchainAddress {
+        value: "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap(),
+    };
+    for n in 1..=2 {
+        app.submit_payout(
+            profile.clone(),
+            wallet_name.clone(),
+            queue_name.clone(),
+            destination.clone(),
+            Satoshis::from(10000),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
+        let unbatched = payouts.list_unbatched(profile.account_id, queue_id).await?;
+        assert_eq!(unbatched.total_sats(), Satoshis::from(10000 * n));
+        assert_eq!(queue.threshold_reached(&unbatched, None), n == 2);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fee_rate_window_trigger() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+
+    let original = "tpubDD4vFnWuTMEcZiaaZPgvzeGyMzWe6qHW8gALk5Md9kutDvtdDjYFwzauEFFRHgov8pAwup5jX88j5YFyiACsPf3pqn5hBjvuTLRAseaJ6b4";
+    let xpub = XPub::try_from((original, Some("m/84'/0'/0'"))).unwrap();
+    let wallet_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let id = XPubs::new(&pool)
+        .persist(
+            NewAccountXPub::builder()
+                .account_id(profile.account_id)
+                .original(original.to_owned())
+                .key_name(wallet_name.clone())
+                .value(xpub)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+    app.create_wpkh_wallet(profile.clone(), wallet_name.clone(), id.to_string(), None)
+        .await?;
+
+    let queue_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let config = PayoutQueueConfig {
+        trigger: PayoutQueueTrigger::FeeRateWindow {
+            max_sats_per_vbyte: 5,
+            deadline: std::time::Duration::from_secs(3600),
+        },
+        ..Default::default()
+    };
+    let queue_id = app
+        .create_payout_queue(profile.clone(), queue_name.clone(), None, Some(config))
+        .await?;
+    let queue = PayoutQueues::new(&pool)
+        .find_by_name(profile.account_id, queue_name.clone())
+        .await?;
+
+    let payouts = Payouts::new(&pool);
+    let cheap = bitcoin::FeeRate::from_sat_per_vb(4.0);
+    let expensive = bitcoin::FeeRate::from_sat_per_vb(20.0);
+    let unbatched = payouts.list_unbatched(profile.account_id, queue_id).await?;
+    assert!(!queue.threshold_reached(&unbatched, Some(cheap)));
+
+    app.submit_payout(
+        profile.clone(),
+        wallet_name,
+        queue_name,
+        PayoutDestination::OnchainAddress {
+            value: "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap(),
+        },
+        Satoshis::from(10000),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+    let unbatched = payouts.list_unbatched(profile.account_id, queue_id).await?;
+    assert!(queue.threshold_reached(&unbatched, Some(cheap)));
+    assert!(!queue.threshold_reached(&unbatched, Some(expensive)));
+    assert!(!queue.threshold_reached(&unbatched, None));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn max_payouts_per_batch_defers_newest() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+
+    let original = "tpubDD4vFnWuTMEcZiaaZPgvzeGyMzWe6qHW8gALk5Md9kutDvtdDjYFwzauEFFRHgov8pAwup5jX88j5YFyiACsPf3pqn5hBjvuTLRAseaJ6b4";
+    let xpub = XPub::try_from((original, Some("m/84'/0'/0'"))).unwrap();
+    let wallet_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let id = XPubs::new(&pool)
+        .persist(
+            NewAccountXPub::builder()
+                .account_id(profile.account_id)
+                .original(original.to_owned())
+                .key_name(wallet_name.clone())
+                .value(xpub)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+    app.create_wpkh_wallet(profile.clone(), wallet_name.clone(), id.to_string(), None)
+        .await?;
+
+    let queue_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let config = PayoutQueueConfig {
+        max_payouts_per_batch: Some(2),
+        ..Default::default()
+    };
+    let queue_id = app
+        .create_payout_queue(profile.clone(), queue_name.clone(), None, Some(config))
+        .await?;
+
+    for sats in [10000, 20000, 30000] {
+        app.submit_payout(
+            profile.clone(),
+            wallet_name.clone(),
+            queue_name.clone(),
+            PayoutDestination::OnchainAddress {
+                value: "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap(),
+            },
+            Satoshis::from(sats),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    }
+
+    let queue = PayoutQueues::new(&pool)
+        .find_by_name(profile.account_id, queue_name)
+        .await?;
+    assert_eq!(queue.config.max_payouts_per_batch, Some(2));
+
+    let mut unbatched = Payouts::new(&pool)
+        .list_unbatched(profile.account_id, queue_id)
+        .await?;
+    assert_eq!(unbatched.truncate(2), 1);
+    assert_eq!(unbatched.n_payouts(), 2);
+    assert_eq!(unbatched.total_sats(), Satoshis::from(30000));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn higher_priority_payouts_are_batched_first() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+
+    let original = "tpubDD4vFnWuTMEcZiaaZPgvzeGyMzWe6qHW8gALk5Md9kutDvtdDjYFwzauEFFRHgov8pAwup5jX88j5YFyiACsPf3pqn5hBjvuTLRAseaJ6b4";
+    let xpub = XPub::try_from((original, Some("m/84'/0'/0'"))).unwrap();
+    let wallet_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let id = XPubs::new(&pool)
+        .persist(
+            NewAccountXPub::builder()
+                .account_id(profile.account_id)
+                .original(original.to_owned())
+                .key_name(wallet_name.clone())
+                .value(xpub)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+    app.create_wpkh_wallet(profile.clone(), wallet_name.clone(), id.to_string(), None)
+        .await?;
+
+    let queue_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let queue_id = app
+        .create_payout_queue(profile.clone(), queue_name.clone(), None, None)
+        .await?;
+
+    for (sats, priority) in [(10000, None), (20000, None), (30000, Some(1))] {
+        app.submit_payout(
+            profile.clone(),
+            wallet_name.clone(),
+            queue_name.clone(),
+            PayoutDestination::OnchainAddress {
+                value: "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap(),
+            },
+            Satoshis::from(sats),
+            None,
+            None,
+            priority,
+            None,
+        )
+        .await?;
+    }
+
+    let mut unbatched = Payouts::new(&pool)
+        .list_unbatched(profile.account_id, queue_id)
+        .await?;
+    assert_eq!(unbatched.truncate(2), 1);
+    assert_eq!(unbatched.n_payouts(), 2);
+    assert_eq!(unbatched.total_sats(), Satoshis::from(40000));
+    let tx_payouts = unbatched.into_tx_payouts();
+    let (_, _, first) = tx_payouts.values().next().unwrap()[0];
+    assert_eq!(first, Satoshis::from(30000));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn scheduled_payouts_are_not_batched_before_execute_after() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+
+    let original = "tpubDD4vFnWuTMEcZiaaZPgvzeGyMzWe6qHW8gALk5Md9kutDvtdDjYFwzauEFFRHgov8pAwup5jX88j5YFyiACsPf3pqn5hBjvuTLRAseaJ6b4";
+    let xpub = XPub::try_from((original, Some("m/84'/0'/0'"))).unwrap();
+    let wallet_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let id = XPubs::new(&pool)
+        .persist(
+            NewAccountXPub::builder()
+                .account_id(profile.account_id)
+                .original(original.to_owned())
+                .key_name(wallet_name.clone())
+                .value(xpub)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+    app.create_wpkh_wallet(profile.clone(), wallet_name.clone(), id.to_string(), None)
+        .await?;
+
+    let queue_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let queue_id = app
+        .create_payout_queue(profile.clone(), queue_name.clone(), None, None)
+        .await?;
+
+    let execute_after = chrono::Utc::now() + chrono::Duration::days(1);
+    let id = app
+        .submit_payout(
+            profile.clone(),
+            wallet_name.clone(),
+            queue_name.clone(),
+            PayoutDestination::OnchainAddress {
+                value: "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap(),
+            },
+            Satoshis::from(10000),
+            None,
+            None,
+            None,
+            Some(execute_after),
         )
         .await?;
 
+    let unbatched = Payouts::new(&pool)
+        .list_unbatched(profile.account_id, queue_id)
+        .await?;
+    assert_eq!(unbatched.n_payouts(), 0);
+
+    let payouts = app.list_payouts(profile.clone(), wallet_name).await?;
+    let payout = payouts.iter().find(|p| p.id == id).unwrap();
+    assert!(payout.is_scheduled());
+    assert_eq!(
+        payout.execute_after.map(|t| t.timestamp()),
+        Some(execute_after.timestamp())
+    );
+
+    app.cancel_payout(profile, id).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn profile_policy_restricts_payouts() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+
+    let original = "tpubDD4vFnWuTMEcZiaaZPgvzeGyMzWe6qHW8gALk5Md9kutDvtdDjYFwzauEFFRHgov8pAwup5jX88j5YFyiACsPf3pqn5hBjvuTLRAseaJ6b4";
+    let xpub = XPub::try_from((original, Some("m/84'/0'/0'"))).unwrap();
+    let wallet_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let id = XPubs::new(&pool)
+        .persist(
+            NewAccountXPub::builder()
+                .account_id(profile.account_id)
+                .original(original.to_owned())
+                .key_name(wallet_name.clone())
+                .value(xpub)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+
+    let app = App::run(pool, AppConfig::default()).await?;
+    app.create_wpkh_wallet(profile.clone(), wallet_name.clone(), id.to_string(), None)
+        .await?;
+
+    let queue_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let queue_id = app
+        .create_payout_queue(profile.clone(), queue_name.clone(), None, None)
+        .await?;
+    let other_queue_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    app.create_payout_queue(profile.clone(), other_queue_name.clone(), None, None)
+        .await?;
+
+    let policy = ProfilePolicy {
+        allowed_payout_queue_ids: Some(std::iter::once(queue_id).collect()),
+        max_payout_sats: Some(Satoshis::from(10_000)),
+        max_daily_payout_sats: Some(Satoshis::from(15_000)),
+        ..Default::default()
+    };
+    let restricted = app
+        .create_profile(
+            profile.clone(),
+            Alphanumeric.sample_string(&mut rand::thread_rng(), 32),
+            Some(policy),
+            false,
+        )
+        .await?;
+
+    let destination = PayoutDestination::OnchainAddress {
+        value: "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap(),
+    };
+    let submit = |queue_name: &String, sats: u64| {
+        app.submit_payout(
+            restricted.clone(),
+            wallet_name.clone(),
+            queue_name.clone(),
+            destination.clone(),
+            Satoshis::from(sats),
+            None,
+            None,
+            None,
+            None,
+        )
+    };
+
+    let err = submit(&other_queue_name, 5_000).await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::ProfileError(
+            ProfileError::PayoutQueueNotAllowed(_)
+        ))
+    ));
+    let err = submit(&queue_name, 20_000).await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::ProfileError(
+            ProfileError::PayoutLimitExceeded(_)
+        ))
+    ));
+    submit(&queue_name, 10_000).await?;
+    let err = submit(&queue_name, 10_000).await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::ProfileError(
+            ProfileError::DailyPayoutLimitExceeded(_)
+        ))
+    ));
+
+    let err = app
+        .create_profile(restricted.clone(), "other".to_string(), None, false)
+        .await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::ProfileError(
+            ProfileError::ProfileManagementNotAllowed
+        ))
+    ));
+    let err = app
+        .import_xpub(
+            restricted.clone(),
+            "other".to_string(),
+            original.to_string(),
+            None,
+        )
+        .await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::ProfileError(
+            ProfileError::AccountLevelAccessNotAllowed
+        ))
+    ));
+    let err = app
+        .create_payout_queue(restricted.clone(), "other".to_string(), None, None)
+        .await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::ProfileError(
+            ProfileError::AccountLevelAccessNotAllowed
+        ))
+    ));
+    let queues = app.list_payout_queues(restricted.clone()).await?;
+    assert_eq!(queues.len(), 1);
+    assert_eq!(queues[0].id, queue_id);
+
+    let concurrent = app
+        .create_profile(
+            profile.clone(),
+            Alphanumeric.sample_string(&mut rand::thread_rng(), 32),
+            Some(ProfilePolicy {
+                max_daily_payout_sats: Some(Satoshis::from(15_000)),
+                ..Default::default()
+            }),
+            false,
+        )
+        .await?;
+    let submit = || {
+        app.submit_payout(
+            concurrent.clone(),
+            wallet_name.clone(),
+            queue_name.clone(),
+            destination.clone(),
+            Satoshis::from(10_000),
+            None,
+            None,
+            None,
+            None,
+        )
+    };
+    let (first, second) = tokio::join!(submit(), submit());
+    assert!(first.is_ok() != second.is_ok());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn large_payouts_await_approval() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+
+    let original = "tpubDD4vFnWuTMEcZiaaZPgvzeGyMzWe6qHW8gALk5Md9kutDvtdDjYFwzauEFFRHgov8pAwup5jX88j5YFyiACsPf3pqn5hBjvuTLRAseaJ6b4";
+    let xpub = XPub::try_from((original, Some("m/84'/0'/0'"))).unwrap();
+    let wallet_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let id = XPubs::new(&pool)
+        .persist(
+            NewAccountXPub::builder()
+                .account_id(profile.account_id)
+                .original(original.to_owned())
+                .key_name(wallet_name.clone())
+                .value(xpub)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+    app.create_wpkh_wallet(profile.clone(), wallet_name.clone(), id.to_string(), None)
+        .await?;
+
+    let queue_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let config = PayoutQueueConfig {
+        approval: Some(PayoutApprovalConfig {
+            min_sats: Satoshis::from(50_000),
+            required_approvals: 2,
+        }),
+        ..Default::default()
+    };
+    let queue_id = app
+        .create_payout_queue(profile.clone(), queue_name.clone(), None, Some(config))
+        .await?;
+
+    let submitter = app
+        .create_profile(
+            profile.clone(),
+            Alphanumeric.sample_string(&mut rand::thread_rng(), 32),
+            None,
+            false,
+        )
+        .await?;
+    let mut approvers = Vec::new();
+    for _ in 0..2 {
+        approvers.push(
+            app.create_profile(
+                profile.clone(),
+                Alphanumeric.sample_string(&mut rand::thread_rng(), 32),
+                None,
+                true,
+            )
+            .await?,
+        );
+    }
+    let approver_of_submitter = app
+        .create_profile(
+            submitter.clone(),
+            Alphanumeric.sample_string(&mut rand::thread_rng(), 32),
+            None,
+            true,
+        )
+        .await?;
+
+    let destination = PayoutDestination::OnchainAddress {
+        value: "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap(),
+    };
+    let mut ids = Vec::new();
+    for sats in [10_000, 100_000, 100_000] {
+        ids.push(
+            app.submit_payout(
+                submitter.clone(),
+                wallet_name.clone(),
+                queue_name.clone(),
+                destination.clone(),
+                Satoshis::from(sats),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?,
+        );
+    }
+    let (small, approved, rejected) = (ids[0], ids[1], ids[2]);
+
+    let payouts = Payouts::new(&pool);
+    let unbatched = payouts.list_unbatched(profile.account_id, queue_id).await?;
+    assert_eq!(unbatched.n_payouts(), 1);
+    let small = app.find_payout(profile.clone(), small).await?;
+    assert!(!small.is_awaiting_approval());
+
+    let err = app.approve_payout(profile.clone(), approved).await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::ProfileError(
+            ProfileError::NotAPayoutApprover(_)
+        ))
+    ));
+    let err = app
+        .approve_payout(approver_of_submitter.clone(), approved)
+        .await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::PayoutError(
+            PayoutError::ApproverCreatedBySubmitter
+        ))
+    ));
+    assert!(!app.approve_payout(approvers[0].clone(), approved).await?);
+    let err = app.approve_payout(approvers[0].clone(), approved).await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::PayoutError(
+            PayoutError::AlreadyApprovedByProfile(_)
+        ))
+    ));
+    assert!(app.approve_payout(approvers[1].clone(), approved).await?);
+    assert!(!app
+        .find_payout(profile.clone(), approved)
+        .await?
+        .is_awaiting_approval());
+
+    app.reject_payout(approvers[0].clone(), rejected).await?;
+    assert!(app
+        .find_payout(profile.clone(), rejected)
+        .await?
+        .is_rejected());
+    let err = app.approve_payout(approvers[1].clone(), rejected).await;
+    assert!(matches!(
+        err,
+        Err(ApplicationError::PayoutError(
+            PayoutError::PayoutNotAwaitingApproval(_)
+        ))
+    ));
+
+    let unbatched = payouts.list_unbatched(profile.account_id, queue_id).await?;
+    assert_eq!(unbatched.n_payouts(), 2);
+
     Ok(())
 }
@@ -0,0 +1,114 @@
+mod helpers;
+
+use rand::distributions::{Alphanumeric, DistString};
+
+use bria::{
+    app::{error::ApplicationError, *},
+    profile::{error::ProfileError, *},
+};
+
+#[tokio::test]
+async fn scoped_and_expiring_api_keys() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+    let app = App::run(pool, AppConfig::default()).await?;
+
+    let name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    app.create_profile(profile.clone(), name.clone(), None, false)
+        .await?;
+
+    let key = app
+        .create_profile_api_key(
+            profile.clone(),
+            name.clone(),
+            vec![ApiKeyScope::SubmitPayouts],
+            None,
+        )
+        .await?;
+    let (_, scopes) = app.authenticate(&key.key).await?;
+    assert!(check_scopes(&scopes, ApiAccess::SubmitPayout).is_ok());
+    assert!(matches!(
+        check_scopes(&scopes, ApiAccess::Read),
+        Err(ProfileError::ApiKeyScopeNotPermitted(ApiAccess::Read))
+    ));
+
+    assert!(matches!(
+        app.create_profile_api_key(
+            profile.clone(),
+            name.clone(),
+            Vec::new(),
+            Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+        )
+        .await,
+        Err(ApplicationError::ProfileError(
+            ProfileError::ApiKeyExpiryInPast(_)
+        ))
+    ));
+    let expired = app
+        .create_profile_api_key(
+            profile.clone(),
+            name.clone(),
+            Vec::new(),
+            Some(chrono::Utc::now() + chrono::Duration::seconds(1)),
+        )
+        .await?;
+    assert_eq!(expired.scopes, vec![ApiKeyScope::Full]);
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    assert!(matches!(
+        app.authenticate(&expired.key).await,
+        Err(ApplicationError::ProfileError(
+            ProfileError::ProfileKeyExpired
+        ))
+    ));
+
+    let keys = app
+        .list_profile_api_keys(profile.clone(), name.clone())
+        .await?;
+    assert_eq!(keys.len(), 2);
+    assert!(keys.iter().all(|k| !k.revoked));
+
+    app.revoke_profile_api_key(profile.clone(), key.id).await?;
+    assert!(matches!(
+        app.authenticate(&key.key).await,
+        Err(ApplicationError::ProfileError(
+            ProfileError::ProfileKeyNotFound
+        ))
+    ));
+    let keys = app.list_profile_api_keys(profile, name).await?;
+    assert!(keys.iter().any(|k| k.id == key.id && k.revoked));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn approver_keys_only_issued_by_creator_chain() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+    let app = App::run(pool, AppConfig::default()).await?;
+
+    let other = app
+        .create_profile(
+            profile.clone(),
+            Alphanumeric.sample_string(&mut rand::thread_rng(), 32),
+            None,
+            false,
+        )
+        .await?;
+    let approver_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let approver = app
+        .create_profile(profile.clone(), approver_name.clone(), None, true)
+        .await?;
+    assert!(approver.payout_approver);
+
+    assert!(matches!(
+        app.create_profile_api_key(other, approver_name.clone(), Vec::new(), None)
+            .await,
+        Err(ApplicationError::ProfileError(
+            ProfileError::ApproverKeyNotAllowed(_)
+        ))
+    ));
+    app.create_profile_api_key(profile, approver_name, Vec::new(), None)
+        .await?;
+
+    Ok(())
+}
@@ -213,3 +213,46 @@ async fn build_psbt() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn skips_unfundable_and_dust_payouts() -> anyhow::Result<()> {
+    let wallet = helpers::random_bdk_wallet()?;
+    let wallet_id = WalletId::new();
+    let destination: bitcoin::Address = "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap();
+    let dust_id = Uuid::new_v4();
+    let unfunded_id = Uuid::new_v4();
+    let payouts = vec![
+        (dust_id, destination.clone(), Satoshis::from(100)),
+        (unfunded_id, destination, Satoshis::from(100_000)),
+    ];
+
+    let FinishedPsbtBuild {
+        psbt,
+        skipped_payouts,
+        ..
+    } = PsbtBuilder::new()
+        .consolidate_deprecated_keychains(false)
+        .fee_rate(FeeRate::from_sat_per_vb(1.0))
+        .accept_wallets()
+        .wallet_payouts(wallet_id, payouts)
+        .accept_current_keychain()
+        .visit_bdk_wallet(KeychainId::new(), &wallet)?
+        .finish();
+
+    assert!(psbt.is_none());
+    let skipped: Vec<_> = skipped_payouts
+        .get(&wallet_id)
+        .expect("wallet should have skipped payouts")
+        .iter()
+        .map(|((id, _, _), reason)| (*id, *reason))
+        .collect();
+    assert_eq!(
+        skipped,
+        vec![
+            (dust_id, PayoutSkipReason::Dust),
+            (unfunded_id, PayoutSkipReason::InsufficientFunds)
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,338 @@
+mod helpers;
+
+use rand::distributions::{Alphanumeric, DistString};
+
+use bria::{
+    app::{error::ApplicationError, *},
+    batch::{error::BatchError, *},
+    payout::*,
+    payout_queue::*,
+    primitives::*,
+    profile::Profile,
+    signing_session::{error::SigningSessionError, *},
+    wallet::*,
+    xpub::*,
+};
+
+async fn create_batch_with_session(
+    pool: &sqlx::PgPool,
+) -> anyhow::Result<(App, Profile, BatchId, SigningSessionId)> {
+    let profile = helpers::create_test_account(pool).await?;
+
+    let original = "tpubDD4vFnWuTMEcZiaaZPgvzeGyMzWe6qHW8gALk5Md9kutDvtdDjYFwzauEFFRHgov8pAwup5jX88j5YFyiACsPf3pqn5hBjvuTLRAseaJ6b4";
+    let xpub = XPub::try_from((original, Some("m/84'/0'/0'"))).unwrap();
+    let wallet_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let xpub_id = XPubs::new(pool)
+        .persist(
+            NewAccountXPub::builder()
+                .account_id(profile.account_id)
+                .original(original.to_owned())
+                .key_name(wallet_name.clone())
+                .value(xpub)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+    app.create_wpkh_wallet(
+        profile.clone(),
+        wallet_name.clone(),
+        xpub_id.to_string(),
+        None,
+    )
+    .await?;
+    let wallet = Wallets::new(pool)
+        .find_by_name(profile.account_id, wallet_name)
+        .await?;
+    let keychain_id = wallet.keychain_ids().next().unwrap();
+
+    let unsigned_psbt =
+        bitcoin::psbt::PartiallySignedTransaction::from_unsigned_tx(bitcoin::Transaction {
+            version: 2,
+            lock_time: bdk::bitcoin::PackedLockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        })?;
+    let batch = NewBatch::builder()
+        .account_id(profile.account_id)
+        .id(BatchId::new())
+        .tx_id(unsigned_psbt.unsigned_tx.txid())
+        .unsigned_psbt(unsigned_psbt.clone())
+        .total_fee_sats(Satoshis::ZERO)
+        .wallet_summaries(
+            std::iter::once((
+                wallet.id,
+                WalletSummary {
+                    wallet_id: wallet.id,
+                    current_keychain_id: keychain_id,
+                    signing_keychains: vec![keychain_id],
+                    total_in_sats: Satoshis::ZERO,
+                    total_spent_sats: Satoshis::ZERO,
+                    fee_sats: Satoshis::ZERO,
+                    change_sats: Satoshis::ZERO,
+                    change_address: None,
+                    change_outpoint: None,
+                    batch_created_ledger_tx_id: None,
+                    batch_broadcast_ledger_tx_id: None,
+                },
+            ))
+            .collect(),
+        )
+        .build()
+        .unwrap();
+    let mut tx = pool.begin().await?;
+    let batch_id = Batches::new(pool).create_in_tx(&mut tx, batch).await?;
+    tx.commit().await?;
+
+    let session = SigningSessions::new(pool)
+        .persist_sessions(
+            std::iter::once((
+                xpub_id,
+                NewSigningSession::builder()
+                    .account_id(profile.account_id)
+                    .batch_id(batch_id)
+                    .xpub_id(xpub_id)
+                    .unsigned_psbt(unsigned_psbt)
+                    .build()
+                    .unwrap(),
+            ))
+            .collect(),
+        )
+        .await?
+        .xpub_sessions
+        .remove(&xpub_id)
+        .unwrap();
+
+    Ok((app, profile, batch_id, session.id))
+}
+
+#[tokio::test]
+async fn list_signing_sessions_by_state() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let (app, profile, batch_id, session_id) = create_batch_with_session(&pool).await?;
+
+    let sessions = app
+        .list_signing_sessions(
+            profile.clone(),
+            Some(batch_id),
+            None,
+            Some(SigningSessionState::Initialized),
+            None,
+            None,
+        )
+        .await?;
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id, session_id);
+    assert!(app
+        .list_signing_sessions(
+            profile.clone(),
+            Some(batch_id),
+            None,
+            Some(SigningSessionState::Failed),
+            None,
+            None,
+        )
+        .await?
+        .is_empty());
+
+    let signing_sessions = SigningSessions::new(&pool);
+    let mut sessions = signing_sessions
+        .list_for_batch(profile.account_id, batch_id)
+        .await?
+        .unwrap()
+        .xpub_sessions;
+    for session in sessions.values_mut() {
+        session.attempt_failed(SigningFailureReason::SignerConfigMissing);
+    }
+    let mut tx = pool.begin().await?;
+    signing_sessions.update_sessions(&mut tx, &sessions).await?;
+    tx.commit().await?;
+
+    let sessions = app
+        .list_signing_sessions(
+            profile,
+            Some(batch_id),
+            None,
+            Some(SigningSessionState::Failed),
+            None,
+            None,
+        )
+        .await?;
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id, session_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_signing_sessions_in_pages() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let (app, profile, batch_id, session_id) = create_batch_with_session(&pool).await?;
+
+    let page = app
+        .list_signing_sessions(profile.clone(), None, None, None, None, Some(1))
+        .await?;
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].id, session_id);
+    assert!(app
+        .list_signing_sessions(profile.clone(), None, None, None, Some(session_id), Some(1))
+        .await?
+        .is_empty());
+
+    app.abort_batch_signing(profile.clone(), batch_id).await?;
+    let aborted = app
+        .list_signing_sessions(
+            profile,
+            None,
+            None,
+            Some(SigningSessionState::Aborted),
+            None,
+            None,
+        )
+        .await?;
+    assert_eq!(aborted.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn abort_batch_signing_cancels_batch() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let (app, profile, batch_id, session_id) = create_batch_with_session(&pool).await?;
+
+    app.abort_batch_signing(profile.clone(), batch_id).await?;
+
+    let (batch, _, sessions) = app.get_batch(profile.clone(), batch_id).await?;
+    assert!(batch.cancelled);
+    let session = sessions
+        .unwrap()
+        .xpub_sessions
+        .into_values()
+        .next()
+        .unwrap();
+    assert_eq!(session.state(), SigningSessionState::Aborted);
+
+    assert!(matches!(
+        app.abort_batch_signing(profile.clone(), batch_id).await,
+        Err(ApplicationError::SigningSessionError(
+            SigningSessionError::NothingToAbort(_)
+        ))
+    ));
+    assert!(matches!(
+        app.retry_signing_session(profile, session_id).await,
+        Err(ApplicationError::SigningSessionError(
+            SigningSessionError::Aborted(_)
+        ))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_signing_session_schedules_attempt() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let (app, profile, _, session_id) = create_batch_with_session(&pool).await?;
+
+    app.retry_signing_session(profile.clone(), session_id)
+        .await?;
+
+    let unknown = SigningSessionId::new();
+    assert!(matches!(
+        app.retry_signing_session(profile, unknown).await,
+        Err(ApplicationError::SigningSessionError(
+            SigningSessionError::NotFound(_)
+        ))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn abort_batch_signing_returns_payouts_to_queue() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let (app, profile, batch_id, _) = create_batch_with_session(&pool).await?;
+    let (batch, _, _) = app.get_batch(profile.clone(), batch_id).await?;
+    let wallet_id = *batch.wallet_summaries.keys().next().unwrap();
+    let wallet = Wallets::new(&pool).find_by_id(wallet_id).await?;
+
+    let queue_name = "manual".to_string();
+    let queue_id = app
+        .create_payout_queue(
+            profile.clone(),
+            queue_name.clone(),
+            None,
+            Some(PayoutQueueConfig {
+                trigger: PayoutQueueTrigger::Manual,
+                ..Default::default()
+            }),
+        )
+        .await?;
+    let payout_id = app
+        .submit_payout(
+            profile.clone(),
+            wallet.name,
+            queue_name,
+            PayoutDestination::OnchainAddress {
+                value: "mgWUuj1J1N882jmqFxtDepEC73Rr22E9GU".parse().unwrap(),
+            },
+            Satoshis::from(10_000),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    let payouts = Payouts::new(&pool);
+    let mut unbatched = payouts.list_unbatched(profile.account_id, queue_id).await?;
+    unbatched.commit_to_batch(
+        batch.bitcoin_tx_id,
+        batch_id,
+        std::iter::once((payout_id, 0)),
+    );
+    let mut tx = pool.begin().await?;
+    payouts.update_unbatched(&mut tx, unbatched).await?;
+    tx.commit().await?;
+    assert_eq!(
+        payouts
+            .list_unbatched(profile.account_id, queue_id)
+            .await?
+            .n_payouts(),
+        0
+    );
+
+    app.abort_batch_signing(profile.clone(), batch_id).await?;
+
+    let unbatched = payouts.list_unbatched(profile.account_id, queue_id).await?;
+    assert_eq!(unbatched.n_payouts(), 1);
+    let payout = payouts.find_by_id(profile.account_id, payout_id).await?;
+    assert_eq!(payout.batch_id, None);
+    assert_eq!(payout.outpoint, None);
+    assert!(Batches::new(&pool)
+        .set_batch_created_ledger_tx_id(batch_id, wallet_id)
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn abort_batch_signing_refuses_signed_batch() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let (app, profile, batch_id, _) = create_batch_with_session(&pool).await?;
+    let (batch, _, _) = app.get_batch(profile.clone(), batch_id).await?;
+    let batches = Batches::new(&pool);
+    batches
+        .set_signed_tx(batch_id, batch.unsigned_psbt.unsigned_tx)
+        .await?;
+
+    assert!(matches!(
+        app.abort_batch_signing(profile.clone(), batch_id).await,
+        Err(ApplicationError::BatchError(
+            BatchError::BatchNotCancellable(_)
+        ))
+    ));
+    let (batch, _, _) = app.get_batch(profile, batch_id).await?;
+    assert!(!batch.cancelled);
+
+    Ok(())
+}
@@ -0,0 +1,370 @@
+mod helpers;
+
+use bdk::{
+    bitcoin::hashes::Hash,
+    wallet::{AddressIndex, AddressInfo},
+    LocalUtxo,
+};
+use rand::distributions::{Alphanumeric, DistString};
+
+use bria::{
+    admin::*,
+    app::{error::ApplicationError, *},
+    ledger::*,
+    primitives::*,
+    utxo::*,
+    wallet::*,
+    xpub::*,
+};
+
+struct TestUtxo {
+    app: App,
+    profile: bria::profile::Profile,
+    wallet_name: String,
+    wallet: Wallet,
+    keychain_id: KeychainId,
+    outpoint: bitcoin::OutPoint,
+}
+
+async fn detect_utxo(
+    pool: &sqlx::PgPool,
+    quarantined_source: Option<&bitcoin::Address>,
+    dust_threshold_sats: Option<Satoshis>,
+) -> anyhow::Result<TestUtxo> {
+    let profile = helpers::create_test_account(pool).await?;
+
+    let original = "tpubDD4vFnWuTMEcZiaaZPgvzeGyMzWe6qHW8gALk5Md9kutDvtdDjYFwzauEFFRHgov8pAwup5jX88j5YFyiACsPf3pqn5hBjvuTLRAseaJ6b4";
+    let xpub = XPub::try_from((original, Some("m/84'/0'/0'"))).unwrap();
+    let wallet_name = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);
+    let id = XPubs::new(pool)
+        .persist(
+            NewAccountXPub::builder()
+                .account_id(profile.account_id)
+                .original(original.to_owned())
+                .key_name(wallet_name.clone())
+                .value(xpub)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+    app.create_wpkh_wallet(profile.clone(), wallet_name.clone(), id.to_string(), None)
+        .await?;
+    app.update_wallet(
+        profile.clone(),
+        wallet_name.clone(),
+        WalletConfigUpdate {
+            dust_threshold_sats,
+            ..WalletConfigUpdate::default()
+        },
+    )
+    .await?;
+    let wallet = Wallets::new(pool)
+        .find_by_name(profile.account_id, wallet_name.clone())
+        .await?;
+    let keychain_id = wallet.keychain_ids().next().unwrap();
+    let dust = wallet.config.is_dust(Satoshis::from(50_000));
+
+    let address = helpers::random_bdk_wallet()?
+        .get_address(AddressIndex::New)?
+        .address;
+    let outpoint = bitcoin::OutPoint {
+        txid: bitcoin::Txid::from_slice(&rand::random::<[u8; 32]>())?,
+        vout: 0,
+    };
+    let utxos = Utxos::new(pool);
+    let (pending_id, tx) = utxos
+        .new_utxo_detected(
+            profile.account_id,
+            wallet.id,
+            keychain_id,
+            &AddressInfo {
+                index: 0,
+                address: address.clone(),
+                keychain: bitcoin::KeychainKind::External,
+            },
+            &LocalUtxo {
+                outpoint,
+                txout: bdk::bitcoin::TxOut {
+                    value: 50_000,
+                    script_pubkey: address.script_pubkey(),
+                },
+                keychain: bitcoin::KeychainKind::External,
+                is_spent: false,
+            },
+            1.0,
+            false,
+            quarantined_source,
+            dust,
+        )
+        .await?
+        .expect("utxo should be new");
+    let ledger_account_ids = wallet
+        .ledger_account_ids
+        .for_income(dust, quarantined_source.is_some());
+    Ledger::new(pool)
+        .utxo_detected(
+            tx,
+            pending_id,
+            UtxoDetectedParams {
+                journal_id: wallet.journal_id,
+                onchain_incoming_account_id: ledger_account_ids.onchain_incoming_id,
+                effective_incoming_account_id: ledger_account_ids.effective_incoming_id,
+                onchain_fee_account_id: ledger_account_ids.fee_id,
+                meta: UtxoDetectedMeta {
+                    account_id: profile.account_id,
+                    wallet_id: wallet.id,
+                    keychain_id,
+                    outpoint,
+                    satoshis: Satoshis::from(50_000),
+                    address,
+                    encumbered_spending_fees: std::iter::once((outpoint, Satoshis::ZERO)).collect(),
+                    confirmation_time: None,
+                },
+            },
+        )
+        .await?;
+
+    Ok(TestUtxo {
+        app,
+        profile,
+        wallet_name,
+        wallet,
+        keychain_id,
+        outpoint,
+    })
+}
+
+#[tokio::test]
+async fn quarantined_utxos_are_not_selected() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let source = helpers::random_bdk_wallet()?
+        .get_address(AddressIndex::New)?
+        .address;
+    let TestUtxo {
+        app,
+        profile,
+        wallet_name,
+        keychain_id,
+        outpoint,
+        ..
+    } = detect_utxo(&pool, Some(&source), None).await?;
+    let summary = app
+        .get_wallet_balance_summary(profile.clone(), wallet_name.clone())
+        .await?;
+    assert_eq!(summary.effective_pending_income, Satoshis::ZERO);
+    assert_eq!(summary.utxo_frozen, Satoshis::from(50_000));
+    let utxos = Utxos::new(&pool);
+
+    let mut tx = pool.begin().await?;
+    let excluded = utxos
+        .outpoints_bdk_should_not_select(&mut tx, std::iter::once(keychain_id))
+        .await?;
+    assert!(excluded[&keychain_id].contains(&outpoint));
+    tx.commit().await?;
+
+    let admin = AdminApp::new(pool.clone(), bitcoin::Network::Regtest);
+    let quarantined = admin
+        .list_quarantined_utxos(Some(profile.account_id))
+        .await?;
+    assert_eq!(quarantined.len(), 1);
+    assert_eq!(quarantined[0].outpoint, outpoint);
+    assert_eq!(quarantined[0].source_address, Some(source));
+
+    admin
+        .release_quarantined_utxo(profile.account_id, outpoint)
+        .await?;
+    assert!(admin
+        .list_quarantined_utxos(Some(profile.account_id))
+        .await?
+        .is_empty());
+    let summary = app
+        .get_wallet_balance_summary(profile.clone(), wallet_name)
+        .await?;
+    assert_eq!(summary.effective_pending_income, Satoshis::from(50_000));
+    assert_eq!(summary.utxo_frozen, Satoshis::ZERO);
+    assert!(matches!(
+        admin
+            .release_quarantined_utxo(profile.account_id, outpoint)
+            .await,
+        Err(AdminApiError::UtxoError(
+            bria::utxo::error::UtxoError::QuarantinedUtxoNotFound(_)
+        ))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn frozen_utxos_are_not_selected() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let TestUtxo {
+        app,
+        profile,
+        wallet_name,
+        wallet,
+        keychain_id,
+        outpoint,
+    } = detect_utxo(&pool, None, None).await?;
+
+    app.freeze_utxo(
+        profile.clone(),
+        wallet_name.clone(),
+        outpoint,
+        Some("disputed deposit".to_string()),
+    )
+    .await?;
+    let (_, keychains) = app.list_utxos(profile.clone(), wallet_name.clone()).await?;
+    let utxo = keychains
+        .into_iter()
+        .flat_map(|k| k.utxos)
+        .find(|u| u.outpoint == outpoint)
+        .unwrap();
+    assert!(utxo.frozen);
+    assert_eq!(utxo.frozen_reason.as_deref(), Some("disputed deposit"));
+    let summary = app
+        .get_wallet_balance_summary(profile.clone(), wallet_name.clone())
+        .await?;
+    assert_eq!(summary.utxo_frozen, Satoshis::from(50_000));
+    assert_eq!(summary.effective_pending_income, Satoshis::ZERO);
+    assert!(matches!(
+        app.freeze_utxo(profile.clone(), wallet_name.clone(), outpoint, None)
+            .await,
+        Err(ApplicationError::UtxoError(
+            bria::utxo::error::UtxoError::UnspentUtxoNotFound(_)
+        ))
+    ));
+
+    let mut tx = pool.begin().await?;
+    let excluded = Utxos::new(&pool)
+        .outpoints_bdk_should_not_select(&mut tx, wallet.keychain_ids())
+        .await?;
+    assert!(excluded[&keychain_id].contains(&outpoint));
+    assert!(matches!(
+        Utxos::new(&pool)
+            .reserve_utxos_in_batch(
+                &mut tx,
+                profile.account_id,
+                BatchId::new(),
+                None,
+                bitcoin::FeeRate::from_sat_per_vb(1.0),
+                std::iter::once((keychain_id, outpoint)),
+            )
+            .await,
+        Err(bria::utxo::error::UtxoError::UtxosNotReservable)
+    ));
+    tx.commit().await?;
+
+    app.unfreeze_utxo(profile.clone(), wallet_name.clone(), outpoint)
+        .await?;
+    let summary = app
+        .get_wallet_balance_summary(profile.clone(), wallet_name.clone())
+        .await?;
+    assert_eq!(summary.utxo_frozen, Satoshis::ZERO);
+    assert_eq!(summary.effective_pending_income, Satoshis::from(50_000));
+    assert!(matches!(
+        app.unfreeze_utxo(profile.clone(), wallet_name.clone(), outpoint)
+            .await,
+        Err(ApplicationError::UtxoError(
+            bria::utxo::error::UtxoError::FrozenUtxoNotFound(_)
+        ))
+    ));
+    let unknown = bitcoin::OutPoint {
+        txid: bitcoin::Txid::from_slice(&rand::random::<[u8; 32]>())?,
+        vout: 0,
+    };
+    assert!(matches!(
+        app.freeze_utxo(profile, wallet_name, unknown, None).await,
+        Err(ApplicationError::UtxoError(
+            bria::utxo::error::UtxoError::UnspentUtxoNotFound(_)
+        ))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dust_utxos_stay_frozen() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let TestUtxo {
+        app,
+        profile,
+        wallet_name,
+        wallet,
+        keychain_id,
+        outpoint,
+    } = detect_utxo(&pool, None, Some(Satoshis::from(50_001))).await?;
+    assert!(wallet.config.is_dust(Satoshis::from(50_000)));
+    assert!(!wallet.config.is_dust(Satoshis::from(50_001)));
+
+    let (_, keychains) = app.list_utxos(profile.clone(), wallet_name.clone()).await?;
+    let utxo = keychains
+        .into_iter()
+        .flat_map(|k| k.utxos)
+        .find(|u| u.outpoint == outpoint)
+        .unwrap();
+    assert!(utxo.frozen);
+    assert_eq!(utxo.frozen_reason.as_deref(), Some("dust"));
+    let summary = app
+        .get_wallet_balance_summary(profile.clone(), wallet_name.clone())
+        .await?;
+    assert_eq!(summary.dust_pending_income, Satoshis::from(50_000));
+    assert_eq!(summary.effective_pending_income, Satoshis::ZERO);
+    assert_eq!(summary.utxo_frozen, Satoshis::ZERO);
+
+    let utxos = Utxos::new(&pool);
+    let mut tx = pool.begin().await?;
+    let excluded = utxos
+        .outpoints_bdk_should_not_select(&mut tx, std::iter::once(keychain_id))
+        .await?;
+    assert!(excluded[&keychain_id].contains(&outpoint));
+    tx.commit().await?;
+
+    let mut tx = pool.begin().await?;
+    let settled = utxos
+        .settle_utxo(&mut tx, keychain_id, outpoint, false, 1)
+        .await?;
+    assert!(settled.dust);
+    Ledger::new(&pool)
+        .utxo_settled(
+            tx,
+            settled.utxo_settled_ledger_tx_id,
+            UtxoSettledParams {
+                journal_id: wallet.journal_id,
+                ledger_account_ids: wallet
+                    .ledger_account_ids
+                    .for_income(settled.dust, settled.quarantined || settled.frozen),
+                pending_id: settled.utxo_detected_ledger_tx_id,
+                meta: UtxoSettledMeta {
+                    account_id: profile.account_id,
+                    wallet_id: wallet.id,
+                    keychain_id,
+                    outpoint,
+                    satoshis: settled.value,
+                    address: settled.address,
+                    confirmation_time: bdk::BlockTime {
+                        height: 1,
+                        timestamp: 123409,
+                    },
+                    already_spent_tx_id: None,
+                },
+            },
+        )
+        .await?;
+    let summary = app
+        .get_wallet_balance_summary(profile.clone(), wallet_name.clone())
+        .await?;
+    assert_eq!(summary.dust_pending_income, Satoshis::ZERO);
+    assert_eq!(summary.dust_settled, Satoshis::from(50_000));
+    assert_eq!(summary.effective_settled, Satoshis::ZERO);
+    assert_eq!(summary.utxo_frozen, Satoshis::ZERO);
+
+    assert!(matches!(
+        app.unfreeze_utxo(profile, wallet_name, outpoint).await,
+        Err(ApplicationError::UtxoError(
+            bria::utxo::error::UtxoError::FrozenUtxoNotFound(_)
+        ))
+    ));
+
+    Ok(())
+}
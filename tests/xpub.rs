@@ -63,3 +63,133 @@ async fn rotate_encryption_key() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn local_xpriv_signer_config() -> anyhow::Result<()> {
+    use bdk::bitcoin::{
+        secp256k1::Secp256k1,
+        util::bip32::{DerivationPath, ExtendedPrivKey, ExtendedPubKey},
+    };
+
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+
+    let secp = Secp256k1::new();
+    let master: ExtendedPrivKey = "tprv8ZgxMBicQKsPd3krDUsBAmtnRsK3rb8u5yi1zhQgMhF1tR8MW7xfE4rnrbbsrbPR52e7rKapu6ztw1jXveJSCGHEriUGZV7mCe88duLp5pj".parse()?;
+    let account = master.derive_priv(&secp, &"m/84'/1'/0'".parse::<DerivationPath>()?)?;
+    let name = "local".to_string();
+    app.import_xpub(
+        profile.clone(),
+        name.clone(),
+        ExtendedPubKey::from_priv(&secp, &account).to_string(),
+        Some("m/84h/1h/0h".to_string()),
+    )
+    .await?;
+
+    let res = app
+        .set_signer_config(
+            profile.clone(),
+            name.clone(),
+            SignerConfig::LocalXpriv(LocalXprivSignerConfig {
+                xpriv: master.to_string(),
+            }),
+        )
+        .await;
+    assert!(res.is_err());
+
+    app.set_signer_config(
+        profile.clone(),
+        name.clone(),
+        SignerConfig::LocalXpriv(LocalXprivSignerConfig {
+            xpriv: account.to_string(),
+        }),
+    )
+    .await?;
+    let xpub = XPubs::new(&pool)
+        .find_from_ref(profile.account_id, XPubRef::Name(name))
+        .await?;
+    assert!(xpub
+        .remote_signing_client(
+            AppConfig::default().signer_encryption.key,
+            &SigningContext::health_check(profile.account_id),
+        )
+        .await?
+        .is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signer_config() -> anyhow::Result<()> {
+    use bdk::bitcoin::{
+        secp256k1::Secp256k1,
+        util::bip32::{DerivationPath, ExtendedPrivKey, ExtendedPubKey},
+    };
+
+    let pool = helpers::init_pool().await?;
+    let profile = helpers::create_test_account(&pool).await?;
+    let app = App::run(pool.clone(), AppConfig::default()).await?;
+
+    let secp = Secp256k1::new();
+    let master: ExtendedPrivKey = "tprv8ZgxMBicQKsPd3krDUsBAmtnRsK3rb8u5yi1zhQgMhF1tR8MW7xfE4rnrbbsrbPR52e7rKapu6ztw1jXveJSCGHEriUGZV7mCe88duLp5pj".parse()?;
+    let account = master.derive_priv(&secp, &"m/84'/1'/0'".parse::<DerivationPath>()?)?;
+    let name = "health".to_string();
+    app.import_xpub(
+        profile.clone(),
+        name.clone(),
+        ExtendedPubKey::from_priv(&secp, &account).to_string(),
+        Some("m/84h/1h/0h".to_string()),
+    )
+    .await?;
+
+    let res = app.test_signer_config(profile.clone(), name.clone()).await;
+    assert!(res.is_err());
+
+    app.set_signer_config(
+        profile.clone(),
+        name.clone(),
+        SignerConfig::LocalXpriv(LocalXprivSignerConfig {
+            xpriv: account.to_string(),
+        }),
+    )
+    .await?;
+    let health = app
+        .test_signer_config(profile.clone(), name.clone())
+        .await?;
+    assert!(health.healthy);
+    assert!(health.error.is_none());
+
+    app.set_signer_config(
+        profile.clone(),
+        name.clone(),
+        SignerConfig::Bitcoind(BitcoindSignerConfig {
+            endpoint: "http://127.0.0.1:1".to_string(),
+            rpc_user: "rpcuser".to_string(),
+            rpc_password: "password".to_string(),
+        }),
+    )
+    .await?;
+    let xpub = XPubs::new(&pool)
+        .find_from_ref(profile.account_id, XPubRef::Name(name.clone()))
+        .await?;
+    assert!(xpub.signer_health().is_none());
+
+    let health = app
+        .test_signer_config(profile.clone(), name.clone())
+        .await?;
+    assert!(!health.healthy);
+    assert!(health.error.is_some());
+    let mut xpub = XPubs::new(&pool)
+        .find_from_ref(profile.account_id, XPubRef::Name(name))
+        .await?;
+    assert!(!xpub.signer_health().expect("health recorded").healthy);
+
+    let undecryptable = xpub
+        .check_signer_health(EncryptionKey::clone_from_slice(&[1; 32]))
+        .await?;
+    assert!(!undecryptable.healthy);
+    assert!(undecryptable.error.is_some());
+
+    Ok(())
+}